@@ -0,0 +1,4 @@
+-- Migration: Add diarized speaker labels to transcripts
+-- speaker_id is the speaker cluster assigned by the diarization engine during
+-- recording (1, 2, 3, ...). NULL means diarization was unavailable for the segment.
+ALTER TABLE transcripts ADD COLUMN speaker_id INTEGER;
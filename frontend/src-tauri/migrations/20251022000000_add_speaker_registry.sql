@@ -0,0 +1,14 @@
+-- Migration: Persistent speaker registry
+-- Named voice profiles that the diarization engine matches new speakers against,
+-- so the same people are recognized across meetings.
+CREATE TABLE IF NOT EXISTS speakers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    embedding BLOB NOT NULL,          -- Little-endian f32 voice embedding (centroid)
+    sample_count INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Registered speaker each transcript segment was identified as (NULL if unknown)
+ALTER TABLE transcripts ADD COLUMN speaker_profile_id INTEGER REFERENCES speakers(id) ON DELETE SET NULL;
@@ -0,0 +1,3 @@
+-- Word-level timings for click-to-seek and word highlighting during playback.
+-- Stored as a JSON array of {word, start, end, confidence} with recording-relative seconds.
+ALTER TABLE transcripts ADD COLUMN words TEXT;
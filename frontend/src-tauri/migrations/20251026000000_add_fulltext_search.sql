@@ -0,0 +1,89 @@
+-- Full-text search over meeting titles, transcripts and summaries (FTS5 + BM25).
+-- Titles and transcripts are external-content indexes over their tables' rowids;
+-- summaries index only the string values of the stored summary JSON.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS meetings_fts USING fts5(
+    title,
+    content = 'meetings',
+    content_rowid = 'rowid',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    transcript,
+    content = 'transcripts',
+    content_rowid = 'rowid',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS summaries_fts USING fts5(
+    summary,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Meetings
+CREATE TRIGGER IF NOT EXISTS meetings_fts_ai AFTER INSERT ON meetings BEGIN
+    INSERT INTO meetings_fts(rowid, title) VALUES (new.rowid, new.title);
+END;
+
+CREATE TRIGGER IF NOT EXISTS meetings_fts_ad AFTER DELETE ON meetings BEGIN
+    INSERT INTO meetings_fts(meetings_fts, rowid, title) VALUES ('delete', old.rowid, old.title);
+END;
+
+CREATE TRIGGER IF NOT EXISTS meetings_fts_au AFTER UPDATE OF title ON meetings BEGIN
+    INSERT INTO meetings_fts(meetings_fts, rowid, title) VALUES ('delete', old.rowid, old.title);
+    INSERT INTO meetings_fts(rowid, title) VALUES (new.rowid, new.title);
+END;
+
+-- Transcripts
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ai AFTER INSERT ON transcripts BEGIN
+    INSERT INTO transcripts_fts(rowid, transcript) VALUES (new.rowid, new.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ad AFTER DELETE ON transcripts BEGIN
+    INSERT INTO transcripts_fts(transcripts_fts, rowid, transcript) VALUES ('delete', old.rowid, old.transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_au AFTER UPDATE OF transcript ON transcripts BEGIN
+    INSERT INTO transcripts_fts(transcripts_fts, rowid, transcript) VALUES ('delete', old.rowid, old.transcript);
+    INSERT INTO transcripts_fts(rowid, transcript) VALUES (new.rowid, new.transcript);
+END;
+
+-- Summaries (JSON keys are not indexed, only the text values)
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ai AFTER INSERT ON summary_processes
+WHEN new.result IS NOT NULL BEGIN
+    INSERT INTO summaries_fts(rowid, summary) VALUES (
+        new.rowid,
+        CASE WHEN json_valid(new.result)
+            THEN (SELECT group_concat(value, ' ') FROM json_tree(new.result) WHERE type = 'text')
+            ELSE new.result
+        END
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ad AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_au AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+    INSERT INTO summaries_fts(rowid, summary)
+    SELECT new.rowid,
+        CASE WHEN json_valid(new.result)
+            THEN (SELECT group_concat(value, ' ') FROM json_tree(new.result) WHERE type = 'text')
+            ELSE new.result
+        END
+    WHERE new.result IS NOT NULL;
+END;
+
+-- Index existing data
+INSERT INTO meetings_fts(meetings_fts) VALUES ('rebuild');
+INSERT INTO transcripts_fts(transcripts_fts) VALUES ('rebuild');
+INSERT INTO summaries_fts(rowid, summary)
+SELECT rowid,
+    CASE WHEN json_valid(result)
+        THEN (SELECT group_concat(value, ' ') FROM json_tree(result) WHERE type = 'text')
+        ELSE result
+    END
+FROM summary_processes
+WHERE result IS NOT NULL;
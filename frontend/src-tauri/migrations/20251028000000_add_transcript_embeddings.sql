@@ -0,0 +1,17 @@
+-- Embedded transcript chunks for semantic search and question answering.
+-- Each chunk covers consecutive segments of one meeting; vectors are little-endian f32 BLOBs.
+CREATE TABLE IF NOT EXISTS transcript_embeddings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    model TEXT NOT NULL,
+    content TEXT NOT NULL,
+    audio_start_time REAL,
+    audio_end_time REAL,
+    embedding BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE,
+    UNIQUE (meeting_id, model, chunk_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_embeddings_model ON transcript_embeddings(model);
@@ -0,0 +1,2 @@
+-- Add transcriptionEndpoint column to transcript_settings table (OpenAI-compatible remote transcription server)
+ALTER TABLE transcript_settings ADD COLUMN transcriptionEndpoint TEXT;
@@ -0,0 +1,14 @@
+-- Snapshots of a meeting's transcript taken before it is replaced wholesale (e.g. by a
+-- high-accuracy re-transcription), so the previous version can be rolled back.
+CREATE TABLE IF NOT EXISTS transcript_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL,
+    reason TEXT NOT NULL,             -- What replaced this version: 'retranscribe', 'rollback'
+    model TEXT,                       -- Model that produced the replacing transcript, if any
+    segments TEXT NOT NULL,           -- JSON array of the replaced transcripts rows
+    segment_count INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_versions_meeting ON transcript_versions(meeting_id, id);
@@ -0,0 +1,15 @@
+-- Migration: Custom vocabulary
+-- Product names, acronyms and jargon the recognizer should spell correctly. Terms are
+-- fed to Whisper as an initial prompt and used to correct recognized text afterwards.
+CREATE TABLE IF NOT EXISTS vocabulary_terms (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    term TEXT NOT NULL,
+    aliases TEXT NOT NULL DEFAULT '[]',  -- JSON array of known misrecognitions of the term
+    meeting_id TEXT REFERENCES meetings(id) ON DELETE CASCADE,  -- NULL for global terms
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- One entry per term within each scope (global or a single meeting)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_vocabulary_terms_scope
+    ON vocabulary_terms(IFNULL(meeting_id, ''), term COLLATE NOCASE);
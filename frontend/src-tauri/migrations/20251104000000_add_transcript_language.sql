@@ -0,0 +1,5 @@
+-- Migration: Per-segment language
+-- Language code of each transcript segment's text and, when it was detected rather
+-- than fixed by the language preference, the detection probability.
+ALTER TABLE transcripts ADD COLUMN language TEXT;
+ALTER TABLE transcripts ADD COLUMN language_probability REAL;
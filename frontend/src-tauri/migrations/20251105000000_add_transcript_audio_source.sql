@@ -0,0 +1,5 @@
+-- Migration: Transcript audio source
+-- Which capture channel a segment was transcribed from when dual-channel transcription
+-- is enabled: 'mic' (the local user) or 'system' (remote participants). NULL for
+-- segments transcribed from the mixed recording.
+ALTER TABLE transcripts ADD COLUMN audio_source TEXT;
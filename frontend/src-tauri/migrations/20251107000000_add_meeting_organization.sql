@@ -0,0 +1,46 @@
+-- Migration: Organize meetings with projects, tags and participants
+-- Projects form a folder hierarchy (parent_id NULL at the top level); a meeting
+-- belongs to at most one project.
+CREATE TABLE IF NOT EXISTS projects (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    parent_id INTEGER REFERENCES projects(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Sibling projects have distinct names
+CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_sibling_name
+    ON projects(IFNULL(parent_id, 0), name COLLATE NOCASE);
+
+ALTER TABLE meetings ADD COLUMN project_id INTEGER REFERENCES projects(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_meetings_project ON meetings(project_id);
+
+-- Free-form labels shared across meetings
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    color TEXT,                       -- Optional display color, e.g. '#4f46e5'
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meeting_tags (
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (meeting_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_meeting_tags_tag ON meeting_tags(tag_id);
+
+-- People who attended a meeting, optionally linked to a registered speaker
+CREATE TABLE IF NOT EXISTS meeting_participants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    email TEXT,
+    speaker_id INTEGER REFERENCES speakers(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_meeting_participants_name
+    ON meeting_participants(meeting_id, name COLLATE NOCASE);
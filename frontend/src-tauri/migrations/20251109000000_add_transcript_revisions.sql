@@ -0,0 +1,21 @@
+-- Migration: Segment-level transcript edits with revision history
+-- Each edit (text, speaker, split, merge, or a revert of an earlier edit) stores the
+-- transcripts rows it replaced and the rows that replaced them, so it can be reviewed
+-- and undone.
+CREATE TABLE IF NOT EXISTS transcript_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL,
+    operation TEXT NOT NULL,          -- 'edit_text', 'set_speaker', 'split', 'merge', 'revert'
+    author TEXT,                      -- Who made the edit, NULL if unknown
+    before_segments TEXT NOT NULL,    -- JSON array of the rows before the edit
+    after_segments TEXT NOT NULL,     -- JSON array of the rows after the edit
+    reverts_id INTEGER,               -- For 'revert': the revision that was undone
+    reverted_by INTEGER,              -- Set once a later revert undoes this revision
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_revisions_meeting ON transcript_revisions(meeting_id, id);
+
+-- Set when the transcript was edited after the summary was generated
+ALTER TABLE summary_processes ADD COLUMN stale INTEGER NOT NULL DEFAULT 0;
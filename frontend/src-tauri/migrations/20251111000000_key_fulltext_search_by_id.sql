@@ -0,0 +1,143 @@
+-- Key the full-text indexes by meeting/transcript id instead of the implicit rowid.
+-- meetings, transcripts and summary_processes have TEXT primary keys, so their rowids
+-- are not stable (VACUUM may renumber them) and external-content indexes over them
+-- silently drift. Each index is now self-contained, and its rowid comes from a small
+-- INTEGER PRIMARY KEY table that maps it to the owning row's id.
+
+DROP TRIGGER IF EXISTS meetings_fts_ai;
+DROP TRIGGER IF EXISTS meetings_fts_ad;
+DROP TRIGGER IF EXISTS meetings_fts_au;
+DROP TRIGGER IF EXISTS transcripts_fts_ai;
+DROP TRIGGER IF EXISTS transcripts_fts_ad;
+DROP TRIGGER IF EXISTS transcripts_fts_au;
+DROP TRIGGER IF EXISTS summaries_fts_ai;
+DROP TRIGGER IF EXISTS summaries_fts_ad;
+DROP TRIGGER IF EXISTS summaries_fts_au;
+
+DROP TABLE IF EXISTS meetings_fts;
+DROP TABLE IF EXISTS transcripts_fts;
+DROP TABLE IF EXISTS summaries_fts;
+
+CREATE TABLE IF NOT EXISTS meetings_fts_ids (
+    id INTEGER PRIMARY KEY,
+    meeting_id TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS transcripts_fts_ids (
+    id INTEGER PRIMARY KEY,
+    transcript_id TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS summaries_fts_ids (
+    id INTEGER PRIMARY KEY,
+    meeting_id TEXT NOT NULL UNIQUE
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS meetings_fts USING fts5(
+    title,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    transcript,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS summaries_fts USING fts5(
+    summary,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Meetings
+CREATE TRIGGER IF NOT EXISTS meetings_fts_ai AFTER INSERT ON meetings BEGIN
+    INSERT OR IGNORE INTO meetings_fts_ids(meeting_id) VALUES (new.id);
+    INSERT INTO meetings_fts(rowid, title)
+    SELECT id, new.title FROM meetings_fts_ids WHERE meeting_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS meetings_fts_ad AFTER DELETE ON meetings BEGIN
+    DELETE FROM meetings_fts
+    WHERE rowid = (SELECT id FROM meetings_fts_ids WHERE meeting_id = old.id);
+    DELETE FROM meetings_fts_ids WHERE meeting_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS meetings_fts_au AFTER UPDATE OF title ON meetings BEGIN
+    DELETE FROM meetings_fts
+    WHERE rowid = (SELECT id FROM meetings_fts_ids WHERE meeting_id = old.id);
+    INSERT INTO meetings_fts(rowid, title)
+    SELECT id, new.title FROM meetings_fts_ids WHERE meeting_id = new.id;
+END;
+
+-- Transcripts
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ai AFTER INSERT ON transcripts BEGIN
+    INSERT OR IGNORE INTO transcripts_fts_ids(transcript_id) VALUES (new.id);
+    INSERT INTO transcripts_fts(rowid, transcript)
+    SELECT id, new.transcript FROM transcripts_fts_ids WHERE transcript_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_ad AFTER DELETE ON transcripts BEGIN
+    DELETE FROM transcripts_fts
+    WHERE rowid = (SELECT id FROM transcripts_fts_ids WHERE transcript_id = old.id);
+    DELETE FROM transcripts_fts_ids WHERE transcript_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_au AFTER UPDATE OF transcript ON transcripts BEGIN
+    DELETE FROM transcripts_fts
+    WHERE rowid = (SELECT id FROM transcripts_fts_ids WHERE transcript_id = old.id);
+    INSERT INTO transcripts_fts(rowid, transcript)
+    SELECT id, new.transcript FROM transcripts_fts_ids WHERE transcript_id = new.id;
+END;
+
+-- Summaries (JSON keys are not indexed, only the text values)
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ai AFTER INSERT ON summary_processes
+WHEN new.result IS NOT NULL BEGIN
+    INSERT OR IGNORE INTO summaries_fts_ids(meeting_id) VALUES (new.meeting_id);
+    INSERT INTO summaries_fts(rowid, summary)
+    SELECT id,
+        CASE WHEN json_valid(new.result)
+            THEN (SELECT group_concat(value, ' ') FROM json_tree(new.result) WHERE type = 'text')
+            ELSE new.result
+        END
+    FROM summaries_fts_ids WHERE meeting_id = new.meeting_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_ad AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM summaries_fts
+    WHERE rowid = (SELECT id FROM summaries_fts_ids WHERE meeting_id = old.meeting_id);
+    DELETE FROM summaries_fts_ids WHERE meeting_id = old.meeting_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_au AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM summaries_fts
+    WHERE rowid = (SELECT id FROM summaries_fts_ids WHERE meeting_id = old.meeting_id);
+    INSERT OR IGNORE INTO summaries_fts_ids(meeting_id)
+    SELECT new.meeting_id WHERE new.result IS NOT NULL;
+    INSERT INTO summaries_fts(rowid, summary)
+    SELECT id,
+        CASE WHEN json_valid(new.result)
+            THEN (SELECT group_concat(value, ' ') FROM json_tree(new.result) WHERE type = 'text')
+            ELSE new.result
+        END
+    FROM summaries_fts_ids
+    WHERE meeting_id = new.meeting_id AND new.result IS NOT NULL;
+END;
+
+-- Index existing data
+INSERT OR IGNORE INTO meetings_fts_ids(meeting_id) SELECT id FROM meetings;
+INSERT INTO meetings_fts(rowid, title)
+SELECT k.id, m.title FROM meetings m JOIN meetings_fts_ids k ON k.meeting_id = m.id;
+
+INSERT OR IGNORE INTO transcripts_fts_ids(transcript_id) SELECT id FROM transcripts;
+INSERT INTO transcripts_fts(rowid, transcript)
+SELECT k.id, t.transcript FROM transcripts t JOIN transcripts_fts_ids k ON k.transcript_id = t.id;
+
+INSERT OR IGNORE INTO summaries_fts_ids(meeting_id)
+SELECT meeting_id FROM summary_processes WHERE result IS NOT NULL;
+INSERT INTO summaries_fts(rowid, summary)
+SELECT k.id,
+    CASE WHEN json_valid(sp.result)
+        THEN (SELECT group_concat(value, ' ') FROM json_tree(sp.result) WHERE type = 'text')
+        ELSE sp.result
+    END
+FROM summary_processes sp JOIN summaries_fts_ids k ON k.meeting_id = sp.meeting_id
+WHERE sp.result IS NOT NULL;
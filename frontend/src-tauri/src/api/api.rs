@@ -5,10 +5,12 @@ use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    audio::transcription::WordTimestamp,
     database::{
-        models::MeetingModel,
+        models::{MeetingListRow, MeetingModel, ParticipantModel, TagModel},
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
+            meeting::MeetingsRepository, participant::ParticipantsRepository,
+            search::SearchRepository, setting::SettingsRepository, tag::TagsRepository,
             transcript::TranscriptsRepository,
         },
     },
@@ -45,6 +47,117 @@ pub struct TranscriptSearchResult {
     pub timestamp: String,
 }
 
+/// Optional filters for full-text meeting search
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    // Inclusive bounds on the meeting creation time (RFC 3339 or YYYY-MM-DD)
+    #[serde(default)]
+    pub from_date: Option<String>,
+    #[serde(default)]
+    pub to_date: Option<String>,
+    // Restrict the search to these meetings
+    #[serde(default)]
+    pub meeting_ids: Option<Vec<String>>,
+}
+
+/// A single highlighted match inside a meeting
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchMatch {
+    pub source: String, // "title", "transcript" or "summary"
+    pub source_id: String,
+    pub snippet: String, // Matched terms wrapped in <mark></mark>
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_start_time: Option<f64>,
+}
+
+/// A meeting matching a search query, with its best matches
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeetingSearchResult {
+    pub meeting_id: String,
+    pub title: String,
+    pub created_at: String,
+    pub score: f64, // BM25 relevance of the best match (higher is better)
+    pub match_count: i64,
+    pub matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResultsPage {
+    pub results: Vec<MeetingSearchResult>,
+    pub total: i64, // Total number of matching meetings
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// How to order a meeting list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingSort {
+    #[default]
+    Newest,
+    Oldest,
+    RecentlyUpdated,
+    Title,
+    Longest,
+    Shortest,
+}
+
+/// Filters, order and page for listing meetings. All filters are optional and combine with AND.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeetingQuery {
+    // Meetings carrying all of these tags (case-insensitive names)
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    // Meetings with a participant whose name contains this text
+    #[serde(default)]
+    pub participant: Option<String>,
+    // Meetings in this project or any of its sub-projects
+    #[serde(default)]
+    pub project_id: Option<i64>,
+    // Inclusive bounds on the meeting creation time (RFC 3339 or YYYY-MM-DD)
+    #[serde(default)]
+    pub from_date: Option<String>,
+    #[serde(default)]
+    pub to_date: Option<String>,
+    // Inclusive bounds on the recording length in seconds
+    #[serde(default)]
+    pub min_duration: Option<f64>,
+    #[serde(default)]
+    pub max_duration: Option<f64>,
+    #[serde(default)]
+    pub sort: MeetingSort,
+    // Page size (all meetings when None) and number of meetings to skip
+    #[serde(default)]
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub offset: Option<i64>,
+}
+
+/// A meeting in a list, with its project, tags and participants
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeetingListItem {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    pub tags: Vec<TagModel>,
+    pub participants: Vec<ParticipantModel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeetingListPage {
+    pub meetings: Vec<MeetingListItem>,
+    pub total: i64, // Total number of matching meetings
+    pub limit: i64,
+    pub offset: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProfileRequest {
     pub email: String,
@@ -100,6 +213,9 @@ pub struct TranscriptConfig {
     pub model: String,
     #[serde(rename = "apiKey")]
     pub api_key: Option<String>,
+    // Server URL for the openaiCompatible provider
+    #[serde(default)]
+    pub endpoint: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -136,6 +252,25 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Diarized speaker label (None if diarization was unavailable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<i64>,
+    // Registered speaker from the voice registry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker_profile_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker_name: Option<String>,
+    // Word-level timings for click-to-seek (None if the engine didn't provide them)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTimestamp>>,
+    // Language of the segment's text (None if unknown)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_probability: Option<f64>,
+    // Capture channel ("mic" or "system") when transcribed in dual-channel mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_source: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -168,6 +303,20 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    // Diarized speaker label (None if diarization was unavailable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker_profile_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTimestamp>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_probability: Option<f64>,
+    // Capture channel ("mic" or "system") when transcribed in dual-channel mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_source: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -298,25 +447,27 @@ async fn make_api_request<R: Runtime, T: for<'de> Deserialize<'de>>(
 
 // API Commands for Tauri
 
+/// Meeting ids and titles, newest first unless `query` filters or orders them differently
 #[tauri::command]
 pub async fn api_get_meetings<R: Runtime>(
     _app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     auth_token: Option<String>,
+    query: Option<MeetingQuery>,
 ) -> Result<Vec<Meeting>, String> {
     log_info!(
         "api_get_meetings called with auth_token(native) : {}",
         auth_token.is_some()
     );
     let pool = state.db_manager.pool();
-    let meetings: Result<Vec<MeetingModel>, sqlx::Error> =
-        MeetingsRepository::get_meetings(pool).await;
+    let meetings: Result<Vec<MeetingListRow>, sqlx::Error> =
+        MeetingsRepository::get_meetings(pool, &query.unwrap_or_default()).await;
 
     match meetings {
-        Ok(meeting_models) => {
-            log_info!("Successfully got {} meetings", meeting_models.len());
+        Ok(meeting_rows) => {
+            log_info!("Successfully got {} meetings", meeting_rows.len());
 
-            let result: Vec<Meeting> = meeting_models
+            let result: Vec<Meeting> = meeting_rows
                 .into_iter()
                 .map(|m| Meeting {
                     id: m.id,
@@ -332,6 +483,68 @@ pub async fn api_get_meetings<R: Runtime>(
     }
 }
 
+/// A page of meetings with their project, tags, participants and duration, filtered
+/// and sorted by `query` (50 per page unless `query.limit` says otherwise)
+#[tauri::command]
+pub async fn api_list_meetings(
+    state: tauri::State<'_, AppState>,
+    query: Option<MeetingQuery>,
+) -> Result<MeetingListPage, String> {
+    let mut query = query.unwrap_or_default();
+    query.limit = Some(query.limit.unwrap_or(50).clamp(1, 200));
+    query.offset = Some(query.offset.unwrap_or(0).max(0));
+    log_info!("api_list_meetings called with {:?}", query);
+
+    let pool = state.db_manager.pool();
+    let rows = MeetingsRepository::get_meetings(pool, &query)
+        .await
+        .map_err(|e| {
+            log_error!("Error listing meetings: {}", e);
+            format!("Failed to list meetings: {}", e)
+        })?;
+    // Counted separately so a page past the end still reports the matches
+    let total = MeetingsRepository::count_meetings(pool, &query)
+        .await
+        .map_err(|e| format!("Failed to count meetings: {}", e))?;
+
+    let ids: Vec<String> = rows.iter().map(|r| r.id.clone()).collect();
+    let mut tags = TagsRepository::tags_for_meetings(pool, &ids)
+        .await
+        .map_err(|e| format!("Failed to load tags: {}", e))?;
+    let mut participants = ParticipantsRepository::participants_for_meetings(pool, &ids)
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))?;
+
+    let meetings = rows
+        .into_iter()
+        .map(|row| {
+            let (meeting_tags, rest): (Vec<_>, Vec<_>) =
+                tags.drain(..).partition(|t| t.meeting_id == row.id);
+            tags = rest;
+            let (meeting_participants, rest): (Vec<_>, Vec<_>) =
+                participants.drain(..).partition(|p| p.meeting_id == row.id);
+            participants = rest;
+            MeetingListItem {
+                id: row.id,
+                title: row.title,
+                created_at: row.created_at.0.to_rfc3339(),
+                updated_at: row.updated_at.0.to_rfc3339(),
+                duration: row.duration,
+                project_id: row.project_id,
+                tags: meeting_tags.into_iter().map(|t| t.tag).collect(),
+                participants: meeting_participants,
+            }
+        })
+        .collect();
+
+    Ok(MeetingListPage {
+        meetings,
+        total,
+        limit: query.limit.unwrap_or(50),
+        offset: query.offset.unwrap_or(0),
+    })
+}
+
 #[tauri::command]
 pub async fn api_search_transcripts<R: Runtime>(
     _app: AppHandle<R>,
@@ -362,6 +575,43 @@ pub async fn api_search_transcripts<R: Runtime>(
     }
 }
 
+/// Full-text search across meeting titles, transcripts and summaries with BM25 ranking,
+/// highlighted snippets, optional date/meeting filters and pagination
+#[tauri::command]
+pub async fn api_search_meetings(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    filters: Option<SearchFilters>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<SearchResultsPage, String> {
+    log_info!(
+        "api_search_meetings called with query: '{}', limit: {:?}, offset: {:?}",
+        query,
+        limit,
+        offset
+    );
+
+    let pool = state.db_manager.pool();
+    let filters = filters.unwrap_or_default();
+
+    match SearchRepository::search_meetings(
+        pool,
+        &query,
+        &filters,
+        limit.unwrap_or(20),
+        offset.unwrap_or(0),
+    )
+    .await
+    {
+        Ok(page) => Ok(page),
+        Err(e) => {
+            log_error!("Error searching meetings for query '{}': {}", query, e);
+            Err(format!("Failed to search meetings: {}", e))
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_get_profile<R: Runtime>(
     app: AppHandle<R>,
@@ -591,6 +841,7 @@ pub async fn api_get_transcript_config<R: Runtime>(
                         provider: config.provider,
                         model: config.model,
                         api_key,
+                        endpoint: config.transcription_endpoint,
                     }))
                 }
                 Err(e) => {
@@ -609,6 +860,7 @@ pub async fn api_get_transcript_config<R: Runtime>(
                 provider: "localWhisper".to_string(),
                 model: "large-v3".to_string(),
                 api_key: None,
+                endpoint: None,
             }))
         }
         Err(e) => {
@@ -625,6 +877,7 @@ pub async fn api_save_transcript_config<R: Runtime>(
     provider: String,
     model: String,
     api_key: Option<String>,
+    endpoint: Option<String>,
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!(
@@ -638,6 +891,13 @@ pub async fn api_save_transcript_config<R: Runtime>(
         return Err(e.to_string());
     }
 
+    if let Some(endpoint) = endpoint {
+        if let Err(e) = SettingsRepository::save_transcript_endpoint(pool, endpoint.trim()).await {
+            log_error!("Failed to save transcription endpoint: {}", e);
+            return Err(e.to_string());
+        }
+    }
+
     if let Some(key) = api_key {
         if !key.is_empty() {
             log_info!("API key provided, saving for transcript provider...");
@@ -812,7 +1072,7 @@ pub async fn api_save_meeting_title<R: Runtime>(
 
 #[tauri::command]
 pub async fn api_save_transcript<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     meeting_title: String,
     transcripts: Vec<serde_json::Value>,
@@ -861,7 +1121,7 @@ pub async fn api_save_transcript<R: Runtime>(
         pool,
         &meeting_title,
         &transcripts_to_save,
-        folder_path,
+        folder_path.clone(),
     )
     .await
     {
@@ -870,6 +1130,9 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+            if let Some(folder) = &folder_path {
+                crate::diarization::batch::run_queued_rediarization(&app, &meeting_id, folder);
+            }
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
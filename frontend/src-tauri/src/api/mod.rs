@@ -1,5 +1,6 @@
 pub mod api;
 pub mod commands;
+pub mod organization;
 
 pub use api::*;
 // Don't re-export commands to avoid conflicts - lib.rs will import directly
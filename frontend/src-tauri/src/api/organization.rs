@@ -0,0 +1,177 @@
+// api/organization.rs
+//
+// Tauri commands for organizing meetings: tags, projects (a folder hierarchy) and
+// participant lists. Listing meetings by these is done by `api_list_meetings`.
+
+use crate::database::models::{ParticipantModel, ProjectModel, TagModel};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::participant::{ParticipantInput, ParticipantsRepository};
+use crate::database::repositories::project::ProjectsRepository;
+use crate::database::repositories::tag::TagsRepository;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info};
+
+// Tags
+
+#[tauri::command]
+pub async fn api_list_tags(state: tauri::State<'_, AppState>) -> Result<Vec<TagModel>, String> {
+    TagsRepository::list_tags(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load tags: {}", e))
+}
+
+/// Create a tag, or rename/recolor tag `id`. Returns the tag's id.
+#[tauri::command]
+pub async fn api_save_tag(
+    state: tauri::State<'_, AppState>,
+    id: Option<i64>,
+    name: String,
+    color: Option<String>,
+) -> Result<i64, String> {
+    log_info!("api_save_tag called for {:?} '{}'", id, name);
+    TagsRepository::save_tag(state.db_manager.pool(), id, &name, color.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Failed to save tag '{}': {}", name, e);
+            format!("Failed to save tag: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_delete_tag(state: tauri::State<'_, AppState>, id: i64) -> Result<bool, String> {
+    log_info!("api_delete_tag called for {}", id);
+    TagsRepository::delete_tag(state.db_manager.pool(), id)
+        .await
+        .map_err(|e| format!("Failed to delete tag: {}", e))
+}
+
+/// Replace a meeting's tags by name, creating new tags as needed
+#[tauri::command]
+pub async fn api_set_meeting_tags(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<TagModel>, String> {
+    log_info!("api_set_meeting_tags called for {} ({} tags)", meeting_id, tags.len());
+    TagsRepository::set_meeting_tags(state.db_manager.pool(), &meeting_id, &tags)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to set tags of {}: {}", meeting_id, e);
+            format!("Failed to save tags: {}", e)
+        })
+}
+
+// Projects
+
+#[tauri::command]
+pub async fn api_list_projects(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ProjectModel>, String> {
+    ProjectsRepository::list_projects(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load projects: {}", e))
+}
+
+/// Create a project under `parent_id` (top level when None). Returns its id.
+#[tauri::command]
+pub async fn api_create_project(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    parent_id: Option<i64>,
+) -> Result<i64, String> {
+    log_info!("api_create_project called for '{}' under {:?}", name, parent_id);
+    ProjectsRepository::create_project(state.db_manager.pool(), &name, parent_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to create project '{}': {}", name, e);
+            format!("Failed to create project: {}", e)
+        })
+}
+
+/// Rename a project and/or move it under another parent
+#[tauri::command]
+pub async fn api_update_project(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    name: String,
+    parent_id: Option<i64>,
+) -> Result<bool, String> {
+    log_info!("api_update_project called for {} ('{}' under {:?})", id, name, parent_id);
+    ProjectsRepository::update_project(state.db_manager.pool(), id, &name, parent_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to update project {}: {}", id, e);
+            format!("Failed to update project: {}", e)
+        })
+}
+
+/// Delete a project; its meetings and sub-projects move up to its parent
+#[tauri::command]
+pub async fn api_delete_project(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<bool, String> {
+    log_info!("api_delete_project called for {}", id);
+    ProjectsRepository::delete_project(state.db_manager.pool(), id)
+        .await
+        .map_err(|e| format!("Failed to delete project: {}", e))
+}
+
+/// Move a meeting into a project, or out of any project with `project_id` None
+#[tauri::command]
+pub async fn api_set_meeting_project(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    project_id: Option<i64>,
+) -> Result<bool, String> {
+    log_info!("api_set_meeting_project called for {} -> {:?}", meeting_id, project_id);
+    MeetingsRepository::set_meeting_project(state.db_manager.pool(), &meeting_id, project_id)
+        .await
+        .map_err(|e| format!("Failed to move meeting: {}", e))
+}
+
+// Participants
+
+#[tauri::command]
+pub async fn api_get_meeting_participants(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ParticipantModel>, String> {
+    ParticipantsRepository::list_participants(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))
+}
+
+/// Replace a meeting's participant list
+#[tauri::command]
+pub async fn api_set_meeting_participants(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    participants: Vec<ParticipantInput>,
+) -> Result<Vec<ParticipantModel>, String> {
+    log_info!(
+        "api_set_meeting_participants called for {} ({} participants)",
+        meeting_id,
+        participants.len()
+    );
+    ParticipantsRepository::set_meeting_participants(
+        state.db_manager.pool(),
+        &meeting_id,
+        &participants,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to set participants of {}: {}", meeting_id, e);
+        format!("Failed to save participants: {}", e)
+    })
+}
+
+/// Participant names used in earlier meetings, most frequent first
+#[tauri::command]
+pub async fn api_list_participant_names(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    ParticipantsRepository::list_participant_names(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load participant names: {}", e))
+}
@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use nnnoiseless::DenoiseState;
 
 use super::encode::encode_single_audio; // Correct path to encode module
+use super::encoder::EncoderSettings;
 
 /// Sanitize a filename to be safe for filesystem use
 pub fn sanitize_filename(name: &str) -> String {
@@ -597,7 +598,15 @@ pub fn write_audio_to_file(
     device: &str,
     skip_encoding: bool,
 ) -> Result<String> {
-    write_audio_to_file_with_meeting_name(audio, sample_rate, output_path, device, skip_encoding, None)
+    write_audio_to_file_with_meeting_name(
+        audio,
+        sample_rate,
+        output_path,
+        device,
+        skip_encoding,
+        None,
+        EncoderSettings::default(),
+    )
 }
 
 pub fn write_audio_to_file_with_meeting_name(
@@ -607,6 +616,7 @@ pub fn write_audio_to_file_with_meeting_name(
     device: &str,
     skip_encoding: bool,
     meeting_name: Option<&str>,
+    settings: EncoderSettings,
 ) -> Result<String> {
     let timestamp = Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string();
     let sanitized_device_name = device.replace(['/', '\\'], "_");
@@ -626,19 +636,21 @@ pub fn write_audio_to_file_with_meeting_name(
         output_path.clone()
     };
 
+    let settings = settings.usable();
     let file_path = final_output_path
-        .join(format!("{}_{}.mp4", sanitized_device_name, timestamp))
+        .join(format!("{}_{}.{}", sanitized_device_name, timestamp, settings.extension()))
         .to_str()
         .expect("Failed to create valid path")
         .to_string();
     let file_path_clone = file_path.clone();
-    // Run FFmpeg in a separate task
+    // Encode with the format's backend (in-process, or ffmpeg for AAC)
     if !skip_encoding {
         encode_single_audio(
             bytemuck::cast_slice(audio),
             sample_rate,
             1,
             &file_path.into(),
+            settings,
         )?;
     }
     Ok(file_path_clone)
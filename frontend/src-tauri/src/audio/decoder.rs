@@ -0,0 +1,239 @@
+// audio/decoder.rs
+//
+// Decode recorded or imported audio files back into f32 samples using symphonia.
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use super::audio_processing::{audio_to_mono, resample};
+use super::ffmpeg::find_ffmpeg_path;
+
+/// Mono audio decoded from a file
+#[derive(Debug, Clone)]
+pub struct DecodedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl DecodedAudio {
+    /// Duration of the decoded audio in seconds
+    pub fn duration_seconds(&self) -> f64 {
+        if self.sample_rate == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    /// Extract the samples between two recording-relative timestamps (in seconds)
+    pub fn slice_seconds(&self, start: f64, end: f64) -> &[f32] {
+        let start_idx = ((start.max(0.0) * self.sample_rate as f64) as usize).min(self.samples.len());
+        let end_idx = ((end.max(0.0) * self.sample_rate as f64) as usize).min(self.samples.len());
+        if end_idx <= start_idx {
+            return &[];
+        }
+        &self.samples[start_idx..end_idx]
+    }
+}
+
+/// Decode the first audio track of a file into mono f32 samples at its native sample rate
+pub fn decode_audio_file(path: &Path) -> Result<DecodedAudio> {
+    let mut samples = Vec::new();
+    let sample_rate = decode_audio_file_streaming(path, |packet, _| {
+        samples.extend_from_slice(packet);
+        Ok(())
+    })?;
+
+    Ok(DecodedAudio {
+        samples,
+        sample_rate,
+    })
+}
+
+/// Decode the first audio track of a file into mono f32 samples at its native sample rate,
+/// handing each decoded packet to `on_samples` (with the sample rate) instead of keeping
+/// the whole file in memory. Returns the sample rate.
+pub fn decode_audio_file_streaming<F>(path: &Path, mut on_samples: F) -> Result<u32>
+where
+    F: FnMut(&[f32], u32) -> Result<()>,
+{
+    let file = std::fs::File::open(path)
+        .map_err(|e| anyhow!("Failed to open audio file {}: {}", path.display(), e))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        mss,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    // Video containers list the video track first; pick the first track that carries audio
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL && t.codec_params.sample_rate.is_some())
+        .ok_or_else(|| anyhow!("No audio track found in {}", path.display()))?;
+    let track_id = track.id;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| anyhow!("Unknown sample rate in {}", path.display()))?;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut total_samples = 0usize;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let channels = spec.channels.count().max(1) as u16;
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                if channels == 1 {
+                    total_samples += buffer.samples().len();
+                    on_samples(buffer.samples(), sample_rate)?;
+                } else {
+                    let mono = audio_to_mono(buffer.samples(), channels);
+                    total_samples += mono.len();
+                    on_samples(&mono, sample_rate)?;
+                }
+            }
+            Err(SymphoniaError::DecodeError(e)) => {
+                // Corrupt packets are skipped rather than failing the whole file
+                warn!("Skipping undecodable packet in {}: {}", path.display(), e);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    info!(
+        "Decoded {}: {} samples at {}Hz ({:.1}s)",
+        path.display(),
+        total_samples,
+        sample_rate,
+        total_samples as f64 / sample_rate as f64
+    );
+
+    Ok(sample_rate)
+}
+
+/// Decode a file and resample it to 16kHz mono (the format expected by transcription and diarization)
+pub fn decode_audio_file_16k(path: &Path) -> Result<DecodedAudio> {
+    let decoded = decode_audio_file(path)?;
+    if decoded.sample_rate == 16000 {
+        return Ok(decoded);
+    }
+
+    let samples = resample(&decoded.samples, decoded.sample_rate, 16000)?;
+    Ok(DecodedAudio {
+        samples,
+        sample_rate: 16000,
+    })
+}
+
+/// Decode any audio or video file to 16kHz mono using the ffmpeg sidecar
+///
+/// Covers containers and codecs symphonia can't read (webm/opus, mkv, avi, wma...).
+pub fn decode_with_ffmpeg_16k(path: &Path) -> Result<DecodedAudio> {
+    let ffmpeg_path = find_ffmpeg_path()
+        .ok_or_else(|| anyhow!("FFmpeg not found; cannot decode {}", path.display()))?;
+
+    let mut command = Command::new(ffmpeg_path);
+    command
+        .arg("-i")
+        .arg(path)
+        .args(["-vn", "-ac", "1", "-ar", "16000", "-f", "f32le", "pipe:1"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "FFmpeg failed to decode {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).lines().last().unwrap_or("")
+        ));
+    }
+
+    let samples: Vec<f32> = output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    if samples.is_empty() {
+        return Err(anyhow!("No audio stream found in {}", path.display()));
+    }
+
+    info!(
+        "Decoded {} with FFmpeg: {} samples at 16000Hz ({:.1}s)",
+        path.display(),
+        samples.len(),
+        samples.len() as f64 / 16000.0
+    );
+
+    Ok(DecodedAudio {
+        samples,
+        sample_rate: 16000,
+    })
+}
+
+/// Decode an imported media file to 16kHz mono, falling back to FFmpeg when symphonia
+/// can't read the container or codec
+pub fn decode_media_file_16k(path: &Path) -> Result<DecodedAudio> {
+    match decode_audio_file_16k(path) {
+        Ok(decoded) if !decoded.samples.is_empty() => Ok(decoded),
+        Ok(_) => {
+            warn!("Symphonia decoded no samples from {}, trying FFmpeg", path.display());
+            decode_with_ffmpeg_16k(path)
+        }
+        Err(e) => {
+            // Symphonia has no Opus decoder; recordings saved as Opus are read in-process
+            #[cfg(feature = "native-opus")]
+            if let Ok(decoded) = super::native_encoder::decode_ogg_opus(path) {
+                return Ok(DecodedAudio {
+                    samples: resample(&decoded.samples, decoded.sample_rate, 16000)?,
+                    sample_rate: 16000,
+                });
+            }
+            warn!("Symphonia could not decode {} ({}), trying FFmpeg", path.display(), e);
+            decode_with_ffmpeg_16k(path)
+        }
+    }
+}
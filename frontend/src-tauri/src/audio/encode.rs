@@ -1,12 +1,8 @@
-use super::ffmpeg::find_ffmpeg_path; // Correct path to encode module
+use super::encoder::{encoder_for, EncoderSettings};
 use super::AudioDevice;
-use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::{
-    path::PathBuf,
-    process::{Command, Stdio},
-};
-use tracing::{debug, error};
+use tracing::debug;
 
 pub struct AudioInput {
     pub data: Arc<Vec<f32>>,
@@ -15,88 +11,38 @@ pub struct AudioInput {
     pub device: Arc<AudioDevice>,
 }
 
+/// Encode raw little-endian f32 PCM bytes into `output_path` with the given settings
 pub fn encode_single_audio(
     data: &[u8],
     sample_rate: u32,
     channels: u16,
     output_path: &PathBuf,
+    settings: EncoderSettings,
 ) -> anyhow::Result<()> {
-    debug!("Starting FFmpeg process for {} bytes of audio data", data.len());
+    debug!("Encoding {} bytes of audio data as {:?}", data.len(), settings.format);
 
     if data.is_empty() {
         return Err(anyhow::anyhow!("No audio data provided for encoding"));
     }
 
-    let ffmpeg_path = find_ffmpeg_path().ok_or_else(|| {
-        anyhow::anyhow!("FFmpeg not found. Please install FFmpeg to save recordings.")
-    })?;
+    // Copy out of the byte buffer, which need not be aligned for f32
+    let samples: Vec<f32> = data
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
 
-    debug!("Using FFmpeg at: {:?}", ffmpeg_path);
-
-    let mut command = Command::new(ffmpeg_path);
-    command
-        .args([
-            "-f",
-            "f32le",
-            "-ar",
-            &sample_rate.to_string(),
-            "-ac",
-            &channels.to_string(),
-            "-i",
-            "pipe:0",
-            "-c:a",
-            "aac",
-            "-b:a",
-            "192k", // Increased from 64k for better audio quality (especially for speech)
-            "-profile:a",
-            "aac_low", // Use AAC-LC profile for better compatibility
-            "-movflags",
-            "+faststart", // Optimize for web streaming
-            "-f",
-            "mp4",
-            output_path.to_str().unwrap(),
-        ])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
-
-    // Hide console window on Windows to prevent CMD popup during recording
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        command.creation_flags(CREATE_NO_WINDOW);
-    }
-
-    debug!("FFmpeg command: {:?}", command);
-
-    #[allow(clippy::zombie_processes)]
-    let mut ffmpeg = command.spawn().expect("Failed to spawn FFmpeg process");
-    debug!("FFmpeg process spawned");
-    let mut stdin = ffmpeg.stdin.take().expect("Failed to open stdin");
-
-    stdin.write_all(data)?;
-
-    debug!("Dropping stdin");
-    drop(stdin);
-    debug!("Waiting for FFmpeg process to exit");
-    let output = ffmpeg.wait_with_output().unwrap();
-    let status = output.status;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-
-    debug!("FFmpeg process exited with status: {}", status);
-    debug!("FFmpeg stdout: {}", stdout);
-    debug!("FFmpeg stderr: {}", stderr);
-
-    if !status.success() {
-        error!("FFmpeg process failed with status: {}", status);
-        error!("FFmpeg stderr: {}", stderr);
-        return Err(anyhow::anyhow!(
-            "FFmpeg process failed with status: {}",
-            status
-        ));
-    }
+    encoder_for(settings).encode(&samples, sample_rate, channels, output_path)
+}
 
-    Ok(())
+/// Interleave two mono recordings into one stereo file (left = first input, right = second).
+///
+/// Used for multitrack recordings so the microphone and system stems can be
+/// listened to together while staying separable by channel.
+pub fn merge_stereo_tracks(
+    left: &PathBuf,
+    right: &PathBuf,
+    output_path: &PathBuf,
+    settings: EncoderSettings,
+) -> anyhow::Result<()> {
+    encoder_for(settings).merge_stereo(left, right, output_path)
 }
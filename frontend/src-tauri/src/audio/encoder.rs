@@ -0,0 +1,423 @@
+// audio/encoder.rs
+//
+// Output formats for saved recordings. `RecordingPreferences.file_format` (plus optional
+// bitrate and sample rate) becomes `EncoderSettings`, and an `AudioEncoder` backend turns
+// f32 PCM into files of that format. The same backend merges checkpoint files, so the
+// incremental saver works for every format.
+//
+// WAV, FLAC and (with the `native-opus` feature) Opus are encoded in-process by
+// `NativeEncoder`; ffmpeg is only needed for AAC or formats this build cannot encode.
+
+use super::ffmpeg::find_ffmpeg_path;
+use super::native_encoder::NativeEncoder;
+use anyhow::{anyhow, Result};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Container/codec of saved recordings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// Uncompressed 16-bit PCM
+    Wav,
+    /// Lossless, roughly half the size of WAV
+    Flac,
+    /// Opus in an Ogg container, the smallest files for speech
+    Opus,
+    /// AAC-LC in MP4, the historical default
+    #[default]
+    Aac,
+}
+
+impl AudioFormat {
+    /// Parse a preference value or file extension ("mp4" and "m4a" mean AAC, "ogg" means Opus)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().trim_start_matches('.').to_ascii_lowercase().as_str() {
+            "wav" => Some(AudioFormat::Wav),
+            "flac" => Some(AudioFormat::Flac),
+            "opus" | "ogg" => Some(AudioFormat::Opus),
+            "aac" | "mp4" | "m4a" => Some(AudioFormat::Aac),
+            _ => None,
+        }
+    }
+
+    /// Format of an existing audio file, judged by its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|e| e.to_str()).and_then(Self::parse)
+    }
+
+    /// File extension used for this format (AAC keeps .mp4 for existing recordings)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Opus => "ogg",
+            AudioFormat::Aac => "mp4",
+        }
+    }
+
+    pub fn is_lossless(&self) -> bool {
+        matches!(self, AudioFormat::Wav | AudioFormat::Flac)
+    }
+
+    /// Default bitrate for lossy formats
+    fn default_bitrate_kbps(&self) -> Option<u32> {
+        match self {
+            AudioFormat::Opus => Some(32), // Transparent for speech
+            AudioFormat::Aac => Some(192),
+            _ => None,
+        }
+    }
+
+    /// Accepted bitrate range for lossy formats
+    fn bitrate_range(&self) -> Option<(u32, u32)> {
+        match self {
+            AudioFormat::Opus => Some((6, 510)),
+            AudioFormat::Aac => Some((32, 320)),
+            _ => None,
+        }
+    }
+
+    /// Output sample rates the codec supports
+    fn supported_sample_rates(&self) -> &'static [u32] {
+        match self {
+            AudioFormat::Opus => &[8000, 12000, 16000, 24000, 48000],
+            _ => &[8000, 16000, 22050, 24000, 32000, 44100, 48000],
+        }
+    }
+}
+
+/// Validated encoding parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncoderSettings {
+    pub format: AudioFormat,
+    /// Target bitrate for lossy formats, None for lossless ones
+    pub bitrate_kbps: Option<u32>,
+    /// Output sample rate, None to keep the input rate
+    pub sample_rate: Option<u32>,
+}
+
+impl Default for EncoderSettings {
+    fn default() -> Self {
+        Self {
+            format: AudioFormat::Aac,
+            bitrate_kbps: AudioFormat::Aac.default_bitrate_kbps(),
+            sample_rate: None,
+        }
+    }
+}
+
+impl EncoderSettings {
+    /// Build settings from preference values, filling in the format's defaults
+    pub fn new(file_format: &str, bitrate_kbps: Option<u32>, sample_rate: Option<u32>) -> Result<Self> {
+        let format = AudioFormat::parse(file_format).ok_or_else(|| {
+            anyhow!("Unsupported audio format '{}' (expected wav, flac, opus or aac)", file_format)
+        })?;
+
+        let bitrate_kbps = match (format.bitrate_range(), bitrate_kbps) {
+            (None, Some(_)) => {
+                return Err(anyhow!("{} is lossless and takes no bitrate", format.extension().to_uppercase()))
+            }
+            (None, None) => None,
+            (Some((min, max)), Some(kbps)) if !(min..=max).contains(&kbps) => {
+                return Err(anyhow!(
+                    "Bitrate {} kbps is out of range for {:?} ({}-{} kbps)",
+                    kbps, format, min, max
+                ))
+            }
+            (Some(_), bitrate) => bitrate.or(format.default_bitrate_kbps()),
+        };
+
+        if let Some(rate) = sample_rate {
+            if !format.supported_sample_rates().contains(&rate) {
+                return Err(anyhow!(
+                    "Sample rate {} Hz is not supported for {:?} (supported: {:?})",
+                    rate, format, format.supported_sample_rates()
+                ));
+            }
+        }
+
+        Ok(Self { format, bitrate_kbps, sample_rate })
+    }
+
+    /// Settings for a format with its default bitrate and the input sample rate
+    pub fn for_format(format: AudioFormat) -> Self {
+        Self {
+            format,
+            bitrate_kbps: format.default_bitrate_kbps(),
+            sample_rate: None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        self.format.extension()
+    }
+
+    /// Settings that can encode input at `input_rate`: the input rate is kept unless the
+    /// format cannot store it, in which case 48kHz is used
+    pub fn for_input_rate(self, input_rate: u32) -> Self {
+        let rate = self.sample_rate.unwrap_or(input_rate);
+        if self.format.supported_sample_rates().contains(&rate) {
+            return self;
+        }
+        Self { sample_rate: Some(48000), ..self }
+    }
+
+    /// Settings that can actually be encoded on this machine. Formats that need ffmpeg
+    /// fall back to FLAC when ffmpeg is missing, so a recording is never lost to it.
+    pub fn usable(self) -> Self {
+        if NativeEncoder::supports(self.format) || find_ffmpeg_path().is_some() {
+            return self;
+        }
+        warn!(
+            "FFmpeg not found, saving {:?} recording as FLAC instead",
+            self.format
+        );
+        let sample_rate = self
+            .sample_rate
+            .filter(|rate| AudioFormat::Flac.supported_sample_rates().contains(rate));
+        Self { sample_rate, ..Self::for_format(AudioFormat::Flac) }
+    }
+}
+
+/// Encoding backend for saved recordings
+pub trait AudioEncoder: Send + Sync {
+    /// Extension of checkpoint files, which may differ from the final format
+    fn checkpoint_extension(&self) -> &'static str;
+
+    /// Write one checkpoint of interleaved f32 samples; `concat` turns checkpoints into the final file
+    fn encode_checkpoint(&self, samples: &[f32], sample_rate: u32, channels: u16, output: &Path) -> Result<()> {
+        self.encode(samples, sample_rate, channels, output)
+    }
+
+    /// Encode interleaved f32 samples into `output`
+    fn encode(&self, samples: &[f32], sample_rate: u32, channels: u16, output: &Path) -> Result<()>;
+
+    /// Join checkpoint files (same settings) into `output`, in order
+    fn concat(&self, inputs: &[PathBuf], output: &Path) -> Result<()>;
+
+    /// Interleave two mono files into a stereo `output` (left = first, right = second)
+    fn merge_stereo(&self, left: &Path, right: &Path, output: &Path) -> Result<()>;
+}
+
+/// Encoder backend for the given settings, preferring the in-process one
+pub fn encoder_for(settings: EncoderSettings) -> Box<dyn AudioEncoder> {
+    if NativeEncoder::supports(settings.format) {
+        Box::new(NativeEncoder::new(settings))
+    } else {
+        Box::new(FfmpegEncoder::new(settings))
+    }
+}
+
+/// Encoder backed by the ffmpeg binary
+pub struct FfmpegEncoder {
+    settings: EncoderSettings,
+}
+
+impl FfmpegEncoder {
+    pub fn new(settings: EncoderSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Output arguments selecting codec, bitrate, sample rate and container
+    fn codec_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = match self.settings.format {
+            AudioFormat::Wav => vec!["-c:a", "pcm_s16le", "-f", "wav"],
+            AudioFormat::Flac => vec!["-c:a", "flac", "-f", "flac"],
+            AudioFormat::Opus => vec!["-c:a", "libopus", "-application", "voip", "-f", "ogg"],
+            AudioFormat::Aac => vec![
+                "-c:a", "aac",
+                "-profile:a", "aac_low", // Use AAC-LC profile for better compatibility
+                "-movflags", "+faststart", // Optimize for web streaming
+                "-f", "mp4",
+            ],
+        }
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+        if let Some(kbps) = self.settings.bitrate_kbps {
+            args.extend(["-b:a".to_string(), format!("{}k", kbps)]);
+        }
+        if let Some(rate) = self.settings.sample_rate {
+            args.extend(["-ar".to_string(), rate.to_string()]);
+        }
+        args
+    }
+
+    fn command() -> Result<Command> {
+        let ffmpeg_path = find_ffmpeg_path().ok_or_else(|| {
+            anyhow!("FFmpeg not found. Please install FFmpeg to save recordings.")
+        })?;
+        let mut command = Command::new(ffmpeg_path);
+
+        // Hide console window on Windows to prevent CMD popup during recording
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        Ok(command)
+    }
+
+    fn run(mut command: Command, what: &str) -> Result<()> {
+        debug!("FFmpeg {} command: {:?}", what, command);
+        let output = command.stdout(Stdio::piped()).stderr(Stdio::piped()).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            error!("FFmpeg {} failed: {}", what, stderr);
+            return Err(anyhow!("FFmpeg {} failed with status: {}", what, output.status));
+        }
+        Ok(())
+    }
+}
+
+impl AudioEncoder for FfmpegEncoder {
+    fn checkpoint_extension(&self) -> &'static str {
+        self.settings.extension()
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32, channels: u16, output: &Path) -> Result<()> {
+        if samples.is_empty() {
+            return Err(anyhow!("No audio data provided for encoding"));
+        }
+
+        let mut command = Self::command()?;
+        command
+            .args(["-y", "-f", "f32le", "-ar", &sample_rate.to_string(), "-ac", &channels.to_string()])
+            .args(["-i", "pipe:0"])
+            .args(self.codec_args())
+            .arg(output)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        debug!("FFmpeg encode command: {:?}", command);
+
+        let mut ffmpeg = command.spawn()?;
+        let mut stdin = ffmpeg
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("Failed to open FFmpeg stdin"))?;
+        stdin.write_all(bytemuck::cast_slice(samples))?;
+        drop(stdin);
+
+        let result = ffmpeg.wait_with_output()?;
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+            error!("FFmpeg encode failed: {}", stderr);
+            return Err(anyhow!("FFmpeg process failed with status: {}", result.status));
+        }
+        Ok(())
+    }
+
+    fn concat(&self, inputs: &[PathBuf], output: &Path) -> Result<()> {
+        let list_file = output.with_extension("concat.txt");
+        let mut list_content = String::new();
+        for input in inputs {
+            // Use absolute path for FFmpeg (required for safe mode)
+            let abs_path = input.canonicalize()?;
+            list_content.push_str(&format!("file '{}'\n", abs_path.display()));
+        }
+        std::fs::write(&list_file, list_content)?;
+
+        let mut command = Self::command()?;
+        command.args(["-y", "-f", "concat", "-safe", "0", "-i"]).arg(&list_file);
+        if self.settings.format.is_lossless() {
+            // Re-encoding lossless audio is cheap and rewrites the length headers correctly
+            command.args(self.codec_args());
+        } else {
+            // Copy codec - no re-encoding, no generation loss
+            command.args(["-c", "copy"]);
+        }
+        command.arg(output);
+
+        let result = Self::run(command, "concat");
+        let _ = std::fs::remove_file(&list_file);
+        result?;
+
+        info!("Merged {} files into {}", inputs.len(), output.display());
+        Ok(())
+    }
+
+    fn merge_stereo(&self, left: &Path, right: &Path, output: &Path) -> Result<()> {
+        let mut command = Self::command()?;
+        command
+            .args(["-y", "-i"])
+            .arg(left)
+            .arg("-i")
+            .arg(right)
+            .args(["-filter_complex", "[0:a][1:a]amerge=inputs=2[a]", "-map", "[a]"])
+            .args(self.codec_args())
+            .arg(output);
+        Self::run(command, "stereo merge")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_formats_and_aliases() {
+        assert_eq!(AudioFormat::parse("MP4"), Some(AudioFormat::Aac));
+        assert_eq!(AudioFormat::parse("m4a"), Some(AudioFormat::Aac));
+        assert_eq!(AudioFormat::parse(".ogg"), Some(AudioFormat::Opus));
+        assert_eq!(AudioFormat::parse("flac"), Some(AudioFormat::Flac));
+        assert_eq!(AudioFormat::parse("mp3"), None);
+        assert_eq!(AudioFormat::from_path(Path::new("meeting/audio.wav")), Some(AudioFormat::Wav));
+    }
+
+    #[test]
+    fn test_fills_defaults_and_validates_options() {
+        let opus = EncoderSettings::new("opus", None, Some(16000)).unwrap();
+        assert_eq!(opus.bitrate_kbps, Some(32));
+        assert_eq!(opus.extension(), "ogg");
+
+        let flac = EncoderSettings::new("flac", None, None).unwrap();
+        assert_eq!(flac.bitrate_kbps, None);
+
+        assert!(EncoderSettings::new("flac", Some(128), None).is_err());
+        assert!(EncoderSettings::new("aac", Some(1000), None).is_err());
+        assert!(EncoderSettings::new("opus", None, Some(44100)).is_err());
+        assert!(EncoderSettings::new("mp3", None, None).is_err());
+    }
+
+    #[test]
+    fn test_default_matches_the_previous_hardcoded_output() {
+        let settings = EncoderSettings::default();
+        assert_eq!(settings, EncoderSettings::new("mp4", None, None).unwrap());
+        let args = FfmpegEncoder::new(settings).codec_args().join(" ");
+        assert!(args.contains("-c:a aac") && args.contains("-b:a 192k"));
+    }
+
+    #[test]
+    fn test_input_rate_is_kept_when_the_format_supports_it() {
+        let flac = EncoderSettings::for_format(AudioFormat::Flac);
+        assert_eq!(flac.for_input_rate(44100), flac);
+
+        let opus = EncoderSettings::for_format(AudioFormat::Opus);
+        assert_eq!(opus.for_input_rate(16000), opus);
+        assert_eq!(opus.for_input_rate(44100).sample_rate, Some(48000));
+    }
+
+    #[test]
+    fn test_lossless_formats_never_need_ffmpeg() {
+        for format in [AudioFormat::Wav, AudioFormat::Flac] {
+            let settings = EncoderSettings::for_format(format);
+            assert_eq!(settings.usable(), settings);
+        }
+
+        let usable = EncoderSettings::default().usable();
+        if find_ffmpeg_path().is_none() {
+            assert_eq!(usable.format, AudioFormat::Flac);
+            assert_eq!(usable.bitrate_kbps, None);
+        } else {
+            assert_eq!(usable, EncoderSettings::default());
+        }
+    }
+}
@@ -0,0 +1,437 @@
+// audio/import.rs
+//
+// Import existing audio or video files (cloud meeting recordings, voice memos) as
+// meetings. The file is decoded, split into speech segments with VAD and transcribed
+// with the configured engine, producing the same folder layout and database rows as a
+// live recording.
+
+use crate::api::TranscriptSegment as DbTranscriptSegment;
+use crate::audio::audio_processing::create_meeting_folder;
+use crate::audio::decoder::{decode_audio_file_streaming, decode_media_file_16k};
+use crate::audio::encoder::EncoderSettings;
+use crate::audio::incremental_saver::IncrementalAudioSaver;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::audio::recording_saver::{
+    write_meeting_metadata, write_transcripts_json_file, DeviceInfo, MeetingMetadata,
+    TranscriptSegment,
+};
+use crate::audio::transcription::provider::offset_words;
+use crate::audio::transcription::worker::transcribe_chunk_with_provider;
+use crate::audio::transcription::{
+    get_or_init_transcription_engine, validate_transcription_model_ready, TranscriptResult,
+    TranscriptionEngine,
+};
+use crate::audio::vad::get_speech_chunks;
+use crate::audio::{AudioChunk, RecordingDeviceType};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// VAD redemption time, matching the live pipeline
+const IMPORT_VAD_REDEMPTION_MS: u32 = 400;
+
+/// Longest slice handed to the engine at once (Whisper's window is 30s)
+const MAX_SEGMENT_SECONDS: usize = 30;
+
+/// Speech segments shorter than this (100ms at 16kHz) are skipped
+const MIN_SEGMENT_SAMPLES: usize = 1600;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub meeting_id: String,
+    pub meeting_name: String,
+    pub folder_path: String,
+    pub duration_seconds: f64,
+    pub segments: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ImportProgress {
+    file_path: String,
+    stage: String,
+    progress: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meeting_id: Option<String>,
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, file_path: &str, stage: &str, progress: u32) {
+    let _ = app.emit(
+        "import-progress",
+        ImportProgress {
+            file_path: file_path.to_string(),
+            stage: stage.to_string(),
+            progress,
+            meeting_id: None,
+        },
+    );
+}
+
+/// Format a recording offset as "[MM:SS]", or "[HH:MM:SS]" past the first hour
+pub(crate) fn format_offset(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (hours, minutes, secs) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("[{:02}:{:02}:{:02}]", hours, minutes, secs)
+    } else {
+        format!("[{:02}:{:02}]", minutes, secs)
+    }
+}
+
+/// Split `len` samples at 16kHz into ranges of at most `MAX_SEGMENT_SECONDS`
+fn split_ranges(len: usize) -> Vec<(usize, usize)> {
+    let max = MAX_SEGMENT_SECONDS * 16000;
+    (0..len)
+        .step_by(max)
+        .map(|start| (start, (start + max).min(len)))
+        .collect()
+}
+
+/// Store the imported audio in the meeting folder, encoded like a live recording in the
+/// configured format (video is dropped). Returns the file name, the settings used and the
+/// stored sample rate.
+///
+/// The source is decoded at its native sample rate and streamed through an
+/// `IncrementalAudioSaver`, so only one checkpoint's worth of samples is held at a time.
+/// When symphonia cannot decode the file, the 16kHz ffmpeg decode is stored instead.
+fn store_audio(
+    source: &Path,
+    folder: &Path,
+    settings: EncoderSettings,
+) -> anyhow::Result<(String, EncoderSettings, u32)> {
+    let checkpoints = folder.join(".checkpoints");
+    std::fs::create_dir_all(&checkpoints)?;
+
+    let mut saver: Option<(IncrementalAudioSaver, EncoderSettings, u32)> = None;
+    let streamed = decode_audio_file_streaming(source, |samples, rate| {
+        if saver.is_none() {
+            let settings = settings.for_input_rate(rate);
+            let track = IncrementalAudioSaver::for_track(folder.to_path_buf(), rate, "audio", settings)?;
+            saver = Some((track, settings, rate));
+        }
+        match saver.as_mut() {
+            Some((track, _, _)) => track.add_chunk(saver_chunk(samples, rate)),
+            None => Ok(()),
+        }
+    });
+
+    let (mut track, settings, rate) = match (streamed, saver) {
+        (Ok(_), Some(saver)) => saver,
+        (streamed, _) => {
+            if let Err(e) = streamed {
+                log_warn!("Falling back to ffmpeg to store {}: {}", source.display(), e);
+            }
+            // Drop any checkpoints written before the native decode gave up
+            std::fs::remove_dir_all(&checkpoints)?;
+            std::fs::create_dir_all(&checkpoints)?;
+
+            let audio = decode_media_file_16k(source)?;
+            let settings = settings.for_input_rate(audio.sample_rate);
+            let mut track =
+                IncrementalAudioSaver::for_track(folder.to_path_buf(), audio.sample_rate, "audio", settings)?;
+            for samples in audio.samples.chunks(audio.sample_rate as usize) {
+                track.add_chunk(saver_chunk(samples, audio.sample_rate))?;
+            }
+            (track, settings, audio.sample_rate)
+        }
+    };
+
+    let path = tokio::runtime::Handle::current().block_on(track.finalize())?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("audio.{}", settings.extension()));
+    Ok((file_name, settings, settings.sample_rate.unwrap_or(rate)))
+}
+
+/// Wrap decoded samples for the incremental saver
+fn saver_chunk(samples: &[f32], sample_rate: u32) -> AudioChunk {
+    AudioChunk {
+        data: samples.to_vec(),
+        sample_rate,
+        timestamp: 0.0,
+        chunk_id: 0,
+        device_type: RecordingDeviceType::Microphone,
+    }
+}
+
+/// Import an audio or video file as a new meeting
+///
+/// Emits `import-progress` events (decoding, transcribing, saving, complete) and returns
+/// the created meeting. Nothing is left behind on disk if the import fails.
+#[tauri::command]
+pub async fn api_import_meeting_file<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    file_path: String,
+    meeting_name: Option<String>,
+) -> Result<ImportResult, String> {
+    log_info!("api_import_meeting_file called for {}", file_path);
+    let source = PathBuf::from(&file_path);
+    if !source.is_file() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let meeting_name = meeting_name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .or_else(|| source.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "Imported Meeting".to_string());
+
+    validate_transcription_model_ready(&app).await?;
+    let engine = get_or_init_transcription_engine(&app).await?;
+
+    emit_progress(&app, &file_path, "decoding", 0);
+    let decode_path = source.clone();
+    let audio = tokio::task::spawn_blocking(move || decode_media_file_16k(&decode_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to decode {}: {}", file_path, e))?;
+    let duration_seconds = audio.duration_seconds();
+
+    emit_progress(&app, &file_path, "detecting_speech", 5);
+    let samples = audio.samples;
+    let speech = tokio::task::spawn_blocking(move || {
+        get_speech_chunks(&samples, IMPORT_VAD_REDEMPTION_MS)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Speech detection failed: {}", e))?;
+    log_info!(
+        "Import of {}: {:.1}s of audio, {} speech segments",
+        file_path,
+        duration_seconds,
+        speech.len()
+    );
+
+    let preferences = load_recording_preferences(&app)
+        .await
+        .map_err(|e| format!("Failed to load recording preferences: {}", e))?;
+    let folder = create_meeting_folder(&preferences.save_folder, &meeting_name)
+        .map_err(|e| format!("Failed to create meeting folder: {}", e))?;
+    let _ = std::fs::remove_dir(folder.join(".checkpoints"));
+    // Without ffmpeg, formats it would encode fall back to FLAC, as for live recordings
+    let settings = preferences.encoder_settings_or_default().usable();
+
+    let result = import_into_folder(
+        &app,
+        &state,
+        &engine,
+        &source,
+        &folder,
+        &meeting_name,
+        duration_seconds,
+        speech,
+        settings,
+    )
+    .await;
+
+    match result {
+        Ok(result) => {
+            let _ = app.emit(
+                "import-progress",
+                ImportProgress {
+                    file_path: file_path.clone(),
+                    stage: "complete".to_string(),
+                    progress: 100,
+                    meeting_id: Some(result.meeting_id.clone()),
+                },
+            );
+            log_info!(
+                "Imported {} as meeting {} ({} segments)",
+                file_path,
+                result.meeting_id,
+                result.segments
+            );
+            Ok(result)
+        }
+        Err(e) => {
+            log_error!("Import of {} failed: {}", file_path, e);
+            if let Err(cleanup) = std::fs::remove_dir_all(&folder) {
+                log_warn!("Failed to remove {}: {}", folder.display(), cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn import_into_folder<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+    engine: &TranscriptionEngine,
+    source: &Path,
+    folder: &Path,
+    meeting_name: &str,
+    duration_seconds: f64,
+    speech: Vec<crate::audio::vad::SpeechSegment>,
+    settings: EncoderSettings,
+) -> Result<ImportResult, String> {
+    let file_path = source.to_string_lossy().to_string();
+
+    // Same acceptance rule as the live worker
+    let confidence_threshold = match engine {
+        TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
+        TranscriptionEngine::Parakeet(_) => 0.0,
+    };
+
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    let mut last_error = None;
+    let total = speech.len().max(1);
+    for (i, segment) in speech.into_iter().enumerate() {
+        let segment_start = segment.start_timestamp_ms / 1000.0;
+        for (start, end) in split_ranges(segment.samples.len()) {
+            if end - start < MIN_SEGMENT_SAMPLES {
+                continue;
+            }
+
+            let audio_start_time = segment_start + start as f64 / 16000.0;
+            let duration = (end - start) as f64 / 16000.0;
+            let chunk = AudioChunk {
+                data: segment.samples[start..end].to_vec(),
+                sample_rate: 16000,
+                timestamp: audio_start_time,
+                chunk_id: segments.len() as u64,
+                device_type: RecordingDeviceType::Microphone,
+            };
+
+            let TranscriptResult { text, confidence, mut words, language, language_probability, .. } =
+                match transcribe_chunk_with_provider(engine, chunk, app).await {
+                    Ok(result) => result,
+                    Err(e) => {
+                        log_warn!("Skipping segment at {:.1}s: {}", audio_start_time, e);
+                        last_error = Some(e.to_string());
+                        continue;
+                    }
+                };
+            if text.trim().is_empty() || confidence.is_some_and(|c| c < confidence_threshold) {
+                continue;
+            }
+            offset_words(&mut words, audio_start_time);
+
+            let sequence_id = segments.len() as u64;
+            segments.push(TranscriptSegment {
+                id: format!("seg_{}", sequence_id),
+                text,
+                audio_start_time,
+                audio_end_time: audio_start_time + duration,
+                duration,
+                display_time: format_offset(audio_start_time),
+                confidence: confidence.unwrap_or(0.85),
+                sequence_id,
+                speaker_id: None,
+                speaker_profile_id: None,
+                speaker_name: None,
+                words,
+                language,
+                language_probability,
+                audio_source: None,
+            });
+        }
+
+        let progress = 10 + ((i + 1) * 80 / total) as u32;
+        emit_progress(app, &file_path, "transcribing", progress);
+    }
+
+    if segments.is_empty() {
+        return Err(match last_error {
+            Some(e) => format!("Transcription failed: {}", e),
+            None => "No speech was found in the imported file".to_string(),
+        });
+    }
+
+    emit_progress(app, &file_path, "saving", 92);
+    let store_source = source.to_path_buf();
+    let store_folder = folder.to_path_buf();
+    let (audio_file, settings, sample_rate) =
+        tokio::task::spawn_blocking(move || store_audio(&store_source, &store_folder, settings))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))?
+            .map_err(|e| format!("Failed to store imported audio: {}", e))?;
+
+    write_transcripts_json_file(folder, &segments)
+        .map_err(|e| format!("Failed to save transcripts: {}", e))?;
+
+    let db_segments: Vec<DbTranscriptSegment> = segments
+        .iter()
+        .map(|s| DbTranscriptSegment {
+            id: s.id.clone(),
+            text: s.text.clone(),
+            timestamp: s.display_time.clone(),
+            audio_start_time: Some(s.audio_start_time),
+            audio_end_time: Some(s.audio_end_time),
+            duration: Some(s.duration),
+            speaker_id: None,
+            speaker_profile_id: None,
+            words: (!s.words.is_empty()).then(|| s.words.clone()),
+            language: s.language.clone(),
+            language_probability: s.language_probability.map(f64::from),
+            audio_source: None,
+        })
+        .collect();
+    let folder_path = folder.to_string_lossy().to_string();
+    let meeting_id = TranscriptsRepository::save_transcript(
+        state.db_manager.pool(),
+        meeting_name,
+        &db_segments,
+        Some(folder_path.clone()),
+    )
+    .await
+    .map_err(|e| format!("Failed to save meeting: {}", e))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let metadata = MeetingMetadata {
+        version: "1.0".to_string(),
+        meeting_id: Some(meeting_id.clone()),
+        meeting_name: Some(meeting_name.to_string()),
+        created_at: now.clone(),
+        completed_at: Some(now),
+        duration_seconds: Some(duration_seconds),
+        devices: DeviceInfo {
+            microphone: None,
+            system_audio: None,
+        },
+        audio_file,
+        transcript_file: "transcripts.json".to_string(),
+        sample_rate,
+        status: "completed".to_string(),
+        stems: None,
+        encoder: Some(settings),
+    };
+    if let Err(e) = write_meeting_metadata(folder, &metadata) {
+        // The meeting row already exists; a missing metadata.json only loses the audio file name
+        log_warn!("Failed to write metadata.json for {}: {}", meeting_id, e);
+    }
+
+    Ok(ImportResult {
+        meeting_id,
+        meeting_name: meeting_name.to_string(),
+        folder_path,
+        duration_seconds,
+        segments: segments.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(0.0), "[00:00]");
+        assert_eq!(format_offset(135.7), "[02:15]");
+        assert_eq!(format_offset(3725.0), "[01:02:05]");
+    }
+
+    #[test]
+    fn test_split_ranges_caps_segment_length() {
+        assert_eq!(split_ranges(16000), vec![(0, 16000)]);
+        let ranges = split_ranges(16000 * 70);
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[1], (480_000, 960_000));
+        assert_eq!(ranges[2], (960_000, 1_120_000));
+        assert!(split_ranges(0).is_empty());
+    }
+}
@@ -1,13 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::{Result, anyhow};
-use log::{info, warn, error};
-use super::encode::encode_single_audio;
+use log::{debug, info, warn};
+use super::encoder::{encoder_for, AudioEncoder, EncoderSettings};
 use super::recording_state::AudioChunk;
 
-#[cfg (target_os = "macos")]
-use super::ffmpeg::find_ffmpeg_path;
-
-/// Audio data without device type (we only store mixed audio)
+/// Audio data without device type (each saver stores a single track)
 #[derive(Clone)]
 struct AudioData {
     data: Vec<f32>,
@@ -16,13 +13,24 @@ struct AudioData {
 
 /// Incremental audio saver that writes checkpoints every 30 seconds
 /// to minimize memory usage and enable crash recovery
+///
+/// Each saver writes one track: the mix ("audio") or a per-source stem ("mic", "system").
+/// Checkpoints are named `<track>_chunk_NNN.<ext>` so several savers can share the
+/// meeting's .checkpoints/ directory, and the final file is `<track>.<ext>`, where the
+/// extension follows the configured output format. Checkpoints use the encoder's
+/// checkpoint extension, which is lossless FLAC when Opus is encoded in-process.
 pub struct IncrementalAudioSaver {
+    track: String,
+    encoder: Box<dyn AudioEncoder>,
+    extension: &'static str,
     checkpoint_buffer: Vec<AudioData>,
     checkpoint_interval_samples: usize,  // 30s at 48kHz = 1,440,000 samples
     checkpoint_count: u32,
     checkpoints_dir: PathBuf,
     meeting_folder: PathBuf,
     sample_rate: u32,
+    resumed: bool,                        // Reopened after a crash by `resume`
+    dropped_checkpoint: Option<PathBuf>,  // Damaged last checkpoint left out of the merge
 }
 
 impl IncrementalAudioSaver {
@@ -32,6 +40,16 @@ impl IncrementalAudioSaver {
     /// * `meeting_folder` - Path to the meeting folder (contains .checkpoints/)
     /// * `sample_rate` - Sample rate of audio (typically 48000)
     pub fn new(meeting_folder: PathBuf, sample_rate: u32) -> Result<Self> {
+        Self::for_track(meeting_folder, sample_rate, "audio", EncoderSettings::default())
+    }
+
+    /// Create a saver for a named track (e.g. "mic" or "system" stems) in a given format
+    pub fn for_track(
+        meeting_folder: PathBuf,
+        sample_rate: u32,
+        track: &str,
+        settings: EncoderSettings,
+    ) -> Result<Self> {
         let checkpoints_dir = meeting_folder.join(".checkpoints");
 
         // Verify checkpoints directory exists
@@ -40,15 +58,41 @@ impl IncrementalAudioSaver {
         }
 
         Ok(Self {
+            track: track.to_string(),
+            encoder: encoder_for(settings),
+            extension: settings.extension(),
             checkpoint_buffer: Vec::new(),
             checkpoint_interval_samples: sample_rate as usize * 30, // 30 seconds
             checkpoint_count: 0,
             checkpoints_dir,
             meeting_folder,
             sample_rate,
+            resumed: false,
+            dropped_checkpoint: None,
         })
     }
 
+    /// Reopen a track left behind by an interrupted recording
+    ///
+    /// Picks up the checkpoints already on disk so `finalize` merges them as usual.
+    /// Counting stops at the first missing or empty file, which drops a checkpoint
+    /// that was still being written when the app went down. A last checkpoint that is
+    /// non-empty but truncated is left out by `finalize` if merging fails with it, and
+    /// kept on disk (see `dropped_checkpoint`) until the user decides to delete it.
+    pub fn resume(
+        meeting_folder: PathBuf,
+        sample_rate: u32,
+        track: &str,
+        settings: EncoderSettings,
+    ) -> Result<Self> {
+        let mut saver = Self::for_track(meeting_folder, sample_rate, track, settings)?;
+        let extension = saver.encoder.checkpoint_extension();
+        saver.checkpoint_count = count_checkpoints(&saver.checkpoints_dir, track, extension);
+        saver.resumed = true;
+        info!("Resumed {} track with {} checkpoints", track, saver.checkpoint_count);
+        Ok(saver)
+    }
+
     /// Add an audio chunk to the buffer
     /// Automatically saves a checkpoint when buffer reaches 30 seconds
     pub fn add_chunk(&mut self, chunk: AudioChunk) -> Result<()> {
@@ -89,12 +133,11 @@ impl IncrementalAudioSaver {
         }
 
         // Generate checkpoint filename
-        let checkpoint_path = self.checkpoints_dir
-            .join(format!("audio_chunk_{:03}.mp4", self.checkpoint_count));
+        let checkpoint_path = self.checkpoint_path(self.checkpoint_count);
 
         // Encode and save checkpoint
-        encode_single_audio(
-            bytemuck::cast_slice(&audio_data),
+        self.encoder.encode_checkpoint(
+            &audio_data,
             self.sample_rate,
             1,  // mono
             &checkpoint_path
@@ -103,7 +146,8 @@ impl IncrementalAudioSaver {
         let duration_seconds = audio_data.len() as f32 / self.sample_rate as f32;
         self.checkpoint_count += 1;
 
-        info!("💾 Saved checkpoint {}: {:.2}s of audio ({} samples)",
+        info!("💾 Saved {} checkpoint {}: {:.2}s of audio ({} samples)",
+              self.track,
               self.checkpoint_count,
               duration_seconds,
               audio_data.len());
@@ -111,11 +155,16 @@ impl IncrementalAudioSaver {
         Ok(())
     }
 
+    fn checkpoint_path(&self, index: u32) -> PathBuf {
+        self.checkpoints_dir
+            .join(format!("{}_chunk_{:03}.{}", self.track, index, self.encoder.checkpoint_extension()))
+    }
+
     /// Finalize the recording: save final checkpoint, merge all checkpoints, cleanup
     ///
-    /// Returns the path to the final merged audio.mp4 file
+    /// Returns the path to the final merged `<track>.<ext>` file
     pub async fn finalize(&mut self) -> Result<PathBuf> {
-        info!("Finalizing incremental recording...");
+        info!("Finalizing incremental {} recording...", self.track);
 
         // Save final buffer if not empty
         if !self.checkpoint_buffer.is_empty() {
@@ -128,15 +177,35 @@ impl IncrementalAudioSaver {
             return Err(anyhow!("No audio checkpoints to merge - recording may have failed"));
         }
 
-        // Merge all checkpoints using FFmpeg concat
-        let final_audio_path = self.meeting_folder.join("audio.mp4");
-        self.merge_checkpoints(&final_audio_path).await?;
+        // Merge all checkpoints with the format's encoder
+        let final_audio_path = self
+            .meeting_folder
+            .join(format!("{}.{}", self.track, self.extension));
+        let mut merged = self.checkpoint_count;
+        if let Err(e) = self.merge_checkpoints(&final_audio_path, merged).await {
+            // A crash mid-write can leave the last checkpoint of a resumed recording
+            // non-empty but truncated, which would make every merge attempt fail; retry
+            // without it. A live recording wrote all its checkpoints itself, so a failure
+            // there is real and nothing is dropped.
+            if !self.resumed || merged < 2 {
+                return Err(e);
+            }
+            warn!("Merge failed ({}), retrying without the last {} checkpoint", e, self.track);
+            merged -= 1;
+            self.merge_checkpoints(&final_audio_path, merged).await?;
+            self.dropped_checkpoint = Some(self.checkpoint_path(merged));
+        }
 
-        // Clean up checkpoints directory
-        info!("Cleaning up {} checkpoint files", self.checkpoint_count);
-        if let Err(e) = std::fs::remove_dir_all(&self.checkpoints_dir) {
-            warn!("Failed to clean up checkpoints directory: {}", e);
-            // Non-fatal - user can manually delete
+        // Clean up the merged checkpoints; the directory goes once no other track uses it
+        info!("Cleaning up {} {} checkpoint files", merged, self.track);
+        for i in 0..merged {
+            if let Err(e) = std::fs::remove_file(self.checkpoint_path(i)) {
+                warn!("Failed to remove checkpoint {}: {}", i, e);
+                // Non-fatal - user can manually delete
+            }
+        }
+        if std::fs::remove_dir(&self.checkpoints_dir).is_err() {
+            debug!("Checkpoints directory still in use by other tracks");
         }
 
         info!("✅ Finalized recording: {}", final_audio_path.display());
@@ -144,68 +213,24 @@ impl IncrementalAudioSaver {
         Ok(final_audio_path)
     }
 
-    /// Merge all checkpoint files into final audio.mp4 using FFmpeg concat
-    /// Uses concat demuxer for fast merging without re-encoding
-    async fn merge_checkpoints(&self, output: &PathBuf) -> Result<()> {
-        info!("Merging {} checkpoints into final audio file...", self.checkpoint_count);
+    /// Merge the first `count` checkpoint files into the final track file
+    /// AAC checkpoints are joined without re-encoding; Opus is encoded here from its
+    /// lossless FLAC checkpoints, and WAV/FLAC are rewritten losslessly
+    async fn merge_checkpoints(&self, output: &Path, count: u32) -> Result<()> {
+        info!("Merging {} checkpoints into final audio file...", count);
 
-        // Create concat list file for FFmpeg
-        let list_file = self.checkpoints_dir.join("concat_list.txt");
-        let mut list_content = String::new();
-
-        for i in 0..self.checkpoint_count {
-            let checkpoint_path = self.checkpoints_dir
-                .join(format!("audio_chunk_{:03}.mp4", i));
+        let mut checkpoints = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            let checkpoint_path = self.checkpoint_path(i);
 
             // Verify checkpoint exists
             if !checkpoint_path.exists() {
                 return Err(anyhow!("Checkpoint file missing: {}", checkpoint_path.display()));
             }
-
-            // Use absolute path for FFmpeg (required for safe mode)
-            let abs_path = checkpoint_path.canonicalize()?;
-            list_content.push_str(&format!("file '{}'\n", abs_path.display()));
-        }
-
-        std::fs::write(&list_file, list_content)?;
-
-        #[cfg(target_os = "macos")]
-        let ffmpeg_path = find_ffmpeg_path()
-            .ok_or_else(|| anyhow!("FFmpeg not found. Please install FFmpeg to finalize recordings."))?;
-        
-        #[cfg(not(target_os = "macos"))]
-        let ffmpeg_path = "ffmpeg";  // Assume ffmpeg is in PATH on Windows/Linux
-        info!("Using FFmpeg at: {:?}", ffmpeg_path);
-
-        // Run FFmpeg concat command
-        // Using concat demuxer with copy codec for fast merging (no re-encoding)
-        
-        let mut command = std::process::Command::new(ffmpeg_path);
-        
-        command.args(&[
-            "-f", "concat",          // Use concat demuxer
-            "-safe", "0",            // Allow absolute paths
-            "-i", list_file.to_str().unwrap(),
-            "-c", "copy",            // Copy codec - no re-encoding!
-            "-y",                    // Overwrite output file
-            output.to_str().unwrap()
-        ]);
-
-        // Hide console window on Windows to prevent CMD popup during finalization
-        #[cfg(target_os = "windows")]
-        {
-            use std::os::windows::process::CommandExt;
-            const CREATE_NO_WINDOW: u32 = 0x08000000;
-            command.creation_flags(CREATE_NO_WINDOW);
+            checkpoints.push(checkpoint_path);
         }
 
-        let ffmpeg_output = command.output()?;
-
-        if !ffmpeg_output.status.success() {
-            let stderr = String::from_utf8_lossy(&ffmpeg_output.stderr);
-            error!("FFmpeg merge failed: {}", stderr);
-            return Err(anyhow!("FFmpeg concat failed: {}", stderr));
-        }
+        self.encoder.concat(&checkpoints, output)?;
 
         // Verify output file was created
         if !output.exists() {
@@ -213,7 +238,7 @@ impl IncrementalAudioSaver {
         }
 
         info!("✅ Successfully merged {} checkpoints → {}",
-              self.checkpoint_count, output.display());
+              count, output.display());
 
         Ok(())
     }
@@ -227,12 +252,31 @@ impl IncrementalAudioSaver {
     pub fn get_checkpoint_count(&self) -> u32 {
         self.checkpoint_count
     }
+
+    /// Checkpoint `finalize` had to leave out of a resumed recording, still on disk
+    pub fn dropped_checkpoint(&self) -> Option<&Path> {
+        self.dropped_checkpoint.as_deref()
+    }
+}
+
+/// Number of contiguous, non-empty checkpoints of a track in a checkpoints directory
+pub fn count_checkpoints(checkpoints_dir: &Path, track: &str, extension: &str) -> u32 {
+    let mut count = 0;
+    loop {
+        let path = checkpoints_dir.join(format!("{}_chunk_{:03}.{}", track, count, extension));
+        match std::fs::metadata(&path) {
+            Ok(meta) if meta.len() > 0 => count += 1,
+            _ => return count,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
+    use super::super::decoder::decode_audio_file;
+    use super::super::encoder::AudioFormat;
     use super::super::recording_state::DeviceType;
 
     #[tokio::test]
@@ -249,10 +293,12 @@ mod tests {
         ).unwrap();
 
         // Add 60 seconds worth of audio (should create 2 checkpoints)
-        for _ in 0..120 {  // 120 chunks of 0.5s each
+        for i in 0..120 {  // 120 chunks of 0.5s each
             let chunk = AudioChunk {
                 data: vec![0.5f32; 24000],  // 0.5s at 48kHz
                 sample_rate: 48000,
+                timestamp: i as f64 * 0.5,
+                chunk_id: i,
                 device_type: DeviceType::Microphone,
             };
             saver.add_chunk(chunk).unwrap();
@@ -286,4 +332,95 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("No audio checkpoints"));
     }
+
+    #[test]
+    fn test_count_checkpoints_stops_at_gap_or_partial_file() {
+        let temp_dir = tempdir().unwrap();
+        let checkpoints = temp_dir.path();
+        std::fs::write(checkpoints.join("audio_chunk_000.mp4"), b"data").unwrap();
+        std::fs::write(checkpoints.join("audio_chunk_001.mp4"), b"data").unwrap();
+        std::fs::write(checkpoints.join("audio_chunk_002.mp4"), b"").unwrap(); // Cut off mid-write
+        std::fs::write(checkpoints.join("audio_chunk_003.mp4"), b"data").unwrap();
+        std::fs::write(checkpoints.join("mic_chunk_000.mp4"), b"data").unwrap();
+
+        assert_eq!(count_checkpoints(checkpoints, "audio", "mp4"), 2);
+        assert_eq!(count_checkpoints(checkpoints, "mic", "mp4"), 1);
+        assert_eq!(count_checkpoints(checkpoints, "mic", "flac"), 0);
+        assert_eq!(count_checkpoints(checkpoints, "system", "mp4"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_resume_finalize_drops_truncated_last_checkpoint() {
+        let temp_dir = tempdir().unwrap();
+        let meeting_folder = temp_dir.path().join("Crashed_Meeting");
+        let checkpoints = meeting_folder.join(".checkpoints");
+        std::fs::create_dir_all(&checkpoints).unwrap();
+
+        let settings = EncoderSettings { format: AudioFormat::Wav, bitrate_kbps: None, sample_rate: None };
+
+        // Two complete checkpoints written before the crash
+        let mut saver = IncrementalAudioSaver::for_track(meeting_folder.clone(), 16000, "audio", settings).unwrap();
+        for i in 0..120 {
+            saver.add_chunk(AudioChunk {
+                data: vec![0.25f32; 8000],
+                sample_rate: 16000,
+                timestamp: i as f64 * 0.5,
+                chunk_id: i,
+                device_type: DeviceType::Microphone,
+            }).unwrap();
+        }
+        assert_eq!(saver.get_checkpoint_count(), 2);
+
+        // The third was cut off a few bytes into its header
+        let complete = std::fs::read(checkpoints.join("audio_chunk_000.wav")).unwrap();
+        std::fs::write(checkpoints.join("audio_chunk_002.wav"), &complete[..20]).unwrap();
+
+        let mut resumed = IncrementalAudioSaver::resume(
+            meeting_folder.clone(),
+            16000,
+            "audio",
+            settings,
+        ).unwrap();
+        assert_eq!(resumed.get_checkpoint_count(), 3);
+
+        let final_path = resumed.finalize().await.unwrap();
+        let merged = decode_audio_file(&final_path).unwrap();
+        assert_eq!(merged.samples.len(), 16000 * 60);
+
+        // Only the damaged checkpoint is left, for the user to delete
+        let dropped = checkpoints.join("audio_chunk_002.wav");
+        assert_eq!(resumed.dropped_checkpoint(), Some(dropped.as_path()));
+        assert!(dropped.exists());
+        assert!(!checkpoints.join("audio_chunk_000.wav").exists());
+        assert!(!checkpoints.join("audio_chunk_001.wav").exists());
+    }
+
+    #[tokio::test]
+    async fn test_live_finalize_does_not_drop_checkpoints() {
+        let temp_dir = tempdir().unwrap();
+        let meeting_folder = temp_dir.path().join("Live_Meeting");
+        let checkpoints = meeting_folder.join(".checkpoints");
+        std::fs::create_dir_all(&checkpoints).unwrap();
+
+        let settings = EncoderSettings { format: AudioFormat::Wav, bitrate_kbps: None, sample_rate: None };
+        let mut saver = IncrementalAudioSaver::for_track(meeting_folder.clone(), 16000, "audio", settings).unwrap();
+        for i in 0..180 {
+            saver.add_chunk(AudioChunk {
+                data: vec![0.25f32; 8000],
+                sample_rate: 16000,
+                timestamp: i as f64 * 0.5,
+                chunk_id: i,
+                device_type: DeviceType::Microphone,
+            }).unwrap();
+        }
+        assert_eq!(saver.get_checkpoint_count(), 3);
+
+        // Damage the last checkpoint behind the saver's back
+        let complete = std::fs::read(checkpoints.join("audio_chunk_000.wav")).unwrap();
+        std::fs::write(checkpoints.join("audio_chunk_002.wav"), &complete[..20]).unwrap();
+
+        assert!(saver.finalize().await.is_err());
+        assert!(saver.dropped_checkpoint().is_none());
+        assert_eq!(count_checkpoints(&checkpoints, "audio", "wav"), 3);
+    }
 }
@@ -1,6 +1,11 @@
 // src/audio/mod.rs
 pub mod audio_processing;
 pub mod encode;
+pub mod encoder;
+pub mod native_encoder;
+pub mod decoder;
+pub mod import;
+pub mod recovery;
 pub mod ffmpeg;
 pub mod vad;
 
@@ -83,8 +88,9 @@ pub use level_monitor::{AudioLevelMonitor, AudioLevelData, AudioLevelUpdate};
 pub use buffer_pool::{AudioBufferPool, PooledBuffer};
 pub use post_processor::{PostProcessor, PostProcessRequest, PostProcessResponse};
 pub use hardware_detector::{HardwareProfile, AdaptiveWhisperConfig, PerformanceTier, GpuType};
+pub use encoder::{AudioEncoder, AudioFormat, EncoderSettings};
 pub use encode::{
-    encode_single_audio, AudioInput
+    encode_single_audio, merge_stereo_tracks, AudioInput
 };
 pub use device_monitor::{AudioDeviceMonitor, DeviceEvent, DeviceMonitorType};
 
@@ -0,0 +1,559 @@
+// audio/native_encoder.rs
+//
+// In-process encoder backend: WAV and FLAC are written natively, Opus through libopus
+// (the `native-opus` feature). Saving a recording then needs no ffmpeg binary and no
+// process per checkpoint; ffmpeg is only used for formats without a native encoder.
+//
+// WAV and FLAC checkpoints are written in the final format. Opus checkpoints are
+// written as FLAC and encoded once when merged: every Opus stream starts with encoder
+// delay, so joining independently encoded 30s files would shift audio against the
+// transcript timestamps at every boundary.
+
+use super::audio_processing::resample;
+use super::decoder::decode_audio_file;
+use super::encoder::{AudioEncoder, AudioFormat, EncoderSettings};
+use anyhow::{anyhow, Result};
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+use log::info;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Bit depth of natively written PCM (WAV and FLAC)
+const BITS_PER_SAMPLE: u16 = 16;
+
+pub struct NativeEncoder {
+    settings: EncoderSettings,
+}
+
+impl NativeEncoder {
+    pub fn new(settings: EncoderSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Whether a format can be encoded without ffmpeg in this build
+    pub fn supports(format: AudioFormat) -> bool {
+        match format {
+            AudioFormat::Wav | AudioFormat::Flac => true,
+            AudioFormat::Opus => cfg!(feature = "native-opus"),
+            AudioFormat::Aac => false,
+        }
+    }
+
+    /// Convert to the configured output sample rate, if any
+    fn to_output_rate(&self, samples: &[f32], sample_rate: u32, channels: u16) -> Result<(Vec<f32>, u32)> {
+        match self.settings.sample_rate {
+            Some(rate) if rate != sample_rate => {
+                Ok((convert_rate(samples, channels, sample_rate, rate)?, rate))
+            }
+            _ => Ok((samples.to_vec(), sample_rate)),
+        }
+    }
+
+    /// Decode a file written by this encoder into mono samples
+    fn decode_mono(&self, path: &Path) -> Result<(Vec<f32>, u32)> {
+        match self.settings.format {
+            #[cfg(feature = "native-opus")]
+            AudioFormat::Opus => ogg_opus::read_mono(path),
+            _ => {
+                let decoded = decode_audio_file(path)?;
+                Ok((decoded.samples, decoded.sample_rate))
+            }
+        }
+    }
+}
+
+impl AudioEncoder for NativeEncoder {
+    fn checkpoint_extension(&self) -> &'static str {
+        match self.settings.format {
+            AudioFormat::Opus => AudioFormat::Flac.extension(),
+            format => format.extension(),
+        }
+    }
+
+    fn encode_checkpoint(&self, samples: &[f32], sample_rate: u32, channels: u16, output: &Path) -> Result<()> {
+        match self.settings.format {
+            AudioFormat::Opus => write_flac(output, samples, sample_rate, channels),
+            _ => self.encode(samples, sample_rate, channels, output),
+        }
+    }
+
+    fn encode(&self, samples: &[f32], sample_rate: u32, channels: u16, output: &Path) -> Result<()> {
+        if samples.is_empty() {
+            return Err(anyhow!("No audio data provided for encoding"));
+        }
+        let (samples, rate) = self.to_output_rate(samples, sample_rate, channels)?;
+
+        match self.settings.format {
+            AudioFormat::Wav => write_wav(output, &samples, rate, channels),
+            AudioFormat::Flac => write_flac(output, &samples, rate, channels),
+            #[cfg(feature = "native-opus")]
+            AudioFormat::Opus => {
+                let mut writer = ogg_opus::OggOpusWriter::create(output, rate, channels, self.settings.bitrate_kbps)?;
+                writer.write(&samples)?;
+                writer.finish()
+            }
+            format => Err(anyhow!("No native encoder for {:?}", format)),
+        }
+    }
+
+    fn concat(&self, inputs: &[PathBuf], output: &Path) -> Result<()> {
+        if inputs.is_empty() {
+            return Err(anyhow!("No files to merge"));
+        }
+
+        match self.settings.format {
+            AudioFormat::Wav => concat_wav(inputs, output)?,
+            AudioFormat::Flac => {
+                // FLAC frames carry their position, so the merged stream is re-encoded
+                let mut samples = Vec::new();
+                let mut rate = 0;
+                for input in inputs {
+                    let decoded = decode_audio_file(input)?;
+                    rate = decoded.sample_rate;
+                    samples.extend(decoded.samples);
+                }
+                write_flac(output, &samples, rate, 1)?;
+            }
+            #[cfg(feature = "native-opus")]
+            AudioFormat::Opus => {
+                // One continuous Opus stream over the lossless checkpoints
+                let mut writer: Option<ogg_opus::OggOpusWriter> = None;
+                for input in inputs {
+                    let decoded = decode_audio_file(input)?;
+                    let (samples, rate) = self.to_output_rate(&decoded.samples, decoded.sample_rate, 1)?;
+                    if writer.is_none() {
+                        writer = Some(ogg_opus::OggOpusWriter::create(output, rate, 1, self.settings.bitrate_kbps)?);
+                    }
+                    if let Some(writer) = writer.as_mut() {
+                        writer.write(&samples)?;
+                    }
+                }
+                if let Some(writer) = writer {
+                    writer.finish()?;
+                }
+            }
+            format => return Err(anyhow!("No native encoder for {:?}", format)),
+        }
+
+        info!("Merged {} files into {}", inputs.len(), output.display());
+        Ok(())
+    }
+
+    fn merge_stereo(&self, left: &Path, right: &Path, output: &Path) -> Result<()> {
+        let (left, left_rate) = self.decode_mono(left)?;
+        let (right, right_rate) = self.decode_mono(right)?;
+        if left_rate != right_rate {
+            return Err(anyhow!("Tracks have different sample rates ({} vs {})", left_rate, right_rate));
+        }
+
+        let len = left.len().max(right.len());
+        let mut interleaved = Vec::with_capacity(len * 2);
+        for i in 0..len {
+            interleaved.push(left.get(i).copied().unwrap_or(0.0));
+            interleaved.push(right.get(i).copied().unwrap_or(0.0));
+        }
+        self.encode(&interleaved, left_rate, 2, output)
+    }
+}
+
+/// Decode an Ogg Opus recording to mono samples at 48kHz
+#[cfg(feature = "native-opus")]
+pub fn decode_ogg_opus(path: &Path) -> Result<super::decoder::DecodedAudio> {
+    let (samples, sample_rate) = ogg_opus::read_mono(path)?;
+    Ok(super::decoder::DecodedAudio { samples, sample_rate })
+}
+
+/// Resample interleaved audio channel by channel
+fn convert_rate(samples: &[f32], channels: u16, from: u32, to: u32) -> Result<Vec<f32>> {
+    let channels = channels.max(1) as usize;
+    if channels == 1 {
+        return resample(samples, from, to);
+    }
+
+    let resampled: Vec<Vec<f32>> = (0..channels)
+        .map(|c| {
+            let channel: Vec<f32> = samples.iter().skip(c).step_by(channels).copied().collect();
+            resample(&channel, from, to)
+        })
+        .collect::<Result<_>>()?;
+    let frames = resampled.iter().map(|c| c.len()).min().unwrap_or(0);
+    Ok((0..frames)
+        .flat_map(|i| resampled.iter().map(move |c| c[i]))
+        .collect())
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+/// 44-byte header of a 16-bit PCM WAV file
+fn wav_header(sample_rate: u32, channels: u16, data_len: u32) -> Vec<u8> {
+    let block_align = channels * BITS_PER_SAMPLE / 8;
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(36 + data_len).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    header.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    header.extend_from_slice(&channels.to_le_bytes());
+    header.extend_from_slice(&sample_rate.to_le_bytes());
+    header.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes()); // byte rate
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    header
+}
+
+/// Write interleaved f32 samples as a 16-bit PCM WAV stream
+pub(crate) fn write_wav_to<W: Write>(mut writer: W, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    let data_len = u32::try_from(samples.len() * 2).map_err(|_| anyhow!("Audio too long for WAV"))?;
+    writer.write_all(&wav_header(sample_rate, channels, data_len))?;
+    for sample in samples {
+        writer.write_all(&to_i16(*sample).to_le_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_wav(output: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    write_wav_to(BufWriter::new(File::create(output)?), samples, sample_rate, channels)
+}
+
+/// Locate the format and data chunk of a PCM WAV file: (sample_rate, channels, data offset, data length)
+fn read_wav_layout(file: &mut File) -> Result<(u32, u16, u64, u32)> {
+    let mut riff = [0u8; 12];
+    file.read_exact(&mut riff)?;
+    if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+        return Err(anyhow!("Not a WAV file"));
+    }
+
+    let mut format = None;
+    loop {
+        let mut chunk = [0u8; 8];
+        file.read_exact(&mut chunk)?;
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        match &chunk[0..4] {
+            b"fmt " => {
+                let mut fmt = vec![0u8; size as usize];
+                file.read_exact(&mut fmt)?;
+                if fmt.len() < 16 {
+                    return Err(anyhow!("Truncated WAV format chunk"));
+                }
+                let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+                let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+                format = Some((sample_rate, channels));
+            }
+            b"data" => {
+                let (sample_rate, channels) = format.ok_or_else(|| anyhow!("WAV data before format chunk"))?;
+                return Ok((sample_rate, channels, file.stream_position()?, size));
+            }
+            _ => {
+                // Chunks are word aligned
+                file.seek(SeekFrom::Current(size as i64 + (size % 2) as i64))?;
+            }
+        }
+    }
+}
+
+/// Join WAV files by copying their sample data under one header
+fn concat_wav(inputs: &[PathBuf], output: &Path) -> Result<()> {
+    let mut layouts = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let mut file = File::open(input)?;
+        let layout = read_wav_layout(&mut file)?;
+        layouts.push((file, layout));
+    }
+
+    let (_, (sample_rate, channels, _, _)) = layouts[0];
+    let mut total: u32 = 0;
+    for (_, (rate, ch, _, len)) in &layouts {
+        if (*rate, *ch) != (sample_rate, channels) {
+            return Err(anyhow!("Cannot merge WAV files with different formats"));
+        }
+        total = total.checked_add(*len).ok_or_else(|| anyhow!("Audio too long for WAV"))?;
+    }
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    writer.write_all(&wav_header(sample_rate, channels, total))?;
+    for (file, (_, _, offset, len)) in layouts.iter_mut() {
+        file.seek(SeekFrom::Start(*offset))?;
+        std::io::copy(&mut BufReader::new(file.take(*len as u64)), &mut writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_flac(output: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<()> {
+    if samples.is_empty() {
+        return Err(anyhow!("No audio data provided for encoding"));
+    }
+
+    let ints: Vec<i32> = samples.iter().map(|s| to_i16(*s) as i32).collect();
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| anyhow!("Invalid FLAC encoder config: {:?}", e))?;
+    let source = flacenc::source::MemSource::from_samples(
+        &ints,
+        channels as usize,
+        BITS_PER_SAMPLE as usize,
+        sample_rate as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| anyhow!("FLAC encoding failed: {:?}", e))?;
+
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| anyhow!("FLAC serialization failed: {:?}", e))?;
+    std::fs::write(output, sink.as_slice())?;
+    Ok(())
+}
+
+#[cfg(feature = "native-opus")]
+mod ogg_opus {
+    //! Ogg Opus streams (RFC 7845) on top of libopus
+
+    use anyhow::{anyhow, Result};
+    use ogg::{PacketReader, PacketWriteEndInfo, PacketWriter};
+    use opus::{Application, Bitrate, Channels, Decoder, Encoder};
+    use std::fs::File;
+    use std::io::{BufReader, BufWriter, Write};
+    use std::path::Path;
+
+    /// Granule positions are always counted at 48kHz
+    const GRANULE_RATE: u32 = 48000;
+    const FRAME_MS: u32 = 20;
+    /// Largest Opus packet (120ms at 48kHz) per channel
+    const MAX_FRAME_SAMPLES: usize = 5760;
+    const STREAM_SERIAL: u32 = 0x4d45_4554;
+
+    fn channels_of(count: u16) -> Result<Channels> {
+        match count {
+            1 => Ok(Channels::Mono),
+            2 => Ok(Channels::Stereo),
+            n => Err(anyhow!("Opus supports mono or stereo, not {} channels", n)),
+        }
+    }
+
+    /// Streaming Ogg Opus writer; samples can be fed in any block size
+    pub struct OggOpusWriter {
+        encoder: Encoder,
+        writer: PacketWriter<'static, BufWriter<File>>,
+        channels: usize,
+        frame_len: usize,
+        granule_step: u64,
+        pre_skip: u64,
+        pending: Vec<f32>,
+        /// Real samples (per channel) fed so far
+        position: u64,
+        /// Encoded packet not yet written, so the last one can end the stream
+        last_packet: Option<Vec<u8>>,
+        last_granule: u64,
+    }
+
+    impl OggOpusWriter {
+        pub fn create(path: &Path, sample_rate: u32, channels: u16, bitrate_kbps: Option<u32>) -> Result<Self> {
+            let mut encoder = Encoder::new(sample_rate, channels_of(channels)?, Application::Voip)?;
+            if let Some(kbps) = bitrate_kbps {
+                encoder.set_bitrate(Bitrate::Bits(kbps as i32 * 1000))?;
+            }
+            // Every supported Opus rate divides 48kHz
+            let granule_step = (GRANULE_RATE / sample_rate) as u64;
+            let pre_skip = encoder.get_lookahead()? as u64 * granule_step;
+
+            let mut writer = PacketWriter::new(BufWriter::new(File::create(path)?));
+            writer.write_packet(opus_head(channels, pre_skip as u16, sample_rate), STREAM_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+            writer.write_packet(opus_tags(), STREAM_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+            Ok(Self {
+                encoder,
+                writer,
+                channels: channels as usize,
+                frame_len: (sample_rate * FRAME_MS / 1000) as usize * channels as usize,
+                granule_step,
+                pre_skip,
+                pending: Vec::new(),
+                position: 0,
+                last_packet: None,
+                last_granule: 0,
+            })
+        }
+
+        pub fn write(&mut self, samples: &[f32]) -> Result<()> {
+            self.position += (samples.len() / self.channels) as u64;
+            self.pending.extend_from_slice(samples);
+            self.encode_full_frames()
+        }
+
+        fn encode_full_frames(&mut self) -> Result<()> {
+            let mut packet = vec![0u8; 4000];
+            let mut offset = 0;
+            while self.pending.len() - offset >= self.frame_len {
+                let len = self.encoder.encode_float(&self.pending[offset..offset + self.frame_len], &mut packet)?;
+                offset += self.frame_len;
+                self.push_packet(packet[..len].to_vec())?;
+            }
+            self.pending.drain(..offset);
+            Ok(())
+        }
+
+        fn push_packet(&mut self, packet: Vec<u8>) -> Result<()> {
+            if let Some(previous) = self.last_packet.replace(packet) {
+                self.writer.write_packet(previous, STREAM_SERIAL, PacketWriteEndInfo::NormalPacket, self.last_granule)?;
+            }
+            // Trailing padding never counts as audio, which keeps granules monotonic
+            let encoded = self.last_granule + (self.frame_len / self.channels) as u64 * self.granule_step;
+            self.last_granule = encoded.min(self.pre_skip + self.position * self.granule_step);
+            Ok(())
+        }
+
+        /// Flush the encoder delay and close the stream
+        pub fn finish(mut self) -> Result<()> {
+            // Pad with silence so the last real samples make it out of the encoder
+            let lookahead = (self.pre_skip / self.granule_step) as usize * self.channels;
+            let padding = lookahead + self.frame_len - (self.pending.len() + lookahead) % self.frame_len;
+            self.pending.extend(std::iter::repeat(0.0).take(padding));
+            self.encode_full_frames()?;
+
+            if let Some(last) = self.last_packet.take() {
+                let end = self.pre_skip + self.position * self.granule_step;
+                self.writer.write_packet(last, STREAM_SERIAL, PacketWriteEndInfo::EndStream, end)?;
+            }
+            self.writer.into_inner().flush()?;
+            Ok(())
+        }
+    }
+
+    fn opus_head(channels: u16, pre_skip: u16, input_rate: u32) -> Vec<u8> {
+        let mut head = b"OpusHead".to_vec();
+        head.push(1); // version
+        head.push(channels as u8);
+        head.extend_from_slice(&pre_skip.to_le_bytes());
+        head.extend_from_slice(&input_rate.to_le_bytes());
+        head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+        head.push(0); // channel mapping family
+        head
+    }
+
+    fn opus_tags() -> Vec<u8> {
+        let vendor = b"meetily";
+        let mut tags = b"OpusTags".to_vec();
+        tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        tags.extend_from_slice(vendor);
+        tags.extend_from_slice(&0u32.to_le_bytes()); // no user comments
+        tags
+    }
+
+    /// Decode an Ogg Opus file to mono samples at 48kHz, honouring pre-skip and end trimming
+    pub fn read_mono(path: &Path) -> Result<(Vec<f32>, u32)> {
+        let mut reader = PacketReader::new(BufReader::new(File::open(path)?));
+        let head = reader
+            .read_packet()?
+            .ok_or_else(|| anyhow!("Empty Ogg file: {}", path.display()))?;
+        if head.data.len() < 19 || &head.data[0..8] != b"OpusHead" {
+            return Err(anyhow!("Not an Ogg Opus file: {}", path.display()));
+        }
+        let channels = head.data[9].max(1) as usize;
+        let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as usize;
+        let _tags = reader.read_packet()?;
+
+        let mut decoder = Decoder::new(GRANULE_RATE, channels_of(channels as u16)?)?;
+        let mut pcm = vec![0f32; MAX_FRAME_SAMPLES * channels];
+        let mut samples = Vec::new();
+        let mut end_granule = 0;
+        while let Some(packet) = reader.read_packet()? {
+            let decoded = decoder.decode_float(&packet.data, &mut pcm, false)?;
+            for frame in pcm[..decoded * channels].chunks(channels) {
+                samples.push(frame.iter().sum::<f32>() / channels as f32);
+            }
+            end_granule = packet.absgp_page() as usize;
+        }
+
+        samples.truncate(end_granule.max(pre_skip));
+        samples.drain(..pre_skip.min(samples.len()));
+        Ok((samples, GRANULE_RATE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len).map(|i| (i as f32 * 0.05).sin() * 0.5).collect()
+    }
+
+    #[test]
+    fn test_wav_checkpoints_merge_without_reencoding() {
+        let dir = tempdir().unwrap();
+        let encoder = NativeEncoder::new(EncoderSettings::for_format(AudioFormat::Wav));
+        let first = dir.path().join("a.wav");
+        let second = dir.path().join("b.wav");
+        encoder.encode_checkpoint(&tone(48000), 48000, 1, &first).unwrap();
+        encoder.encode_checkpoint(&tone(24000), 48000, 1, &second).unwrap();
+
+        let merged = dir.path().join("merged.wav");
+        encoder.concat(&[first, second], &merged).unwrap();
+
+        let decoded = decode_audio_file(&merged).unwrap();
+        assert_eq!(decoded.sample_rate, 48000);
+        assert_eq!(decoded.samples.len(), 72000);
+    }
+
+    #[test]
+    fn test_flac_round_trips_and_resamples() {
+        let dir = tempdir().unwrap();
+        let settings = EncoderSettings::new("flac", None, Some(16000)).unwrap();
+        let encoder = NativeEncoder::new(settings);
+        let path = dir.path().join("audio.flac");
+        encoder.encode(&tone(48000), 48000, 1, &path).unwrap();
+
+        let decoded = decode_audio_file(&path).unwrap();
+        assert_eq!(decoded.sample_rate, 16000);
+        assert!((decoded.samples.len() as i64 - 16000).abs() < 200);
+    }
+
+    #[test]
+    fn test_stereo_merge_interleaves_tracks() {
+        let dir = tempdir().unwrap();
+        let encoder = NativeEncoder::new(EncoderSettings::for_format(AudioFormat::Wav));
+        let mic = dir.path().join("mic.wav");
+        let system = dir.path().join("system.wav");
+        encoder.encode(&vec![0.5; 4800], 48000, 1, &mic).unwrap();
+        encoder.encode(&vec![-0.5; 2400], 48000, 1, &system).unwrap();
+
+        let stereo = dir.path().join("stereo.wav");
+        encoder.merge_stereo(&mic, &system, &stereo).unwrap();
+
+        let mut file = File::open(&stereo).unwrap();
+        let (rate, channels, _, len) = read_wav_layout(&mut file).unwrap();
+        assert_eq!((rate, channels, len), (48000, 2, 4800 * 2 * 2));
+    }
+
+    #[cfg(feature = "native-opus")]
+    #[test]
+    fn test_opus_checkpoints_merge_into_one_gapless_stream() {
+        let dir = tempdir().unwrap();
+        let encoder = NativeEncoder::new(EncoderSettings::for_format(AudioFormat::Opus));
+        assert_eq!(encoder.checkpoint_extension(), "flac");
+
+        let checkpoints: Vec<PathBuf> = (0..3)
+            .map(|i| {
+                let path = dir.path().join(format!("audio_chunk_{:03}.flac", i));
+                encoder.encode_checkpoint(&tone(48000), 48000, 1, &path).unwrap();
+                path
+            })
+            .collect();
+        let merged = dir.path().join("audio.ogg");
+        encoder.concat(&checkpoints, &merged).unwrap();
+
+        // Exactly the recorded length, with no encoder delay inserted between checkpoints
+        let (samples, rate) = ogg_opus::read_mono(&merged).unwrap();
+        assert_eq!(rate, 48000);
+        assert_eq!(samples.len(), 3 * 48000);
+    }
+}
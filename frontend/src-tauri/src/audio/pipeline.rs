@@ -11,7 +11,7 @@ use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolat
 use super::devices::AudioDevice;
 use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
 use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
-use super::vad::{ContinuousVadProcessor};
+use super::vad::{ContinuousVadProcessor, SpeechSegment};
 
 /// Ring buffer for synchronized audio mixing
 /// Accumulates samples from mic and system streams until we have aligned windows
@@ -682,6 +682,8 @@ pub struct AudioPipeline {
     transcription_sender: mpsc::UnboundedSender<AudioChunk>,
     state: Arc<RecordingState>,
     vad_processor: ContinuousVadProcessor,
+    // Dual-channel mode: system audio gets its own VAD and `vad_processor` only sees the mic
+    system_vad_processor: Option<ContinuousVadProcessor>,
     sample_rate: u32,
     chunk_id_counter: u64,
     // Performance optimization: reduce logging frequency
@@ -694,6 +696,8 @@ pub struct AudioPipeline {
     mixer: ProfessionalAudioMixer,
     // Recording sender for pre-mixed audio
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
+    // Recording sender for per-source stems (multitrack recordings)
+    recording_sender_for_stems: Option<mpsc::UnboundedSender<AudioChunk>>,
 }
 
 impl AudioPipeline {
@@ -707,6 +711,7 @@ impl AudioPipeline {
         mic_device_kind: super::device_detection::InputDeviceKind,
         system_device_name: String,
         system_device_kind: super::device_detection::InputDeviceKind,
+        dual_channel: bool,
     ) -> Self {
         // Log device characteristics for adaptive buffering
         info!("🎛️ AudioPipeline initializing with device characteristics:");
@@ -737,6 +742,19 @@ impl AudioPipeline {
             }
         };
 
+        let system_vad_processor = if dual_channel {
+            info!("Dual-channel transcription: mic and system audio are segmented and transcribed separately");
+            match ContinuousVadProcessor::new(sample_rate, redemption_time) {
+                Ok(processor) => Some(processor),
+                Err(e) => {
+                    error!("Failed to create system audio VAD processor, transcribing mixed audio: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         // Initialize professional audio mixing components
         let ring_buffer = AudioMixerRingBuffer::new(sample_rate);
         let mixer = ProfessionalAudioMixer::new(sample_rate);
@@ -749,6 +767,7 @@ impl AudioPipeline {
             transcription_sender,
             state,
             vad_processor,
+            system_vad_processor,
             sample_rate,
             chunk_id_counter: 0,
             // Performance optimization: reduce logging frequency
@@ -760,6 +779,7 @@ impl AudioPipeline {
             ring_buffer,
             mixer,
             recording_sender_for_mixed: None,  // Will be set by manager
+            recording_sender_for_stems: None,  // Will be set by manager
         }
     }
 
@@ -831,38 +851,15 @@ impl AudioPipeline {
                             // Previous 2x gain was causing excessive limiting/distortion
                             let mixed_with_gain = mixed_clean;
 
-                            // STEP 3: Send mixed audio for transcription (VAD + Whisper)
-                            match self.vad_processor.process_audio(&mixed_with_gain) {
-                                Ok(speech_segments) => {
-                                    for segment in speech_segments {
-                                        let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
-
-                                        if segment.samples.len() >= 800 {  // Minimum 50ms at 16kHz - matches Parakeet capability
-                                            info!("📤 Sending VAD segment: {:.1}ms, {} samples",
-                                                  duration_ms, segment.samples.len());
-
-                                            let transcription_chunk = AudioChunk {
-                                                data: segment.samples,
-                                                sample_rate: 16000,
-                                                timestamp: segment.start_timestamp_ms / 1000.0,
-                                                chunk_id: self.chunk_id_counter,
-                                                device_type: DeviceType::Microphone,  // Mixed audio
-                                            };
-
-                                            if let Err(e) = self.transcription_sender.send(transcription_chunk) {
-                                                warn!("Failed to send VAD segment: {}", e);
-                                            } else {
-                                                self.chunk_id_counter += 1;
-                                            }
-                                        } else {
-                                            debug!("⏭️ Dropping short VAD segment: {:.1}ms ({} samples < 800)",
-                                                   duration_ms, segment.samples.len());
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ VAD error: {}", e);
-                                }
+                            // STEP 3: Send speech for transcription (VAD + Whisper)
+                            // Dual-channel mode segments each source on its own so transcripts
+                            // can be attributed to the local user or the remote side; the
+                            // windows are aligned, so both VADs share one timeline
+                            if self.system_vad_processor.is_some() {
+                                self.segment_speech(&mic_window, DeviceType::Microphone);
+                                self.segment_speech(&sys_window, DeviceType::System);
+                            } else {
+                                self.segment_speech(&mixed_with_gain, DeviceType::Microphone);  // Mixed audio
                             }
 
                             // STEP 4: Send mixed audio for recording (WAV file)
@@ -876,6 +873,22 @@ impl AudioPipeline {
                                 };
                                 let _ = sender.send(recording_chunk);
                             }
+
+                            // STEP 5: Send the aligned source windows as separate stems
+                            if let Some(ref sender) = self.recording_sender_for_stems {
+                                for (data, device_type) in [
+                                    (mic_window, DeviceType::Microphone),
+                                    (sys_window, DeviceType::System),
+                                ] {
+                                    let _ = sender.send(AudioChunk {
+                                        data,
+                                        sample_rate: self.sample_rate,
+                                        timestamp: chunk.timestamp,
+                                        chunk_id: self.chunk_id_counter,
+                                        device_type,
+                                    });
+                                }
+                            }
                         }
                     }
                 }
@@ -900,44 +913,68 @@ impl AudioPipeline {
     fn flush_remaining_audio(&mut self) -> Result<()> {
         info!("Flushing remaining audio from pipeline (processed {} chunks)", self.processed_chunks);
 
-        // Flush any remaining audio from VAD processor and send segments to transcription
-        match self.vad_processor.flush() {
-            Ok(final_segments) => {
-                for segment in final_segments {
-                    let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
-
-                    // Send segments >= 50ms (800 samples at 16kHz) - matches main pipeline filter
-                    if segment.samples.len() >= 800 {
-                        info!("📤 Sending final VAD segment to Whisper: {:.1}ms duration, {} samples",
-                              duration_ms, segment.samples.len());
-
-                        let transcription_chunk = AudioChunk {
-                            data: segment.samples,
-                            sample_rate: 16000,
-                            timestamp: segment.start_timestamp_ms / 1000.0,
-                            chunk_id: self.chunk_id_counter,
-                            device_type: DeviceType::Microphone,
-                        };
-
-                        if let Err(e) = self.transcription_sender.send(transcription_chunk) {
-                            warn!("Failed to send final VAD segment: {}", e);
-                        } else {
-                            self.chunk_id_counter += 1;
-                        }
-                    } else {
-                        info!("⏭️ Skipping short final segment: {:.1}ms ({} samples < 800)",
-                              duration_ms, segment.samples.len());
-                    }
-                }
-            }
-            Err(e) => {
-                warn!("Failed to flush VAD processor: {}", e);
+        // Flush any remaining audio from the VAD processors and send segments to transcription
+        let mut flushed = vec![(DeviceType::Microphone, self.vad_processor.flush())];
+        if let Some(ref mut system_vad) = self.system_vad_processor {
+            flushed.push((DeviceType::System, system_vad.flush()));
+        }
+
+        for (device_type, result) in flushed {
+            match result {
+                Ok(final_segments) => self.send_speech_segments(final_segments, device_type),
+                Err(e) => warn!("Failed to flush {:?} VAD processor: {}", device_type, e),
             }
         }
 
         Ok(())
     }
 
+    /// Run one source's audio through its VAD and queue the finished speech segments
+    fn segment_speech(&mut self, samples: &[f32], device_type: DeviceType) {
+        let vad = match device_type {
+            DeviceType::System => match self.system_vad_processor.as_mut() {
+                Some(vad) => vad,
+                None => return,
+            },
+            DeviceType::Microphone => &mut self.vad_processor,
+        };
+
+        match vad.process_audio(samples) {
+            Ok(speech_segments) => self.send_speech_segments(speech_segments, device_type),
+            Err(e) => warn!("⚠️ VAD error: {}", e),
+        }
+    }
+
+    /// Send VAD speech segments to transcription, tagged with the source they came from
+    fn send_speech_segments(&mut self, segments: Vec<SpeechSegment>, device_type: DeviceType) {
+        for segment in segments {
+            let duration_ms = segment.end_timestamp_ms - segment.start_timestamp_ms;
+
+            // Minimum 50ms (800 samples at 16kHz) - matches Parakeet capability
+            if segment.samples.len() >= 800 {
+                info!("📤 Sending {:?} VAD segment: {:.1}ms, {} samples",
+                      device_type, duration_ms, segment.samples.len());
+
+                let transcription_chunk = AudioChunk {
+                    data: segment.samples,
+                    sample_rate: 16000,
+                    timestamp: segment.start_timestamp_ms / 1000.0,
+                    chunk_id: self.chunk_id_counter,
+                    device_type: device_type.clone(),
+                };
+
+                if let Err(e) = self.transcription_sender.send(transcription_chunk) {
+                    warn!("Failed to send VAD segment: {}", e);
+                } else {
+                    self.chunk_id_counter += 1;
+                }
+            } else {
+                debug!("⏭️ Dropping short VAD segment: {:.1}ms ({} samples < 800)",
+                       duration_ms, segment.samples.len());
+            }
+        }
+    }
+
 }
 
 /// Simple audio pipeline manager
@@ -962,10 +999,12 @@ impl AudioPipelineManager {
         target_chunk_duration_ms: u32,
         sample_rate: u32,
         recording_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
+        stem_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
         mic_device_name: String,
         mic_device_kind: super::device_detection::InputDeviceKind,
         system_device_name: String,
         system_device_kind: super::device_detection::InputDeviceKind,
+        dual_channel: bool,
     ) -> Result<()> {
         // Log device information for adaptive buffering
         info!("🎙️ Starting pipeline with device info:");
@@ -989,11 +1028,13 @@ impl AudioPipelineManager {
             mic_device_kind,
             system_device_name,
             system_device_kind,
+            dual_channel,
         );
 
         // CRITICAL FIX: Connect recording sender to receive pre-mixed audio
         // This ensures both mic AND system audio are captured in recordings
         pipeline.recording_sender_for_mixed = recording_sender;
+        pipeline.recording_sender_for_stems = stem_sender;
 
         let handle = tokio::spawn(async move {
             pipeline.run().await
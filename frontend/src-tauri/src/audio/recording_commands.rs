@@ -14,6 +14,8 @@ use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::task::JoinHandle;
 
 use super::{parse_audio_device, RecordingManager, DeviceEvent, DeviceMonitorType};
+use super::recording_preferences::load_recording_preferences;
+use super::encoder::EncoderSettings;
 
 // Import transcription modules
 use super::transcription::{
@@ -55,6 +57,26 @@ pub struct TranscriptionStatus {
 // RECORDING COMMANDS
 // ============================================================================
 
+/// Apply the user's recording preferences (dual-channel transcription, multitrack stems
+/// and output format) to a new recording manager, returning the dual-channel flag
+async fn apply_recording_preferences<R: Runtime>(
+    app: &AppHandle<R>,
+    manager: &mut RecordingManager,
+) -> bool {
+    let (dual_channel, multitrack, encoder_settings) = match load_recording_preferences(app).await {
+        Ok(prefs) => (
+            prefs.dual_channel_transcription,
+            prefs.multitrack_recording,
+            prefs.encoder_settings_or_default(),
+        ),
+        Err(_) => (false, false, EncoderSettings::default()),
+    };
+    manager.set_dual_channel_transcription(dual_channel);
+    manager.set_multitrack_recording(multitrack);
+    manager.set_encoder_settings(encoder_settings);
+    dual_channel
+}
+
 /// Start recording with default devices
 pub async fn start_recording<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
     start_recording_with_meeting_name(app, None).await
@@ -110,6 +132,8 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     });
     manager.set_meeting_name(Some(effective_meeting_name));
 
+    let dual_channel = apply_recording_preferences(&app, &mut manager).await;
+
     // Set up error callback
     let app_for_error = app.clone();
     manager.set_error_callback(move |error| {
@@ -134,7 +158,7 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
     reset_speech_detected_flag(); // Reset for new recording session
 
     // Start optimized parallel transcription task and store handle
-    let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
+    let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver, dual_channel);
     {
         let mut global_task = TRANSCRIPTION_TASK.lock().unwrap();
         *global_task = Some(task_handle);
@@ -159,6 +183,13 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    speaker_id: update.speaker_id,
+                    speaker_profile_id: update.speaker_profile_id,
+                    speaker_name: update.speaker_name.clone(),
+                    words: update.words.clone(),
+                    language: update.language.clone(),
+                    language_probability: update.language_probability,
+                    audio_source: update.audio_source.clone(),
                 };
 
                 // Save to recording manager
@@ -265,6 +296,8 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     });
     manager.set_meeting_name(Some(effective_meeting_name));
 
+    let dual_channel = apply_recording_preferences(&app, &mut manager).await;
+
     // Set up error callback
     let app_for_error = app.clone();
     manager.set_error_callback(move |error| {
@@ -289,7 +322,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
     reset_speech_detected_flag(); // Reset for new recording session
 
     // Start optimized parallel transcription task and store handle
-    let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver);
+    let task_handle = transcription::start_transcription_task(app.clone(), transcription_receiver, dual_channel);
     {
         let mut global_task = TRANSCRIPTION_TASK.lock().unwrap();
         *global_task = Some(task_handle);
@@ -314,6 +347,13 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
                     display_time: update.timestamp.clone(), // Use wall-clock timestamp for display
                     confidence: update.confidence,
                     sequence_id: update.sequence_id,
+                    speaker_id: update.speaker_id,
+                    speaker_profile_id: update.speaker_profile_id,
+                    speaker_name: update.speaker_name.clone(),
+                    words: update.words.clone(),
+                    language: update.language.clone(),
+                    language_probability: update.language_probability,
+                    audio_source: update.audio_source.clone(),
                 };
 
                 // Save to recording manager
@@ -29,6 +29,7 @@ pub struct RecordingManager {
     recording_saver: RecordingSaver,
     device_monitor: Option<AudioDeviceMonitor>,
     device_event_receiver: Option<mpsc::UnboundedReceiver<DeviceEvent>>,
+    dual_channel_transcription: bool,
 }
 
 // SAFETY: RecordingManager contains types that we've marked as Send
@@ -49,6 +50,7 @@ impl RecordingManager {
             recording_saver: RecordingSaver::new(),
             device_monitor: Some(device_monitor),
             device_event_receiver: Some(device_event_receiver),
+            dual_channel_transcription: false,
         }
     }
 
@@ -68,6 +70,7 @@ impl RecordingManager {
         // CRITICAL FIX: Create recording sender for pre-mixed audio from pipeline
         // Pipeline will mix mic + system audio professionally and send to this channel
         let recording_sender = self.recording_saver.start_accumulation();
+        let stem_sender = self.recording_saver.start_stem_accumulation();
 
         // Start recording state first
         self.state.start_recording()?;
@@ -105,10 +108,12 @@ impl RecordingManager {
             0, // Ignored - using dynamic sizing internally
             48000, // 48kHz sample rate
             Some(recording_sender), // CRITICAL: Pass recording sender to receive pre-mixed audio
+            stem_sender, // Per-source stems, only when multitrack recording is enabled
             mic_name,
             mic_kind,
             sys_name,
             sys_kind,
+            self.dual_channel_transcription,
         )?;
 
         // Give the pipeline a moment to fully initialize before starting streams
@@ -413,6 +418,21 @@ impl RecordingManager {
         self.recording_saver.set_meeting_name(name);
     }
 
+    /// Transcribe microphone and system audio as separate channels (takes effect on start)
+    pub fn set_dual_channel_transcription(&mut self, enabled: bool) {
+        self.dual_channel_transcription = enabled;
+    }
+
+    /// Also record microphone and system audio as separate tracks (takes effect on start)
+    pub fn set_multitrack_recording(&mut self, enabled: bool) {
+        self.recording_saver.set_multitrack(enabled);
+    }
+
+    /// Set the output format of saved audio (takes effect on start)
+    pub fn set_encoder_settings(&mut self, settings: super::encoder::EncoderSettings) {
+        self.recording_saver.set_encoder_settings(settings);
+    }
+
     /// Add a structured transcript segment to be saved later
     pub fn add_transcript_segment(&self, segment: super::recording_saver::TranscriptSegment) {
         self.recording_saver.add_transcript_segment(segment);
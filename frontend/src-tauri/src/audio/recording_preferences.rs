@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
 use log::{info, warn};
 
 #[cfg(target_os = "macos")]
@@ -9,29 +10,76 @@ use anyhow::Result;
 
 #[cfg(target_os = "macos")]
 use crate::audio::capture::AudioCaptureBackend;
+use crate::audio::encoder::EncoderSettings;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RecordingPreferences {
     pub save_folder: PathBuf,
     pub auto_save: bool,
+    /// Output format: "wav", "flac", "opus" or "aac" ("mp4")
     pub file_format: String,
+    /// Bitrate for lossy formats, None for the format's default
+    #[serde(default)]
+    pub audio_bitrate_kbps: Option<u32>,
+    /// Output sample rate, None to keep the capture rate (48kHz)
+    #[serde(default)]
+    pub audio_sample_rate: Option<u32>,
+    /// Transcribe microphone and system audio separately, labelling segments by source
+    #[serde(default)]
+    pub dual_channel_transcription: bool,
+    /// Also save microphone and system audio as separate tracks plus a stereo file
+    #[serde(default)]
+    pub multitrack_recording: bool,
+    /// Re-run speaker diarization over the whole recording once it has been saved
+    #[serde(default)]
+    pub rediarize_after_recording: bool,
     #[cfg(target_os = "macos")]
     #[serde(default)]
     pub system_audio_backend: Option<String>,
 }
 
+/// Store file and keys the channel and format settings are persisted under
+const PREFERENCES_STORE: &str = "preferences.json";
+const DUAL_CHANNEL_KEY: &str = "dual_channel_transcription";
+const MULTITRACK_KEY: &str = "multitrack_recording";
+const REDIARIZE_KEY: &str = "rediarize_after_recording";
+const FILE_FORMAT_KEY: &str = "recording_file_format";
+const BITRATE_KEY: &str = "recording_bitrate_kbps";
+const SAMPLE_RATE_KEY: &str = "recording_sample_rate";
+
 impl Default for RecordingPreferences {
     fn default() -> Self {
         Self {
             save_folder: get_default_recordings_folder(),
             auto_save: true,
             file_format: "mp4".to_string(),
+            audio_bitrate_kbps: None,
+            audio_sample_rate: None,
+            dual_channel_transcription: false,
+            multitrack_recording: false,
+            rediarize_after_recording: false,
             #[cfg(target_os = "macos")]
             system_audio_backend: Some("coreaudio".to_string()),
         }
     }
 }
 
+impl RecordingPreferences {
+    /// Validated encoder settings for the configured format
+    pub fn encoder_settings(&self) -> Result<EncoderSettings> {
+        EncoderSettings::new(&self.file_format, self.audio_bitrate_kbps, self.audio_sample_rate)
+    }
+
+    /// Encoder settings for the configured format, falling back to the default ones when
+    /// the stored values are invalid
+    pub fn encoder_settings_or_default(&self) -> EncoderSettings {
+        self.encoder_settings().unwrap_or_else(|e| {
+            warn!("Invalid recording format settings, using default: {}", e);
+            EncoderSettings::default()
+        })
+    }
+}
+
 /// Get the default recordings folder based on platform
 pub fn get_default_recordings_folder() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -88,12 +136,11 @@ pub fn generate_recording_filename(format: &str) -> String {
 
 /// Load recording preferences from store
 pub async fn load_recording_preferences<R: Runtime>(
-    _app: &AppHandle<R>,
+    app: &AppHandle<R>,
 ) -> Result<RecordingPreferences> {
-    // Try to load from Tauri store, fallback to defaults
-    // For now, return defaults - can be enhanced to use tauri-plugin-store
+    // Defaults, plus the settings that are persisted in the Tauri store
     #[cfg(target_os = "macos")]
-    let prefs = {
+    let mut prefs = {
         let mut p = RecordingPreferences::default();
         let backend = crate::audio::capture::get_current_backend();
         p.system_audio_backend = Some(backend.to_string());
@@ -101,21 +148,63 @@ pub async fn load_recording_preferences<R: Runtime>(
     };
 
     #[cfg(not(target_os = "macos"))]
-    let prefs = RecordingPreferences::default();
+    let mut prefs = RecordingPreferences::default();
+
+    match app.store(PREFERENCES_STORE) {
+        Ok(store) => {
+            prefs.dual_channel_transcription = store
+                .get(DUAL_CHANNEL_KEY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            prefs.multitrack_recording = store
+                .get(MULTITRACK_KEY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            prefs.rediarize_after_recording = store
+                .get(REDIARIZE_KEY)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if let Some(format) = store.get(FILE_FORMAT_KEY).and_then(|v| v.as_str().map(String::from)) {
+                prefs.file_format = format;
+            }
+            prefs.audio_bitrate_kbps = store
+                .get(BITRATE_KEY)
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32);
+            prefs.audio_sample_rate = store
+                .get(SAMPLE_RATE_KEY)
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32);
+        }
+        Err(e) => warn!("Failed to open preferences store: {}", e),
+    }
 
-    info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}",
-          prefs.save_folder, prefs.auto_save, prefs.file_format);
+    info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}, dual_channel={}, multitrack={}",
+          prefs.save_folder, prefs.auto_save, prefs.file_format, prefs.dual_channel_transcription,
+          prefs.multitrack_recording);
     Ok(prefs)
 }
 
 /// Save recording preferences to store
 pub async fn save_recording_preferences<R: Runtime>(
-    _app: &AppHandle<R>,
+    app: &AppHandle<R>,
     preferences: &RecordingPreferences,
 ) -> Result<()> {
-    // For now, just log - can be enhanced to use tauri-plugin-store
-    info!("Saving recording preferences: save_folder={:?}, auto_save={}, format={}",
-          preferences.save_folder, preferences.auto_save, preferences.file_format);
+    info!("Saving recording preferences: save_folder={:?}, auto_save={}, format={}, dual_channel={}, multitrack={}",
+          preferences.save_folder, preferences.auto_save, preferences.file_format,
+          preferences.dual_channel_transcription, preferences.multitrack_recording);
+
+    // Reject format options the encoder can't honour before anything is persisted
+    preferences.encoder_settings()?;
+
+    let store = app.store(PREFERENCES_STORE)?;
+    store.set(DUAL_CHANNEL_KEY, preferences.dual_channel_transcription);
+    store.set(MULTITRACK_KEY, preferences.multitrack_recording);
+    store.set(REDIARIZE_KEY, preferences.rediarize_after_recording);
+    store.set(FILE_FORMAT_KEY, preferences.file_format.clone());
+    store.set(BITRATE_KEY, preferences.audio_bitrate_kbps);
+    store.set(SAMPLE_RATE_KEY, preferences.audio_sample_rate);
+    store.save()?;
 
     // Save backend preference to global config
     #[cfg(target_os = "macos")]
@@ -5,12 +5,15 @@ use log::{info, warn, error};
 use tauri::{AppHandle, Runtime, Emitter};
 use tokio::sync::mpsc;
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use super::recording_state::AudioChunk;
+use super::recording_state::{AudioChunk, DeviceType};
 use super::recording_preferences::load_recording_preferences;
 use super::audio_processing::create_meeting_folder;
+use super::transcription::WordTimestamp;
 use super::incremental_saver::IncrementalAudioSaver;
+use super::encode::merge_stereo_tracks;
+use super::encoder::EncoderSettings;
 
 /// Structured transcript segment for JSON export
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +26,20 @@ pub struct TranscriptSegment {
     pub display_time: String,   // Formatted time for display like "[02:15]"
     pub confidence: f32,
     pub sequence_id: u64,
+    #[serde(default)]
+    pub speaker_id: Option<usize>, // Diarized speaker, None when diarization was unavailable
+    #[serde(default)]
+    pub speaker_profile_id: Option<i64>, // Registered speaker from the voice registry
+    #[serde(default)]
+    pub speaker_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>, // Recording-relative word timings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>, // Language code of the text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language_probability: Option<f32>, // Detection probability, None if the language was fixed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_source: Option<String>, // "mic" or "system" in dual-channel mode, None when mixed
 }
 
 /// Meeting metadata structure
@@ -39,6 +56,10 @@ pub struct MeetingMetadata {
     pub transcript_file: String,
     pub sample_rate: u32,
     pub status: String,  // "recording", "completed", "error"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stems: Option<AudioStems>, // Per-source tracks, present for multitrack recordings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoder: Option<EncoderSettings>, // Output settings, so recovery encodes the same way
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,9 +68,149 @@ pub struct DeviceInfo {
     pub system_audio: Option<String>,
 }
 
+/// Per-source audio files of a multitrack recording (relative to the meeting folder)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioStems {
+    pub microphone: String,
+    pub system: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stereo: Option<String>, // Mic on the left channel, system audio on the right
+}
+
+/// Which recorded track to read from a meeting folder
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioTrack {
+    #[default]
+    Mixed,
+    Mic,
+    System,
+}
+
+impl AudioTrack {
+    /// The transcript `audio_source` label for segments taken from this track
+    pub fn audio_source(&self) -> Option<&'static str> {
+        match self {
+            AudioTrack::Mixed => None,
+            AudioTrack::Mic => Some("mic"),
+            AudioTrack::System => Some("system"),
+        }
+    }
+}
+
+/// Read a meeting folder's metadata.json
+pub fn read_meeting_metadata(folder: &Path) -> Result<MeetingMetadata> {
+    let contents = std::fs::read_to_string(folder.join("metadata.json"))?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Write a meeting folder's metadata.json (atomic write with temp file)
+pub fn write_meeting_metadata(folder: &Path, metadata: &MeetingMetadata) -> Result<()> {
+    let metadata_path = folder.join("metadata.json");
+    let temp_path = folder.join(".metadata.json.tmp");
+
+    let json_string = serde_json::to_string_pretty(metadata)?;
+    std::fs::write(&temp_path, json_string)?;
+    std::fs::rename(&temp_path, &metadata_path)?;  // Atomic
+
+    Ok(())
+}
+
+/// Read the segments of a meeting folder's transcripts.json
+pub fn read_transcripts_json(folder: &Path) -> Result<Vec<TranscriptSegment>> {
+    let contents = std::fs::read_to_string(folder.join("transcripts.json"))?;
+    let json: serde_json::Value = serde_json::from_str(&contents)?;
+    let segments = json
+        .get("segments")
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("transcripts.json has no segments"))?;
+    Ok(serde_json::from_value(segments)?)
+}
+
+/// Write transcripts.json to a meeting folder (atomic write with temp file and validation)
+pub fn write_transcripts_json_file(folder: &Path, segments: &[TranscriptSegment]) -> Result<()> {
+    info!("Writing {} transcript segments to JSON", segments.len());
+
+    let transcript_path = folder.join("transcripts.json");
+    let temp_path = folder.join(".transcripts.json.tmp");
+
+    // Create JSON structure
+    let json = serde_json::json!({
+        "version": "1.0",
+        "segments": segments,
+        "last_updated": chrono::Utc::now().to_rfc3339(),
+        "total_segments": segments.len()
+    });
+
+    // Serialize to pretty JSON string
+    let json_string = serde_json::to_string_pretty(&json)
+        .map_err(|e| {
+            error!("Failed to serialize transcripts to JSON: {}", e);
+            anyhow::anyhow!("JSON serialization failed: {}", e)
+        })?;
+
+    // Write to temp file with error handling
+    std::fs::write(&temp_path, &json_string)
+        .map_err(|e| {
+            error!("Failed to write transcript temp file to {}: {}", temp_path.display(), e);
+            anyhow::anyhow!("Failed to write temp file: {}", e)
+        })?;
+
+    // Verify temp file was written correctly
+    if !temp_path.exists() {
+        error!("Temp transcript file does not exist after write: {}", temp_path.display());
+        return Err(anyhow::anyhow!("Temp file verification failed"));
+    }
+
+    // Atomic rename
+    std::fs::rename(&temp_path, &transcript_path)
+        .map_err(|e| {
+            error!("Failed to rename transcript file from {} to {}: {}",
+                   temp_path.display(), transcript_path.display(), e);
+            anyhow::anyhow!("Failed to rename transcript file: {}", e)
+        })?;
+
+    info!("✅ Successfully wrote transcripts.json with {} segments", segments.len());
+    Ok(())
+}
+
+/// Resolve the final audio file of a meeting folder (from metadata.json, falling back to audio.mp4)
+pub fn meeting_audio_path(folder: &Path) -> PathBuf {
+    let audio_file = read_meeting_metadata(folder)
+        .map(|m| m.audio_file)
+        .unwrap_or_else(|_| "audio.mp4".to_string());
+    folder.join(audio_file)
+}
+
+/// Resolve a specific track of a meeting folder; stems only exist for multitrack recordings
+pub fn meeting_track_path(folder: &Path, track: AudioTrack) -> Result<PathBuf> {
+    if track == AudioTrack::Mixed {
+        return Ok(meeting_audio_path(folder));
+    }
+
+    let stems = read_meeting_metadata(folder)
+        .ok()
+        .and_then(|m| m.stems)
+        .ok_or_else(|| anyhow::anyhow!("This meeting was not recorded with separate tracks"))?;
+    let file = match track {
+        AudioTrack::Mic => stems.microphone,
+        _ => stems.system,
+    };
+
+    let path = folder.join(file);
+    if !path.exists() {
+        return Err(anyhow::anyhow!("Track file not found: {}", path.display()));
+    }
+    Ok(path)
+}
+
 /// New recording saver using incremental saving strategy
 pub struct RecordingSaver {
     incremental_saver: Option<Arc<AsyncMutex<IncrementalAudioSaver>>>,
+    // Per-source stem savers (microphone, system), only for multitrack recordings
+    stem_savers: Option<(Arc<AsyncMutex<IncrementalAudioSaver>>, Arc<AsyncMutex<IncrementalAudioSaver>>)>,
+    multitrack: bool,
+    encoder_settings: EncoderSettings,
     meeting_folder: Option<PathBuf>,
     meeting_name: Option<String>,
     metadata: Option<MeetingMetadata>,
@@ -62,6 +223,9 @@ impl RecordingSaver {
     pub fn new() -> Self {
         Self {
             incremental_saver: None,
+            stem_savers: None,
+            multitrack: false,
+            encoder_settings: EncoderSettings::default(),
             meeting_folder: None,
             meeting_name: None,
             metadata: None,
@@ -76,6 +240,16 @@ impl RecordingSaver {
         self.meeting_name = name;
     }
 
+    /// Also save microphone and system audio as separate tracks (takes effect on start)
+    pub fn set_multitrack(&mut self, enabled: bool) {
+        self.multitrack = enabled;
+    }
+
+    /// Set the output format of saved audio (takes effect on start)
+    pub fn set_encoder_settings(&mut self, settings: EncoderSettings) {
+        self.encoder_settings = settings;
+    }
+
     /// Set device information in metadata
     pub fn set_device_info(&mut self, mic_name: Option<String>, sys_name: Option<String>) {
         if let Some(ref mut metadata) = self.metadata {
@@ -102,8 +276,13 @@ impl RecordingSaver {
                 info!("Updated transcript segment {} (seq: {}) - total segments: {}",
                       segment.id, segment.sequence_id, segments.len());
             } else {
-                // New segment, add it
-                segments.push(segment.clone());
+                // New segment: keep the list ordered by start time, since in dual-channel
+                // mode mic and system segments can finish transcribing out of order
+                let position = segments
+                    .iter()
+                    .rposition(|s| s.audio_start_time <= segment.audio_start_time)
+                    .map_or(0, |i| i + 1);
+                segments.insert(position, segment.clone());
                 info!("Added new transcript segment {} (seq: {}) - total segments: {}",
                       segment.id, segment.sequence_id, segments.len());
             }
@@ -130,6 +309,13 @@ impl RecordingSaver {
             display_time: "[00:00]".to_string(),
             confidence: 1.0,
             sequence_id: 0,
+            speaker_id: None,
+            speaker_profile_id: None,
+            speaker_name: None,
+            words: Vec::new(),
+            language: None,
+            language_probability: None,
+            audio_source: None,
         };
         self.add_transcript_segment(segment);
     }
@@ -196,6 +382,40 @@ impl RecordingSaver {
         sender
     }
 
+    /// Start per-source stem accumulation for multitrack recordings
+    ///
+    /// Must be called after `start_accumulation`. Chunks are routed to the microphone
+    /// or system stem by their device type. Returns None when multitrack is disabled.
+    pub fn start_stem_accumulation(&mut self) -> Option<mpsc::UnboundedSender<AudioChunk>> {
+        let (mic_saver, system_saver) = self.stem_savers.clone()?;
+        info!("Initializing per-source stem savers for multitrack recording");
+
+        let (sender, mut receiver) = mpsc::unbounded_channel::<AudioChunk>();
+        let is_saving_clone = self.is_saving.clone();
+
+        tokio::spawn(async move {
+            while let Some(chunk) = receiver.recv().await {
+                let should_continue = is_saving_clone.lock().map(|s| *s).unwrap_or(false);
+                if !should_continue {
+                    break;
+                }
+
+                let saver_arc = match chunk.device_type {
+                    DeviceType::Microphone => &mic_saver,
+                    DeviceType::System => &system_saver,
+                };
+                let mut saver_guard = saver_arc.lock().await;
+                if let Err(e) = saver_guard.add_chunk(chunk) {
+                    error!("Failed to add chunk to stem saver: {}", e);
+                }
+            }
+
+            info!("Stem accumulation task ended");
+        });
+
+        Some(sender)
+    }
+
     /// Initialize meeting folder structure and metadata
     fn initialize_meeting_folder(&mut self, meeting_name: &str) -> Result<()> {
         // Load preferences to get base recordings folder
@@ -204,8 +424,30 @@ impl RecordingSaver {
         // Create meeting folder structure
         let meeting_folder = create_meeting_folder(&base_folder, meeting_name)?;
 
-        // Initialize incremental saver
-        let incremental_saver = IncrementalAudioSaver::new(meeting_folder.clone(), 48000)?;
+        // Initialize incremental saver; without ffmpeg, formats it would encode fall back to FLAC
+        let settings = self.encoder_settings.usable();
+        self.encoder_settings = settings;
+        let ext = settings.extension();
+        let incremental_saver =
+            IncrementalAudioSaver::for_track(meeting_folder.clone(), 48000, "audio", settings)?;
+
+        // Stem savers share the checkpoints directory with the mixed track
+        let stems = if self.multitrack {
+            let mic_saver = IncrementalAudioSaver::for_track(meeting_folder.clone(), 48000, "mic", settings)?;
+            let system_saver = IncrementalAudioSaver::for_track(meeting_folder.clone(), 48000, "system", settings)?;
+            self.stem_savers = Some((
+                Arc::new(AsyncMutex::new(mic_saver)),
+                Arc::new(AsyncMutex::new(system_saver)),
+            ));
+            Some(AudioStems {
+                microphone: format!("mic.{}", ext),
+                system: format!("system.{}", ext),
+                stereo: None,
+            })
+        } else {
+            self.stem_savers = None;
+            None
+        };
 
         // Create initial metadata
         let metadata = MeetingMetadata {
@@ -219,10 +461,12 @@ impl RecordingSaver {
                 microphone: None,  // Could be enhanced to store actual device names
                 system_audio: None,
             },
-            audio_file: "audio.mp4".to_string(),
+            audio_file: format!("audio.{}", ext),
             transcript_file: "transcripts.json".to_string(),
             sample_rate: 48000,
             status: "recording".to_string(),
+            stems,
+            encoder: Some(settings),
         };
 
         // Write initial metadata.json
@@ -237,14 +481,7 @@ impl RecordingSaver {
 
     /// Write metadata.json to disk (atomic write with temp file)
     fn write_metadata(&self, folder: &PathBuf, metadata: &MeetingMetadata) -> Result<()> {
-        let metadata_path = folder.join("metadata.json");
-        let temp_path = folder.join(".metadata.json.tmp");
-
-        let json_string = serde_json::to_string_pretty(metadata)?;
-        std::fs::write(&temp_path, json_string)?;
-        std::fs::rename(&temp_path, &metadata_path)?;  // Atomic
-
-        Ok(())
+        write_meeting_metadata(folder, metadata)
     }
 
     /// Write transcripts.json to disk (atomic write with temp file and validation)
@@ -257,49 +494,7 @@ impl RecordingSaver {
             return Err(anyhow::anyhow!("Failed to lock transcript segments"));
         };
 
-        info!("Writing {} transcript segments to JSON", segments_clone.len());
-
-        let transcript_path = folder.join("transcripts.json");
-        let temp_path = folder.join(".transcripts.json.tmp");
-
-        // Create JSON structure
-        let json = serde_json::json!({
-            "version": "1.0",
-            "segments": segments_clone,
-            "last_updated": chrono::Utc::now().to_rfc3339(),
-            "total_segments": segments_clone.len()
-        });
-
-        // Serialize to pretty JSON string
-        let json_string = serde_json::to_string_pretty(&json)
-            .map_err(|e| {
-                error!("Failed to serialize transcripts to JSON: {}", e);
-                anyhow::anyhow!("JSON serialization failed: {}", e)
-            })?;
-
-        // Write to temp file with error handling
-        std::fs::write(&temp_path, &json_string)
-            .map_err(|e| {
-                error!("Failed to write transcript temp file to {}: {}", temp_path.display(), e);
-                anyhow::anyhow!("Failed to write temp file: {}", e)
-            })?;
-
-        // Verify temp file was written correctly
-        if !temp_path.exists() {
-            error!("Temp transcript file does not exist after write: {}", temp_path.display());
-            return Err(anyhow::anyhow!("Temp file verification failed"));
-        }
-
-        // Atomic rename
-        std::fs::rename(&temp_path, &transcript_path)
-            .map_err(|e| {
-                error!("Failed to rename transcript file from {} to {}: {}",
-                       temp_path.display(), transcript_path.display(), e);
-                anyhow::anyhow!("Failed to rename transcript file: {}", e)
-            })?;
-
-        info!("✅ Successfully wrote transcripts.json with {} segments", segments_clone.len());
-        Ok(())
+        write_transcripts_json_file(folder, &segments_clone)
     }
 
     // in frontend/src-tauri/src/audio/recording_saver.rs
@@ -349,12 +544,17 @@ impl RecordingSaver {
             return Ok(None);
         }
 
-        // Finalize incremental saver (merge checkpoints into final audio.mp4)
+        // Finalize incremental saver (merge checkpoints into final audio file)
         let final_audio_path = if let Some(saver_arc) = &self.incremental_saver {
             let mut saver = saver_arc.lock().await;
             match saver.finalize().await {
                 Ok(path) => {
                     info!("✅ Successfully finalized audio: {}", path.display());
+                    if preferences.rediarize_after_recording {
+                        if let Some(folder) = &self.meeting_folder {
+                            crate::diarization::batch::queue_rediarization(folder);
+                        }
+                    }
                     path
                 }
                 Err(e) => {
@@ -367,6 +567,11 @@ impl RecordingSaver {
             return Err("No incremental saver initialized".to_string());
         };
 
+        // Finalize stems; a failure here keeps the mixed recording and drops the stems
+        if let Some((mic_saver, system_saver)) = self.stem_savers.take() {
+            self.finalize_stems(mic_saver, system_saver).await;
+        }
+
         // Save final transcripts.json with validation
         if let Some(folder) = &self.meeting_folder {
             if let Err(e) = self.write_transcripts_json(folder) {
@@ -428,6 +633,51 @@ impl RecordingSaver {
         Ok(Some(final_audio_path.to_string_lossy().to_string()))
     }
 
+    /// Merge the stem checkpoints and build the stereo file (mic left, system right)
+    async fn finalize_stems(
+        &mut self,
+        mic_saver: Arc<AsyncMutex<IncrementalAudioSaver>>,
+        system_saver: Arc<AsyncMutex<IncrementalAudioSaver>>,
+    ) {
+        let mic_path = mic_saver.lock().await.finalize().await;
+        let system_path = system_saver.lock().await.finalize().await;
+
+        let (mic_path, system_path) = match (mic_path, system_path) {
+            (Ok(mic), Ok(system)) => (mic, system),
+            (mic, system) => {
+                warn!("Failed to finalize audio stems (mic: {:?}, system: {:?})",
+                      mic.err(), system.err());
+                if let Some(metadata) = self.metadata.as_mut() {
+                    metadata.stems = None;
+                }
+                return;
+            }
+        };
+        info!("✅ Finalized audio stems: {}, {}", mic_path.display(), system_path.display());
+
+        let Some(folder) = self.meeting_folder.clone() else {
+            return;
+        };
+        let settings = self.encoder_settings;
+        let stereo_file = format!("audio_stereo.{}", settings.extension());
+        let stereo_path = folder.join(&stereo_file);
+        let merged = tokio::task::spawn_blocking(move || {
+            merge_stereo_tracks(&mic_path, &system_path, &stereo_path, settings)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Stereo merge task failed: {}", e))
+        .and_then(|r| r);
+
+        match merged {
+            Ok(()) => {
+                if let Some(stems) = self.metadata.as_mut().and_then(|m| m.stems.as_mut()) {
+                    stems.stereo = Some(stereo_file);
+                }
+            }
+            Err(e) => warn!("Failed to build stereo file from stems: {}", e),
+        }
+    }
+
     /// Get the meeting folder path (for passing to backend)
     pub fn get_meeting_folder(&self) -> Option<&PathBuf> {
         self.meeting_folder.as_ref()
@@ -453,3 +703,90 @@ impl Default for RecordingSaver {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(sequence_id: u64, start: f64, source: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            id: format!("seg_{}", sequence_id),
+            text: format!("segment {}", sequence_id),
+            audio_start_time: start,
+            audio_end_time: start + 1.0,
+            duration: 1.0,
+            display_time: "[00:00]".to_string(),
+            confidence: 0.9,
+            sequence_id,
+            speaker_id: None,
+            speaker_profile_id: None,
+            speaker_name: None,
+            words: Vec::new(),
+            language: None,
+            language_probability: None,
+            audio_source: Some(source.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_interleaves_channel_segments_by_start_time() {
+        let saver = RecordingSaver::new();
+        // The system segment started first but finished transcribing after the mic one
+        saver.add_transcript_segment(segment(0, 2.0, "mic"));
+        saver.add_transcript_segment(segment(1, 0.5, "system"));
+        saver.add_transcript_segment(segment(2, 3.0, "system"));
+        saver.add_transcript_segment(segment(3, 2.0, "system"));
+
+        let order: Vec<u64> = saver
+            .get_transcript_segments()
+            .iter()
+            .map(|s| s.sequence_id)
+            .collect();
+        assert_eq!(order, vec![1, 0, 3, 2]);
+    }
+
+    fn metadata(stems: Option<AudioStems>) -> MeetingMetadata {
+        MeetingMetadata {
+            version: "1.0".to_string(),
+            meeting_id: None,
+            meeting_name: None,
+            created_at: String::new(),
+            completed_at: None,
+            duration_seconds: None,
+            devices: DeviceInfo { microphone: None, system_audio: None },
+            audio_file: "audio.mp4".to_string(),
+            transcript_file: "transcripts.json".to_string(),
+            sample_rate: 48000,
+            status: "completed".to_string(),
+            stems,
+            encoder: None,
+        }
+    }
+
+    #[test]
+    fn test_resolves_stem_tracks_from_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path();
+        write_meeting_metadata(folder, &metadata(Some(AudioStems {
+            microphone: "mic.mp4".to_string(),
+            system: "system.mp4".to_string(),
+            stereo: None,
+        }))).unwrap();
+        std::fs::write(folder.join("mic.mp4"), b"").unwrap();
+
+        assert_eq!(meeting_track_path(folder, AudioTrack::Mixed).unwrap(), folder.join("audio.mp4"));
+        assert_eq!(meeting_track_path(folder, AudioTrack::Mic).unwrap(), folder.join("mic.mp4"));
+        // Listed in metadata but never written
+        assert!(meeting_track_path(folder, AudioTrack::System).is_err());
+    }
+
+    #[test]
+    fn test_single_track_recordings_have_no_stems() {
+        let dir = tempfile::tempdir().unwrap();
+        write_meeting_metadata(dir.path(), &metadata(None)).unwrap();
+
+        assert!(meeting_track_path(dir.path(), AudioTrack::Mic).is_err());
+        let json = std::fs::read_to_string(dir.path().join("metadata.json")).unwrap();
+        assert!(!json.contains("stems"));
+    }
+}
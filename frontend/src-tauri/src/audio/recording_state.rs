@@ -14,6 +14,16 @@ pub enum DeviceType {
     System,
 }
 
+impl DeviceType {
+    /// Source label stored on transcript segments in dual-channel mode
+    pub fn audio_source(&self) -> &'static str {
+        match self {
+            DeviceType::Microphone => "mic",
+            DeviceType::System => "system",
+        }
+    }
+}
+
 /// Audio chunk with metadata for processing
 #[derive(Debug, Clone)]
 pub struct AudioChunk {
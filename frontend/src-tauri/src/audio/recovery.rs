@@ -0,0 +1,481 @@
+// audio/recovery.rs
+//
+// Recovery of recordings interrupted by a crash or power loss. A live recording leaves
+// its folder at status "recording" with 30s checkpoints under .checkpoints/ and an
+// incrementally written transcripts.json. On startup those folders are offered to the
+// user, who can either recover them (merge the checkpoints exactly as `finalize` would,
+// then register the meeting) or discard them.
+
+use crate::api::TranscriptSegment as DbTranscriptSegment;
+use crate::audio::encode::merge_stereo_tracks;
+use crate::audio::encoder::{encoder_for, AudioFormat, EncoderSettings};
+use crate::audio::incremental_saver::{count_checkpoints, IncrementalAudioSaver};
+use crate::audio::recording_preferences::{get_default_recordings_folder, load_recording_preferences};
+use crate::audio::recording_saver::{
+    read_meeting_metadata, read_transcripts_json, write_meeting_metadata, MeetingMetadata,
+    TranscriptSegment,
+};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterruptedRecording {
+    pub folder_path: String,
+    pub meeting_name: Option<String>,
+    pub created_at: String,
+    /// Approximate recorded audio, from the number of 30s checkpoints
+    pub audio_seconds: f64,
+    pub segments: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecoveryResult {
+    pub meeting_id: String,
+    pub meeting_name: String,
+    pub folder_path: String,
+    pub segments: usize,
+    pub audio_recovered: bool,
+    /// Some checkpoints could not be merged; the folder stays listed so recovery can be retried
+    pub checkpoints_left: bool,
+    /// Damaged last checkpoints left out of the merged audio. They stay on disk until the
+    /// user deletes them with `api_discard_dropped_checkpoints`.
+    pub dropped_checkpoints: Vec<String>,
+}
+
+/// Seconds of audio per checkpoint (see `IncrementalAudioSaver`)
+const CHECKPOINT_SECONDS: f64 = 30.0;
+
+/// Meeting folders under `base` whose metadata.json is still at status "recording"
+pub fn find_interrupted_recordings(base: &Path, active: Option<&Path>) -> Vec<InterruptedRecording> {
+    let Ok(entries) = std::fs::read_dir(base) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<InterruptedRecording> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir() && Some(path.as_path()) != active)
+        .filter_map(|folder| {
+            let metadata = read_meeting_metadata(&folder).ok()?;
+            if metadata.status != "recording" {
+                return None;
+            }
+            let ext = encoder_for(recording_settings(&metadata)).checkpoint_extension();
+            let checkpoints = count_checkpoints(&folder.join(".checkpoints"), "audio", ext);
+            let segments = read_transcripts_json(&folder).map(|s| s.len()).unwrap_or(0);
+            Some(InterruptedRecording {
+                folder_path: folder.to_string_lossy().to_string(),
+                meeting_name: metadata.meeting_name,
+                created_at: metadata.created_at,
+                audio_seconds: checkpoints as f64 * CHECKPOINT_SECONDS,
+                segments,
+            })
+        })
+        .collect();
+
+    found.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    found
+}
+
+/// Output settings a recording was started with. Folders written before the settings were
+/// stored in metadata.json fall back to the format's defaults, judged by the audio file name.
+fn recording_settings(metadata: &MeetingMetadata) -> EncoderSettings {
+    metadata.encoder.unwrap_or_else(|| {
+        let format = AudioFormat::from_path(Path::new(&metadata.audio_file)).unwrap_or_default();
+        EncoderSettings::for_format(format)
+    })
+}
+
+/// Folders recordings may have been written to: the configured save folder and the default
+async fn recording_roots<R: Runtime>(app: &AppHandle<R>) -> Vec<PathBuf> {
+    let mut roots = vec![get_default_recordings_folder()];
+    if let Ok(preferences) = load_recording_preferences(app).await {
+        if !roots.contains(&preferences.save_folder) {
+            roots.push(preferences.save_folder);
+        }
+    }
+    roots
+}
+
+/// A meeting folder directly under one of `roots`, canonicalized
+fn recording_folder(folder_path: &str, roots: &[PathBuf]) -> Result<PathBuf, String> {
+    let folder = std::fs::canonicalize(folder_path)
+        .map_err(|e| format!("Recording folder not found ({}): {}", folder_path, e))?;
+    let in_roots = folder.parent().is_some_and(|parent| {
+        roots
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| root == parent)
+    });
+    if !in_roots {
+        return Err(format!("{} is not in a recordings folder", folder_path));
+    }
+    Ok(folder)
+}
+
+/// Only folders the scanner reports may be recovered or deleted: a meeting folder directly
+/// under one of `roots`, still at status "recording" and not the recording in progress
+fn interrupted_folder(
+    folder_path: &str,
+    roots: &[PathBuf],
+    active: Option<&Path>,
+) -> Result<(PathBuf, MeetingMetadata), String> {
+    let folder = recording_folder(folder_path, roots)?;
+    if active.and_then(|a| a.canonicalize().ok()).is_some_and(|a| a == folder) {
+        return Err("This recording is still in progress".to_string());
+    }
+
+    let metadata = read_meeting_metadata(&folder)
+        .map_err(|e| format!("Not a recording folder ({}): {}", folder_path, e))?;
+    if metadata.status != "recording" {
+        return Err("This recording is not marked as interrupted".to_string());
+    }
+    Ok((folder, metadata))
+}
+
+/// Check a folder passed by the frontend against the scanner's roots and the live recording
+async fn scanned_folder<R: Runtime>(
+    app: &AppHandle<R>,
+    folder_path: &str,
+) -> Result<(PathBuf, MeetingMetadata), String> {
+    let active = super::recording_commands::get_meeting_folder_path()
+        .await?
+        .map(PathBuf::from);
+    let roots = recording_roots(app).await;
+    interrupted_folder(folder_path, &roots, active.as_deref())
+}
+
+/// Merge the checkpoints of one track; Ok(None) if the track has no checkpoints.
+/// A damaged checkpoint the merge had to leave out is added to `dropped`.
+async fn merge_track(
+    folder: &Path,
+    metadata: &MeetingMetadata,
+    track: &str,
+    dropped: &mut Vec<String>,
+) -> anyhow::Result<Option<PathBuf>> {
+    let settings = recording_settings(metadata);
+    let mut saver = IncrementalAudioSaver::resume(folder.to_path_buf(), metadata.sample_rate, track, settings)?;
+    if saver.get_checkpoint_count() == 0 {
+        return Ok(None);
+    }
+    let path = saver.finalize().await?;
+    if let Some(checkpoint) = saver.dropped_checkpoint() {
+        log_warn!("Left damaged checkpoint {} out of the {} track", checkpoint.display(), track);
+        dropped.push(checkpoint.to_string_lossy().to_string());
+    }
+    Ok(Some(path))
+}
+
+/// Merge audio, finish metadata.json and register the meeting in the database
+async fn recover_folder(
+    state: &AppState,
+    folder: &Path,
+    mut metadata: MeetingMetadata,
+) -> Result<RecoveryResult, String> {
+    let folder_path = folder.to_string_lossy().to_string();
+    let meeting_name = metadata
+        .meeting_name
+        .clone()
+        .unwrap_or_else(|| "Recovered Meeting".to_string());
+
+    // Checkpoints of a track whose merge failed are left on disk for another attempt
+    let mut checkpoints_left = false;
+    let mut dropped_checkpoints = Vec::new();
+
+    // The mixed track may already be merged if the crash hit after `finalize`
+    let audio_recovered = match merge_track(folder, &metadata, "audio", &mut dropped_checkpoints).await {
+        Ok(Some(path)) => {
+            log_info!("Recovered audio for {}: {}", folder_path, path.display());
+            true
+        }
+        Ok(None) => folder.join(&metadata.audio_file).exists(),
+        Err(e) => {
+            log_warn!("Failed to merge checkpoints of {}: {}", folder_path, e);
+            checkpoints_left = true;
+            false
+        }
+    };
+
+    if metadata.stems.is_some() {
+        let mic = merge_track(folder, &metadata, "mic", &mut dropped_checkpoints).await;
+        let system = merge_track(folder, &metadata, "system", &mut dropped_checkpoints).await;
+        match (mic, system) {
+            (Ok(Some(mic)), Ok(Some(system))) => {
+                let settings = recording_settings(&metadata);
+                let stereo_file = format!("audio_stereo.{}", settings.extension());
+                let stereo = folder.join(&stereo_file);
+                let merged = tokio::task::spawn_blocking(move || {
+                    merge_stereo_tracks(&mic, &system, &stereo, settings)
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!("Stereo merge task failed: {}", e))
+                .and_then(|r| r);
+                match merged {
+                    Ok(()) => {
+                        if let Some(stems) = metadata.stems.as_mut() {
+                            stems.stereo = Some(stereo_file);
+                        }
+                    }
+                    Err(e) => log_warn!("Failed to build stereo file for {}: {}", folder_path, e),
+                }
+            }
+            (mic, system) => {
+                checkpoints_left |= mic.is_err() || system.is_err();
+                log_warn!(
+                    "Dropping stems of {} (mic: {:?}, system: {:?})",
+                    folder_path,
+                    mic.map(|p| p.is_some()),
+                    system.map(|p| p.is_some())
+                );
+                metadata.stems = None;
+            }
+        }
+    }
+
+    let segments: Vec<TranscriptSegment> = read_transcripts_json(folder).unwrap_or_else(|e| {
+        log_warn!("No usable transcripts.json in {}: {}", folder_path, e);
+        Vec::new()
+    });
+    if !audio_recovered && segments.is_empty() {
+        return Err("Neither audio nor transcript could be recovered from this recording".to_string());
+    }
+
+    let pool = state.db_manager.pool();
+    let existing: Option<String> = sqlx::query_scalar("SELECT id FROM meetings WHERE folder_path = ?")
+        .bind(&folder_path)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let meeting_id = match existing {
+        Some(id) => {
+            log_info!("Recording {} is already registered as {}", folder_path, id);
+            id
+        }
+        None => {
+            let db_segments: Vec<DbTranscriptSegment> = segments
+                .iter()
+                .map(|s| DbTranscriptSegment {
+                    id: s.id.clone(),
+                    text: s.text.clone(),
+                    timestamp: s.display_time.clone(),
+                    audio_start_time: Some(s.audio_start_time),
+                    audio_end_time: Some(s.audio_end_time),
+                    duration: Some(s.duration),
+                    speaker_id: s.speaker_id.map(|id| id as i64),
+                    speaker_profile_id: s.speaker_profile_id,
+                    words: (!s.words.is_empty()).then(|| s.words.clone()),
+                    language: s.language.clone(),
+                    language_probability: s.language_probability.map(f64::from),
+                    audio_source: s.audio_source.clone(),
+                })
+                .collect();
+            TranscriptsRepository::save_transcript(pool, &meeting_name, &db_segments, Some(folder_path.clone()))
+                .await
+                .map_err(|e| format!("Failed to save meeting: {}", e))?
+        }
+    };
+
+    metadata.meeting_id = Some(meeting_id.clone());
+    metadata.duration_seconds = segments.last().map(|s| s.audio_end_time);
+    if checkpoints_left {
+        // Stay at "recording" so the scanner offers the folder again; a retry finds the
+        // meeting row by folder path and only merges what is left
+        log_warn!("{} keeps unmerged checkpoints and stays listed for recovery", folder_path);
+    } else {
+        metadata.status = "completed".to_string();
+        metadata.completed_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    if let Err(e) = write_meeting_metadata(folder, &metadata) {
+        // The meeting row exists; the folder would just be offered for recovery again
+        log_warn!("Failed to update metadata.json of {}: {}", folder_path, e);
+    }
+
+    Ok(RecoveryResult {
+        meeting_id,
+        meeting_name,
+        folder_path,
+        segments: segments.len(),
+        audio_recovered,
+        checkpoints_left,
+        dropped_checkpoints,
+    })
+}
+
+/// List recordings that were interrupted before they could be saved
+///
+/// The recording currently in progress, if any, is never reported.
+#[tauri::command]
+pub async fn api_list_interrupted_recordings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<InterruptedRecording>, String> {
+    let active = super::recording_commands::get_meeting_folder_path()
+        .await?
+        .map(PathBuf::from);
+
+    let mut found = Vec::new();
+    for root in recording_roots(&app).await {
+        found.extend(find_interrupted_recordings(&root, active.as_deref()));
+    }
+    if !found.is_empty() {
+        log_info!("Found {} interrupted recordings", found.len());
+    }
+    Ok(found)
+}
+
+/// Recover an interrupted recording into a regular meeting
+#[tauri::command]
+pub async fn api_recover_recording<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    folder_path: String,
+) -> Result<RecoveryResult, String> {
+    log_info!("api_recover_recording called for {}", folder_path);
+    let (folder, metadata) = scanned_folder(&app, &folder_path).await?;
+
+    let result = recover_folder(&state, &folder, metadata).await.map_err(|e| {
+        log_error!("Recovery of {} failed: {}", folder_path, e);
+        e
+    })?;
+    log_info!(
+        "Recovered {} as meeting {} ({} segments, audio: {})",
+        folder_path,
+        result.meeting_id,
+        result.segments,
+        result.audio_recovered
+    );
+    Ok(result)
+}
+
+/// Delete an interrupted recording's folder instead of recovering it
+#[tauri::command]
+pub async fn api_discard_interrupted_recording<R: Runtime>(
+    app: AppHandle<R>,
+    folder_path: String,
+) -> Result<(), String> {
+    log_info!("api_discard_interrupted_recording called for {}", folder_path);
+    let (folder, _) = scanned_folder(&app, &folder_path).await?;
+    std::fs::remove_dir_all(&folder).map_err(|e| format!("Failed to delete recording: {}", e))
+}
+
+/// Delete the checkpoints recovery left out of a recovered recording's audio, once the
+/// user has accepted losing them. Only folders whose recovery has completed qualify, so
+/// checkpoints still waiting to be merged are never touched.
+#[tauri::command]
+pub async fn api_discard_dropped_checkpoints<R: Runtime>(
+    app: AppHandle<R>,
+    folder_path: String,
+) -> Result<(), String> {
+    log_info!("api_discard_dropped_checkpoints called for {}", folder_path);
+    let roots = recording_roots(&app).await;
+    let folder = recording_folder(&folder_path, &roots)?;
+    discard_checkpoints(&folder)
+}
+
+fn discard_checkpoints(folder: &Path) -> Result<(), String> {
+    let metadata = read_meeting_metadata(folder)
+        .map_err(|e| format!("Not a recording folder ({}): {}", folder.display(), e))?;
+    if metadata.status == "recording" {
+        return Err("This recording has not been recovered yet".to_string());
+    }
+
+    let checkpoints = folder.join(".checkpoints");
+    if !checkpoints.exists() {
+        return Ok(());
+    }
+    std::fs::remove_dir_all(&checkpoints).map_err(|e| format!("Failed to delete checkpoints: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recording_saver::DeviceInfo;
+
+    fn write_folder(base: &Path, name: &str, status: &str, checkpoints: usize) -> PathBuf {
+        let folder = base.join(name);
+        std::fs::create_dir_all(folder.join(".checkpoints")).unwrap();
+        for i in 0..checkpoints {
+            std::fs::write(folder.join(".checkpoints").join(format!("audio_chunk_{:03}.mp4", i)), b"data").unwrap();
+        }
+        let metadata = MeetingMetadata {
+            version: "1.0".to_string(),
+            meeting_id: None,
+            meeting_name: Some(name.to_string()),
+            created_at: format!("2025-11-0{}T10:00:00Z", checkpoints + 1),
+            completed_at: None,
+            duration_seconds: None,
+            devices: DeviceInfo { microphone: None, system_audio: None },
+            audio_file: "audio.mp4".to_string(),
+            transcript_file: "transcripts.json".to_string(),
+            sample_rate: 48000,
+            status: status.to_string(),
+            stems: None,
+            encoder: None,
+        };
+        write_meeting_metadata(&folder, &metadata).unwrap();
+        folder
+    }
+
+    #[test]
+    fn test_finds_only_folders_still_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        write_folder(dir.path(), "done", "completed", 0);
+        write_folder(dir.path(), "crashed", "recording", 3);
+        write_folder(dir.path(), "newer_crash", "recording", 4);
+        std::fs::create_dir_all(dir.path().join("not_a_meeting")).unwrap();
+
+        let found = find_interrupted_recordings(dir.path(), None);
+        let names: Vec<_> = found.iter().filter_map(|r| r.meeting_name.as_deref()).collect();
+        assert_eq!(names, vec!["newer_crash", "crashed"]);
+        assert_eq!(found[1].audio_seconds, 90.0);
+    }
+
+    #[test]
+    fn test_skips_the_active_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let roots = vec![dir.path().to_path_buf()];
+        let active = write_folder(dir.path(), "live", "recording", 1);
+
+        assert!(find_interrupted_recordings(dir.path(), Some(&active)).is_empty());
+        assert!(interrupted_folder(&active.to_string_lossy(), &roots, Some(&active)).is_err());
+        assert!(interrupted_folder(&active.to_string_lossy(), &roots, None).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_folders_outside_the_recording_roots() {
+        let recordings = tempfile::tempdir().unwrap();
+        let elsewhere = tempfile::tempdir().unwrap();
+        let roots = vec![recordings.path().to_path_buf()];
+        let stray = write_folder(elsewhere.path(), "stray", "recording", 1);
+        let nested = write_folder(&recordings.path().join("sub"), "nested", "recording", 1);
+
+        assert!(interrupted_folder(&stray.to_string_lossy(), &roots, None).is_err());
+        assert!(interrupted_folder(&nested.to_string_lossy(), &roots, None).is_err());
+        // Both temp dirs share a parent, so this climbs out of the root
+        let escaped = recordings
+            .path()
+            .join("..")
+            .join(elsewhere.path().file_name().unwrap())
+            .join("stray");
+        assert!(interrupted_folder(&escaped.to_string_lossy(), &roots, None).is_err());
+    }
+
+    #[test]
+    fn test_discards_checkpoints_only_after_recovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let crashed = write_folder(dir.path(), "crashed", "recording", 2);
+        let recovered = write_folder(dir.path(), "recovered", "completed", 1);
+
+        assert!(discard_checkpoints(&crashed).is_err());
+        assert!(crashed.join(".checkpoints").join("audio_chunk_001.mp4").exists());
+
+        discard_checkpoints(&recovered).unwrap();
+        assert!(!recovered.join(".checkpoints").exists());
+        assert!(recovered.join("metadata.json").exists());
+        // Nothing left to delete is not an error
+        discard_checkpoints(&recovered).unwrap();
+    }
+}
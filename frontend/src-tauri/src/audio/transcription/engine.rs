@@ -2,7 +2,12 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
+use super::parakeet_provider::ParakeetProvider;
 use super::provider::TranscriptionProvider;
+use super::remote_provider::{
+    RemoteTranscriptionProvider, GROQ_TRANSCRIPTION_URL, OPENAI_TRANSCRIPTION_URL,
+};
+use super::whisper_provider::WhisperProvider;
 use log::{info, warn};
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, Runtime};
@@ -74,6 +79,7 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 provider: "localWhisper".to_string(),
                 model: "large-v3".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
         Err(e) => {
@@ -82,6 +88,7 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 provider: "localWhisper".to_string(),
                 model: "large-v3".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
     };
@@ -135,16 +142,91 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 }
             }
         }
+        "openai" | "groq" | "openaiCompatible" => {
+            info!("🔍 Validating remote transcription settings...");
+            remote_endpoint(&config)?;
+            if config.provider != "openaiCompatible"
+                && !config.api_key.as_deref().is_some_and(|k| !k.trim().is_empty())
+            {
+                return Err(format!(
+                    "No API key saved for '{}'. Please add one in the transcript settings.",
+                    config.provider
+                ));
+            }
+            info!("✅ Remote transcription configured ({})", config.provider);
+            Ok(())
+        }
         other => {
             warn!("❌ Unsupported transcription provider for local recording: {}", other);
             Err(format!(
-                "Provider '{}' is not supported for local transcription. Please select 'localWhisper' or 'parakeet'.",
+                "Provider '{}' is not supported for transcription. Please select 'localWhisper', 'parakeet', 'openai', 'groq' or 'openaiCompatible'.",
                 other
             ))
         }
     }
 }
 
+/// Transcriptions endpoint of an OpenAI-compatible provider
+fn remote_endpoint(config: &crate::api::api::TranscriptConfig) -> Result<String, String> {
+    match config.provider.as_str() {
+        "openai" => Ok(OPENAI_TRANSCRIPTION_URL.to_string()),
+        "groq" => Ok(GROQ_TRANSCRIPTION_URL.to_string()),
+        _ => config
+            .endpoint
+            .clone()
+            .filter(|e| !e.trim().is_empty())
+            .ok_or_else(|| {
+                "No transcription server URL configured. Please enter it in the transcript settings."
+                    .to_string()
+            }),
+    }
+}
+
+/// Local engine used when a remote provider can't be reached: whichever local model is
+/// already loaded, otherwise the smallest downloaded Whisper model. Loaded by the remote
+/// provider on its first failed request.
+async fn local_fallback_provider() -> Option<Arc<dyn TranscriptionProvider>> {
+    let parakeet = crate::parakeet_engine::commands::PARAKEET_ENGINE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .cloned();
+    if let Some(engine) = parakeet {
+        if engine.is_model_loaded().await {
+            return Some(Arc::new(ParakeetProvider::new(engine)));
+        }
+    }
+
+    if let Err(e) = crate::whisper_engine::commands::whisper_init().await {
+        warn!("⚠️ No local fallback for remote transcription: {}", e);
+        return None;
+    }
+    let engine = crate::whisper_engine::commands::WHISPER_ENGINE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .cloned()?;
+
+    if !engine.is_model_loaded().await {
+        let models = engine.discover_models().await.ok()?;
+        let smallest = models
+            .iter()
+            .filter(|m| matches!(m.status, crate::whisper_engine::ModelStatus::Available))
+            .min_by_key(|m| m.size_mb);
+        let Some(model) = smallest else {
+            warn!("⚠️ No local Whisper model downloaded; remote transcription has no fallback");
+            return None;
+        };
+        if let Err(e) = engine.load_model(&model.name).await {
+            warn!("⚠️ Failed to load fallback Whisper model '{}': {}", model.name, e);
+            return None;
+        }
+        info!("✅ Loaded Whisper model '{}' as remote transcription fallback", model.name);
+    }
+
+    Some(Arc::new(WhisperProvider::new(engine)))
+}
+
 /// Get or initialize the appropriate transcription engine based on provider configuration
 pub async fn get_or_init_transcription_engine<R: Runtime>(
     app: &AppHandle<R>,
@@ -170,6 +252,7 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 provider: "localWhisper".to_string(),
                 model: "large-v3".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
         Err(e) => {
@@ -178,6 +261,7 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 provider: "localWhisper".to_string(),
                 model: "large-v3".to_string(),
                 api_key: None,
+                endpoint: None,
             }
         }
     };
@@ -212,6 +296,20 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 }
             }
         }
+        "openai" | "groq" | "openaiCompatible" => {
+            let endpoint = remote_endpoint(&config)?;
+            info!(
+                "🌐 Initializing remote transcription engine: {} ({})",
+                endpoint, config.model
+            );
+            let provider = RemoteTranscriptionProvider::new(
+                &endpoint,
+                config.model,
+                config.api_key,
+                Some(|| Box::pin(local_fallback_provider())),
+            )?;
+            Ok(TranscriptionEngine::Provider(Arc::new(provider)))
+        }
         "localWhisper" | _ => {
             info!("🎤 Initializing Whisper transcription engine");
             let whisper_engine = get_or_init_whisper(app).await?;
@@ -5,13 +5,17 @@
 pub mod provider;
 pub mod whisper_provider;
 pub mod parakeet_provider;
+pub mod remote_provider;
+pub mod vocabulary;
+pub mod vocabulary_commands;
 pub mod engine;
 pub mod worker;
 
 // Re-export commonly used types
-pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult, WordTimestamp};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
+pub use remote_provider::RemoteTranscriptionProvider;
 pub use engine::{
     TranscriptionEngine,
     validate_transcription_model_ready,
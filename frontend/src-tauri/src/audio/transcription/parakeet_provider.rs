@@ -33,16 +33,25 @@ impl TranscriptionProvider for ParakeetProvider {
             );
         }
 
-        match self.engine.transcribe_audio(audio).await {
-            Ok(text) => Ok(TranscriptResult {
-                text: text.trim().to_string(),
+        let duration = audio.len() as f32 / 16000.0;
+        match self.engine.transcribe_audio_with_timestamps(audio).await {
+            Ok(result) => Ok(TranscriptResult {
+                text: result.text.trim().to_string(),
                 confidence: None, // Parakeet doesn't provide confidence scores
                 is_partial: false, // Parakeet doesn't provide partial results
+                words: result.word_timestamps(duration),
+                language: None, // Parakeet doesn't identify the spoken language
+                language_probability: None,
+                prompted: false,
             }),
             Err(e) => Err(TranscriptionError::EngineFailed(e.to_string())),
         }
     }
 
+    fn supports_word_timestamps(&self) -> bool {
+        true
+    }
+
     async fn is_model_loaded(&self) -> bool {
         self.engine.is_model_loaded().await
     }
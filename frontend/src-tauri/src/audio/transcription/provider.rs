@@ -4,6 +4,7 @@
 // transcription engines (Whisper, Parakeet, future providers).
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // TRANSCRIPTION PROVIDER TRAIT & ERROR TYPES
@@ -37,12 +38,73 @@ impl std::fmt::Display for TranscriptionError {
 
 impl std::error::Error for TranscriptionError {}
 
+/// Timing of a single recognized word
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTimestamp {
+    pub word: String,
+    pub start: f64, // Seconds, relative to the transcribed audio (recording-relative once persisted)
+    pub end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
 /// Unified transcription result across all providers
 #[derive(Debug, Clone)]
 pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    pub words: Vec<WordTimestamp>, // Empty if provider doesn't support word timings
+    pub language: Option<String>, // Language code of the text, None if unknown
+    pub language_probability: Option<f32>, // Detection probability, None if the language was fixed
+    pub prompted: bool, // Whether recognition was biased towards the active vocabulary
+}
+
+/// Shift word timings from chunk-relative to recording-relative time
+pub fn offset_words(words: &mut [WordTimestamp], offset_seconds: f64) {
+    for word in words {
+        word.start += offset_seconds;
+        word.end += offset_seconds;
+    }
+}
+
+/// Group sub-word tokens into words.
+///
+/// Both Whisper and Parakeet emit tokens where a leading space marks the start of a new
+/// word; continuation tokens (including trailing punctuation) are appended to the current
+/// word and extend its end time. Word confidence is the minimum of its token confidences.
+pub fn words_from_tokens<I>(tokens: I) -> Vec<WordTimestamp>
+where
+    I: IntoIterator<Item = (String, f64, f64, Option<f32>)>,
+{
+    let mut words: Vec<WordTimestamp> = Vec::new();
+
+    for (text, start, end, confidence) in tokens {
+        let starts_word = text.starts_with(char::is_whitespace);
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match words.last_mut() {
+            Some(current) if !starts_word => {
+                current.word.push_str(trimmed);
+                current.end = current.end.max(end);
+                current.confidence = match (current.confidence, confidence) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            _ => words.push(WordTimestamp {
+                word: trimmed.to_string(),
+                start,
+                end: end.max(start),
+                confidence,
+            }),
+        }
+    }
+
+    words
 }
 
 /// Trait for transcription providers (Whisper, Parakeet, future providers)
@@ -55,13 +117,25 @@ pub trait TranscriptionProvider: Send + Sync {
     /// * `language` - Optional language hint (e.g., "en", "es", "fr")
     ///
     /// # Returns
-    /// * `TranscriptResult` with text, optional confidence, and partial flag
+    /// * `TranscriptResult` with text, optional confidence, partial flag, and word timings
+    ///   (relative to the start of `audio`) when `supports_word_timestamps` is true
     async fn transcribe(
         &self,
         audio: Vec<f32>,
         language: Option<String>,
     ) -> std::result::Result<TranscriptResult, TranscriptionError>;
 
+    /// Whether `transcribe` fills in `TranscriptResult::words`
+    fn supports_word_timestamps(&self) -> bool {
+        false
+    }
+
+    /// Whether `transcribe` biases recognition towards the active vocabulary.
+    /// Output of providers that can't is fuzzy-corrected against the glossary instead.
+    fn supports_prompt(&self) -> bool {
+        false
+    }
+
     /// Check if a model is currently loaded
     async fn is_model_loaded(&self) -> bool;
 
@@ -71,3 +145,44 @@ pub trait TranscriptionProvider: Send + Sync {
     /// Get the provider name (for logging/debugging)
     fn provider_name(&self) -> &'static str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, start: f64, end: f64, p: f32) -> (String, f64, f64, Option<f32>) {
+        (text.to_string(), start, end, Some(p))
+    }
+
+    #[test]
+    fn test_words_from_tokens_joins_subwords() {
+        let words = words_from_tokens(vec![
+            token(" Hel", 0.0, 0.2, 0.9),
+            token("lo", 0.2, 0.4, 0.7),
+            token(",", 0.4, 0.45, 0.95),
+            token(" world", 0.6, 1.0, 0.8),
+        ]);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].word, "Hello,");
+        assert_eq!(words[0].start, 0.0);
+        assert_eq!(words[0].end, 0.45);
+        assert_eq!(words[0].confidence, Some(0.7));
+        assert_eq!(words[1].word, "world");
+        assert_eq!(words[1].start, 0.6);
+    }
+
+    #[test]
+    fn test_words_from_tokens_first_token_without_space() {
+        let words = words_from_tokens(vec![token("Hi", 0.0, 0.3, 0.9), token(" ", 0.3, 0.3, 0.9)]);
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].word, "Hi");
+    }
+
+    #[test]
+    fn test_offset_words() {
+        let mut words = words_from_tokens(vec![token(" a", 1.0, 1.5, 0.9)]);
+        offset_words(&mut words, 10.0);
+        assert_eq!((words[0].start, words[0].end), (11.0, 11.5));
+    }
+}
@@ -0,0 +1,556 @@
+// audio/transcription/remote_provider.rs
+//
+// Remote transcription provider for OpenAI-compatible `/v1/audio/transcriptions`
+// endpoints (OpenAI, Groq, whisper.cpp server, faster-whisper-server...), with retries
+// and a fallback to a local engine when the server is unreachable. The "auto-translate"
+// language preference posts to the sibling `/audio/translations` endpoint instead.
+
+use super::provider::{TranscriptionError, TranscriptionProvider, TranscriptResult, WordTimestamp};
+use crate::audio::native_encoder::write_wav_to;
+use async_trait::async_trait;
+use log::warn;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+pub const OPENAI_TRANSCRIPTION_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+pub const GROQ_TRANSCRIPTION_URL: &str = "https://api.groq.com/openai/v1/audio/transcriptions";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Retries after the first attempt, for timeouts, connection errors, 429 and 5xx
+const MAX_RETRIES: u32 = 2;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// After a request fails for good, go straight to the local fallback for this long
+/// instead of stalling every segment on an unreachable server
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Resolve a configured server URL to its transcriptions endpoint.
+///
+/// Accepts the full endpoint, an API base ending in `/v1`, or a bare server URL.
+pub fn transcription_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.ends_with("/audio/transcriptions") {
+        endpoint.to_string()
+    } else if endpoint.ends_with("/v1") {
+        format!("{}/audio/transcriptions", endpoint)
+    } else {
+        format!("{}/v1/audio/transcriptions", endpoint)
+    }
+}
+
+/// Encode 16kHz mono samples as a 16-bit PCM WAV file
+fn encode_wav(samples: &[f32]) -> anyhow::Result<Vec<u8>> {
+    let mut wav = Vec::with_capacity(44 + samples.len() * 2);
+    write_wav_to(&mut wav, samples, 16000, 1)?;
+    Ok(wav)
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseTranscription {
+    text: String,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+/// Normalize a reported language ("english" from OpenAI, "en" from most local servers)
+/// to Whisper's language code
+fn language_code(name: &str) -> Option<String> {
+    whisper_rs::get_lang_id(&name.trim().to_lowercase())
+        .and_then(whisper_rs::get_lang_str)
+        .map(str::to_string)
+}
+
+/// Turn a `verbose_json` (or plain `json`) response into a transcript result.
+///
+/// Confidence is the mean segment probability, `exp(avg_logprob)`, when segments are
+/// reported. Servers report the detected language but not its probability.
+fn parse_response(body: &str) -> Result<TranscriptResult, TranscriptionError> {
+    let parsed: VerboseTranscription = serde_json::from_str(body).map_err(|e| {
+        TranscriptionError::EngineFailed(format!("Invalid transcription response: {}", e))
+    })?;
+
+    let logprobs: Vec<f64> = parsed.segments.iter().filter_map(|s| s.avg_logprob).collect();
+    let confidence = (!logprobs.is_empty())
+        .then(|| (logprobs.iter().sum::<f64>() / logprobs.len() as f64).exp() as f32);
+
+    let words = parsed
+        .words
+        .into_iter()
+        .filter(|w| !w.word.trim().is_empty())
+        .map(|w| WordTimestamp {
+            word: w.word.trim().to_string(),
+            start: w.start,
+            end: w.end.max(w.start),
+            confidence: None,
+        })
+        .collect();
+
+    Ok(TranscriptResult {
+        text: parsed.text.trim().to_string(),
+        confidence,
+        is_partial: false,
+        words,
+        language: parsed.language.as_deref().and_then(language_code),
+        language_probability: None,
+        prompted: true,
+    })
+}
+
+/// Builds the local engine used when the server can't be reached. Only called on the
+/// first failure, so a working server never loads a local model.
+pub type FallbackLoader =
+    fn() -> Pin<Box<dyn Future<Output = Option<Arc<dyn TranscriptionProvider>>> + Send>>;
+
+/// Why a request failed: `Retryable` for timeouts, connection errors, 429 and 5xx
+/// (the server is unavailable), `Fatal` for everything else
+enum AttemptError {
+    Retryable(String),
+    Fatal(String),
+}
+
+/// Transcription provider backed by an OpenAI-compatible HTTP endpoint
+pub struct RemoteTranscriptionProvider {
+    client: Client,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    load_fallback: Option<FallbackLoader>,
+    fallback: OnceCell<Option<Arc<dyn TranscriptionProvider>>>,
+    unavailable_until: Mutex<Option<Instant>>,
+}
+
+impl RemoteTranscriptionProvider {
+    pub fn new(
+        endpoint: &str,
+        model: String,
+        api_key: Option<String>,
+        load_fallback: Option<FallbackLoader>,
+    ) -> Result<Self, String> {
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            url: transcription_url(endpoint),
+            model,
+            api_key: api_key.filter(|k| !k.trim().is_empty()),
+            load_fallback,
+            fallback: OnceCell::new(),
+            unavailable_until: Mutex::new(None),
+        })
+    }
+
+    /// The local fallback engine, loaded on first use
+    async fn fallback(&self) -> Option<&Arc<dyn TranscriptionProvider>> {
+        let load = self.load_fallback?;
+        self.fallback.get_or_init(load).await.as_ref()
+    }
+
+    fn in_cooldown(&self) -> bool {
+        self.unavailable_until
+            .lock()
+            .ok()
+            .and_then(|until| *until)
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    fn set_cooldown(&self, until: Option<Instant>) {
+        if let Ok(mut guard) = self.unavailable_until.lock() {
+            *guard = until;
+        }
+    }
+
+    fn failure_message(&self, error: &str) -> String {
+        format!("Remote transcription at {} failed: {}", self.url, error)
+    }
+
+    async fn attempt(&self, wav: &[u8], language: Option<&str>) -> Result<TranscriptResult, AttemptError> {
+        let file = Part::bytes(wav.to_vec())
+            .file_name("audio.wav")
+            .mime_str("audio/wav")
+            .map_err(|e| AttemptError::Fatal(e.to_string()))?;
+        let mut form = Form::new()
+            .part("file", file)
+            .text("model", self.model.clone())
+            .text("response_format", "verbose_json");
+        if let Some(prompt) = super::vocabulary::active_vocabulary().initial_prompt() {
+            form = form.text("prompt", prompt);
+        }
+
+        // Translations take neither a language nor word timestamps
+        let url = match language {
+            Some("auto-translate") => self.url.replace("/audio/transcriptions", "/audio/translations"),
+            _ => {
+                form = form
+                    .text("timestamp_granularities[]", "word")
+                    .text("timestamp_granularities[]", "segment");
+                if let Some(language) = language.filter(|l| *l != "auto") {
+                    form = form.text("language", language.to_string());
+                }
+                self.url.clone()
+            }
+        };
+
+        let mut request = self.client.post(&url).multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() || e.is_connect() || e.is_request() {
+                AttemptError::Retryable(e.to_string())
+            } else {
+                AttemptError::Fatal(e.to_string())
+            }
+        })?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| AttemptError::Retryable(e.to_string()))?;
+        if !status.is_success() {
+            let message = format!("HTTP {}: {}", status, body.chars().take(300).collect::<String>());
+            return Err(if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                AttemptError::Retryable(message)
+            } else {
+                AttemptError::Fatal(message)
+            });
+        }
+
+        let mut result = parse_response(&body).map_err(|e| AttemptError::Fatal(e.to_string()))?;
+        // Translations report the source language, but the text is English
+        if language == Some("auto-translate") {
+            result.language = Some("en".to_string());
+        }
+        Ok(result)
+    }
+
+    /// Transcribe with retries. Errors keep their kind so the caller only falls back to the
+    /// local engine when the server is unavailable, not when it rejected the request.
+    async fn transcribe_remote(
+        &self,
+        audio: &[f32],
+        language: Option<&str>,
+    ) -> Result<TranscriptResult, AttemptError> {
+        let wav = encode_wav(audio).map_err(|e| AttemptError::Fatal(e.to_string()))?;
+        let mut attempt = 0;
+        loop {
+            match self.attempt(&wav, language).await {
+                Ok(result) => return Ok(result),
+                Err(AttemptError::Retryable(e)) if attempt < MAX_RETRIES => {
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                    warn!(
+                        "Remote transcription attempt {} failed ({}), retrying in {:?}",
+                        attempt + 1,
+                        e,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(AttemptError::Retryable(e)) => {
+                    return Err(AttemptError::Retryable(self.failure_message(&e)));
+                }
+                Err(AttemptError::Fatal(e)) => {
+                    return Err(AttemptError::Fatal(self.failure_message(&e)));
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for RemoteTranscriptionProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        if self.in_cooldown() {
+            if let Some(fallback) = self.fallback().await {
+                return fallback.transcribe(audio, language).await;
+            }
+        }
+
+        match self.transcribe_remote(&audio, language.as_deref()).await {
+            Ok(result) => {
+                self.set_cooldown(None);
+                Ok(result)
+            }
+            // A bad API key, a rejected request or an unreadable response would fail again;
+            // report it instead of quietly running every segment on the local engine
+            Err(AttemptError::Fatal(e)) => Err(TranscriptionError::EngineFailed(e)),
+            Err(AttemptError::Retryable(e)) => match self.fallback().await {
+                Some(fallback) => {
+                    warn!(
+                        "{}; using local {} for the next {:?}",
+                        e,
+                        fallback.provider_name(),
+                        FAILURE_COOLDOWN
+                    );
+                    self.set_cooldown(Some(Instant::now() + FAILURE_COOLDOWN));
+                    fallback.transcribe(audio, language).await
+                }
+                None => Err(TranscriptionError::EngineFailed(e)),
+            },
+        }
+    }
+
+    fn supports_word_timestamps(&self) -> bool {
+        true
+    }
+
+    fn supports_prompt(&self) -> bool {
+        true
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        // Nothing to load locally; reachability is checked per request
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Remote Whisper"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const REMOTE_BODY: &str = r#"{"text": "from the server"}"#;
+
+    /// Minimal HTTP server answering the nth request with `responses[n]` (the last one
+    /// repeats). Returns its URL and the number of requests it has received.
+    async fn spawn_mock_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = responses[index.min(responses.len() - 1)];
+                tokio::spawn(async move {
+                    // Read the whole multipart upload before answering
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    loop {
+                        let n = socket.read(&mut chunk).await.unwrap_or(0);
+                        if n == 0 {
+                            return;
+                        }
+                        buffer.extend_from_slice(&chunk[..n]);
+                        let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+                            continue;
+                        };
+                        let head = String::from_utf8_lossy(&buffer[..pos]).to_lowercase();
+                        let complete = match head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length:"))
+                            .and_then(|v| v.trim().parse::<usize>().ok())
+                        {
+                            Some(length) => buffer.len() >= pos + 4 + length,
+                            None => buffer.ends_with(b"0\r\n\r\n"),
+                        };
+                        if complete {
+                            break;
+                        }
+                    }
+
+                    let reply = format!(
+                        "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+
+        (format!("http://{}", address), hits)
+    }
+
+    /// Local engine standing in for Whisper/Parakeet
+    struct LocalStub;
+
+    #[async_trait]
+    impl TranscriptionProvider for LocalStub {
+        async fn transcribe(
+            &self,
+            _audio: Vec<f32>,
+            _language: Option<String>,
+        ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+            Ok(TranscriptResult {
+                text: "from the local engine".to_string(),
+                confidence: None,
+                is_partial: false,
+                words: Vec::new(),
+                language: None,
+                language_probability: None,
+                prompted: false,
+            })
+        }
+
+        async fn is_model_loaded(&self) -> bool {
+            true
+        }
+
+        async fn get_current_model(&self) -> Option<String> {
+            None
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "Local Stub"
+        }
+    }
+
+    // One loader (and load counter) per test, as tests run concurrently
+    static RETRY_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static FAILURE_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static COOLDOWN_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static REJECTED_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+    fn load_stub(loads: &'static AtomicUsize) -> Pin<Box<dyn Future<Output = Option<Arc<dyn TranscriptionProvider>>> + Send>> {
+        loads.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Some(Arc::new(LocalStub) as Arc<dyn TranscriptionProvider>) })
+    }
+
+    fn provider(endpoint: &str, load_fallback: FallbackLoader) -> RemoteTranscriptionProvider {
+        RemoteTranscriptionProvider::new(endpoint, "whisper-1".to_string(), None, Some(load_fallback)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_server_error_is_retried() {
+        let (endpoint, hits) = spawn_mock_server(vec![(500, "overloaded"), (200, REMOTE_BODY)]).await;
+        let remote = provider(&endpoint, || load_stub(&RETRY_LOADS));
+
+        let result = remote.transcribe(vec![0.0; 1600], None).await.unwrap();
+        assert_eq!(result.text, "from the server");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert_eq!(RETRY_LOADS.load(Ordering::SeqCst), 0);
+        assert!(!remote.in_cooldown());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_fall_back_to_local_engine() {
+        let (endpoint, hits) = spawn_mock_server(vec![(503, "down")]).await;
+        let remote = provider(&endpoint, || load_stub(&FAILURE_LOADS));
+
+        let result = remote.transcribe(vec![0.0; 1600], None).await.unwrap();
+        assert_eq!(result.text, "from the local engine");
+        assert_eq!(hits.load(Ordering::SeqCst), 1 + MAX_RETRIES as usize);
+        assert_eq!(FAILURE_LOADS.load(Ordering::SeqCst), 1);
+        assert!(remote.in_cooldown());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_request_is_reported_without_fallback() {
+        let (endpoint, hits) = spawn_mock_server(vec![(401, r#"{"error": "invalid api key"}"#)]).await;
+        let remote = provider(&endpoint, || load_stub(&REJECTED_LOADS));
+
+        let error = remote.transcribe(vec![0.0; 1600], None).await.unwrap_err();
+        assert!(error.to_string().contains("401"));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        assert_eq!(REJECTED_LOADS.load(Ordering::SeqCst), 0);
+        assert!(!remote.in_cooldown());
+    }
+
+    #[tokio::test]
+    async fn test_cooldown_skips_remote_call() {
+        let (endpoint, hits) = spawn_mock_server(vec![(200, REMOTE_BODY)]).await;
+        let remote = provider(&endpoint, || load_stub(&COOLDOWN_LOADS));
+        remote.set_cooldown(Some(Instant::now() + FAILURE_COOLDOWN));
+
+        for _ in 0..2 {
+            let result = remote.transcribe(vec![0.0; 1600], None).await.unwrap();
+            assert_eq!(result.text, "from the local engine");
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+        // The fallback is loaded once and reused
+        assert_eq!(COOLDOWN_LOADS.load(Ordering::SeqCst), 1);
+
+        remote.set_cooldown(None);
+        let result = remote.transcribe(vec![0.0; 1600], None).await.unwrap();
+        assert_eq!(result.text, "from the server");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_transcription_url_accepts_base_or_full_endpoint() {
+        let full = "http://gpu-box:8080/v1/audio/transcriptions";
+        assert_eq!(transcription_url("http://gpu-box:8080"), full);
+        assert_eq!(transcription_url("http://gpu-box:8080/"), full);
+        assert_eq!(transcription_url("http://gpu-box:8080/v1"), full);
+        assert_eq!(transcription_url(full), full);
+    }
+
+    #[test]
+    fn test_encode_wav_header() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0]).unwrap();
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes([wav[40], wav[41], wav[42], wav[43]]), 6);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+    }
+
+    #[test]
+    fn test_parse_verbose_response() {
+        let body = r#"{
+            "text": " Hello world. ",
+            "language": "english",
+            "segments": [{"avg_logprob": -0.2}, {"avg_logprob": -0.4}],
+            "words": [{"word": " Hello", "start": 0.0, "end": 0.4}, {"word": "world.", "start": 0.5, "end": 0.9}]
+        }"#;
+        let result = parse_response(body).unwrap();
+        assert_eq!(result.text, "Hello world.");
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[0].word, "Hello");
+        assert!((result.confidence.unwrap() - (-0.3f64).exp() as f32).abs() < 1e-6);
+        assert_eq!(result.language.as_deref(), Some("en"));
+
+        let plain = parse_response(r#"{"text": "Hi"}"#).unwrap();
+        assert_eq!(plain.text, "Hi");
+        assert!(plain.confidence.is_none() && plain.words.is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+// audio/transcription/vocabulary.rs
+//
+// Custom vocabulary biasing. Whisper is given the glossary as an initial prompt, which
+// steers it towards the right spellings; every engine's output then gets a correction
+// pass that rewrites known misrecognitions (aliases) and, for engines that can't be
+// prompted such as Parakeet, fuzzy-matches near-miss spellings onto glossary terms.
+
+use super::provider::WordTimestamp;
+use crate::database::models::VocabularyTermModel;
+use log::warn;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::sync::{Arc, RwLock};
+
+/// Whisper only attends to the last ~224 prompt tokens; stay well inside that
+const MAX_PROMPT_CHARS: usize = 600;
+
+/// Minimum normalized Levenshtein similarity for a fuzzy correction
+const FUZZY_THRESHOLD: f64 = 0.8;
+
+/// Terms shorter than this (in letters and digits) are only corrected on an exact match,
+/// since short acronyms are within edit distance of too many ordinary words
+const MIN_FUZZY_KEY_LEN: usize = 5;
+
+/// Glossary applied to live and imported transcription (global terms)
+static ACTIVE_VOCABULARY: Lazy<RwLock<Arc<Vocabulary>>> =
+    Lazy::new(|| RwLock::new(Arc::new(Vocabulary::default())));
+
+/// The glossary live transcription currently uses
+pub fn active_vocabulary() -> Arc<Vocabulary> {
+    ACTIVE_VOCABULARY
+        .read()
+        .map(|v| v.clone())
+        .unwrap_or_default()
+}
+
+pub fn set_active_vocabulary(vocabulary: Vocabulary) {
+    if let Ok(mut active) = ACTIVE_VOCABULARY.write() {
+        *active = Arc::new(vocabulary);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    term: String,
+    key: String,
+    word_count: usize,
+    alias_patterns: Vec<Regex>,
+    /// (word count, match key) of each alias, for matching runs of word timings
+    alias_keys: Vec<(usize, String)>,
+}
+
+/// A compiled glossary
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    entries: Vec<Entry>,
+}
+
+/// Lowercase letters and digits only, so spacing and punctuation don't affect matching
+fn match_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Case-insensitive whole-word pattern for an alias
+fn alias_pattern(alias: &str) -> Option<Regex> {
+    let escaped = regex::escape(alias.trim());
+    if escaped.is_empty() {
+        return None;
+    }
+    let starts_word = alias.trim().starts_with(|c: char| c.is_alphanumeric());
+    let ends_word = alias.trim().ends_with(|c: char| c.is_alphanumeric());
+    let pattern = format!(
+        "(?i){}{}{}",
+        if starts_word { r"\b" } else { "" },
+        escaped,
+        if ends_word { r"\b" } else { "" }
+    );
+    match Regex::new(&pattern) {
+        Ok(regex) => Some(regex),
+        Err(e) => {
+            warn!("Skipping vocabulary alias '{}': {}", alias, e);
+            None
+        }
+    }
+}
+
+/// Split a token into (leading punctuation, core, trailing punctuation)
+fn split_punctuation(token: &str) -> (&str, &str, &str) {
+    let core_start = token.find(|c: char| c.is_alphanumeric()).unwrap_or(token.len());
+    let core_end = token
+        .rfind(|c: char| c.is_alphanumeric())
+        .map(|i| i + token[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(core_start)
+        .max(core_start);
+    (&token[..core_start], &token[core_start..core_end], &token[core_end..])
+}
+
+impl Vocabulary {
+    /// Build a glossary from `(term, aliases)` pairs
+    pub fn new<I>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Vec<String>)>,
+    {
+        let entries = terms
+            .into_iter()
+            .filter_map(|(term, aliases)| {
+                let term = term.trim().to_string();
+                let key = match_key(&term);
+                if key.is_empty() {
+                    return None;
+                }
+                Some(Entry {
+                    word_count: term.split_whitespace().count().max(1),
+                    alias_patterns: aliases.iter().filter_map(|a| alias_pattern(a)).collect(),
+                    alias_keys: aliases
+                        .iter()
+                        .map(|a| (a.split_whitespace().count(), match_key(a)))
+                        .filter(|(count, key)| *count > 0 && !key.is_empty())
+                        .collect(),
+                    term,
+                    key,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    pub fn from_models(models: &[VocabularyTermModel]) -> Self {
+        Self::new(models.iter().map(|m| {
+            let aliases: Vec<String> = serde_json::from_str(&m.aliases).unwrap_or_default();
+            (m.term.clone(), aliases)
+        }))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whisper initial prompt listing the glossary terms, or None if there are none
+    pub fn initial_prompt(&self) -> Option<String> {
+        let mut prompt = String::from("Glossary:");
+        let mut added = 0;
+        for entry in &self.entries {
+            if prompt.len() + entry.term.len() + 2 > MAX_PROMPT_CHARS {
+                break;
+            }
+            prompt.push_str(if added == 0 { " " } else { ", " });
+            prompt.push_str(&entry.term);
+            added += 1;
+        }
+        (added > 0).then(|| prompt + ".")
+    }
+
+    /// Rewrite known misrecognitions (aliases) to their glossary spelling
+    pub fn replace_aliases(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for entry in &self.entries {
+            for pattern in &entry.alias_patterns {
+                if pattern.is_match(&result) {
+                    result = pattern.replace_all(&result, regex::NoExpand(&entry.term)).into_owned();
+                }
+            }
+        }
+        result
+    }
+
+    /// Longest run of tokens starting at `i` that spells one of the aliases
+    fn alias_match(&self, tokens: &[&str], i: usize) -> Option<(usize, &Entry)> {
+        let mut best: Option<(usize, &Entry)> = None;
+        for entry in &self.entries {
+            for (size, key) in &entry.alias_keys {
+                if i + size > tokens.len() || best.is_some_and(|(s, _)| s >= *size) {
+                    continue;
+                }
+                if match_key(&tokens[i..i + size].concat()) == *key {
+                    best = Some((*size, entry));
+                }
+            }
+        }
+        best
+    }
+
+    /// Run of tokens starting at `i` spelled almost like a glossary term. A run must start
+    /// with the same letter as the term and be at least `FUZZY_THRESHOLD` similar; the
+    /// most similar term wins.
+    fn fuzzy_match(&self, tokens: &[&str], i: usize) -> Option<(usize, &Entry)> {
+        let mut best: Option<(f64, usize, &Entry)> = None;
+        for entry in &self.entries {
+            // Recognizers often split one unfamiliar word in two, so also try one extra token
+            for size in [entry.word_count, entry.word_count + 1] {
+                if i + size > tokens.len() {
+                    continue;
+                }
+                let key = match_key(&tokens[i..i + size].concat());
+                if key.chars().next() != entry.key.chars().next() {
+                    continue;
+                }
+                let score = if key == entry.key {
+                    1.0
+                } else if entry.key.chars().count() < MIN_FUZZY_KEY_LEN {
+                    continue;
+                } else {
+                    strsim::normalized_levenshtein(&key, &entry.key)
+                };
+                if score >= FUZZY_THRESHOLD && !best.is_some_and(|(s, _, _)| s >= score) {
+                    best = Some((score, size, entry));
+                }
+            }
+        }
+        best.map(|(_, size, entry)| (size, entry))
+    }
+
+    /// Replace word runs that are spelled almost like a glossary term with the term
+    pub fn fuzzy_correct(&self, text: &str) -> String {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let mut output: Vec<String> = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+
+        while i < tokens.len() {
+            match self.fuzzy_match(&tokens, i) {
+                Some((size, entry)) => {
+                    let (leading, _, _) = split_punctuation(tokens[i]);
+                    let (_, _, trailing) = split_punctuation(tokens[i + size - 1]);
+                    output.push(format!("{}{}{}", leading, entry.term, trailing));
+                    i += size;
+                }
+                None => {
+                    output.push(tokens[i].to_string());
+                    i += 1;
+                }
+            }
+        }
+
+        output.join(" ")
+    }
+
+    /// Full correction pass: aliases first, then fuzzy matching
+    pub fn correct(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+        self.fuzzy_correct(&self.replace_aliases(text))
+    }
+
+    /// Apply the same corrections to word timings so they keep matching the text.
+    /// A multi-word alias or split-up term becomes one word spanning the replaced run.
+    pub fn correct_words(&self, words: &mut Vec<WordTimestamp>, fuzzy: bool) {
+        if self.is_empty() || words.is_empty() {
+            return;
+        }
+        *words = merge_matches(words, |tokens, i| self.alias_match(tokens, i));
+        if fuzzy {
+            *words = merge_matches(words, |tokens, i| self.fuzzy_match(tokens, i));
+        }
+    }
+}
+
+/// Replace each matched run of words with a single word carrying the glossary spelling,
+/// the run's outer punctuation and its combined timing
+fn merge_matches<'v, F>(words: &[WordTimestamp], find: F) -> Vec<WordTimestamp>
+where
+    F: Fn(&[&str], usize) -> Option<(usize, &'v Entry)>,
+{
+    let tokens: Vec<&str> = words.iter().map(|w| w.word.trim()).collect();
+    let mut merged = Vec::with_capacity(words.len());
+    let mut i = 0;
+
+    while i < words.len() {
+        match find(&tokens, i) {
+            Some((size, entry)) => {
+                let run = &words[i..i + size];
+                // Whisper words carry their leading space; keep it so they still concatenate
+                let space = &run[0].word[..run[0].word.len() - run[0].word.trim_start().len()];
+                let (leading, _, _) = split_punctuation(tokens[i]);
+                let (_, _, trailing) = split_punctuation(tokens[i + size - 1]);
+                merged.push(WordTimestamp {
+                    word: format!("{}{}{}{}", space, leading, entry.term, trailing),
+                    start: run[0].start,
+                    end: run[size - 1].end,
+                    confidence: run.iter().filter_map(|w| w.confidence).reduce(f32::min),
+                });
+                i += size;
+            }
+            None => {
+                merged.push(words[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocabulary() -> Vocabulary {
+        Vocabulary::new(vec![
+            ("Kubernetes".to_string(), vec!["cooper netties".to_string()]),
+            ("Meetily".to_string(), Vec::new()),
+            ("gRPC".to_string(), vec!["g r p c".to_string()]),
+        ])
+    }
+
+    #[test]
+    fn test_builds_prompt_from_terms() {
+        assert_eq!(
+            vocabulary().initial_prompt().as_deref(),
+            Some("Glossary: Kubernetes, Meetily, gRPC.")
+        );
+        assert_eq!(Vocabulary::default().initial_prompt(), None);
+    }
+
+    #[test]
+    fn test_replaces_aliases_case_insensitively() {
+        let v = vocabulary();
+        assert_eq!(v.replace_aliases("We moved to Cooper Netties."), "We moved to Kubernetes.");
+        assert_eq!(v.replace_aliases("talk over G R P C now"), "talk over gRPC now");
+    }
+
+    #[test]
+    fn test_fuzzy_corrects_near_misses_and_keeps_punctuation() {
+        let v = vocabulary();
+        assert_eq!(v.correct("Open meetilly, then kubernets."), "Open Meetily, then Kubernetes.");
+        assert_eq!(v.correct("the meet illy app"), "the Meetily app");
+        // Short terms need an exact match and ordinary words are left alone
+        assert_eq!(v.correct("grip the meeting"), "grip the meeting");
+        assert_eq!(v.correct("use grpc"), "use gRPC");
+    }
+
+    fn timed(words: &[&str]) -> Vec<WordTimestamp> {
+        words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| WordTimestamp {
+                word: w.to_string(),
+                start: i as f64,
+                end: i as f64 + 1.0,
+                confidence: Some(0.9 - i as f32 / 10.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_correct_words_merges_multi_word_runs() {
+        let v = vocabulary();
+        let mut words = timed(&[" We", " use", " Cooper", " netties,", " and", " g", " r", " p", " c."]);
+        v.correct_words(&mut words, false);
+        let text: Vec<&str> = words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(text, vec![" We", " use", " Kubernetes,", " and", " gRPC."]);
+        assert_eq!((words[2].start, words[2].end), (2.0, 4.0));
+        assert_eq!((words[4].start, words[4].end), (5.0, 9.0));
+        assert_eq!(words[4].confidence, Some(0.9 - 8.0 / 10.0));
+    }
+
+    #[test]
+    fn test_correct_words_fuzzy_matches_split_terms() {
+        let v = vocabulary();
+        let mut words = timed(&["open", "meet", "illy", "now"]);
+        v.correct_words(&mut words, true);
+        let text: Vec<&str> = words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(text, vec!["open", "Meetily", "now"]);
+        assert_eq!((words[1].start, words[1].end), (1.0, 3.0));
+    }
+}
@@ -0,0 +1,144 @@
+// audio/transcription/vocabulary_commands.rs
+//
+// Tauri commands for managing the custom vocabulary (glossary).
+
+use super::vocabulary::{set_active_vocabulary, Vocabulary};
+use crate::database::models::VocabularyTermModel;
+use crate::database::repositories::vocabulary::VocabularyRepository;
+use crate::state::AppState;
+use log::{info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Glossary entry for UI display
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VocabularyTermInfo {
+    pub id: i64,
+    pub term: String,
+    pub aliases: Vec<String>,
+    pub meeting_id: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<VocabularyTermModel> for VocabularyTermInfo {
+    fn from(t: VocabularyTermModel) -> Self {
+        Self {
+            id: t.id,
+            aliases: serde_json::from_str(&t.aliases).unwrap_or_default(),
+            term: t.term,
+            meeting_id: t.meeting_id,
+            created_at: t.created_at.0.to_rfc3339(),
+            updated_at: t.updated_at.0.to_rfc3339(),
+        }
+    }
+}
+
+/// Load the global glossary into live transcription
+pub async fn refresh_active_vocabulary(pool: &SqlitePool) {
+    match VocabularyRepository::load_effective_terms(pool, None).await {
+        Ok(terms) => {
+            log_info!("Loaded {} custom vocabulary terms", terms.len());
+            set_active_vocabulary(Vocabulary::from_models(&terms));
+        }
+        Err(e) => log_warn!("Failed to load custom vocabulary: {}", e),
+    }
+}
+
+/// Glossary for transcribing a saved meeting: global terms plus the meeting's own
+pub async fn load_meeting_vocabulary(pool: &SqlitePool, meeting_id: &str) -> Vocabulary {
+    match VocabularyRepository::load_effective_terms(pool, Some(meeting_id)).await {
+        Ok(terms) => Vocabulary::from_models(&terms),
+        Err(e) => {
+            log_warn!("Failed to load custom vocabulary for {}: {}", meeting_id, e);
+            Vocabulary::default()
+        }
+    }
+}
+
+async fn term_info(pool: &SqlitePool, id: i64) -> Result<VocabularyTermInfo, String> {
+    VocabularyRepository::get_term(pool, id)
+        .await
+        .map_err(|e| format!("Failed to load vocabulary term: {}", e))?
+        .map(VocabularyTermInfo::from)
+        .ok_or_else(|| format!("Vocabulary term not found: {}", id))
+}
+
+/// Lists the global glossary, or a meeting's own terms when `meeting_id` is given
+#[tauri::command]
+pub async fn api_list_vocabulary(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<Vec<VocabularyTermInfo>, String> {
+    VocabularyRepository::list_terms(state.db_manager.pool(), meeting_id.as_deref())
+        .await
+        .map(|terms| terms.into_iter().map(VocabularyTermInfo::from).collect())
+        .map_err(|e| format!("Failed to list vocabulary: {}", e))
+}
+
+/// Adds a term, or updates the spelling and aliases of an existing one in the same scope
+///
+/// Meeting-scoped terms apply when that meeting is re-transcribed; global terms also
+/// apply to live recordings and imports.
+#[tauri::command]
+pub async fn api_save_vocabulary_term(
+    state: tauri::State<'_, AppState>,
+    term: String,
+    aliases: Vec<String>,
+    meeting_id: Option<String>,
+) -> Result<VocabularyTermInfo, String> {
+    log_info!("api_save_vocabulary_term called for '{}'", term);
+    let pool = state.db_manager.pool();
+    let id = VocabularyRepository::save_term(pool, &term, &aliases, meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to save vocabulary term: {}", e))?;
+    refresh_active_vocabulary(pool).await;
+    term_info(pool, id).await
+}
+
+#[tauri::command]
+pub async fn api_delete_vocabulary_term(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+) -> Result<bool, String> {
+    let pool = state.db_manager.pool();
+    let deleted = VocabularyRepository::delete_term(pool, id)
+        .await
+        .map_err(|e| format!("Failed to delete vocabulary term: {}", e))?;
+    refresh_active_vocabulary(pool).await;
+    Ok(deleted)
+}
+
+/// Records `original` as a misrecognition of `corrected` and reloads the live glossary
+pub async fn learn_correction(
+    pool: &SqlitePool,
+    original: &str,
+    corrected: &str,
+    meeting_id: Option<&str>,
+) -> Result<i64, String> {
+    let (original, corrected) = (original.trim(), corrected.trim());
+    if original.is_empty() || corrected.is_empty() || original.eq_ignore_ascii_case(corrected) {
+        return Err("A correction needs different original and corrected text".to_string());
+    }
+    log_info!("Learning vocabulary correction '{}' -> '{}'", original, corrected);
+
+    let id = VocabularyRepository::add_alias(pool, corrected, original, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to save vocabulary correction: {}", e))?;
+    refresh_active_vocabulary(pool).await;
+    Ok(id)
+}
+
+/// Learns from a correction made in the transcript: `original` is recorded as a
+/// misrecognition of `corrected`, so future transcripts spell it correctly
+#[tauri::command]
+pub async fn api_learn_vocabulary_correction(
+    state: tauri::State<'_, AppState>,
+    original: String,
+    corrected: String,
+    meeting_id: Option<String>,
+) -> Result<VocabularyTermInfo, String> {
+    let pool = state.db_manager.pool();
+    let id = learn_correction(pool, &original, &corrected, meeting_id.as_deref()).await?;
+    term_info(pool, id).await
+}
@@ -24,20 +24,33 @@ impl TranscriptionProvider for WhisperProvider {
         audio: Vec<f32>,
         language: Option<String>,
     ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let prompt = super::vocabulary::active_vocabulary().initial_prompt();
         match self
             .engine
-            .transcribe_audio_with_confidence(audio, language)
+            .transcribe_audio_with_confidence(audio, language, prompt.as_deref())
             .await
         {
-            Ok((text, confidence, is_partial)) => Ok(TranscriptResult {
+            Ok((text, confidence, is_partial, words, language)) => Ok(TranscriptResult {
                 text: text.trim().to_string(),
                 confidence: Some(confidence),
                 is_partial,
+                words,
+                language_probability: language.as_ref().and_then(|(_, p)| *p),
+                language: language.map(|(code, _)| code),
+                prompted: true,
             }),
             Err(e) => Err(TranscriptionError::EngineFailed(e.to_string())),
         }
     }
 
+    fn supports_word_timestamps(&self) -> bool {
+        true
+    }
+
+    fn supports_prompt(&self) -> bool {
+        true
+    }
+
     async fn is_model_loaded(&self) -> bool {
         self.engine.is_model_loaded().await
     }
@@ -3,13 +3,13 @@
 // Parallel transcription worker pool and chunk processing logic.
 
 use super::engine::TranscriptionEngine;
-use super::provider::TranscriptionError;
+use super::provider::{offset_words, TranscriptResult, TranscriptionError, WordTimestamp};
 use crate::audio::AudioChunk;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter, Runtime};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 
 // Sequence counter for transcript updates
 static SEQUENCE_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -36,15 +36,30 @@ pub struct TranscriptUpdate {
     pub audio_start_time: f64, // Seconds from recording start (e.g., 125.3)
     pub audio_end_time: f64,   // Seconds from recording start (e.g., 128.6)
     pub duration: f64,          // Segment duration in seconds (e.g., 3.3)
+    pub speaker_id: Option<usize>, // Diarized speaker (None if diarization model unavailable)
+    pub speaker_profile_id: Option<i64>, // Registered speaker matched from the voice registry
+    pub speaker_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTimestamp>, // Recording-relative word timings (empty if unsupported)
+    #[serde(default)]
+    pub language: Option<String>, // Language code of the text, None if unknown
+    #[serde(default)]
+    pub language_probability: Option<f32>, // None when the language was fixed, not detected
+    #[serde(default)]
+    pub audio_source: Option<String>, // "mic" or "system" in dual-channel mode, None for mixed audio
 }
 
 // NOTE: get_transcript_history and get_recording_meeting_name functions
 // have been moved to recording_commands.rs where they have access to RECORDING_MANAGER
 
 /// Optimized parallel transcription task ensuring ZERO chunk loss
+///
+/// With `dual_channel`, chunks come from the mic and system audio separately and each
+/// transcript is labelled with the source its chunk came from.
 pub fn start_transcription_task<R: Runtime>(
     app: AppHandle<R>,
     transcription_receiver: tokio::sync::mpsc::UnboundedReceiver<AudioChunk>,
+    dual_channel: bool,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         info!("🚀 Starting optimized parallel transcription task - guaranteeing zero chunk loss");
@@ -63,6 +78,23 @@ pub fn start_transcription_task<R: Runtime>(
             }
         };
 
+        // Diarization runs inline when the embedding model has been downloaded
+        let diarization_engine = crate::diarization::commands::get_or_init_diarization_engine(&app).await;
+        match &diarization_engine {
+            Some(engine) => {
+                engine.reset_session().await;
+                if let Some(state) = app.try_state::<crate::state::AppState>() {
+                    crate::diarization::speaker_commands::refresh_engine_registry(state.db_manager.pool()).await;
+                }
+                info!("🗣️ Speaker diarization enabled for this recording");
+            }
+            None => info!("🗣️ Diarization model not available - transcripts will not carry speaker labels"),
+        }
+
+        if let Some(state) = app.try_state::<crate::state::AppState>() {
+            super::vocabulary_commands::refresh_active_vocabulary(state.db_manager.pool()).await;
+        }
+
         // Create parallel workers for faster processing while preserving ALL chunks
         const NUM_WORKERS: usize = 1; // Serial processing ensures transcripts emit in chronological order
         let (work_sender, work_receiver) = tokio::sync::mpsc::unbounded_channel::<AudioChunk>();
@@ -83,6 +115,7 @@ pub fn start_transcription_task<R: Runtime>(
                 TranscriptionEngine::Parakeet(e) => TranscriptionEngine::Parakeet(e.clone()),
                 TranscriptionEngine::Provider(p) => TranscriptionEngine::Provider(p.clone()),
             };
+            let diarization_clone = diarization_engine.clone();
             let app_clone = app.clone();
             let work_receiver_clone = work_receiver.clone();
             let chunks_completed_clone = chunks_completed.clone();
@@ -142,6 +175,13 @@ pub fn start_transcription_task<R: Runtime>(
 
                             let chunk_timestamp = chunk.timestamp;
                             let chunk_duration = chunk.data.len() as f64 / chunk.sample_rate as f64;
+                            let audio_source = dual_channel
+                                .then(|| chunk.device_type.audio_source().to_string());
+
+                            // Keep a copy of the audio for speaker embedding extraction
+                            let diarization_audio = diarization_clone
+                                .as_ref()
+                                .map(|_| (chunk.data.clone(), chunk.sample_rate));
 
                             // Transcribe with provider-agnostic approach
                             match transcribe_chunk_with_provider(
@@ -151,7 +191,15 @@ pub fn start_transcription_task<R: Runtime>(
                             )
                             .await
                             {
-                                Ok((transcript, confidence_opt, is_partial)) => {
+                                Ok(TranscriptResult {
+                                    text: transcript,
+                                    confidence: confidence_opt,
+                                    is_partial,
+                                    mut words,
+                                    language,
+                                    language_probability,
+                                    ..
+                                }) => {
                                     // Provider-aware confidence threshold
                                     let confidence_threshold = match &engine_clone {
                                         TranscriptionEngine::Whisper(_) | TranscriptionEngine::Provider(_) => 0.3,
@@ -195,6 +243,22 @@ pub fn start_transcription_task<R: Runtime>(
                                         let sequence_id = SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst);
                                         let audio_start_time = chunk_timestamp; // Already in seconds from recording start
                                         let audio_end_time = chunk_timestamp + chunk_duration;
+                                        offset_words(&mut words, audio_start_time);
+
+                                        let speaker_id = match (&diarization_clone, diarization_audio) {
+                                            (Some(engine), Some((data, sample_rate))) => {
+                                                diarize_chunk(engine, data, sample_rate).await
+                                            }
+                                            _ => None,
+                                        };
+                                        let speaker_identity = match (&diarization_clone, speaker_id) {
+                                            (Some(engine), Some(id)) => engine.identify_speaker(id).await,
+                                            _ => None,
+                                        };
+                                        let (speaker_profile_id, speaker_name) = match speaker_identity {
+                                            Some((profile_id, name)) => (Some(profile_id), Some(name)),
+                                            None => (None, None),
+                                        };
 
                                         // Save structured transcript segment to recording manager (only final results)
                                         // Save ALL segments (partial and final) to ensure complete JSON
@@ -217,6 +281,13 @@ pub fn start_transcription_task<R: Runtime>(
                                             audio_start_time,
                                             audio_end_time,
                                             duration: chunk_duration,
+                                            speaker_id,
+                                            speaker_profile_id,
+                                            speaker_name,
+                                            words,
+                                            language,
+                                            language_probability,
+                                            audio_source,
                                         };
 
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
@@ -404,12 +475,12 @@ pub fn start_transcription_task<R: Runtime>(
 }
 
 /// Transcribe audio chunk using the appropriate provider (Whisper, Parakeet, or trait-based)
-/// Returns: (text, confidence Option, is_partial)
-async fn transcribe_chunk_with_provider<R: Runtime>(
+/// Word timings in the result are chunk-relative; empty text means nothing was recognized.
+pub(crate) async fn transcribe_chunk_with_provider<R: Runtime>(
     engine: &TranscriptionEngine,
     chunk: AudioChunk,
     app: &AppHandle<R>,
-) -> std::result::Result<(String, Option<f32>, bool), TranscriptionError> {
+) -> std::result::Result<TranscriptResult, TranscriptionError> {
     // Convert to 16kHz mono for transcription
     let transcription_data = if chunk.sample_rate != 16000 {
         crate::audio::audio_processing::resample_audio(&chunk.data, chunk.sample_rate, 16000)
@@ -442,28 +513,40 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
         energy
     );
 
+    // Custom vocabulary: prompted engines get the glossary up front and only known
+    // misrecognitions are rewritten; other engines are fuzzy-corrected afterwards
+    let vocabulary = super::vocabulary::active_vocabulary();
+
     // Transcribe using the appropriate engine (with improved error handling)
     match engine {
         TranscriptionEngine::Whisper(whisper_engine) => {
             // Get language preference from global state
             let language = crate::get_language_preference_internal();
+            let prompt = vocabulary.initial_prompt();
 
             match whisper_engine
-                .transcribe_audio_with_confidence(speech_samples, language)
+                .transcribe_audio_with_confidence(speech_samples, language, prompt.as_deref())
                 .await
             {
-                Ok((text, confidence, is_partial)) => {
-                    let cleaned_text = text.trim().to_string();
-                    if cleaned_text.is_empty() {
-                        return Ok((String::new(), Some(confidence), is_partial));
+                Ok((text, confidence, is_partial, mut words, language)) => {
+                    let cleaned_text = vocabulary.replace_aliases(text.trim());
+                    vocabulary.correct_words(&mut words, false);
+                    if !cleaned_text.is_empty() {
+                        info!(
+                            "Whisper transcription complete for chunk {}: '{}' (confidence: {:.2}, partial: {}, language: {:?})",
+                            chunk.chunk_id, cleaned_text, confidence, is_partial, language
+                        );
                     }
 
-                    info!(
-                        "Whisper transcription complete for chunk {}: '{}' (confidence: {:.2}, partial: {})",
-                        chunk.chunk_id, cleaned_text, confidence, is_partial
-                    );
-
-                    Ok((cleaned_text, Some(confidence), is_partial))
+                    Ok(TranscriptResult {
+                        text: cleaned_text,
+                        confidence: Some(confidence),
+                        is_partial,
+                        words,
+                        language_probability: language.as_ref().and_then(|(_, p)| *p),
+                        language: language.map(|(code, _)| code),
+                        prompted: true,
+                    })
                 }
                 Err(e) => {
                     error!(
@@ -486,20 +569,29 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             }
         }
         TranscriptionEngine::Parakeet(parakeet_engine) => {
-            match parakeet_engine.transcribe_audio(speech_samples).await {
-                Ok(text) => {
-                    let cleaned_text = text.trim().to_string();
-                    if cleaned_text.is_empty() {
-                        return Ok((String::new(), None, false));
+            let duration = speech_samples.len() as f32 / 16000.0;
+            match parakeet_engine.transcribe_audio_with_timestamps(speech_samples).await {
+                Ok(result) => {
+                    let cleaned_text = vocabulary.correct(result.text.trim());
+                    let mut words = result.word_timestamps(duration);
+                    vocabulary.correct_words(&mut words, true);
+                    if !cleaned_text.is_empty() {
+                        info!(
+                            "Parakeet transcription complete for chunk {}: '{}'",
+                            chunk.chunk_id, cleaned_text
+                        );
                     }
 
-                    info!(
-                        "Parakeet transcription complete for chunk {}: '{}'",
-                        chunk.chunk_id, cleaned_text
-                    );
-
-                    // Parakeet doesn't provide confidence or partial results
-                    Ok((cleaned_text, None, false))
+                    // Parakeet doesn't provide confidence, partial results or language identification
+                    Ok(TranscriptResult {
+                        text: cleaned_text,
+                        confidence: None,
+                        is_partial: false,
+                        words,
+                        language: None,
+                        language_probability: None,
+                        prompted: false,
+                    })
                 }
                 Err(e) => {
                     error!(
@@ -526,10 +618,18 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
             let language = crate::get_language_preference_internal();
 
             match provider.transcribe(speech_samples, language).await {
-                Ok(result) => {
-                    let cleaned_text = result.text.trim().to_string();
-                    if cleaned_text.is_empty() {
-                        return Ok((String::new(), result.confidence, result.is_partial));
+                Ok(mut result) => {
+                    // A remote provider may have handed this segment to its local fallback
+                    let fuzzy = !result.prompted;
+                    let cleaned_text = if fuzzy {
+                        vocabulary.correct(result.text.trim())
+                    } else {
+                        vocabulary.replace_aliases(result.text.trim())
+                    };
+                    vocabulary.correct_words(&mut result.words, fuzzy);
+                    result.text = cleaned_text;
+                    if result.text.is_empty() {
+                        return Ok(result);
                     }
 
                     let confidence_str = match result.confidence {
@@ -541,12 +641,12 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
                         "{} transcription complete for chunk {}: '{}' ({}, partial: {})",
                         provider.provider_name(),
                         chunk.chunk_id,
-                        cleaned_text,
+                        result.text,
                         confidence_str,
                         result.is_partial
                     );
 
-                    Ok((cleaned_text, result.confidence, result.is_partial))
+                    Ok(result)
                 }
                 Err(e) => {
                     error!(
@@ -572,6 +672,30 @@ async fn transcribe_chunk_with_provider<R: Runtime>(
     }
 }
 
+/// Assign a speaker to a chunk using the diarization engine.
+/// Failures are logged and yield None so transcription is never blocked by diarization.
+async fn diarize_chunk(
+    engine: &crate::diarization::DiarizationEngine,
+    data: Vec<f32>,
+    sample_rate: u32,
+) -> Option<usize> {
+    // Speaker embedding model expects 16kHz mono, same as transcription
+    let samples = if sample_rate != 16000 {
+        crate::audio::audio_processing::resample_audio(&data, sample_rate, 16000)
+    } else {
+        data
+    };
+
+    match engine.process_segment(&samples).await {
+        Ok(0) => None, // Segment too short to identify a speaker
+        Ok(speaker_id) => Some(speaker_id),
+        Err(e) => {
+            warn!("Diarization failed for chunk: {}", e);
+            None
+        }
+    }
+}
+
 /// Format current timestamp (wall-clock time)
 fn format_current_timestamp() -> String {
     let now = std::time::SystemTime::now()
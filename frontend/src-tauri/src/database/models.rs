@@ -9,6 +9,20 @@ pub struct MeetingModel {
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
     pub folder_path: Option<String>,
+    #[sqlx(default)]
+    pub project_id: Option<i64>,
+}
+
+/// A meeting as listed by `MeetingsRepository::get_meetings`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingListRow {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub folder_path: Option<String>,
+    pub project_id: Option<i64>,
+    pub duration: Option<f64>, // Seconds, from the end of the last transcript segment
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -35,6 +49,48 @@ pub struct Transcript {
     pub audio_start_time: Option<f64>,
     pub audio_end_time: Option<f64>,
     pub duration: Option<f64>,
+    // Diarized speaker label for this segment
+    pub speaker_id: Option<i64>,
+    // Registered speaker this segment was identified as
+    pub speaker_profile_id: Option<i64>,
+    #[sqlx(default)]
+    pub speaker_name: Option<String>,
+    // JSON array of word timings (see audio::transcription::WordTimestamp)
+    pub words: Option<String>,
+    // Language code of the text, and its detection probability when auto-detected
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub language_probability: Option<f64>,
+    // Capture channel in dual-channel mode: "mic" or "system"
+    #[serde(default)]
+    pub audio_source: Option<String>,
+}
+
+/// A replaced version of a meeting's transcript (segments omitted)
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptVersion {
+    pub id: i64,
+    pub meeting_id: String,
+    pub reason: String,
+    pub model: Option<String>,
+    pub segment_count: i64,
+    pub created_at: DateTimeUtc,
+}
+
+/// A segment-level transcript edit; the before/after fields hold JSON arrays of
+/// `Transcript` rows
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptRevisionModel {
+    pub id: i64,
+    pub meeting_id: String,
+    pub operation: String, // 'edit_text', 'set_speaker', 'split', 'merge' or 'revert'
+    pub author: Option<String>,
+    pub before_segments: String,
+    pub after_segments: String,
+    pub reverts_id: Option<i64>,
+    pub reverted_by: Option<i64>,
+    pub created_at: DateTimeUtc,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -50,6 +106,8 @@ pub struct SummaryProcess {
     pub chunk_count: i64,
     pub processing_time: f64,
     pub metadata: Option<String>, // JSON
+    #[sqlx(default)]
+    pub stale: bool, // Transcript edited since the summary was generated
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -112,4 +170,63 @@ pub struct TranscriptSetting {
     #[sqlx(rename = "openaiApiKey")]
     #[serde(rename = "openaiApiKey")]
     pub openai_api_key: Option<String>,
+    #[sqlx(rename = "transcriptionEndpoint")]
+    #[serde(rename = "transcriptionEndpoint")]
+    pub transcription_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SpeakerModel {
+    pub id: i64,
+    pub name: String,
+    #[serde(skip)]
+    pub embedding: Vec<u8>, // Little-endian f32 voice embedding
+    pub sample_count: i64,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct VocabularyTermModel {
+    pub id: i64,
+    pub term: String,
+    pub aliases: String, // JSON array of known misrecognitions
+    pub meeting_id: Option<String>, // None for global terms
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ProjectModel {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>, // None for top-level projects
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TagModel {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+/// A tag together with a meeting carrying it
+#[derive(Debug, Clone, FromRow)]
+pub struct MeetingTagRow {
+    pub meeting_id: String,
+    #[sqlx(flatten)]
+    pub tag: TagModel,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ParticipantModel {
+    pub id: i64,
+    pub meeting_id: String,
+    pub name: String,
+    pub email: Option<String>,
+    pub speaker_id: Option<i64>, // Registered speaker this participant is
+    pub created_at: DateTimeUtc,
 }
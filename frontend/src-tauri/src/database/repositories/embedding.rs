@@ -0,0 +1,150 @@
+use crate::diarization::clustering::cosine_similarity;
+use crate::diarization::registry::{embedding_from_bytes, embedding_to_bytes};
+use crate::semantic_search::chunker::TextChunk;
+use chrono::Utc;
+use futures_util::TryStreamExt;
+use sqlx::{Error as SqlxError, FromRow, SqlitePool};
+use tracing::info;
+
+/// A stored chunk scored against a query embedding
+#[derive(Debug, Clone, FromRow)]
+pub struct EmbeddingMatch {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub chunk_index: i64,
+    pub content: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    #[sqlx(skip)]
+    pub score: f32,
+}
+
+#[derive(FromRow)]
+struct EmbeddingRow {
+    #[sqlx(flatten)]
+    chunk: EmbeddingMatch,
+    embedding: Vec<u8>,
+}
+
+pub struct EmbeddingsRepository;
+
+impl EmbeddingsRepository {
+    /// Meetings with transcripts that have no chunks embedded with `model` yet
+    pub async fn meetings_without_embeddings(
+        pool: &SqlitePool,
+        model: &str,
+    ) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar(
+            "SELECT m.id FROM meetings m
+             WHERE EXISTS (SELECT 1 FROM transcripts t WHERE t.meeting_id = m.id)
+               AND NOT EXISTS (
+                   SELECT 1 FROM transcript_embeddings e WHERE e.meeting_id = m.id AND e.model = ?
+               )
+             ORDER BY m.created_at DESC",
+        )
+        .bind(model)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replaces all of a meeting's chunks for `model` in one transaction
+    pub async fn replace_meeting_embeddings(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        model: &str,
+        chunks: &[(TextChunk, Vec<f32>)],
+    ) -> Result<(), SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        sqlx::query("DELETE FROM transcript_embeddings WHERE meeting_id = ? AND model = ?")
+            .bind(meeting_id)
+            .bind(model)
+            .execute(&mut *transaction)
+            .await?;
+
+        for (chunk, embedding) in chunks {
+            sqlx::query(
+                "INSERT INTO transcript_embeddings (meeting_id, chunk_index, model, content, audio_start_time, audio_end_time, embedding, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(chunk.chunk_index as i64)
+            .bind(model)
+            .bind(&chunk.content)
+            .bind(chunk.audio_start_time)
+            .bind(chunk.audio_end_time)
+            .bind(embedding_to_bytes(embedding))
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        info!("Stored {} embedded chunks for meeting {}", chunks.len(), meeting_id);
+        Ok(())
+    }
+
+    /// Drops a meeting's embeddings so it is re-indexed on the next search
+    pub async fn delete_meeting_embeddings(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<u64, SqlxError> {
+        let result = sqlx::query("DELETE FROM transcript_embeddings WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Drops every vector produced by `model` (used for a full re-index)
+    pub async fn delete_model_embeddings(pool: &SqlitePool, model: &str) -> Result<u64, SqlxError> {
+        let result = sqlx::query("DELETE FROM transcript_embeddings WHERE model = ?")
+            .bind(model)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Returns the `limit` chunks most similar to `query` (cosine similarity).
+    /// Rows are streamed so memory stays bounded however many meetings are indexed.
+    pub async fn search_similar(
+        pool: &SqlitePool,
+        model: &str,
+        query: &[f32],
+        limit: usize,
+        meeting_ids: Option<&[String]>,
+    ) -> Result<Vec<EmbeddingMatch>, SqlxError> {
+        let meeting_ids_json = meeting_ids
+            .map(|ids| serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string()));
+
+        let mut rows = sqlx::query_as::<_, EmbeddingRow>(
+            "SELECT e.meeting_id, m.title AS meeting_title, e.chunk_index, e.content,
+                    e.audio_start_time, e.audio_end_time, e.embedding
+             FROM transcript_embeddings e JOIN meetings m ON m.id = e.meeting_id
+             WHERE e.model = ?1
+               AND (?2 IS NULL OR e.meeting_id IN (SELECT value FROM json_each(?2)))",
+        )
+        .bind(model)
+        .bind(meeting_ids_json.as_deref())
+        .fetch(pool);
+
+        let mut best: Vec<EmbeddingMatch> = Vec::with_capacity(limit + 1);
+        while let Some(row) = rows.try_next().await? {
+            let embedding = embedding_from_bytes(&row.embedding);
+            if embedding.len() != query.len() {
+                continue;
+            }
+            let score = cosine_similarity(query, &embedding);
+            if best.len() == limit && best.last().map(|b| score <= b.score).unwrap_or(false) {
+                continue;
+            }
+
+            let position = best.partition_point(|b| b.score >= score);
+            best.insert(position, EmbeddingMatch { score, ..row.chunk });
+            best.truncate(limit);
+        }
+
+        Ok(best)
+    }
+}
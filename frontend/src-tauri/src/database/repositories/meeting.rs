@@ -1,18 +1,139 @@
-use crate::api::{MeetingDetails, MeetingTranscript};
-use crate::database::models::{MeetingModel, Transcript};
+use crate::api::{MeetingDetails, MeetingQuery, MeetingSort, MeetingTranscript};
+use crate::database::models::{MeetingListRow, MeetingModel, Transcript};
+use crate::database::repositories::search::inclusive_end_of_day;
+use crate::database::repositories::tag::clean_names;
 use chrono::Utc;
-use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use sqlx::query::QueryAs;
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Connection, Error as SqlxError, Sqlite, SqliteConnection, SqlitePool};
 use tracing::{error, info};
 
+// Meetings with their recording length, filtered by project (including sub-projects),
+// creation date, tags (all must match), participant name and duration
+const LIST_SQL: &str = "
+WITH RECURSIVE project_tree(id) AS (
+    SELECT id FROM projects WHERE id = ?1
+    UNION ALL
+    SELECT p.id FROM projects p JOIN project_tree t ON p.parent_id = t.id
+),
+listed AS (
+    SELECT m.id, m.title, m.created_at, m.updated_at, m.folder_path, m.project_id,
+           (SELECT MAX(t.audio_end_time) FROM transcripts t WHERE t.meeting_id = m.id) AS duration
+    FROM meetings m
+    WHERE (?1 IS NULL OR m.project_id IN (SELECT id FROM project_tree))
+      AND (?2 IS NULL OR datetime(m.created_at) >= datetime(?2))
+      AND (?3 IS NULL OR datetime(m.created_at) <= datetime(?3))
+      AND (?4 IS NULL OR ?5 = (
+          SELECT COUNT(*) FROM meeting_tags mt JOIN tags tg ON tg.id = mt.tag_id
+          WHERE mt.meeting_id = m.id AND tg.name IN (SELECT value FROM json_each(?4))))
+      AND (?6 IS NULL OR EXISTS (
+          SELECT 1 FROM meeting_participants mp
+          WHERE mp.meeting_id = m.id AND instr(lower(mp.name), lower(?6)) > 0))
+),
+filtered AS (
+    SELECT * FROM listed
+    WHERE (?7 IS NULL OR duration >= ?7) AND (?8 IS NULL OR duration <= ?8)
+)";
+
+/// Values for LIST_SQL's ?1-?8 filter parameters, normalized from a `MeetingQuery`
+struct ListFilters<'q> {
+    query: &'q MeetingQuery,
+    tags_json: Option<String>,
+    tag_count: Option<i64>,
+    participant: Option<&'q str>,
+    to_date: Option<String>,
+}
+
+impl<'q> ListFilters<'q> {
+    fn new(query: &'q MeetingQuery) -> Self {
+        let tags = query.tags.as_deref().map(clean_names).filter(|t| !t.is_empty());
+        Self {
+            query,
+            tags_json: tags
+                .as_ref()
+                .map(|t| serde_json::to_string(t).unwrap_or_else(|_| "[]".to_string())),
+            tag_count: tags.as_ref().map(|t| t.len() as i64),
+            participant: query
+                .participant
+                .as_deref()
+                .map(str::trim)
+                .filter(|p| !p.is_empty()),
+            to_date: query.to_date.as_deref().map(inclusive_end_of_day),
+        }
+    }
+
+    fn bind<O>(
+        &'q self,
+        sql: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    ) -> QueryAs<'q, Sqlite, O, SqliteArguments<'q>> {
+        sql.bind(self.query.project_id)
+            .bind(self.query.from_date.as_deref())
+            .bind(self.to_date.as_deref())
+            .bind(self.tags_json.as_deref())
+            .bind(self.tag_count)
+            .bind(self.participant)
+            .bind(self.query.min_duration)
+            .bind(self.query.max_duration)
+    }
+}
+
+fn order_clause(sort: MeetingSort) -> &'static str {
+    match sort {
+        MeetingSort::Newest => "f.created_at DESC, f.id",
+        MeetingSort::Oldest => "f.created_at ASC, f.id",
+        MeetingSort::RecentlyUpdated => "f.updated_at DESC, f.id",
+        MeetingSort::Title => "f.title COLLATE NOCASE ASC, f.created_at DESC",
+        MeetingSort::Longest => "f.duration IS NULL, f.duration DESC, f.created_at DESC",
+        MeetingSort::Shortest => "f.duration IS NULL, f.duration ASC, f.created_at DESC",
+    }
+}
+
 pub struct MeetingsRepository;
 
 impl MeetingsRepository {
-    pub async fn get_meetings(pool: &SqlitePool) -> Result<Vec<MeetingModel>, sqlx::Error> {
-        let meetings =
-            sqlx::query_as::<_, MeetingModel>("SELECT * FROM meetings ORDER BY created_at DESC")
-                .fetch_all(pool)
-                .await?;
-        Ok(meetings)
+    /// Lists meetings matching `query`, in its sort order and page
+    pub async fn get_meetings(
+        pool: &SqlitePool,
+        query: &MeetingQuery,
+    ) -> Result<Vec<MeetingListRow>, sqlx::Error> {
+        let filters = ListFilters::new(query);
+        let sql = format!(
+            "{} SELECT f.* FROM filtered f ORDER BY {} LIMIT ?9 OFFSET ?10",
+            LIST_SQL,
+            order_clause(query.sort)
+        );
+        filters
+            .bind(sqlx::query_as::<_, MeetingListRow>(&sql))
+            .bind(query.limit.unwrap_or(-1))
+            .bind(query.offset.unwrap_or(0).max(0))
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Number of meetings matching `query`'s filters, across all pages
+    pub async fn count_meetings(pool: &SqlitePool, query: &MeetingQuery) -> Result<i64, sqlx::Error> {
+        let filters = ListFilters::new(query);
+        let sql = format!("{} SELECT COUNT(*) FROM filtered", LIST_SQL);
+        let (total,): (i64,) = filters
+            .bind(sqlx::query_as::<_, (i64,)>(&sql))
+            .fetch_one(pool)
+            .await?;
+        Ok(total)
+    }
+
+    /// Moves a meeting into a project, or out of any project with `project_id` None
+    pub async fn set_meeting_project(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        project_id: Option<i64>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meetings SET project_id = ?, updated_at = ? WHERE id = ?")
+            .bind(project_id)
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
     }
 
     pub async fn delete_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
@@ -75,7 +196,11 @@ impl MeetingsRepository {
         if let Some(meeting) = meeting {
             // Get all transcripts for this meeting
             let transcripts =
-                sqlx::query_as::<_, Transcript>("SELECT * FROM transcripts WHERE meeting_id = ?")
+                sqlx::query_as::<_, Transcript>(
+                    "SELECT t.*, s.name AS speaker_name FROM transcripts t
+                     LEFT JOIN speakers s ON s.id = t.speaker_profile_id
+                     WHERE t.meeting_id = ?",
+                )
                     .bind(meeting_id)
                     .fetch_all(&mut *transaction)
                     .await?;
@@ -92,6 +217,13 @@ impl MeetingsRepository {
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    speaker_id: t.speaker_id,
+                    speaker_profile_id: t.speaker_profile_id,
+                    speaker_name: t.speaker_name,
+                    words: t.words.and_then(|w| serde_json::from_str(&w).ok()),
+                    language: t.language,
+                    language_probability: t.language_probability,
+                    audio_source: t.audio_source,
                 })
                 .collect::<Vec<_>>();
 
@@ -173,7 +305,7 @@ impl MeetingsRepository {
     }
 }
 
-async fn delete_meeting_with_transaction(
+pub(crate) async fn delete_meeting_with_transaction(
     transaction: &mut SqliteConnection,
     meeting_id: &str,
 ) -> Result<bool, SqlxError> {
@@ -201,13 +333,33 @@ async fn delete_meeting_with_transaction(
         .execute(&mut *transaction)
         .await?;
 
-    // 3. Delete from transcripts
+    // 3. Delete from transcript_embeddings
+    sqlx::query("DELETE FROM transcript_embeddings WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 4. Delete from transcripts
     sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
         .await?;
 
-    // 4. Finally, delete the meeting
+    // 5. Delete tag links, participants and edit history
+    sqlx::query("DELETE FROM meeting_tags WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query("DELETE FROM meeting_participants WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+    sqlx::query("DELETE FROM transcript_revisions WHERE meeting_id = ?")
+        .bind(meeting_id)
+        .execute(&mut *transaction)
+        .await?;
+
+    // 6. Finally, delete the meeting
     let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
@@ -215,3 +367,220 @@ async fn delete_meeting_with_transaction(
 
     Ok(result.rows_affected() > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// (id, project, tags, participant, recording length)
+    const MEETINGS: [(&str, Option<i64>, &[&str], Option<&str>, Option<f64>); 6] = [
+        ("m1", Some(1), &["urgent", "budget"], Some("Alice Smith"), Some(600.0)),
+        ("m2", Some(2), &["urgent"], Some("Bob"), Some(1800.0)),
+        ("m3", Some(3), &["Budget", "urgent"], Some("alice jones"), Some(3600.0)),
+        ("m4", Some(4), &["urgent", "budget"], Some("Alice"), Some(120.0)),
+        ("m5", None, &[], None, None),
+        ("m6", Some(1), &["budget"], Some("Carol"), Some(300.0)),
+    ];
+
+    /// Projects 1 > 2 > 3 form a chain; 4 is a separate top-level project
+    async fn seeded_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let now = Utc::now();
+        for (id, parent) in [(1, None), (2, Some(1)), (3, Some(2)), (4, None)] {
+            sqlx::query("INSERT INTO projects (id, name, parent_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+                .bind(id)
+                .bind(format!("Project {}", id))
+                .bind(parent)
+                .bind(now)
+                .bind(now)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        for name in ["urgent", "budget"] {
+            sqlx::query("INSERT INTO tags (name, created_at) VALUES (?, ?)")
+                .bind(name)
+                .bind(now)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let start = Utc.with_ymd_and_hms(2025, 10, 1, 9, 0, 0).unwrap();
+        for (i, &(id, project, tag_names, participant, duration)) in MEETINGS.iter().enumerate() {
+            let created = start + Duration::days(i as i64);
+            sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at, project_id) VALUES (?, ?, ?, ?, ?)")
+                .bind(id)
+                .bind(format!("Meeting {}", id))
+                .bind(created)
+                .bind(created)
+                .bind(project)
+                .execute(&pool)
+                .await
+                .unwrap();
+            for &tag in tag_names {
+                sqlx::query("INSERT INTO meeting_tags (meeting_id, tag_id) SELECT ?, id FROM tags WHERE name = ?")
+                    .bind(id)
+                    .bind(tag)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+            if let Some(name) = participant {
+                sqlx::query("INSERT INTO meeting_participants (meeting_id, name, created_at) VALUES (?, ?, ?)")
+                    .bind(id)
+                    .bind(name)
+                    .bind(now)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+            }
+            if let Some(end) = duration {
+                for (n, segment_end) in [end / 2.0, end].into_iter().enumerate() {
+                    sqlx::query(
+                        "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time)
+                         VALUES (?, ?, 'text', '00:00', ?, ?)",
+                    )
+                    .bind(format!("{}-t{}", id, n))
+                    .bind(id)
+                    .bind(segment_end - 1.0)
+                    .bind(segment_end)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+                }
+            }
+        }
+        pool
+    }
+
+    async fn listed(pool: &SqlitePool, query: &MeetingQuery) -> Vec<String> {
+        let mut ids: Vec<String> = MeetingsRepository::get_meetings(pool, query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn tags(names: &[&str]) -> Option<Vec<String>> {
+        Some(names.iter().map(|n| n.to_string()).collect())
+    }
+
+    #[tokio::test]
+    async fn test_project_filter_includes_sub_projects() {
+        let pool = seeded_pool().await;
+        let in_project = |id| MeetingQuery {
+            project_id: Some(id),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &in_project(1)).await, vec!["m1", "m2", "m3", "m6"]);
+        assert_eq!(listed(&pool, &in_project(2)).await, vec!["m2", "m3"]);
+        assert_eq!(listed(&pool, &in_project(4)).await, vec!["m4"]);
+    }
+
+    #[tokio::test]
+    async fn test_tag_filter_requires_every_tag() {
+        let pool = seeded_pool().await;
+        let tagged = |names: &[&str]| MeetingQuery {
+            tags: tags(names),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &tagged(&["URGENT", "budget"])).await, vec!["m1", "m3", "m4"]);
+        assert_eq!(listed(&pool, &tagged(&["urgent"])).await, vec!["m1", "m2", "m3", "m4"]);
+        // Repeated names count once; blank names mean no tag filter
+        assert_eq!(listed(&pool, &tagged(&["budget", "Budget "])).await, vec!["m1", "m3", "m4", "m6"]);
+        assert_eq!(listed(&pool, &tagged(&[" "])).await.len(), MEETINGS.len());
+        assert!(listed(&pool, &tagged(&["urgent", "missing"])).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_participant_and_duration_filters() {
+        let pool = seeded_pool().await;
+        let attended_by = MeetingQuery {
+            participant: Some(" ALICE ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &attended_by).await, vec!["m1", "m3", "m4"]);
+
+        // Bounds are inclusive, and meetings without transcripts have no length to compare
+        let between = MeetingQuery {
+            min_duration: Some(300.0),
+            max_duration: Some(1800.0),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &between).await, vec!["m1", "m2", "m6"]);
+        let at_least = MeetingQuery {
+            min_duration: Some(1800.0),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &at_least).await, vec!["m2", "m3"]);
+
+        let combined = MeetingQuery {
+            project_id: Some(1),
+            tags: tags(&["budget"]),
+            participant: Some("alice".to_string()),
+            max_duration: Some(1000.0),
+            ..Default::default()
+        };
+        assert_eq!(listed(&pool, &combined).await, vec!["m1"]);
+    }
+
+    #[tokio::test]
+    async fn test_count_agrees_with_paged_listing() {
+        let pool = seeded_pool().await;
+        let queries = [
+            MeetingQuery::default(),
+            MeetingQuery {
+                project_id: Some(1),
+                sort: MeetingSort::Longest,
+                ..Default::default()
+            },
+            MeetingQuery {
+                tags: tags(&["urgent"]),
+                sort: MeetingSort::Title,
+                ..Default::default()
+            },
+            MeetingQuery {
+                participant: Some("a".to_string()),
+                min_duration: Some(100.0),
+                sort: MeetingSort::Oldest,
+                ..Default::default()
+            },
+        ];
+
+        for query in queries {
+            let total = MeetingsRepository::count_meetings(&pool, &query).await.unwrap();
+            let all: Vec<String> = MeetingsRepository::get_meetings(&pool, &query)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|m| m.id)
+                .collect();
+            assert_eq!(total, all.len() as i64, "{:?}", query);
+
+            let mut paged = Vec::new();
+            for offset in (0..total + 2).step_by(2) {
+                let page = MeetingQuery {
+                    limit: Some(2),
+                    offset: Some(offset),
+                    ..query.clone()
+                };
+                assert_eq!(MeetingsRepository::count_meetings(&pool, &page).await.unwrap(), total);
+                let rows = MeetingsRepository::get_meetings(&pool, &page).await.unwrap();
+                paged.extend(rows.into_iter().map(|m| m.id));
+            }
+            assert_eq!(paged, all, "{:?}", query);
+        }
+    }
+}
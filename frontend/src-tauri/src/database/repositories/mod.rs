@@ -1,5 +1,13 @@
+pub mod embedding;
 pub mod meeting;
+pub mod participant;
+pub mod project;
+pub mod search;
 pub mod setting;
+pub mod speaker;
 pub mod summary;
+pub mod tag;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod transcript_revision;
+pub mod vocabulary;
@@ -0,0 +1,106 @@
+use crate::database::models::ParticipantModel;
+use crate::database::repositories::tag::clean_names;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, SqlitePool};
+
+/// A participant as entered by the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParticipantInput {
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub speaker_id: Option<i64>, // Registered speaker this participant is
+}
+
+pub struct ParticipantsRepository;
+
+impl ParticipantsRepository {
+    pub async fn list_participants(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<ParticipantModel>, SqlxError> {
+        Self::participants_for_meetings(pool, &[meeting_id.to_string()]).await
+    }
+
+    /// Participants of the given meetings, ordered by name
+    pub async fn participants_for_meetings(
+        pool: &SqlitePool,
+        meeting_ids: &[String],
+    ) -> Result<Vec<ParticipantModel>, SqlxError> {
+        let ids = serde_json::to_string(meeting_ids)
+            .map_err(|e| SqlxError::Protocol(e.to_string()))?;
+        sqlx::query_as::<_, ParticipantModel>(
+            "SELECT * FROM meeting_participants
+             WHERE meeting_id IN (SELECT value FROM json_each(?))
+             ORDER BY name COLLATE NOCASE",
+        )
+        .bind(ids)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replaces a meeting's participant list. Entries are de-duplicated by name; the
+    /// first entry of a name wins.
+    pub async fn set_meeting_participants(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        participants: &[ParticipantInput],
+    ) -> Result<Vec<ParticipantModel>, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        let meeting_exists = sqlx::query("SELECT 1 FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(&mut *transaction)
+            .await?
+            .is_some();
+        if !meeting_exists {
+            transaction.rollback().await?;
+            return Err(SqlxError::RowNotFound);
+        }
+
+        sqlx::query("DELETE FROM meeting_participants WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let names: Vec<String> = participants.iter().map(|p| p.name.clone()).collect();
+        let now = Utc::now();
+        for name in clean_names(&names) {
+            let Some(participant) = participants.iter().find(|p| p.name.trim() == name) else {
+                continue;
+            };
+            let email = participant
+                .email
+                .as_deref()
+                .map(str::trim)
+                .filter(|e| !e.is_empty());
+            sqlx::query(
+                "INSERT INTO meeting_participants (meeting_id, name, email, speaker_id, created_at)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(&name)
+            .bind(email)
+            .bind(participant.speaker_id)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+
+        Self::list_participants(pool, meeting_id).await
+    }
+
+    /// Distinct participant names across all meetings, most frequent first (for autocomplete)
+    pub async fn list_participant_names(pool: &SqlitePool) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar(
+            "SELECT MIN(name) FROM meeting_participants
+             GROUP BY name COLLATE NOCASE
+             ORDER BY COUNT(*) DESC, MIN(name) COLLATE NOCASE",
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,133 @@
+use crate::database::models::ProjectModel;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct ProjectsRepository;
+
+impl ProjectsRepository {
+    pub async fn list_projects(pool: &SqlitePool) -> Result<Vec<ProjectModel>, SqlxError> {
+        sqlx::query_as::<_, ProjectModel>("SELECT * FROM projects ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_project(
+        pool: &SqlitePool,
+        id: i64,
+    ) -> Result<Option<ProjectModel>, SqlxError> {
+        sqlx::query_as::<_, ProjectModel>("SELECT * FROM projects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Creates a project under `parent_id` (top level when None) and returns its id
+    pub async fn create_project(
+        pool: &SqlitePool,
+        name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<i64, SqlxError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(SqlxError::Protocol("project name cannot be empty".to_string()));
+        }
+        if let Some(parent_id) = parent_id {
+            if Self::get_project(pool, parent_id).await?.is_none() {
+                return Err(SqlxError::RowNotFound);
+            }
+        }
+
+        let now = Utc::now();
+        let result = sqlx::query(
+            "INSERT INTO projects (name, parent_id, created_at, updated_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(name)
+        .bind(parent_id)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        info!("Created project '{}'", name);
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Renames a project and/or moves it under another parent.
+    /// Moving a project into itself or one of its sub-projects is rejected.
+    pub async fn update_project(
+        pool: &SqlitePool,
+        id: i64,
+        name: &str,
+        parent_id: Option<i64>,
+    ) -> Result<bool, SqlxError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(SqlxError::Protocol("project name cannot be empty".to_string()));
+        }
+
+        if let Some(parent_id) = parent_id {
+            let creates_cycle: bool = sqlx::query_scalar(
+                "WITH RECURSIVE subtree(id) AS (
+                     SELECT ?1
+                     UNION ALL
+                     SELECT p.id FROM projects p JOIN subtree s ON p.parent_id = s.id
+                 )
+                 SELECT EXISTS (SELECT 1 FROM subtree WHERE id = ?2)",
+            )
+            .bind(id)
+            .bind(parent_id)
+            .fetch_one(pool)
+            .await?;
+            if creates_cycle {
+                return Err(SqlxError::Protocol(
+                    "a project cannot be moved into itself or one of its sub-projects".to_string(),
+                ));
+            }
+        }
+
+        let result =
+            sqlx::query("UPDATE projects SET name = ?, parent_id = ?, updated_at = ? WHERE id = ?")
+                .bind(name)
+                .bind(parent_id)
+                .bind(Utc::now())
+                .bind(id)
+                .execute(pool)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Deletes a project. Its sub-projects and meetings move up to its parent
+    /// (or to the top level), so nothing inside is lost.
+    pub async fn delete_project(pool: &SqlitePool, id: i64) -> Result<bool, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        let project: Option<ProjectModel> =
+            sqlx::query_as("SELECT * FROM projects WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+        let Some(project) = project else {
+            transaction.rollback().await?;
+            return Ok(false);
+        };
+
+        sqlx::query("UPDATE projects SET parent_id = ? WHERE parent_id = ?")
+            .bind(project.parent_id)
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET project_id = ? WHERE project_id = ?")
+            .bind(project.parent_id)
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM projects WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!("Deleted project '{}'", project.name);
+        Ok(true)
+    }
+}
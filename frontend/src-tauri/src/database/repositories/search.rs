@@ -0,0 +1,346 @@
+use crate::api::{MeetingSearchResult, SearchFilters, SearchMatch, SearchResultsPage};
+use sqlx::{Error as SqlxError, FromRow, SqlitePool};
+use tracing::info;
+
+/// Number of highlighted matches returned per meeting (match_count still reports all of them)
+const MAX_MATCHES_PER_MEETING: usize = 5;
+
+/// BM25 multiplier for title hits; a title match says more about a meeting than a single segment
+const TITLE_WEIGHT: f64 = 2.0;
+
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+// Every FTS index yields rows of the same shape so they can be ranked together.
+// bm25() is negative with better matches lower, so ordering is ascending.
+const SEARCH_SQL: &str = "
+WITH hits AS MATERIALIZED (
+    SELECT m.id AS meeting_id, 'title' AS source, m.id AS source_id,
+           highlight(meetings_fts, 0, '<mark>', '</mark>') AS snippet,
+           bm25(meetings_fts) * ?7 AS rank,
+           NULL AS timestamp, NULL AS audio_start_time
+    FROM meetings_fts
+    JOIN meetings_fts_ids k ON k.id = meetings_fts.rowid
+    JOIN meetings m ON m.id = k.meeting_id
+    WHERE meetings_fts MATCH ?1
+    UNION ALL
+    SELECT t.meeting_id, 'transcript', t.id,
+           snippet(transcripts_fts, 0, '<mark>', '</mark>', '…', 16),
+           bm25(transcripts_fts),
+           t.timestamp, t.audio_start_time
+    FROM transcripts_fts
+    JOIN transcripts_fts_ids k ON k.id = transcripts_fts.rowid
+    JOIN transcripts t ON t.id = k.transcript_id
+    WHERE transcripts_fts MATCH ?1
+    UNION ALL
+    SELECT sp.meeting_id, 'summary', sp.meeting_id,
+           snippet(summaries_fts, 0, '<mark>', '</mark>', '…', 16),
+           bm25(summaries_fts),
+           NULL, NULL
+    FROM summaries_fts
+    JOIN summaries_fts_ids k ON k.id = summaries_fts.rowid
+    JOIN summary_processes sp ON sp.meeting_id = k.meeting_id
+    WHERE summaries_fts MATCH ?1
+),
+filtered AS MATERIALIZED (
+    SELECT h.*, m.title, m.created_at
+    FROM hits h JOIN meetings m ON m.id = h.meeting_id
+    WHERE (?2 IS NULL OR datetime(m.created_at) >= datetime(?2))
+      AND (?3 IS NULL OR datetime(m.created_at) <= datetime(?3))
+      AND (?4 IS NULL OR m.id IN (SELECT value FROM json_each(?4)))
+),
+ranked AS (
+    SELECT meeting_id, MIN(rank) AS best_rank, COUNT(*) AS match_count
+    FROM filtered GROUP BY meeting_id
+),
+page AS (
+    SELECT * FROM ranked ORDER BY best_rank, meeting_id LIMIT ?5 OFFSET ?6
+)
+SELECT f.meeting_id, f.title, f.created_at, f.source, f.source_id, f.snippet, f.rank,
+       f.timestamp, f.audio_start_time, p.best_rank, p.match_count,
+       (SELECT COUNT(*) FROM ranked) AS total
+FROM filtered f JOIN page p ON p.meeting_id = f.meeting_id
+ORDER BY p.best_rank, f.meeting_id, f.rank";
+
+#[derive(Debug, FromRow)]
+struct SearchHitRow {
+    meeting_id: String,
+    title: String,
+    created_at: String,
+    source: String,
+    source_id: String,
+    snippet: String,
+    #[allow(dead_code)]
+    rank: f64,
+    timestamp: Option<String>,
+    audio_start_time: Option<f64>,
+    best_rank: f64,
+    match_count: i64,
+    total: i64,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Full-text search over meeting titles, transcripts and summaries.
+    ///
+    /// Results are grouped per meeting and ordered by the BM25 rank of each meeting's
+    /// best match. The query supports "quoted phrases", prefix* terms and AND/OR/NOT.
+    pub async fn search_meetings(
+        pool: &SqlitePool,
+        query: &str,
+        filters: &SearchFilters,
+        limit: i64,
+        offset: i64,
+    ) -> Result<SearchResultsPage, SqlxError> {
+        let limit = limit.clamp(1, 100);
+        let offset = offset.max(0);
+        let empty_page = SearchResultsPage {
+            results: Vec::new(),
+            total: 0,
+            limit,
+            offset,
+        };
+
+        let fts_query = match build_fts_query(query) {
+            Some(q) => q,
+            None => return Ok(empty_page),
+        };
+
+        let meeting_ids = filters
+            .meeting_ids
+            .as_ref()
+            .map(|ids| serde_json::to_string(ids).unwrap_or_else(|_| "[]".to_string()));
+        let to_date = filters.to_date.as_deref().map(inclusive_end_of_day);
+
+        let rows = sqlx::query_as::<_, SearchHitRow>(SEARCH_SQL)
+            .bind(&fts_query)
+            .bind(filters.from_date.as_deref())
+            .bind(to_date.as_deref())
+            .bind(meeting_ids.as_deref())
+            .bind(limit)
+            .bind(offset)
+            .bind(TITLE_WEIGHT)
+            .fetch_all(pool)
+            .await?;
+
+        let total = rows.first().map(|r| r.total).unwrap_or(0);
+        let mut results: Vec<MeetingSearchResult> = Vec::new();
+
+        for row in rows {
+            if results.last().map(|r| r.meeting_id != row.meeting_id).unwrap_or(true) {
+                results.push(MeetingSearchResult {
+                    meeting_id: row.meeting_id.clone(),
+                    title: row.title.clone(),
+                    created_at: row.created_at.clone(),
+                    score: -row.best_rank,
+                    match_count: row.match_count,
+                    matches: Vec::new(),
+                });
+            }
+
+            if let Some(current) = results.last_mut() {
+                if current.matches.len() < MAX_MATCHES_PER_MEETING {
+                    current.matches.push(SearchMatch {
+                        source: row.source,
+                        source_id: row.source_id,
+                        snippet: row.snippet,
+                        timestamp: row.timestamp,
+                        audio_start_time: row.audio_start_time,
+                    });
+                }
+            }
+        }
+
+        info!(
+            "Search for {:?} matched {} meetings (returning {} from offset {})",
+            fts_query,
+            total,
+            results.len(),
+            offset
+        );
+
+        Ok(SearchResultsPage {
+            results,
+            total,
+            limit,
+            offset,
+        })
+    }
+}
+
+/// Treat a bare YYYY-MM-DD upper bound as the end of that day
+pub(crate) fn inclusive_end_of_day(date: &str) -> String {
+    if date.len() == 10 {
+        format!("{} 23:59:59", date)
+    } else {
+        date.to_string()
+    }
+}
+
+/// Translate user input into an FTS5 MATCH expression.
+///
+/// Every term is quoted so punctuation can't produce syntax errors. "Quoted phrases",
+/// trailing-* prefix terms and the AND/OR/NOT operators (upper case) are kept; dangling
+/// operators are dropped. Returns None if there is nothing to search for.
+pub fn build_fts_query(input: &str) -> Option<String> {
+    enum Token {
+        Term(String, bool), // text, is_prefix
+        Phrase(String),
+        Op(&'static str),
+    }
+
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let phrase: String = chars.by_ref().take_while(|&c| c != '"').collect();
+            if !phrase.trim().is_empty() {
+                tokens.push(Token::Phrase(phrase.trim().to_string()));
+            }
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || c == '(' || c == ')' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            match word.as_str() {
+                "AND" => tokens.push(Token::Op("AND")),
+                "OR" => tokens.push(Token::Op("OR")),
+                "NOT" => tokens.push(Token::Op("NOT")),
+                _ => {
+                    let is_prefix = word.ends_with('*');
+                    let term = word.trim_end_matches('*').to_string();
+                    if !term.is_empty() {
+                        tokens.push(Token::Term(term, is_prefix));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut parts: Vec<String> = Vec::new();
+    let mut pending_op: Option<&'static str> = None;
+
+    for token in tokens {
+        match token {
+            Token::Op(op) => {
+                // Operators need a left operand; a later operator replaces an earlier one
+                if !parts.is_empty() {
+                    pending_op = Some(op);
+                }
+            }
+            Token::Term(term, is_prefix) => {
+                if let Some(op) = pending_op.take() {
+                    parts.push(op.to_string());
+                }
+                let quoted = format!("\"{}\"", term.replace('"', "\"\""));
+                parts.push(if is_prefix { format!("{}*", quoted) } else { quoted });
+            }
+            Token::Phrase(phrase) => {
+                if let Some(op) = pending_op.take() {
+                    parts.push(op.to_string());
+                }
+                parts.push(format!("\"{}\"", phrase.replace('"', "\"\"")));
+            }
+        }
+    }
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[test]
+    fn test_build_fts_query_quotes_terms() {
+        assert_eq!(
+            build_fts_query("budget review").as_deref(),
+            Some("\"budget\" \"review\"")
+        );
+        assert_eq!(build_fts_query("c++ o'neil").as_deref(), Some("\"c++\" \"o'neil\""));
+    }
+
+    #[test]
+    fn test_build_fts_query_phrases_prefixes_and_operators() {
+        assert_eq!(
+            build_fts_query("\"action items\" OR deadl* NOT draft").as_deref(),
+            Some("\"action items\" OR \"deadl\"* NOT \"draft\"")
+        );
+    }
+
+    #[test]
+    fn test_build_fts_query_drops_dangling_operators() {
+        assert_eq!(build_fts_query("NOT budget AND").as_deref(), Some("\"budget\""));
+        assert_eq!(build_fts_query("  ()  \"\"  OR ").as_deref(), None);
+        assert_eq!(build_fts_query("and or").as_deref(), Some("\"and\" \"or\""));
+    }
+
+    #[test]
+    fn test_inclusive_end_of_day() {
+        assert_eq!(inclusive_end_of_day("2025-10-20"), "2025-10-20 23:59:59");
+        assert_eq!(inclusive_end_of_day("2025-10-20T08:00:00Z"), "2025-10-20T08:00:00Z");
+    }
+
+    #[tokio::test]
+    async fn test_search_survives_deletes_and_vacuum() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for i in 0..20 {
+            sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, datetime('now'), datetime('now'))")
+                .bind(format!("m{}", i))
+                .bind(format!("Meeting {}", i))
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query("INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES (?, ?, ?, '00:00')")
+                .bind(format!("t{}", i))
+                .bind(format!("m{}", i))
+                .bind(format!("budget item {}", i))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        // Deleting rows leaves gaps that VACUUM is free to renumber
+        sqlx::query("DELETE FROM meetings WHERE CAST(substr(id, 2) AS INTEGER) % 2 = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM transcripts WHERE CAST(substr(id, 2) AS INTEGER) % 2 = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("VACUUM").execute(&pool).await.unwrap();
+
+        let page = SearchRepository::search_meetings(&pool, "budget", &SearchFilters::default(), 100, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 10);
+        for result in &page.results {
+            assert_eq!(result.matches.len(), 1);
+            let matched = &result.matches[0];
+            assert_eq!(matched.source_id, result.meeting_id.replacen('m', "t", 1));
+            let n = &result.meeting_id[1..];
+            assert_eq!(matched.snippet, format!("<mark>budget</mark> item {}", n));
+        }
+    }
+}
@@ -23,7 +23,7 @@ pub struct SaveTranscriptConfigRequest {
 
 pub struct SettingsRepository;
 
-// Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
+// Transcript providers: localWhisper, parakeet, deepgram, elevenLabs, groq, openai, openaiCompatible
 // Summary providers: openai, claude, ollama, groq, added openrouter
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
@@ -158,6 +158,26 @@ impl SettingsRepository {
         Ok(())
     }
 
+    /// Save the server URL used by the `openaiCompatible` transcript provider
+    pub async fn save_transcript_endpoint(
+        pool: &SqlitePool,
+        endpoint: &str,
+    ) -> std::result::Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model, transcriptionEndpoint)
+            VALUES ('1', 'localWhisper', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                transcriptionEndpoint = $1
+            "#,
+        )
+        .bind(endpoint)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn save_transcript_api_key(
         pool: &SqlitePool,
         provider: &str,
@@ -170,6 +190,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "openaiCompatible" => "whisperApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -202,6 +223,7 @@ impl SettingsRepository {
             "elevenLabs" => "elevenLabsApiKey",
             "groq" => "groqApiKey",
             "openai" => "openaiApiKey",
+            "openaiCompatible" => "whisperApiKey",
             _ => {
                 return Err(sqlx::Error::Protocol(
                     format!("Invalid provider: {}", provider).into(),
@@ -0,0 +1,267 @@
+use crate::database::models::SpeakerModel;
+use crate::diarization::registry::{
+    embedding_from_bytes, embedding_to_bytes, merge_embeddings, SpeakerProfile,
+};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::{error, info};
+
+pub struct SpeakersRepository;
+
+impl SpeakersRepository {
+    pub async fn list_speakers(pool: &SqlitePool) -> Result<Vec<SpeakerModel>, SqlxError> {
+        sqlx::query_as::<_, SpeakerModel>("SELECT * FROM speakers ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_speaker(
+        pool: &SqlitePool,
+        speaker_id: i64,
+    ) -> Result<Option<SpeakerModel>, SqlxError> {
+        sqlx::query_as::<_, SpeakerModel>("SELECT * FROM speakers WHERE id = ?")
+            .bind(speaker_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn find_speaker_by_name(
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Option<SpeakerModel>, SqlxError> {
+        sqlx::query_as::<_, SpeakerModel>("SELECT * FROM speakers WHERE name = ? COLLATE NOCASE")
+            .bind(name.trim())
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Loads all registered speakers as voice profiles for the diarization engine
+    pub async fn load_profiles(pool: &SqlitePool) -> Result<Vec<SpeakerProfile>, SqlxError> {
+        let speakers = Self::list_speakers(pool).await?;
+        Ok(speakers
+            .into_iter()
+            .map(|s| SpeakerProfile {
+                id: s.id,
+                name: s.name,
+                embedding: embedding_from_bytes(&s.embedding),
+                sample_count: s.sample_count,
+            })
+            .collect())
+    }
+
+    /// Enrolls a voice sample under `name`.
+    /// Creates the speaker if the name is new, otherwise folds the sample into the existing profile.
+    pub async fn enroll_speaker(
+        pool: &SqlitePool,
+        name: &str,
+        embedding: &[f32],
+        sample_count: i64,
+    ) -> Result<i64, SqlxError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(SqlxError::Protocol("speaker name cannot be empty".to_string()));
+        }
+
+        let mut transaction = pool.begin().await?;
+        let now = Utc::now();
+
+        let existing: Option<SpeakerModel> =
+            sqlx::query_as("SELECT * FROM speakers WHERE name = ? COLLATE NOCASE")
+                .bind(name)
+                .fetch_optional(&mut *transaction)
+                .await?;
+
+        let speaker_id = match existing {
+            Some(speaker) => {
+                let merged = merge_embeddings(
+                    &embedding_from_bytes(&speaker.embedding),
+                    speaker.sample_count,
+                    embedding,
+                    sample_count,
+                );
+                sqlx::query(
+                    "UPDATE speakers SET embedding = ?, sample_count = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(embedding_to_bytes(&merged))
+                .bind(speaker.sample_count + sample_count)
+                .bind(now)
+                .bind(speaker.id)
+                .execute(&mut *transaction)
+                .await?;
+                info!("Added {} voice samples to speaker '{}'", sample_count, speaker.name);
+                speaker.id
+            }
+            None => {
+                let result = sqlx::query(
+                    "INSERT INTO speakers (name, embedding, sample_count, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(name)
+                .bind(embedding_to_bytes(embedding))
+                .bind(sample_count)
+                .bind(now)
+                .bind(now)
+                .execute(&mut *transaction)
+                .await?;
+                info!("Enrolled new speaker '{}'", name);
+                result.last_insert_rowid()
+            }
+        };
+
+        transaction.commit().await?;
+        Ok(speaker_id)
+    }
+
+    /// Links transcript segments of a meeting to a registered speaker.
+    /// With `speaker_label` set, every segment carrying that diarization label is linked;
+    /// otherwise only the given transcript row is.
+    pub async fn assign_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcript_id: &str,
+        speaker_label: Option<i64>,
+        speaker_id: i64,
+    ) -> Result<u64, SqlxError> {
+        let result = match speaker_label {
+            Some(label) => {
+                sqlx::query(
+                    "UPDATE transcripts SET speaker_profile_id = ? WHERE meeting_id = ? AND speaker_id = ?",
+                )
+                .bind(speaker_id)
+                .bind(meeting_id)
+                .bind(label)
+                .execute(pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    "UPDATE transcripts SET speaker_profile_id = ? WHERE meeting_id = ? AND id = ?",
+                )
+                .bind(speaker_id)
+                .bind(meeting_id)
+                .bind(transcript_id)
+                .execute(pool)
+                .await?
+            }
+        };
+        Ok(result.rows_affected())
+    }
+
+    pub async fn rename_speaker(
+        pool: &SqlitePool,
+        speaker_id: i64,
+        new_name: &str,
+    ) -> Result<bool, SqlxError> {
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(SqlxError::Protocol("speaker name cannot be empty".to_string()));
+        }
+
+        // Names are unique regardless of case; two profiles of one person are merged instead
+        let taken: Option<i64> =
+            sqlx::query_scalar("SELECT id FROM speakers WHERE name = ? COLLATE NOCASE AND id != ?")
+                .bind(new_name)
+                .bind(speaker_id)
+                .fetch_optional(pool)
+                .await?;
+        if taken.is_some() {
+            return Err(SqlxError::Protocol(format!(
+                "speaker '{}' already exists; merge the two speakers instead",
+                new_name
+            )));
+        }
+
+        let result = sqlx::query("UPDATE speakers SET name = ?, updated_at = ? WHERE id = ?")
+            .bind(new_name)
+            .bind(Utc::now())
+            .bind(speaker_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Merges `source_id` into `target_id`: voice embeddings are combined, transcript
+    /// segments are relinked, and the source speaker is removed.
+    pub async fn merge_speakers(
+        pool: &SqlitePool,
+        source_id: i64,
+        target_id: i64,
+    ) -> Result<bool, SqlxError> {
+        if source_id == target_id {
+            return Err(SqlxError::Protocol("cannot merge a speaker into itself".to_string()));
+        }
+
+        let mut transaction = pool.begin().await?;
+
+        let source: Option<SpeakerModel> = sqlx::query_as("SELECT * FROM speakers WHERE id = ?")
+            .bind(source_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        let target: Option<SpeakerModel> = sqlx::query_as("SELECT * FROM speakers WHERE id = ?")
+            .bind(target_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+
+        let (source, target) = match (source, target) {
+            (Some(s), Some(t)) => (s, t),
+            _ => {
+                error!("Speaker merge failed: {} or {} not found", source_id, target_id);
+                transaction.rollback().await?;
+                return Ok(false);
+            }
+        };
+
+        let merged = merge_embeddings(
+            &embedding_from_bytes(&target.embedding),
+            target.sample_count,
+            &embedding_from_bytes(&source.embedding),
+            source.sample_count,
+        );
+
+        sqlx::query(
+            "UPDATE speakers SET embedding = ?, sample_count = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(embedding_to_bytes(&merged))
+        .bind(target.sample_count + source.sample_count)
+        .bind(Utc::now())
+        .bind(target_id)
+        .execute(&mut *transaction)
+        .await?;
+
+        sqlx::query("UPDATE transcripts SET speaker_profile_id = ? WHERE speaker_profile_id = ?")
+            .bind(target_id)
+            .bind(source_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        sqlx::query("DELETE FROM speakers WHERE id = ?")
+            .bind(source_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!("Merged speaker '{}' into '{}'", source.name, target.name);
+        Ok(true)
+    }
+
+    pub async fn delete_speaker(pool: &SqlitePool, speaker_id: i64) -> Result<bool, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("UPDATE transcripts SET speaker_profile_id = NULL WHERE speaker_profile_id = ?")
+            .bind(speaker_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM speakers WHERE id = ?")
+            .bind(speaker_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            transaction.rollback().await?;
+            return Ok(false);
+        }
+
+        transaction.commit().await?;
+        Ok(true)
+    }
+}
@@ -100,7 +100,8 @@ impl SummaryProcessesRepository {
                 updated_at = excluded.updated_at,
                 start_time = excluded.start_time,
                 result = NULL,
-                error = NULL
+                error = NULL,
+                stale = 0
             "#
         )
         .bind(meeting_id)
@@ -126,7 +127,7 @@ impl SummaryProcessesRepository {
         sqlx::query(
             r#"
             UPDATE summary_processes
-            SET status = 'completed', result = ?, updated_at = ?, end_time = ?, chunk_count = ?, processing_time = ?, error = NULL
+            SET status = 'completed', result = ?, updated_at = ?, end_time = ?, chunk_count = ?, processing_time = ?, error = NULL, stale = 0
             WHERE meeting_id = ?
             "#
         )
@@ -141,6 +142,71 @@ impl SummaryProcessesRepository {
         Ok(())
     }
 
+    /// Stores an in-progress result so a crash mid-summary keeps what was generated so far.
+    /// Only applies while the process is still running; a finished job is never overwritten.
+    pub async fn update_process_partial(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        partial: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let result_str = serde_json::to_string(partial)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'processing', result = ?, updated_at = ?
+            WHERE meeting_id = ? AND status IN ('PENDING', 'processing')
+            "#,
+        )
+        .bind(result_str)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks a running process as cancelled, keeping any partial result
+    pub async fn update_process_cancelled(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            r#"
+            UPDATE summary_processes
+            SET status = 'cancelled', error = 'Cancelled by user', updated_at = ?, end_time = ?
+            WHERE meeting_id = ? AND status IN ('PENDING', 'processing')
+            "#,
+        )
+        .bind(now)
+        .bind(now)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replaces the process metadata JSON (chunk checkpoints live here)
+    pub async fn update_process_metadata(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        metadata: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let metadata_str = serde_json::to_string(metadata)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize metadata: {}", e)))?;
+
+        sqlx::query("UPDATE summary_processes SET metadata = ?, updated_at = ? WHERE meeting_id = ?")
+            .bind(metadata_str)
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn update_process_failed(
         pool: &SqlitePool,
         meeting_id: &str,
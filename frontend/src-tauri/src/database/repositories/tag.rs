@@ -0,0 +1,187 @@
+use crate::database::models::{MeetingTagRow, TagModel};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, Sqlite, SqlitePool, Transaction};
+use tracing::info;
+
+pub struct TagsRepository;
+
+/// Normalizes user-supplied names: trimmed, non-empty, de-duplicated case-insensitively
+pub fn clean_names(names: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for name in names {
+        let name = name.trim();
+        if name.is_empty() || cleaned.iter().any(|n| n.to_lowercase() == name.to_lowercase()) {
+            continue;
+        }
+        cleaned.push(name.to_string());
+    }
+    cleaned
+}
+
+impl TagsRepository {
+    pub async fn list_tags(pool: &SqlitePool) -> Result<Vec<TagModel>, SqlxError> {
+        sqlx::query_as::<_, TagModel>("SELECT * FROM tags ORDER BY name COLLATE NOCASE")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Creates a tag, or renames/recolors tag `id` when given. Returns the tag's id.
+    pub async fn save_tag(
+        pool: &SqlitePool,
+        id: Option<i64>,
+        name: &str,
+        color: Option<&str>,
+    ) -> Result<i64, SqlxError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(SqlxError::Protocol("tag name cannot be empty".to_string()));
+        }
+
+        match id {
+            Some(id) => {
+                // Names are unique regardless of case; renaming a tag's own case is fine
+                let taken: Option<i64> =
+                    sqlx::query_scalar("SELECT id FROM tags WHERE name = ? COLLATE NOCASE AND id != ?")
+                        .bind(name)
+                        .bind(id)
+                        .fetch_optional(pool)
+                        .await?;
+                if taken.is_some() {
+                    return Err(SqlxError::Protocol(format!("tag '{}' already exists", name)));
+                }
+
+                let result = sqlx::query("UPDATE tags SET name = ?, color = ? WHERE id = ?")
+                    .bind(name)
+                    .bind(color)
+                    .bind(id)
+                    .execute(pool)
+                    .await?;
+                if result.rows_affected() == 0 {
+                    return Err(SqlxError::RowNotFound);
+                }
+                Ok(id)
+            }
+            None => {
+                let mut transaction = pool.begin().await?;
+                let id = Self::find_or_create(&mut transaction, name).await?;
+                sqlx::query("UPDATE tags SET color = ? WHERE id = ?")
+                    .bind(color)
+                    .bind(id)
+                    .execute(&mut *transaction)
+                    .await?;
+                transaction.commit().await?;
+                Ok(id)
+            }
+        }
+    }
+
+    async fn find_or_create(
+        transaction: &mut Transaction<'_, Sqlite>,
+        name: &str,
+    ) -> Result<i64, SqlxError> {
+        let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM tags WHERE name = ? COLLATE NOCASE")
+            .bind(name)
+            .fetch_optional(&mut **transaction)
+            .await?;
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+
+        let result = sqlx::query("INSERT INTO tags (name, created_at) VALUES (?, ?)")
+            .bind(name)
+            .bind(Utc::now())
+            .execute(&mut **transaction)
+            .await?;
+        info!("Created tag '{}'", name);
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Deletes a tag and removes it from every meeting
+    pub async fn delete_tag(pool: &SqlitePool, id: i64) -> Result<bool, SqlxError> {
+        let mut transaction = pool.begin().await?;
+        sqlx::query("DELETE FROM meeting_tags WHERE tag_id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        let result = sqlx::query("DELETE FROM tags WHERE id = ?")
+            .bind(id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replaces a meeting's tags with `names`, creating tags that don't exist yet
+    pub async fn set_meeting_tags(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        names: &[String],
+    ) -> Result<Vec<TagModel>, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        let meeting_exists = sqlx::query("SELECT 1 FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(&mut *transaction)
+            .await?
+            .is_some();
+        if !meeting_exists {
+            transaction.rollback().await?;
+            return Err(SqlxError::RowNotFound);
+        }
+
+        sqlx::query("DELETE FROM meeting_tags WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        for name in clean_names(names) {
+            let tag_id = Self::find_or_create(&mut transaction, &name).await?;
+            sqlx::query("INSERT OR IGNORE INTO meeting_tags (meeting_id, tag_id) VALUES (?, ?)")
+                .bind(meeting_id)
+                .bind(tag_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(Self::tags_for_meetings(pool, &[meeting_id.to_string()])
+            .await?
+            .into_iter()
+            .map(|row| row.tag)
+            .collect())
+    }
+
+    /// Tags of the given meetings, ordered by name
+    pub async fn tags_for_meetings(
+        pool: &SqlitePool,
+        meeting_ids: &[String],
+    ) -> Result<Vec<MeetingTagRow>, SqlxError> {
+        let ids = serde_json::to_string(meeting_ids)
+            .map_err(|e| SqlxError::Protocol(e.to_string()))?;
+        sqlx::query_as::<_, MeetingTagRow>(
+            "SELECT mt.meeting_id, t.* FROM meeting_tags mt JOIN tags t ON t.id = mt.tag_id
+             WHERE mt.meeting_id IN (SELECT value FROM json_each(?))
+             ORDER BY t.name COLLATE NOCASE",
+        )
+        .bind(ids)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_names() {
+        let names = vec![
+            " Roadmap ".to_string(),
+            "roadmap".to_string(),
+            "".to_string(),
+            "Hiring".to_string(),
+            "Über".to_string(),
+            "über".to_string(),
+        ];
+        assert_eq!(clean_names(&names), vec!["Roadmap", "Hiring", "Über"]);
+    }
+}
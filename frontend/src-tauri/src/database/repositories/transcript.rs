@@ -1,6 +1,9 @@
-use crate::api::{TranscriptSearchResult, TranscriptSegment};
-use chrono::Utc;
-use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use crate::api::{SearchFilters, TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::{Transcript, TranscriptVersion};
+use crate::database::repositories::meeting::delete_meeting_with_transaction;
+use crate::database::repositories::search::{SearchRepository, HIGHLIGHT_END, HIGHLIGHT_START};
+use chrono::{DateTime, Utc};
+use sqlx::{Connection, Error as SqlxError, Sqlite, SqlitePool, Transaction};
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -17,20 +20,50 @@ impl TranscriptsRepository {
         folder_path: Option<String>,
     ) -> Result<String, SqlxError> {
         let meeting_id = format!("meeting-{}", Uuid::new_v4());
+        Self::save_transcript_with_id(
+            pool,
+            &meeting_id,
+            meeting_title,
+            Utc::now(),
+            transcripts,
+            folder_path,
+            false,
+        )
+        .await?;
+        Ok(meeting_id)
+    }
 
+    /// Saves a meeting under a given id and creation time (used when importing archives).
+    /// With `replace_existing`, a meeting with that id and all its data is deleted in the
+    /// same transaction, so it survives if the save fails; otherwise an existing id fails.
+    pub async fn save_transcript_with_id(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        meeting_title: &str,
+        created_at: DateTime<Utc>,
+        transcripts: &[TranscriptSegment],
+        folder_path: Option<String>,
+        replace_existing: bool,
+    ) -> Result<(), SqlxError> {
         let mut conn = pool.acquire().await?;
         let mut transaction = conn.begin().await?;
 
-        let now = Utc::now();
+        if replace_existing {
+            if let Err(e) = delete_meeting_with_transaction(&mut transaction, meeting_id).await {
+                error!("Failed to replace meeting {}: {}", meeting_id, e);
+                transaction.rollback().await?;
+                return Err(e);
+            }
+        }
 
         // 1. Create the new meeting
         let result = sqlx::query(
             "INSERT INTO meetings (id, title, created_at, updated_at, folder_path) VALUES (?, ?, ?, ?, ?)",
         )
-        .bind(&meeting_id)
+        .bind(meeting_id)
         .bind(meeting_title)
-        .bind(now)
-        .bind(now)
+        .bind(created_at)
+        .bind(created_at)
         .bind(&folder_path)
         .execute(&mut *transaction)
         .await;
@@ -43,20 +76,31 @@ impl TranscriptsRepository {
 
         info!("Successfully created meeting with id: {}", meeting_id);
 
-        // 2. Save each transcript segment with audio timing fields
+        // 2. Save each transcript segment with audio timing fields and speaker label
         for segment in transcripts {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_id, speaker_profile_id, words, language, language_probability, audio_source)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
-            .bind(&meeting_id)
+            .bind(meeting_id)
             .bind(&segment.text)
             .bind(&segment.timestamp)
             .bind(segment.audio_start_time)
             .bind(segment.audio_end_time)
             .bind(segment.duration)
+            .bind(segment.speaker_id)
+            .bind(segment.speaker_profile_id)
+            .bind(
+                segment
+                    .words
+                    .as_ref()
+                    .and_then(|w| serde_json::to_string(w).ok()),
+            )
+            .bind(&segment.language)
+            .bind(segment.language_probability)
+            .bind(&segment.audio_source)
             .execute(&mut *transaction)
             .await;
 
@@ -79,11 +123,239 @@ impl TranscriptsRepository {
         // Commit the transaction
         transaction.commit().await?;
 
-        Ok(meeting_id)
+        Ok(())
+    }
+
+    /// Returns all transcript segments of a meeting in playback order, with the
+    /// names of registered speakers.
+    pub async fn get_meeting_transcripts(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<Transcript>, SqlxError> {
+        sqlx::query_as::<_, Transcript>(
+            "SELECT t.*, s.name AS speaker_name FROM transcripts t
+             LEFT JOIN speakers s ON s.id = t.speaker_profile_id
+             WHERE t.meeting_id = ? ORDER BY t.audio_start_time, t.timestamp",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Rewrites the speaker labels of a meeting's segments in a single transaction.
+    /// Each entry is (transcript_id, speaker_id, speaker_profile_id).
+    pub async fn update_speaker_labels(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        labels: &[(String, Option<i64>, Option<i64>)],
+    ) -> Result<u64, SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let mut updated = 0;
+
+        for (transcript_id, speaker_id, speaker_profile_id) in labels {
+            let result = sqlx::query(
+                "UPDATE transcripts SET speaker_id = ?, speaker_profile_id = ? WHERE id = ? AND meeting_id = ?",
+            )
+            .bind(speaker_id)
+            .bind(speaker_profile_id)
+            .bind(transcript_id)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+            updated += result.rows_affected();
+        }
+
+        transaction.commit().await?;
+        info!("Updated speaker labels on {} segments of meeting {}", updated, meeting_id);
+        Ok(updated)
+    }
+
+    /// Snapshots a meeting's current segments into `transcript_versions` and deletes
+    /// them, as the first step of replacing the whole transcript. Returns the version id.
+    async fn archive_meeting_transcripts(
+        transaction: &mut Transaction<'_, Sqlite>,
+        meeting_id: &str,
+        reason: &str,
+        model: Option<&str>,
+    ) -> Result<i64, SqlxError> {
+        let current = sqlx::query_as::<_, Transcript>(
+            "SELECT * FROM transcripts WHERE meeting_id = ? ORDER BY audio_start_time, timestamp",
+        )
+        .bind(meeting_id)
+        .fetch_all(&mut **transaction)
+        .await?;
+        let snapshot =
+            serde_json::to_string(&current).map_err(|e| SqlxError::Protocol(e.to_string()))?;
+
+        let version_id: i64 = sqlx::query_scalar(
+            "INSERT INTO transcript_versions (meeting_id, reason, model, segments, segment_count, created_at)
+             VALUES (?, ?, ?, ?, ?, ?) RETURNING id",
+        )
+        .bind(meeting_id)
+        .bind(reason)
+        .bind(model)
+        .bind(&snapshot)
+        .bind(current.len() as i64)
+        .bind(Utc::now())
+        .fetch_one(&mut **transaction)
+        .await?;
+
+        sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut **transaction)
+            .await?;
+
+        Ok(version_id)
+    }
+
+    /// Atomically replaces all of a meeting's segments, keeping the previous ones as a
+    /// version that `restore_transcript_version` can roll back to. Returns that version's id.
+    pub async fn replace_meeting_transcripts(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcripts: &[TranscriptSegment],
+        reason: &str,
+        model: Option<&str>,
+    ) -> Result<i64, SqlxError> {
+        let mut transaction = pool.begin().await?;
+        let version_id =
+            Self::archive_meeting_transcripts(&mut transaction, meeting_id, reason, model).await?;
+
+        for segment in transcripts {
+            sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_id, speaker_profile_id, words, language, language_probability, audio_source)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(format!("transcript-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(&segment.text)
+            .bind(&segment.timestamp)
+            .bind(segment.audio_start_time)
+            .bind(segment.audio_end_time)
+            .bind(segment.duration)
+            .bind(segment.speaker_id)
+            .bind(segment.speaker_profile_id)
+            .bind(
+                segment
+                    .words
+                    .as_ref()
+                    .and_then(|w| serde_json::to_string(w).ok()),
+            )
+            .bind(&segment.language)
+            .bind(segment.language_probability)
+            .bind(&segment.audio_source)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        // The summary no longer describes the transcript
+        sqlx::query("UPDATE summary_processes SET stale = 1 WHERE meeting_id = ? AND result IS NOT NULL")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Replaced transcript of meeting {} with {} segments (previous kept as version {})",
+            meeting_id,
+            transcripts.len(),
+            version_id
+        );
+        Ok(version_id)
+    }
+
+    /// Lists the saved transcript versions of a meeting, newest first.
+    pub async fn list_transcript_versions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptVersion>, SqlxError> {
+        sqlx::query_as::<_, TranscriptVersion>(
+            "SELECT id, meeting_id, reason, model, segment_count, created_at
+             FROM transcript_versions WHERE meeting_id = ? ORDER BY id DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Restores a saved transcript version. The transcript being replaced is itself
+    /// kept as a 'rollback' version, so a restore can be undone too.
+    /// Returns the restored segments, or None if the version doesn't exist.
+    pub async fn restore_transcript_version(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        version_id: i64,
+    ) -> Result<Option<Vec<Transcript>>, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        let snapshot: Option<String> = sqlx::query_scalar(
+            "SELECT segments FROM transcript_versions WHERE id = ? AND meeting_id = ?",
+        )
+        .bind(version_id)
+        .bind(meeting_id)
+        .fetch_optional(&mut *transaction)
+        .await?;
+        let Some(snapshot) = snapshot else {
+            return Ok(None);
+        };
+        let segments: Vec<Transcript> =
+            serde_json::from_str(&snapshot).map_err(|e| SqlxError::Protocol(e.to_string()))?;
+
+        Self::archive_meeting_transcripts(&mut transaction, meeting_id, "rollback", None).await?;
+
+        for segment in &segments {
+            sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, summary, action_items, key_points, audio_start_time, audio_end_time, duration, speaker_id, speaker_profile_id, words, language, language_probability, audio_source)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&segment.id)
+            .bind(meeting_id)
+            .bind(&segment.transcript)
+            .bind(&segment.timestamp)
+            .bind(&segment.summary)
+            .bind(&segment.action_items)
+            .bind(&segment.key_points)
+            .bind(segment.audio_start_time)
+            .bind(segment.audio_end_time)
+            .bind(segment.duration)
+            .bind(segment.speaker_id)
+            .bind(segment.speaker_profile_id)
+            .bind(&segment.words)
+            .bind(&segment.language)
+            .bind(segment.language_probability)
+            .bind(&segment.audio_source)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        // The summary no longer describes the transcript
+        sqlx::query("UPDATE summary_processes SET stale = 1 WHERE meeting_id = ? AND result IS NOT NULL")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Restored transcript version {} of meeting {} ({} segments)",
+            version_id,
+            meeting_id,
+            segments.len()
+        );
+        Ok(Some(segments))
     }
 
     /// Searches for a query string within the transcripts.
-    /// It returns a list of matching transcripts with context.
+    /// Returns one result per matching meeting, best match first.
     pub async fn search_transcripts(
         pool: &SqlitePool,
         query: &str,
@@ -92,6 +364,39 @@ impl TranscriptsRepository {
             return Ok(Vec::new());
         }
 
+        match SearchRepository::search_meetings(pool, query, &SearchFilters::default(), 100, 0).await
+        {
+            Ok(page) => Ok(page
+                .results
+                .into_iter()
+                .map(|result| {
+                    let best = result.matches.first();
+                    let match_context = best
+                        .map(|m| m.snippet.replace(HIGHLIGHT_START, "").replace(HIGHLIGHT_END, ""))
+                        .unwrap_or_default();
+                    let timestamp = best
+                        .and_then(|m| m.timestamp.clone())
+                        .unwrap_or(result.created_at);
+                    TranscriptSearchResult {
+                        id: result.meeting_id,
+                        title: result.title,
+                        match_context,
+                        timestamp,
+                    }
+                })
+                .collect()),
+            Err(e) => {
+                // Fall back to a plain substring scan if the FTS index is unavailable
+                error!("Full-text search failed, falling back to substring search: {}", e);
+                Self::search_transcripts_like(pool, query).await
+            }
+        }
+    }
+
+    async fn search_transcripts_like(
+        pool: &SqlitePool,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, SqlxError> {
         let search_query = format!("%{}%", query.to_lowercase());
 
         let rows = sqlx::query_as::<_, (String, String, String, String)>(
@@ -121,26 +426,131 @@ impl TranscriptsRepository {
     }
 
     /// Helper function to extract a snippet of text around the first match of a query.
+    /// Works on characters rather than bytes so multi-byte text is never split.
     fn get_match_context(transcript: &str, query: &str) -> String {
-        let transcript_lower = transcript.to_lowercase();
-        let query_lower = query.to_lowercase();
+        const CONTEXT_CHARS: usize = 100;
+
+        // Lowercase char-by-char so indices stay aligned with the original text
+        let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+        let chars: Vec<char> = transcript.chars().collect();
+        let haystack: Vec<char> = chars.iter().copied().map(fold).collect();
+        let needle: Vec<char> = query.chars().map(fold).collect();
 
-        match transcript_lower.find(&query_lower) {
+        let match_index = if needle.is_empty() || needle.len() > haystack.len() {
+            None
+        } else {
+            haystack.windows(needle.len()).position(|w| w == needle.as_slice())
+        };
+
+        match match_index {
             Some(match_index) => {
-                let start_index = match_index.saturating_sub(100);
-                let end_index = (match_index + query.len() + 100).min(transcript.len());
+                let start_index = match_index.saturating_sub(CONTEXT_CHARS);
+                let end_index = (match_index + needle.len() + CONTEXT_CHARS).min(chars.len());
 
                 let mut context = String::new();
                 if start_index > 0 {
                     context.push_str("...");
                 }
-                context.push_str(&transcript[start_index..end_index]);
-                if end_index < transcript.len() {
+                context.extend(&chars[start_index..end_index]);
+                if end_index < chars.len() {
                     context.push_str("...");
                 }
                 context
             }
-            None => transcript.chars().take(200).collect(), // Fallback to the start of the transcript
+            None => chars.iter().take(200).collect(), // Fallback to the start of the transcript
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    #[test]
+    fn test_get_match_context_multibyte() {
+        let transcript = format!("{}Größe des Budgets{}", "ü".repeat(150), "é".repeat(150));
+        let context = TranscriptsRepository::get_match_context(&transcript, "größe");
+        assert!(context.starts_with("..."));
+        assert!(context.ends_with("..."));
+        assert!(context.contains("Größe des Budgets"));
+    }
+
+    #[test]
+    fn test_get_match_context_short_text() {
+        assert_eq!(
+            TranscriptsRepository::get_match_context("日本語のテキスト", "テキ"),
+            "日本語のテキスト"
+        );
+    }
+
+    fn segment(text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            id: String::new(),
+            text: text.to_string(),
+            timestamp: "[00:00]".to_string(),
+            audio_start_time: Some(0.0),
+            audio_end_time: Some(2.0),
+            duration: Some(2.0),
+            speaker_id: None,
+            speaker_profile_id: None,
+            words: None,
+            language: None,
+            language_probability: None,
+            audio_source: None,
+        }
+    }
+
+    async fn is_stale(pool: &SqlitePool, meeting_id: &str) -> bool {
+        sqlx::query_scalar("SELECT stale FROM summary_processes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_restore_mark_summary_stale() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let meeting_id = TranscriptsRepository::save_transcript(&pool, "Standup", &[segment("first take")], None)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result)
+             VALUES (?, 'completed', '2025-01-01', '2025-01-01', '{}')",
+        )
+        .bind(&meeting_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(!is_stale(&pool, &meeting_id).await);
+
+        let version_id = TranscriptsRepository::replace_meeting_transcripts(
+            &pool,
+            &meeting_id,
+            &[segment("second take")],
+            "retranscribe",
+            Some("large-v3"),
+        )
+        .await
+        .unwrap();
+        assert!(is_stale(&pool, &meeting_id).await);
+
+        sqlx::query("UPDATE summary_processes SET stale = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let restored = TranscriptsRepository::restore_transcript_version(&pool, &meeting_id, version_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored[0].transcript, "first take");
+        assert!(is_stale(&pool, &meeting_id).await);
+    }
+}
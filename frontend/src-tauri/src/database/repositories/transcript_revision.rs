@@ -0,0 +1,156 @@
+use crate::database::models::{Transcript, TranscriptRevisionModel};
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct TranscriptRevisionsRepository;
+
+/// Whether two rows of the same segment carry the same content (ignoring joined columns)
+fn same_segment(a: &Transcript, b: &Transcript) -> bool {
+    a.id == b.id
+        && a.transcript == b.transcript
+        && a.audio_start_time == b.audio_start_time
+        && a.audio_end_time == b.audio_end_time
+        && a.speaker_id == b.speaker_id
+        && a.speaker_profile_id == b.speaker_profile_id
+        && a.words == b.words
+}
+
+impl TranscriptRevisionsRepository {
+    pub async fn list_revisions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptRevisionModel>, SqlxError> {
+        sqlx::query_as::<_, TranscriptRevisionModel>(
+            "SELECT * FROM transcript_revisions WHERE meeting_id = ? ORDER BY id DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_revision(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        revision_id: i64,
+    ) -> Result<Option<TranscriptRevisionModel>, SqlxError> {
+        sqlx::query_as::<_, TranscriptRevisionModel>(
+            "SELECT * FROM transcript_revisions WHERE id = ? AND meeting_id = ?",
+        )
+        .bind(revision_id)
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Replaces the `before` rows of a meeting with the `after` rows and records the
+    /// change as a revision, all in one transaction. The meeting's summary is flagged
+    /// stale.
+    ///
+    /// Fails without changing anything if a `before` row no longer matches the database,
+    /// i.e. another edit touched the segment since it was read.
+    pub async fn apply_revision(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        operation: &str,
+        author: Option<&str>,
+        before: &[Transcript],
+        after: &[Transcript],
+        reverts_id: Option<i64>,
+    ) -> Result<TranscriptRevisionModel, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        for expected in before {
+            let current: Option<Transcript> =
+                sqlx::query_as("SELECT * FROM transcripts WHERE id = ? AND meeting_id = ?")
+                    .bind(&expected.id)
+                    .bind(meeting_id)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+            if !current.is_some_and(|current| same_segment(&current, expected)) {
+                transaction.rollback().await?;
+                return Err(SqlxError::Protocol(format!(
+                    "segment {} was changed by another edit",
+                    expected.id
+                )));
+            }
+            sqlx::query("DELETE FROM transcripts WHERE id = ?")
+                .bind(&expected.id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        for segment in after {
+            sqlx::query(
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, summary, action_items, key_points, audio_start_time, audio_end_time, duration, speaker_id, speaker_profile_id, words, language, language_probability, audio_source)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&segment.id)
+            .bind(meeting_id)
+            .bind(&segment.transcript)
+            .bind(&segment.timestamp)
+            .bind(&segment.summary)
+            .bind(&segment.action_items)
+            .bind(&segment.key_points)
+            .bind(segment.audio_start_time)
+            .bind(segment.audio_end_time)
+            .bind(segment.duration)
+            .bind(segment.speaker_id)
+            .bind(segment.speaker_profile_id)
+            .bind(&segment.words)
+            .bind(&segment.language)
+            .bind(segment.language_probability)
+            .bind(&segment.audio_source)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let to_json = |rows: &[Transcript]| {
+            serde_json::to_string(rows).map_err(|e| SqlxError::Protocol(e.to_string()))
+        };
+        let now = Utc::now();
+        let revision = sqlx::query_as::<_, TranscriptRevisionModel>(
+            "INSERT INTO transcript_revisions (meeting_id, operation, author, before_segments, after_segments, reverts_id, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(meeting_id)
+        .bind(operation)
+        .bind(author)
+        .bind(to_json(before)?)
+        .bind(to_json(after)?)
+        .bind(reverts_id)
+        .bind(now)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        if let Some(reverts_id) = reverts_id {
+            sqlx::query("UPDATE transcript_revisions SET reverted_by = ? WHERE id = ?")
+                .bind(revision.id)
+                .bind(reverts_id)
+                .execute(&mut *transaction)
+                .await?;
+        }
+
+        // The summary no longer describes the transcript
+        sqlx::query("UPDATE summary_processes SET stale = 1 WHERE meeting_id = ? AND result IS NOT NULL")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Recorded transcript revision {} ({}) for meeting {}: {} -> {} segments",
+            revision.id,
+            operation,
+            meeting_id,
+            before.len(),
+            after.len()
+        );
+        Ok(revision)
+    }
+}
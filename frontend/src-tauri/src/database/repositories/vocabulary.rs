@@ -0,0 +1,191 @@
+use crate::database::models::VocabularyTermModel;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+use tracing::info;
+
+pub struct VocabularyRepository;
+
+/// Normalizes a user-supplied alias list: trimmed, non-empty, de-duplicated case-insensitively
+fn clean_aliases(term: &str, aliases: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for alias in aliases {
+        let alias = alias.trim();
+        if alias.is_empty()
+            || alias.eq_ignore_ascii_case(term)
+            || cleaned.iter().any(|a| a.eq_ignore_ascii_case(alias))
+        {
+            continue;
+        }
+        cleaned.push(alias.to_string());
+    }
+    cleaned
+}
+
+impl VocabularyRepository {
+    /// Lists the terms of one scope: global terms when `meeting_id` is None,
+    /// otherwise only that meeting's terms
+    pub async fn list_terms(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<VocabularyTermModel>, SqlxError> {
+        sqlx::query_as::<_, VocabularyTermModel>(
+            "SELECT * FROM vocabulary_terms WHERE IFNULL(meeting_id, '') = IFNULL(?, '') ORDER BY term COLLATE NOCASE",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Loads the terms that apply to a transcription: all global terms plus the
+    /// meeting's own terms when `meeting_id` is given
+    pub async fn load_effective_terms(
+        pool: &SqlitePool,
+        meeting_id: Option<&str>,
+    ) -> Result<Vec<VocabularyTermModel>, SqlxError> {
+        sqlx::query_as::<_, VocabularyTermModel>(
+            "SELECT * FROM vocabulary_terms WHERE meeting_id IS NULL OR meeting_id = ? ORDER BY term COLLATE NOCASE",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_term(
+        pool: &SqlitePool,
+        id: i64,
+    ) -> Result<Option<VocabularyTermModel>, SqlxError> {
+        sqlx::query_as::<_, VocabularyTermModel>("SELECT * FROM vocabulary_terms WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Creates a term, or replaces the spelling and aliases of an existing one in the same scope
+    pub async fn save_term(
+        pool: &SqlitePool,
+        term: &str,
+        aliases: &[String],
+        meeting_id: Option<&str>,
+    ) -> Result<i64, SqlxError> {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(SqlxError::Protocol("vocabulary term cannot be empty".to_string()));
+        }
+        let aliases = serde_json::to_string(&clean_aliases(term, aliases))
+            .map_err(|e| SqlxError::Protocol(e.to_string()))?;
+        let now = Utc::now();
+
+        // Upsert on the scope index, so concurrent saves of one term can't race each other
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO vocabulary_terms (term, aliases, meeting_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(IFNULL(meeting_id, ''), term COLLATE NOCASE)
+             DO UPDATE SET term = excluded.term, aliases = excluded.aliases, updated_at = excluded.updated_at
+             RETURNING id",
+        )
+        .bind(term)
+        .bind(&aliases)
+        .bind(meeting_id)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        info!("Saved vocabulary term '{}'", term);
+        Ok(id)
+    }
+
+    /// Records `alias` as a misrecognition of `term`, creating the term if needed
+    pub async fn add_alias(
+        pool: &SqlitePool,
+        term: &str,
+        alias: &str,
+        meeting_id: Option<&str>,
+    ) -> Result<i64, SqlxError> {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err(SqlxError::Protocol("vocabulary term cannot be empty".to_string()));
+        }
+        let alias = clean_aliases(term, &[alias.to_string()]).pop();
+        let now = Utc::now();
+
+        // Append inside the upsert so concurrent corrections of one term can't drop each other's alias
+        let id: i64 = sqlx::query_scalar(
+            "INSERT INTO vocabulary_terms (term, aliases, meeting_id, created_at, updated_at)
+             VALUES (?1, CASE WHEN ?2 IS NULL THEN '[]' ELSE json_array(?2) END, ?3, ?4, ?4)
+             ON CONFLICT(IFNULL(meeting_id, ''), term COLLATE NOCASE)
+             DO UPDATE SET
+                 aliases = CASE
+                     WHEN ?2 IS NULL
+                         OR lower(vocabulary_terms.term) = lower(?2)
+                         OR EXISTS (SELECT 1 FROM json_each(vocabulary_terms.aliases) WHERE lower(value) = lower(?2))
+                     THEN vocabulary_terms.aliases
+                     ELSE json_insert(vocabulary_terms.aliases, '$[#]', ?2)
+                 END,
+                 updated_at = excluded.updated_at
+             RETURNING id",
+        )
+        .bind(term)
+        .bind(alias.as_deref())
+        .bind(meeting_id)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        info!("Added alias to vocabulary term '{}'", term);
+        Ok(id)
+    }
+
+    pub async fn delete_term(pool: &SqlitePool, id: i64) -> Result<bool, SqlxError> {
+        let result = sqlx::query("DELETE FROM vocabulary_terms WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_add_alias_keeps_every_alias() {
+        // A file database so the upserts really race on separate connections
+        let dir = tempfile::tempdir().unwrap();
+        let options = SqliteConnectOptions::new()
+            .filename(dir.path().join("vocabulary.sqlite"))
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let id = VocabularyRepository::save_term(&pool, "Kubernetes", &["Cooper Netties".to_string()], None)
+            .await
+            .unwrap();
+        let corrections = ["Cube Ernest", "Koo Bernetes", "cooper netties", "Cuber Nettis", "Kuber Nets"];
+        let handles: Vec<_> = corrections
+            .into_iter()
+            .enumerate()
+            .map(|(i, alias)| {
+                let pool = pool.clone();
+                let term = if i % 2 == 0 { "kubernetes" } else { "Kubernetes" };
+                tokio::spawn(async move { VocabularyRepository::add_alias(&pool, term, alias, None).await })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap().unwrap(), id);
+        }
+
+        let saved = VocabularyRepository::get_term(&pool, id).await.unwrap().unwrap();
+        let mut aliases: Vec<String> = serde_json::from_str(&saved.aliases).unwrap();
+        assert_eq!(aliases[0], "Cooper Netties");
+        aliases.sort();
+        assert_eq!(
+            aliases,
+            vec!["Cooper Netties", "Cube Ernest", "Cuber Nettis", "Koo Bernetes", "Kuber Nets"]
+        );
+        assert_eq!(saved.term, "Kubernetes");
+    }
+}
@@ -0,0 +1,304 @@
+// diarization/batch.rs
+//
+// Offline re-diarization of a finished recording. The live pass clusters greedily as
+// segments arrive and can never revisit a decision; this pass embeds every segment of
+// the saved audio and clusters them all at once.
+
+use crate::audio::decoder::decode_media_file_16k;
+use crate::audio::recording_saver::{meeting_track_path, read_transcripts_json, write_transcripts_json_file, AudioTrack};
+use crate::database::models::Transcript;
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::diarization::clustering::{agglomerative_cluster, AHC_MERGE_THRESHOLD};
+use crate::diarization::commands::get_or_init_diarization_engine;
+use crate::diarization::registry::{average_embeddings, best_match, REGISTRY_MATCH_THRESHOLD};
+use crate::state::AppState;
+use log::{info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Segments shorter than this don't yield a reliable embedding and inherit a neighbour's label
+const MIN_SEGMENT_SECONDS: f64 = 0.8;
+
+/// Upper bound on the automatically chosen speaker count
+const MAX_AUTO_SPEAKERS: usize = 10;
+
+/// Tolerance when matching transcripts.json entries to DB rows by start time
+const TIMESTAMP_MATCH_TOLERANCE: f64 = 0.05;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RediarizationResult {
+    pub meeting_id: String,
+    pub num_speakers: usize,
+    pub segments_updated: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RediarizationProgress {
+    meeting_id: String,
+    stage: String,
+    progress: u32,
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, stage: &str, progress: u32) {
+    let _ = app.emit(
+        "rediarization-progress",
+        RediarizationProgress {
+            meeting_id: meeting_id.to_string(),
+            stage: stage.to_string(),
+            progress,
+        },
+    );
+}
+
+/// Give each unembedded segment the label of the closest embedded segment in time
+fn fill_missing_labels(starts: &[f64], labels: &mut [Option<usize>]) {
+    let known: Vec<(f64, usize)> = starts
+        .iter()
+        .zip(labels.iter())
+        .filter_map(|(start, label)| label.map(|l| (*start, l)))
+        .collect();
+
+    for (start, label) in starts.iter().zip(labels.iter_mut()) {
+        if label.is_none() {
+            *label = known
+                .iter()
+                .min_by(|a, b| (a.0 - start).abs().total_cmp(&(b.0 - start).abs()))
+                .map(|(_, l)| *l);
+        }
+    }
+}
+
+/// Re-run speaker diarization over a saved meeting's audio and rewrite its speaker labels
+///
+/// `num_speakers` forces the number of speakers; otherwise it is chosen automatically.
+/// Labels are updated in the database and in the recording's transcripts.json.
+/// `track` diarizes a single stem of a multitrack recording; segments already attributed
+/// to the other source are then left alone.
+#[tauri::command]
+pub async fn api_rediarize_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    num_speakers: Option<usize>,
+    track: Option<AudioTrack>,
+) -> Result<RediarizationResult, String> {
+    let track = track.unwrap_or_default();
+    log_info!(
+        "api_rediarize_meeting called for {} (num_speakers: {:?}, track: {:?})",
+        meeting_id,
+        num_speakers,
+        track
+    );
+    rediarize_meeting(&app, state.db_manager.pool(), meeting_id, num_speakers, track).await
+}
+
+/// Recording folders finalized with "re-diarize after recording" enabled, waiting for
+/// their meeting to be saved to the database
+static QUEUED_FOLDERS: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Queue a re-diarization pass for a recording whose audio was just finalized.
+/// It runs once the meeting is saved (see `run_queued_rediarization`).
+pub fn queue_rediarization(folder: &Path) {
+    if let Ok(mut queued) = QUEUED_FOLDERS.lock() {
+        queued.insert(folder.to_path_buf());
+    }
+}
+
+/// Start the queued re-diarization pass for a newly saved meeting, if one is waiting
+/// on its recording folder. Runs in the background; progress is reported through the
+/// usual `rediarization-progress` events.
+pub fn run_queued_rediarization<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, folder: &str) {
+    let queued = QUEUED_FOLDERS
+        .lock()
+        .map(|mut queued| queued.remove(Path::new(folder)))
+        .unwrap_or(false);
+    if !queued {
+        return;
+    }
+
+    let app = app.clone();
+    let meeting_id = meeting_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let pool = app.state::<AppState>().db_manager.pool().clone();
+        log_info!("Running queued re-diarization for {}", meeting_id);
+        if let Err(e) = rediarize_meeting(&app, &pool, meeting_id.clone(), None, AudioTrack::default()).await {
+            log_warn!("Re-diarization after recording failed for {}: {}", meeting_id, e);
+            emit_progress(&app, &meeting_id, "failed", 100);
+        }
+    });
+}
+
+async fn rediarize_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    meeting_id: String,
+    num_speakers: Option<usize>,
+    track: AudioTrack,
+) -> Result<RediarizationResult, String> {
+    let engine = get_or_init_diarization_engine(app)
+        .await
+        .ok_or_else(|| "Diarization model is not available. Please download it first.".to_string())?;
+
+    let folder_path: Option<String> =
+        sqlx::query_scalar("SELECT folder_path FROM meetings WHERE id = ?")
+            .bind(&meeting_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let folder = PathBuf::from(
+        folder_path.ok_or_else(|| "Recording folder not available for this meeting".to_string())?,
+    );
+
+    let segments: Vec<Transcript> = TranscriptsRepository::get_meeting_transcripts(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcripts: {}", e))?
+        .into_iter()
+        .filter(|t| t.audio_start_time.is_some())
+        .filter(|t| match (track.audio_source(), t.audio_source.as_deref()) {
+            (Some(wanted), Some(source)) => wanted == source,
+            _ => true,
+        })
+        .collect();
+    if segments.is_empty() {
+        return Err("This meeting has no timed transcript segments to diarize".to_string());
+    }
+
+    let audio_path = meeting_track_path(&folder, track).map_err(|e| e.to_string())?;
+    emit_progress(app, &meeting_id, "decoding", 0);
+    let audio = tokio::task::spawn_blocking(move || decode_media_file_16k(&audio_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to decode meeting audio: {}", e))?;
+
+    // Embed every segment long enough to carry a voice print
+    let mut embedded_index = Vec::new();
+    let mut embeddings = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let start = segment.audio_start_time.unwrap_or(0.0);
+        let end = segment
+            .audio_end_time
+            .unwrap_or(start + segment.duration.unwrap_or(0.0));
+
+        if end - start >= MIN_SEGMENT_SECONDS {
+            match engine.extract_embedding(audio.slice_seconds(start, end)).await {
+                Ok(embedding) => {
+                    embedded_index.push(i);
+                    embeddings.push(embedding);
+                }
+                Err(e) => log_warn!("Failed to embed segment {}: {}", segment.id, e),
+            }
+        }
+
+        let progress = ((i + 1) * 80 / segments.len()) as u32;
+        emit_progress(app, &meeting_id, "embedding", progress);
+    }
+
+    if embeddings.is_empty() {
+        return Err("No segment had enough audio to extract a voice embedding".to_string());
+    }
+
+    emit_progress(app, &meeting_id, "clustering", 85);
+    // Clustering is quadratic in the segment count; keep it off the async runtime
+    let (embeddings, clustered) = tokio::task::spawn_blocking(move || {
+        let clustered = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, num_speakers, MAX_AUTO_SPEAKERS);
+        (embeddings, clustered)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+    let num_found = clustered.iter().copied().max().unwrap_or(0);
+
+    let mut labels: Vec<Option<usize>> = vec![None; segments.len()];
+    for (&i, &label) in embedded_index.iter().zip(&clustered) {
+        labels[i] = Some(label);
+    }
+    let starts: Vec<f64> = segments
+        .iter()
+        .map(|s| s.audio_start_time.unwrap_or(0.0))
+        .collect();
+    fill_missing_labels(&starts, &mut labels);
+
+    // Name clusters that match a registered voice
+    let profiles = SpeakersRepository::load_profiles(pool)
+        .await
+        .map_err(|e| format!("Failed to load speaker registry: {}", e))?;
+    let mut identities: HashMap<usize, (i64, String)> = HashMap::new();
+    for label in 1..=num_found {
+        let members: Vec<Vec<f32>> = embeddings
+            .iter()
+            .zip(&clustered)
+            .filter(|(_, l)| **l == label)
+            .map(|(e, _)| e.clone())
+            .collect();
+        if let Some(centroid) = average_embeddings(&members) {
+            if let Some(profile) = best_match(&profiles, &centroid, REGISTRY_MATCH_THRESHOLD) {
+                identities.insert(label, (profile.id, profile.name.clone()));
+            }
+        }
+    }
+
+    emit_progress(app, &meeting_id, "saving", 90);
+    let updates: Vec<(String, Option<i64>, Option<i64>)> = segments
+        .iter()
+        .zip(&labels)
+        .map(|(segment, label)| {
+            (
+                segment.id.clone(),
+                label.map(|l| l as i64),
+                label.and_then(|l| identities.get(&l).map(|(id, _)| *id)),
+            )
+        })
+        .collect();
+    let segments_updated = TranscriptsRepository::update_speaker_labels(pool, &meeting_id, &updates)
+        .await
+        .map_err(|e| format!("Failed to save speaker labels: {}", e))?;
+
+    // Keep the recording folder's transcripts.json in sync with the database
+    match read_transcripts_json(&folder) {
+        Ok(mut saved) => {
+            for entry in saved.iter_mut() {
+                if let (Some(wanted), Some(source)) = (track.audio_source(), entry.audio_source.as_deref()) {
+                    if wanted != source {
+                        continue;
+                    }
+                }
+                let closest = starts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, start)| (i, (start - entry.audio_start_time).abs()))
+                    .filter(|(_, diff)| *diff <= TIMESTAMP_MATCH_TOLERANCE)
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some((i, _)) = closest {
+                    entry.speaker_id = labels[i];
+                    let identity = labels[i].and_then(|l| identities.get(&l));
+                    entry.speaker_profile_id = identity.map(|(id, _)| *id);
+                    entry.speaker_name = identity.map(|(_, name)| name.clone());
+                }
+            }
+            if let Err(e) = write_transcripts_json_file(&folder, &saved) {
+                log_warn!("Failed to rewrite transcripts.json for {}: {}", meeting_id, e);
+            }
+        }
+        Err(e) => log_warn!("No transcripts.json to update for {}: {}", meeting_id, e),
+    }
+
+    emit_progress(app, &meeting_id, "complete", 100);
+    log_info!(
+        "Re-diarized meeting {}: {} speakers across {} segments",
+        meeting_id,
+        num_found,
+        segments_updated
+    );
+
+    Ok(RediarizationResult {
+        meeting_id,
+        num_speakers: num_found,
+        segments_updated,
+    })
+}
@@ -50,9 +50,14 @@ impl OnlineClustering {
         self.clusters.insert(id, embedding.to_vec());
         id
     }
+
+    /// Current centroid of a speaker cluster
+    pub fn centroid(&self, id: usize) -> Option<&[f32]> {
+        self.clusters.get(&id).map(|c| c.as_slice())
+    }
 }
 
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
     let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
     let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
@@ -62,3 +67,191 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
         dot / (norm_a * norm_b)
     }
 }
+
+/// Default average-linkage similarity below which clusters are kept apart.
+/// Matches the online threshold so both passes agree on what "same speaker" means.
+pub const AHC_MERGE_THRESHOLD: f32 = 0.5;
+
+/// Offline agglomerative clustering with average linkage over cosine similarity.
+///
+/// Clusters are merged while the most similar pair is above `threshold`, which picks
+/// the speaker count automatically. `num_speakers` forces an exact count instead, and
+/// `max_speakers` caps the automatic count. Returns 1-based labels, numbered in order
+/// of first appearance so the first speaker in the recording is always Speaker 1.
+///
+/// The full dendrogram is built with the nearest-neighbour chain algorithm, which is
+/// O(n²) overall, and then cut. Average linkage is reducible, so this yields the same
+/// clusters as repeatedly merging the globally most similar pair.
+pub fn agglomerative_cluster(
+    embeddings: &[Vec<f32>],
+    threshold: f32,
+    num_speakers: Option<usize>,
+    max_speakers: usize,
+) -> Vec<usize> {
+    let n = embeddings.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let target = num_speakers.map(|k| k.clamp(1, n));
+    let max_speakers = max_speakers.max(1);
+
+    // Merges sorted from most to least similar, then applied until the cut is reached
+    let mut merges = nn_chain_merges(embeddings);
+    merges.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut clusters = n;
+    for (a, b, similarity) in merges {
+        let keep_merging = match target {
+            Some(k) => clusters > k,
+            None => similarity >= threshold || clusters > max_speakers,
+        };
+        if !keep_merging {
+            break;
+        }
+        let (root_a, root_b) = (find_root(&mut parent, a), find_root(&mut parent, b));
+        parent[root_b] = root_a;
+        clusters -= 1;
+    }
+
+    let mut relabel: HashMap<usize, usize> = HashMap::new();
+    (0..n)
+        .map(|i| {
+            let root = find_root(&mut parent, i);
+            let next = relabel.len() + 1;
+            *relabel.entry(root).or_insert(next)
+        })
+        .collect()
+}
+
+/// All n-1 average-linkage merges as (item of one side, item of the other, similarity),
+/// in the order the nearest-neighbour chain finds them
+fn nn_chain_merges(embeddings: &[Vec<f32>]) -> Vec<(usize, usize, f32)> {
+    let n = embeddings.len();
+
+    // Pairwise similarity between live clusters, updated with the Lance-Williams rule.
+    // A merged cluster keeps the slot of one of its halves.
+    let mut sim = vec![0.0f32; n * n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let s = cosine_similarity(&embeddings[i], &embeddings[j]);
+            sim[i * n + j] = s;
+            sim[j * n + i] = s;
+        }
+    }
+
+    let mut size = vec![1usize; n];
+    let mut active = vec![true; n];
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+    let mut chain: Vec<usize> = Vec::with_capacity(n);
+
+    while merges.len() + 1 < n {
+        if chain.is_empty() {
+            chain.extend(active.iter().position(|&a| a));
+        }
+        let a = chain[chain.len() - 1];
+        let previous = chain.len().checked_sub(2).map(|i| chain[i]);
+
+        // Nearest neighbour of the chain's tip, preferring the previous link on ties
+        let mut nearest = previous;
+        let mut nearest_sim = previous.map_or(f32::NEG_INFINITY, |p| sim[a * n + p]);
+        for k in (0..n).filter(|&k| active[k] && k != a) {
+            if sim[a * n + k] > nearest_sim {
+                nearest_sim = sim[a * n + k];
+                nearest = Some(k);
+            }
+        }
+        let Some(b) = nearest else { break };
+
+        if Some(b) != previous {
+            chain.push(b);
+            continue;
+        }
+
+        // Reciprocal nearest neighbours: merge b into a
+        chain.truncate(chain.len() - 2);
+        merges.push((a, b, nearest_sim));
+        let (size_a, size_b) = (size[a] as f32, size[b] as f32);
+        for k in (0..n).filter(|&k| active[k] && k != a && k != b) {
+            let s = (sim[a * n + k] * size_a + sim[b * n + k] * size_b) / (size_a + size_b);
+            sim[a * n + k] = s;
+            sim[k * n + a] = s;
+        }
+        size[a] += size[b];
+        active[b] = false;
+    }
+
+    merges
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noisy(base: &[f32], i: usize) -> Vec<f32> {
+        base.iter()
+            .enumerate()
+            .map(|(d, x)| x + 0.05 * (((i * 7 + d * 3) % 5) as f32 - 2.0) / 2.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_agglomerative_finds_speaker_count() {
+        let a = [1.0, 0.0, 0.0, 0.2];
+        let b = [0.0, 1.0, 0.1, 0.0];
+        let embeddings = vec![noisy(&a, 0), noisy(&b, 1), noisy(&a, 2), noisy(&b, 3), noisy(&a, 4)];
+
+        let labels = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, None, 8);
+        assert_eq!(labels, vec![1, 2, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_agglomerative_single_speaker() {
+        let a = [0.3, 0.9, 0.1];
+        let embeddings: Vec<Vec<f32>> = (0..4).map(|i| noisy(&a, i)).collect();
+
+        let labels = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, None, 8);
+        assert!(labels.iter().all(|&l| l == 1));
+    }
+
+    #[test]
+    fn test_agglomerative_respects_forced_and_max_count() {
+        let embeddings = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+
+        let forced = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, Some(3), 8);
+        assert_eq!(forced, vec![1, 2, 3]);
+
+        let capped = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, None, 2);
+        assert_eq!(capped.iter().max(), Some(&2));
+    }
+
+    #[test]
+    fn test_agglomerative_matches_greedy_merging_on_larger_input() {
+        // Three voices, interleaved, with per-segment noise
+        let bases = [[1.0, 0.1, 0.0, 0.2], [0.0, 1.0, 0.2, 0.0], [0.1, 0.0, 1.0, 0.3]];
+        let embeddings: Vec<Vec<f32>> = (0..60).map(|i| noisy(&bases[(i * 7) % 3], i)).collect();
+
+        let labels = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, None, 8);
+        for (i, label) in labels.iter().enumerate() {
+            let first_of_voice = (0..60).find(|&j| (j * 7) % 3 == (i * 7) % 3).unwrap();
+            assert_eq!(*label, labels[first_of_voice]);
+        }
+        assert_eq!(labels.iter().max(), Some(&3));
+
+        let forced = agglomerative_cluster(&embeddings, AHC_MERGE_THRESHOLD, Some(2), 8);
+        assert_eq!(forced.iter().max(), Some(&2));
+    }
+}
@@ -1,4 +1,4 @@
-use tauri::{command, AppHandle, Emitter, Manager};
+use tauri::{command, AppHandle, Emitter, Manager, Runtime};
 use crate::diarization::DiarizationEngine;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -29,32 +29,56 @@ pub async fn diarization_init() -> Result<(), String> {
     Ok(())
 }
 
+/// Get the diarization engine with its model loaded, initializing it on first use.
+/// Returns None if the embedding model hasn't been downloaded yet.
+pub async fn get_or_init_diarization_engine<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Option<Arc<DiarizationEngine>> {
+    let model_path = app
+        .path()
+        .app_data_dir()
+        .ok()?
+        .join("models")
+        .join("diarization")
+        .join(MODEL_FILENAME);
+
+    if !model_path.exists() {
+        return None;
+    }
+
+    let mut guard = DIARIZATION_ENGINE.lock().await;
+    let engine = guard
+        .get_or_insert_with(|| {
+            info!("Diarization engine initialized");
+            Arc::new(DiarizationEngine::new())
+        })
+        .clone();
+    drop(guard);
+
+    if !engine.is_model_loaded().await {
+        if let Err(e) = engine.load_model(model_path).await {
+            error!("Failed to load diarization model: {}", e);
+            return None;
+        }
+        info!("Diarization model loaded");
+    }
+
+    Some(engine)
+}
+
 #[command]
 pub async fn diarize_segment(
     app: AppHandle,
     audio_data: Vec<f32>,
     _segment_id: String
 ) -> Result<usize, String> {
-    let guard = DIARIZATION_ENGINE.lock().await;
-    if let Some(engine) = guard.as_ref() {
-        // Ensure model is loaded
-        let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-        let model_path = app_data_dir.join("models").join("diarization").join(MODEL_FILENAME);
-
-        if !model_path.exists() {
-            return Ok(0); // No model, no speaker ID
-        }
-
-        // Lazy load model if needed (this might be slow on first call)
-        // In a real implementation we should load it once.
-        // For now let's try to load it if not loaded.
-        // But load_model is async and takes &self (non-mut) but locks internal RwLock.
-        engine.load_model(model_path).await.map_err(|e| e.to_string())?;
+    if DIARIZATION_ENGINE.lock().await.is_none() {
+        return Err("Diarization engine not initialized".to_string());
+    }
 
-        let speaker_id = engine.process_segment(&audio_data).await.map_err(|e| e.to_string())?;
-        Ok(speaker_id)
-    } else {
-        Err("Diarization engine not initialized".to_string())
+    match get_or_init_diarization_engine(&app).await {
+        Some(engine) => engine.process_segment(&audio_data).await.map_err(|e| e.to_string()),
+        None => Ok(0), // No model, no speaker ID
     }
 }
 
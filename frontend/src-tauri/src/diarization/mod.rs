@@ -1,17 +1,25 @@
 pub mod model;
 pub mod extraction;
 pub mod clustering;
+pub mod batch;
+pub mod registry;
 pub mod commands;
+pub mod speaker_commands;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::path::PathBuf;
 use crate::diarization::model::EmbeddingModel;
 use crate::diarization::clustering::OnlineClustering;
+use crate::diarization::registry::{best_match, SpeakerProfile, REGISTRY_MATCH_THRESHOLD};
 
 pub struct DiarizationEngine {
     model: Arc<RwLock<Option<EmbeddingModel>>>,
     clustering: Arc<RwLock<OnlineClustering>>,
+    registry: Arc<RwLock<Vec<SpeakerProfile>>>,
+    // Session speaker id -> registered speaker (id, name)
+    identified: Arc<RwLock<HashMap<usize, (i64, String)>>>,
 }
 
 impl DiarizationEngine {
@@ -19,6 +27,8 @@ impl DiarizationEngine {
         Self {
             model: Arc::new(RwLock::new(None)),
             clustering: Arc::new(RwLock::new(OnlineClustering::new(0.5))), // 0.5 threshold
+            registry: Arc::new(RwLock::new(Vec::new())),
+            identified: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -28,6 +38,32 @@ impl DiarizationEngine {
         Ok(())
     }
 
+    pub async fn is_model_loaded(&self) -> bool {
+        self.model.read().await.is_some()
+    }
+
+    /// Forget all speakers seen so far (called at the start of each recording)
+    pub async fn reset_session(&self) {
+        *self.clustering.write().await = OnlineClustering::new(0.5);
+        self.identified.write().await.clear();
+    }
+
+    /// Replace the registered voice profiles used to name session speakers
+    pub async fn set_registry(&self, profiles: Vec<SpeakerProfile>) {
+        *self.registry.write().await = profiles;
+        // Names may have changed - re-match on next lookup
+        self.identified.write().await.clear();
+    }
+
+    /// Compute a speaker embedding without assigning it to a session cluster
+    pub async fn extract_embedding(&self, audio_samples: &[f32]) -> anyhow::Result<Vec<f32>> {
+        let mut model_guard = self.model.write().await;
+        match model_guard.as_mut() {
+            Some(model) => model.extract_embedding(audio_samples),
+            None => Err(anyhow::anyhow!("Diarization model not loaded")),
+        }
+    }
+
     pub async fn process_segment(&self, audio_samples: &[f32]) -> anyhow::Result<usize> {
         let mut model_guard = self.model.write().await;
         if let Some(model) = model_guard.as_mut() {
@@ -45,4 +81,25 @@ impl DiarizationEngine {
             Err(anyhow::anyhow!("Diarization model not loaded"))
         }
     }
+
+    /// Match a session speaker against the registry, returning the registered (id, name).
+    /// Unmatched speakers are retried on later calls as their centroid improves.
+    pub async fn identify_speaker(&self, speaker_id: usize) -> Option<(i64, String)> {
+        if let Some(known) = self.identified.read().await.get(&speaker_id) {
+            return Some(known.clone());
+        }
+
+        let registry = self.registry.read().await;
+        if registry.is_empty() {
+            return None;
+        }
+
+        let clustering = self.clustering.read().await;
+        let centroid = clustering.centroid(speaker_id)?;
+        let profile = best_match(&registry, centroid, REGISTRY_MATCH_THRESHOLD)?;
+        let identity = (profile.id, profile.name.clone());
+
+        self.identified.write().await.insert(speaker_id, identity.clone());
+        Some(identity)
+    }
 }
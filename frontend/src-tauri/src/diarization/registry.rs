@@ -0,0 +1,117 @@
+use crate::diarization::clustering::cosine_similarity;
+
+/// Minimum cosine similarity for a session cluster to be matched to a registered speaker.
+/// Stricter than the online clustering threshold since a wrong name is worse than no name.
+pub const REGISTRY_MATCH_THRESHOLD: f32 = 0.6;
+
+/// A named voice profile from the persistent speaker registry
+#[derive(Debug, Clone)]
+pub struct SpeakerProfile {
+    pub id: i64,
+    pub name: String,
+    pub embedding: Vec<f32>,
+    pub sample_count: i64,
+}
+
+/// Find the registered speaker whose voice embedding is closest to `embedding`
+pub fn best_match<'a>(
+    profiles: &'a [SpeakerProfile],
+    embedding: &[f32],
+    threshold: f32,
+) -> Option<&'a SpeakerProfile> {
+    profiles
+        .iter()
+        .filter(|p| p.embedding.len() == embedding.len())
+        .map(|p| (p, cosine_similarity(embedding, &p.embedding)))
+        .filter(|(_, sim)| *sim >= threshold)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(p, _)| p)
+}
+
+/// Weighted mean of two embeddings, used when enrolling more samples or merging speakers
+pub fn merge_embeddings(a: &[f32], a_count: i64, b: &[f32], b_count: i64) -> Vec<f32> {
+    if a.len() != b.len() || a_count + b_count <= 0 {
+        return if b_count > a_count { b.to_vec() } else { a.to_vec() };
+    }
+    let total = (a_count + b_count) as f32;
+    let wa = a_count as f32 / total;
+    let wb = b_count as f32 / total;
+    a.iter().zip(b).map(|(x, y)| x * wa + y * wb).collect()
+}
+
+/// Average several embeddings (each L2-normalized first so long segments don't dominate)
+pub fn average_embeddings(embeddings: &[Vec<f32>]) -> Option<Vec<f32>> {
+    let dim = embeddings.first()?.len();
+    let mut sum = vec![0.0f32; dim];
+    let mut count = 0;
+
+    for embedding in embeddings.iter().filter(|e| e.len() == dim) {
+        let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            continue;
+        }
+        for (s, x) in sum.iter_mut().zip(embedding) {
+            *s += x / norm;
+        }
+        count += 1;
+    }
+
+    if count == 0 {
+        return None;
+    }
+    Some(sum.into_iter().map(|s| s / count as f32).collect())
+}
+
+/// Serialize an embedding for storage as a SQLite BLOB
+pub fn embedding_to_bytes(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+/// Deserialize an embedding stored with `embedding_to_bytes`
+pub fn embedding_from_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: i64, embedding: Vec<f32>) -> SpeakerProfile {
+        SpeakerProfile {
+            id,
+            name: format!("Speaker {}", id),
+            embedding,
+            sample_count: 1,
+        }
+    }
+
+    #[test]
+    fn test_best_match_picks_closest_above_threshold() {
+        let profiles = vec![
+            profile(1, vec![1.0, 0.0, 0.0]),
+            profile(2, vec![0.0, 1.0, 0.0]),
+        ];
+
+        let matched = best_match(&profiles, &[0.1, 0.9, 0.0], REGISTRY_MATCH_THRESHOLD);
+        assert_eq!(matched.map(|p| p.id), Some(2));
+
+        let unmatched = best_match(&profiles, &[0.0, 0.0, 1.0], REGISTRY_MATCH_THRESHOLD);
+        assert!(unmatched.is_none());
+    }
+
+    #[test]
+    fn test_merge_embeddings_is_weighted() {
+        let merged = merge_embeddings(&[1.0, 0.0], 3, &[0.0, 1.0], 1);
+        assert!((merged[0] - 0.75).abs() < 1e-6);
+        assert!((merged[1] - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_embedding_bytes_roundtrip() {
+        let embedding = vec![0.5, -1.25, 3.0];
+        assert_eq!(embedding_from_bytes(&embedding_to_bytes(&embedding)), embedding);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::audio::decoder::decode_media_file_16k;
+use crate::audio::recording_saver::meeting_audio_path;
+use crate::database::models::{SpeakerModel, Transcript};
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::diarization::commands::{get_or_init_diarization_engine, DIARIZATION_ENGINE};
+use crate::diarization::registry::average_embeddings;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+
+/// Maximum number of segments sampled when enrolling a speaker from a meeting
+const MAX_ENROLLMENT_SEGMENTS: usize = 20;
+
+/// Minimum segment length (seconds) worth extracting an embedding from
+const MIN_ENROLLMENT_SEGMENT_SECONDS: f64 = 1.0;
+
+/// Registered speaker for UI display
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpeakerInfo {
+    pub id: i64,
+    pub name: String,
+    pub sample_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<SpeakerModel> for SpeakerInfo {
+    fn from(s: SpeakerModel) -> Self {
+        Self {
+            id: s.id,
+            name: s.name,
+            sample_count: s.sample_count,
+            created_at: s.created_at.0.to_rfc3339(),
+            updated_at: s.updated_at.0.to_rfc3339(),
+        }
+    }
+}
+
+/// Push the current registry into the running diarization engine (if any)
+pub async fn refresh_engine_registry(pool: &SqlitePool) {
+    let engine = DIARIZATION_ENGINE.lock().await.clone();
+    if let Some(engine) = engine {
+        match SpeakersRepository::load_profiles(pool).await {
+            Ok(profiles) => {
+                log_info!("Loaded {} registered speakers into diarization engine", profiles.len());
+                engine.set_registry(profiles).await;
+            }
+            Err(e) => log_warn!("Failed to load speaker registry: {}", e),
+        }
+    }
+}
+
+/// Lists all registered speakers
+#[tauri::command]
+pub async fn api_list_speakers(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SpeakerInfo>, String> {
+    log_info!("api_list_speakers called");
+    SpeakersRepository::list_speakers(state.db_manager.pool())
+        .await
+        .map(|speakers| speakers.into_iter().map(SpeakerInfo::from).collect())
+        .map_err(|e| format!("Failed to list speakers: {}", e))
+}
+
+/// Enrolls the speaker of a transcript segment under `name`
+///
+/// The voice embedding is computed from every segment in the meeting that carries the
+/// same diarization label (or just the given segment if it has none), and all those
+/// segments are linked to the registered speaker.
+#[tauri::command]
+pub async fn api_enroll_speaker<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_id: String,
+    name: String,
+) -> Result<SpeakerInfo, String> {
+    log_info!(
+        "api_enroll_speaker called for meeting {} segment {} as '{}'",
+        meeting_id,
+        transcript_id,
+        name
+    );
+    let pool = state.db_manager.pool();
+
+    let engine = get_or_init_diarization_engine(&app)
+        .await
+        .ok_or_else(|| "Diarization model is not available. Please download it first.".to_string())?;
+
+    let folder_path: Option<String> =
+        sqlx::query_scalar("SELECT folder_path FROM meetings WHERE id = ?")
+            .bind(&meeting_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?
+            .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let folder = PathBuf::from(
+        folder_path.ok_or_else(|| "Recording folder not available for this meeting".to_string())?,
+    );
+
+    let labelled: Transcript = sqlx::query_as("SELECT * FROM transcripts WHERE id = ? AND meeting_id = ?")
+        .bind(&transcript_id)
+        .bind(&meeting_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Transcript segment not found: {}", transcript_id))?;
+
+    let mut segments: Vec<Transcript> = match labelled.speaker_id {
+        Some(label) => sqlx::query_as(
+            "SELECT * FROM transcripts WHERE meeting_id = ? AND speaker_id = ? ORDER BY duration DESC",
+        )
+        .bind(&meeting_id)
+        .bind(label)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?,
+        None => vec![labelled.clone()],
+    };
+    segments.retain(|s| {
+        s.audio_start_time.is_some()
+            && s.duration.unwrap_or(0.0) >= MIN_ENROLLMENT_SEGMENT_SECONDS
+    });
+    segments.truncate(MAX_ENROLLMENT_SEGMENTS);
+
+    if segments.is_empty() {
+        return Err("No segments with enough audio to learn this speaker's voice".to_string());
+    }
+
+    let audio_path = meeting_audio_path(&folder);
+    let audio = tokio::task::spawn_blocking(move || decode_media_file_16k(&audio_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to decode meeting audio: {}", e))?;
+
+    let mut embeddings = Vec::new();
+    for segment in &segments {
+        let start = segment.audio_start_time.unwrap_or(0.0);
+        let end = segment
+            .audio_end_time
+            .unwrap_or(start + segment.duration.unwrap_or(0.0));
+        match engine.extract_embedding(audio.slice_seconds(start, end)).await {
+            Ok(embedding) => embeddings.push(embedding),
+            Err(e) => log_warn!("Skipping segment {} for enrollment: {}", segment.id, e),
+        }
+    }
+
+    let embedding = average_embeddings(&embeddings)
+        .ok_or_else(|| "Could not extract a voice embedding from this speaker's audio".to_string())?;
+
+    let speaker_id = SpeakersRepository::enroll_speaker(pool, &name, &embedding, embeddings.len() as i64)
+        .await
+        .map_err(|e| format!("Failed to enroll speaker: {}", e))?;
+
+    let linked = SpeakersRepository::assign_segments(
+        pool,
+        &meeting_id,
+        &transcript_id,
+        labelled.speaker_id,
+        speaker_id,
+    )
+    .await
+    .map_err(|e| format!("Failed to link segments to speaker: {}", e))?;
+    log_info!("Linked {} segments to speaker {}", linked, speaker_id);
+
+    refresh_engine_registry(pool).await;
+
+    SpeakersRepository::get_speaker(pool, speaker_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .map(SpeakerInfo::from)
+        .ok_or_else(|| "Speaker disappeared after enrollment".to_string())
+}
+
+#[tauri::command]
+pub async fn api_rename_speaker(
+    state: tauri::State<'_, AppState>,
+    speaker_id: i64,
+    name: String,
+) -> Result<(), String> {
+    log_info!("api_rename_speaker called for {} -> '{}'", speaker_id, name);
+    let pool = state.db_manager.pool();
+
+    match SpeakersRepository::rename_speaker(pool, speaker_id, &name).await {
+        Ok(true) => {
+            refresh_engine_registry(pool).await;
+            Ok(())
+        }
+        Ok(false) => Err(format!("Speaker not found: {}", speaker_id)),
+        Err(e) => {
+            log_error!("Failed to rename speaker {}: {}", speaker_id, e);
+            Err(format!("Failed to rename speaker: {}", e))
+        }
+    }
+}
+
+/// Merges `source_id` into `target_id` (e.g. when the same person was enrolled twice)
+#[tauri::command]
+pub async fn api_merge_speakers(
+    state: tauri::State<'_, AppState>,
+    source_id: i64,
+    target_id: i64,
+) -> Result<(), String> {
+    log_info!("api_merge_speakers called: {} -> {}", source_id, target_id);
+    let pool = state.db_manager.pool();
+
+    match SpeakersRepository::merge_speakers(pool, source_id, target_id).await {
+        Ok(true) => {
+            refresh_engine_registry(pool).await;
+            Ok(())
+        }
+        Ok(false) => Err("One of the speakers to merge was not found".to_string()),
+        Err(e) => {
+            log_error!("Failed to merge speakers {} -> {}: {}", source_id, target_id, e);
+            Err(format!("Failed to merge speakers: {}", e))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn api_delete_speaker(
+    state: tauri::State<'_, AppState>,
+    speaker_id: i64,
+) -> Result<(), String> {
+    log_info!("api_delete_speaker called for {}", speaker_id);
+    let pool = state.db_manager.pool();
+
+    match SpeakersRepository::delete_speaker(pool, speaker_id).await {
+        Ok(true) => {
+            refresh_engine_registry(pool).await;
+            Ok(())
+        }
+        Ok(false) => Err(format!("Speaker not found: {}", speaker_id)),
+        Err(e) => {
+            log_error!("Failed to delete speaker {}: {}", speaker_id, e);
+            Err(format!("Failed to delete speaker: {}", e))
+        }
+    }
+}
@@ -0,0 +1,518 @@
+// export/archive.rs
+//
+// Portable meeting archives: a zip holding the meeting's audio, transcripts.json,
+// metadata.json, summary and speaker labels, described by a manifest with a schema
+// version and a SHA-256 checksum for every file. Archives are verified before anything
+// from them is used, so a truncated or edited archive is rejected on import.
+
+use crate::audio::recording_saver::{MeetingMetadata, TranscriptSegment};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Archive layout version; bumped whenever an older reader could misread a newer archive
+pub const ARCHIVE_SCHEMA_VERSION: u32 = 1;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const METADATA_FILE: &str = "metadata.json";
+pub const TRANSCRIPTS_FILE: &str = "transcripts.json";
+pub const SUMMARY_FILE: &str = "summary.json";
+pub const SPEAKERS_FILE: &str = "speakers.json";
+
+/// The meeting an archive was exported from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedMeeting {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A file inside the archive with its size and SHA-256 (lowercase hex)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub schema_version: u32,
+    pub generator: String,
+    pub exported_at: String,
+    pub meeting: ArchivedMeeting,
+    pub files: Vec<ArchiveEntry>,
+}
+
+/// A registered speaker referenced by the meeting's segments.
+/// The voice embedding lets the importer recreate the profile when it has no speaker
+/// of that name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSpeaker {
+    pub profile_id: i64,
+    pub name: String,
+    pub sample_count: i64,
+    #[serde(default)]
+    pub embedding: Vec<f32>,
+}
+
+/// Everything in an archive except the audio files
+#[derive(Debug, Clone)]
+pub struct ArchiveContents {
+    pub metadata: MeetingMetadata,
+    pub segments: Vec<TranscriptSegment>,
+    pub summary: Option<Value>,
+    pub speakers: Vec<ArchivedSpeaker>,
+}
+
+impl ArchiveContents {
+    /// Audio files referenced by the metadata (the recording and any stems)
+    pub fn audio_files(&self) -> Vec<String> {
+        let mut files = vec![self.metadata.audio_file.clone()];
+        if let Some(stems) = &self.metadata.stems {
+            files.push(stems.microphone.clone());
+            files.push(stems.system.clone());
+            files.extend(stems.stereo.clone());
+        }
+        files.retain(|f| !f.is_empty());
+        files.dedup();
+        files
+    }
+}
+
+/// What to do when the archived meeting's id already exists in the database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveConflict {
+    /// Import under a new id, keeping the existing meeting
+    #[default]
+    KeepBoth,
+    /// Import under the archived id, deleting the existing meeting's records and folder
+    /// once the import has succeeded
+    Replace,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveImportResult {
+    pub meeting_id: String,
+    pub meeting_name: String,
+    pub folder_path: String,
+    /// The archived id, when the meeting was imported under a new one
+    pub original_meeting_id: Option<String>,
+    pub segments: usize,
+    pub audio_files: usize,
+}
+
+/// Archive path for a meeting-relative file, rejecting absolute paths and `..`
+pub fn safe_relative_path(path: &str) -> Option<PathBuf> {
+    if path.is_empty() || path.contains('\\') {
+        return None;
+    }
+    let path = Path::new(path);
+    path.components()
+        .all(|c| matches!(c, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+}
+
+/// Writer that computes the size and SHA-256 of everything passing through it
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self { inner, hasher: Sha256::new(), size: 0 }
+    }
+
+    fn finish(self, path: &str) -> (W, ArchiveEntry) {
+        let entry = ArchiveEntry {
+            path: path.to_string(),
+            size: self.size,
+            sha256: format!("{:x}", self.hasher.finalize()),
+        };
+        (self.inner, entry)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn json_entry<T: Serialize>(
+    writer: &mut ZipWriter<File>,
+    path: &str,
+    value: &T,
+) -> Result<ArchiveEntry> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    writer.start_file(path, options)?;
+    let mut hashing = HashingWriter::new(&mut *writer);
+    serde_json::to_writer_pretty(&mut hashing, value)?;
+    Ok(hashing.finish(path).1)
+}
+
+/// The transcripts.json document, in the same shape a recording writes
+fn transcripts_document(segments: &[TranscriptSegment]) -> Value {
+    serde_json::json!({
+        "version": "1.0",
+        "segments": segments,
+        "last_updated": chrono::Utc::now().to_rfc3339(),
+        "total_segments": segments.len()
+    })
+}
+
+/// Write a meeting archive to `path`.
+///
+/// Audio files named in the metadata are read from `folder`; missing ones are left out
+/// (the archive then carries the transcript only). The archive is written next to
+/// `path` first and moved into place once complete.
+pub fn write_archive(
+    path: &Path,
+    meeting: ArchivedMeeting,
+    contents: &ArchiveContents,
+    folder: Option<&Path>,
+) -> Result<ArchiveManifest> {
+    let temp_path = path.with_extension("part");
+    let result = write_archive_to(&temp_path, meeting, contents, folder);
+    match result {
+        Ok(manifest) => {
+            std::fs::rename(&temp_path, path)
+                .with_context(|| format!("Failed to move archive to {}", path.display()))?;
+            Ok(manifest)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+fn write_archive_to(
+    path: &Path,
+    meeting: ArchivedMeeting,
+    contents: &ArchiveContents,
+    folder: Option<&Path>,
+) -> Result<ArchiveManifest> {
+    let mut writer = ZipWriter::new(File::create(path)?);
+    let mut files = vec![
+        json_entry(&mut writer, METADATA_FILE, &contents.metadata)?,
+        json_entry(&mut writer, TRANSCRIPTS_FILE, &transcripts_document(&contents.segments))?,
+    ];
+    if let Some(summary) = &contents.summary {
+        files.push(json_entry(&mut writer, SUMMARY_FILE, summary)?);
+    }
+    if !contents.speakers.is_empty() {
+        files.push(json_entry(&mut writer, SPEAKERS_FILE, &contents.speakers)?);
+    }
+
+    for file in contents.audio_files() {
+        let Some(relative) = safe_relative_path(&file) else {
+            log::warn!("Skipping audio file outside the meeting folder: {}", file);
+            continue;
+        };
+        let Some(source) = folder.map(|f| f.join(&relative)).filter(|p| p.is_file()) else {
+            log::warn!("Audio file {} not found, archiving without it", file);
+            continue;
+        };
+
+        // Audio is already compressed; storing it keeps export fast
+        let size = std::fs::metadata(&source)?.len();
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(size > u32::MAX as u64);
+        writer.start_file(file.as_str(), options)?;
+        let mut hashing = HashingWriter::new(&mut writer);
+        std::io::copy(&mut File::open(&source)?, &mut hashing)
+            .with_context(|| format!("Failed to archive {}", source.display()))?;
+        files.push(hashing.finish(&file).1);
+    }
+
+    let manifest = ArchiveManifest {
+        schema_version: ARCHIVE_SCHEMA_VERSION,
+        generator: format!("meetily {}", env!("CARGO_PKG_VERSION")),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        meeting,
+        files,
+    };
+    writer.start_file(MANIFEST_FILE, SimpleFileOptions::default())?;
+    serde_json::to_writer_pretty(&mut writer, &manifest)?;
+    writer.finish()?.sync_all()?;
+    Ok(manifest)
+}
+
+/// An opened archive whose manifest has been validated
+pub struct ArchiveReader {
+    archive: ZipArchive<File>,
+    manifest: ArchiveManifest,
+}
+
+impl ArchiveReader {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut archive = ZipArchive::new(file).context("Not a meeting archive")?;
+
+        let manifest: ArchiveManifest = {
+            let entry = archive
+                .by_name(MANIFEST_FILE)
+                .map_err(|_| anyhow!("Not a meeting archive: {} is missing", MANIFEST_FILE))?;
+            serde_json::from_reader(entry).context("The archive manifest is invalid")?
+        };
+        if manifest.schema_version == 0 || manifest.schema_version > ARCHIVE_SCHEMA_VERSION {
+            bail!(
+                "Archive schema version {} is not supported (this version reads up to {})",
+                manifest.schema_version,
+                ARCHIVE_SCHEMA_VERSION
+            );
+        }
+
+        let mut seen = HashSet::new();
+        for entry in &manifest.files {
+            if safe_relative_path(&entry.path).is_none() || entry.path == MANIFEST_FILE {
+                bail!("The archive manifest lists an invalid path: {}", entry.path);
+            }
+            if !seen.insert(entry.path.as_str()) {
+                bail!("The archive manifest lists {} twice", entry.path);
+            }
+        }
+        for required in [METADATA_FILE, TRANSCRIPTS_FILE] {
+            if !seen.contains(required) {
+                bail!("The archive has no {}", required);
+            }
+        }
+
+        Ok(Self { archive, manifest })
+    }
+
+    pub fn manifest(&self) -> &ArchiveManifest {
+        &self.manifest
+    }
+
+    fn entry(&self, path: &str) -> Option<&ArchiveEntry> {
+        self.manifest.files.iter().find(|e| e.path == path)
+    }
+
+    /// Copy an entry to `dest`, failing if its size or checksum differs from the manifest
+    fn copy_verified<W: Write>(&mut self, entry: &ArchiveEntry, dest: W) -> Result<W> {
+        let mut file = self
+            .archive
+            .by_name(&entry.path)
+            .map_err(|_| anyhow!("{} is listed in the manifest but missing", entry.path))?;
+        let mut hashing = HashingWriter::new(dest);
+        std::io::copy(&mut file, &mut hashing)
+            .with_context(|| format!("Failed to read {} from the archive", entry.path))?;
+        let (dest, actual) = hashing.finish(&entry.path);
+        if actual.size != entry.size || !actual.sha256.eq_ignore_ascii_case(&entry.sha256) {
+            bail!("{} is corrupted (checksum mismatch)", entry.path);
+        }
+        Ok(dest)
+    }
+
+    fn read_json<T: serde::de::DeserializeOwned>(&mut self, path: &str) -> Result<Option<T>> {
+        let Some(entry) = self.entry(path).cloned() else {
+            return Ok(None);
+        };
+        let bytes = self.copy_verified(&entry, Vec::with_capacity(entry.size as usize))?;
+        let value = serde_json::from_slice(&bytes).with_context(|| format!("{} is invalid", path))?;
+        Ok(Some(value))
+    }
+
+    /// Read and verify the metadata, transcript, summary and speaker labels
+    pub fn read_contents(&mut self) -> Result<ArchiveContents> {
+        let metadata: MeetingMetadata = self
+            .read_json(METADATA_FILE)?
+            .ok_or_else(|| anyhow!("The archive has no {}", METADATA_FILE))?;
+        let transcripts: Value = self
+            .read_json(TRANSCRIPTS_FILE)?
+            .ok_or_else(|| anyhow!("The archive has no {}", TRANSCRIPTS_FILE))?;
+        let segments = transcripts
+            .get("segments")
+            .cloned()
+            .ok_or_else(|| anyhow!("{} has no segments", TRANSCRIPTS_FILE))?;
+        let segments: Vec<TranscriptSegment> = serde_json::from_value(segments)
+            .with_context(|| format!("{} is invalid", TRANSCRIPTS_FILE))?;
+
+        Ok(ArchiveContents {
+            metadata,
+            segments,
+            summary: self.read_json(SUMMARY_FILE)?,
+            speakers: self.read_json(SPEAKERS_FILE)?.unwrap_or_default(),
+        })
+    }
+
+    /// Extract and verify every audio file the archive carries into `folder`.
+    /// Returns the extracted paths relative to the folder.
+    pub fn extract_audio(&mut self, contents: &ArchiveContents, folder: &Path) -> Result<Vec<String>> {
+        let mut extracted = Vec::new();
+        for file in contents.audio_files() {
+            let Some(entry) = self.entry(&file).cloned() else {
+                log::warn!("Archive does not include {}, importing without it", file);
+                continue;
+            };
+            let relative = safe_relative_path(&entry.path)
+                .ok_or_else(|| anyhow!("Invalid audio path in archive: {}", entry.path))?;
+            let target = folder.join(relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let dest = File::create(&target)?;
+            self.copy_verified(&entry, dest)?.sync_all()?;
+            extracted.push(entry.path);
+        }
+        Ok(extracted)
+    }
+}
+
+/// Check every file in an archive against its manifest without extracting it.
+/// Returns the manifest of a valid archive.
+pub fn verify_archive(path: &Path) -> Result<ArchiveManifest> {
+    let mut reader = ArchiveReader::open(path)?;
+    for entry in reader.manifest.files.clone() {
+        reader.copy_verified(&entry, std::io::sink())?;
+    }
+    Ok(reader.manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::recording_saver::DeviceInfo;
+    use std::io::Read;
+
+    fn read_member(path: &Path, name: &str) -> Vec<u8> {
+        let mut archive = ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut bytes = Vec::new();
+        archive.by_name(name).unwrap().read_to_end(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn sample_contents() -> ArchiveContents {
+        ArchiveContents {
+            metadata: MeetingMetadata {
+                version: "1.0".to_string(),
+                meeting_id: Some("meeting-1".to_string()),
+                meeting_name: Some("Weekly sync".to_string()),
+                created_at: "2025-01-01T10:00:00Z".to_string(),
+                completed_at: None,
+                duration_seconds: Some(4.0),
+                devices: DeviceInfo { microphone: None, system_audio: None },
+                audio_file: "audio.flac".to_string(),
+                transcript_file: TRANSCRIPTS_FILE.to_string(),
+                sample_rate: 48000,
+                status: "completed".to_string(),
+                stems: None,
+                encoder: None,
+            },
+            segments: vec![TranscriptSegment {
+                id: "seg_0".to_string(),
+                text: "Hello team".to_string(),
+                audio_start_time: 0.0,
+                audio_end_time: 2.0,
+                duration: 2.0,
+                display_time: "[00:00]".to_string(),
+                confidence: 1.0,
+                sequence_id: 0,
+                speaker_id: Some(1),
+                speaker_profile_id: Some(7),
+                speaker_name: Some("Ada".to_string()),
+                words: Vec::new(),
+                language: None,
+                language_probability: None,
+                audio_source: None,
+            }],
+            summary: Some(serde_json::json!({ "markdown": "# Notes" })),
+            speakers: vec![ArchivedSpeaker {
+                profile_id: 7,
+                name: "Ada".to_string(),
+                sample_count: 3,
+                embedding: vec![0.5, -0.25],
+            }],
+        }
+    }
+
+    fn sample_meeting() -> ArchivedMeeting {
+        ArchivedMeeting {
+            id: "meeting-1".to_string(),
+            title: "Weekly sync".to_string(),
+            created_at: "2025-01-01T10:00:00Z".to_string(),
+            updated_at: "2025-01-01T11:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_safe_relative_path() {
+        assert!(safe_relative_path("audio.mp4").is_some());
+        assert!(safe_relative_path("stems/mic.flac").is_some());
+        assert!(safe_relative_path("../audio.mp4").is_none());
+        assert!(safe_relative_path("/etc/passwd").is_none());
+        assert!(safe_relative_path("stems\\..\\x").is_none());
+        assert!(safe_relative_path("").is_none());
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path();
+        std::fs::write(folder.join("audio.flac"), b"fLaC audio bytes").unwrap();
+        let archive_path = folder.join("export.zip");
+
+        let manifest =
+            write_archive(&archive_path, sample_meeting(), &sample_contents(), Some(folder)).unwrap();
+        assert_eq!(manifest.schema_version, ARCHIVE_SCHEMA_VERSION);
+        assert_eq!(manifest.files.len(), 5);
+        assert_eq!(verify_archive(&archive_path).unwrap().meeting.title, "Weekly sync");
+
+        let mut reader = ArchiveReader::open(&archive_path).unwrap();
+        let contents = reader.read_contents().unwrap();
+        assert_eq!(contents.segments[0].text, "Hello team");
+        assert_eq!(contents.speakers[0].embedding, vec![0.5, -0.25]);
+        assert_eq!(contents.summary.unwrap()["markdown"], "# Notes");
+
+        let target = tempfile::tempdir().unwrap();
+        let extracted = reader.extract_audio(&sample_contents(), target.path()).unwrap();
+        assert_eq!(extracted, vec!["audio.flac".to_string()]);
+        assert_eq!(std::fs::read(target.path().join("audio.flac")).unwrap(), b"fLaC audio bytes");
+    }
+
+    #[test]
+    fn test_tampered_archive_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path();
+        let original = folder.join("original.zip");
+        write_archive(&original, sample_meeting(), &sample_contents(), None).unwrap();
+
+        // Rebuild the archive with an edited transcript but the original manifest
+        let tampered = folder.join("tampered.zip");
+        let mut writer = ZipWriter::new(File::create(&tampered).unwrap());
+        for name in [MANIFEST_FILE, METADATA_FILE, SUMMARY_FILE, SPEAKERS_FILE, TRANSCRIPTS_FILE] {
+            let mut bytes = read_member(&original, name);
+            if name == TRANSCRIPTS_FILE {
+                bytes = String::from_utf8(bytes).unwrap().replace("Hello team", "Hello world").into_bytes();
+            }
+            writer.start_file(name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(&bytes).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let err = ArchiveReader::open(&tampered).unwrap().read_contents().unwrap_err();
+        assert!(err.to_string().contains("corrupted"), "{}", err);
+        assert!(verify_archive(&tampered).is_err());
+    }
+}
@@ -0,0 +1,530 @@
+use crate::api::{MeetingTranscript, TranscriptSegment as DbTranscriptSegment};
+use crate::audio::audio_processing::create_meeting_folder;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::audio::recording_saver::{
+    read_meeting_metadata, write_meeting_metadata, write_transcripts_json_file, DeviceInfo,
+    MeetingMetadata, TranscriptSegment,
+};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::diarization::registry::embedding_from_bytes;
+use crate::export::archive::{
+    self, ArchiveConflict, ArchiveContents, ArchiveImportResult, ArchiveReader, ArchivedMeeting,
+    ArchivedSpeaker, TRANSCRIPTS_FILE,
+};
+use crate::export::document::{DocumentFormat, DocumentOptions, MeetingDocument};
+use crate::export::export_file_stem;
+use crate::export::subtitles::{self, SubtitleFormat, SubtitleOptions};
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+
+/// The given path, or one picked in a save dialog (None if cancelled)
+fn resolve_output_path<R: Runtime>(
+    app: &AppHandle<R>,
+    output_path: Option<String>,
+    title: &str,
+    filter_name: &str,
+    extension: &str,
+) -> Option<String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    if output_path.is_some() {
+        return output_path;
+    }
+    let file_name = format!("{}.{}", export_file_stem(title), extension);
+    app.dialog()
+        .file()
+        .set_file_name(&file_name)
+        .add_filter(filter_name, &[extension])
+        .blocking_save_file()
+        .map(|path| path.to_string())
+}
+
+fn write_export(output_path: &str, content: &[u8]) -> Result<(), String> {
+    std::fs::write(output_path, content).map_err(|e| {
+        log_warn!("Failed to write export to {}: {}", output_path, e);
+        format!("Failed to write {}: {}", output_path, e)
+    })
+}
+
+/// Export a meeting's transcript as SRT or WebVTT captions
+///
+/// Without `output_path` a save dialog is shown. Returns the written path, or None
+/// if the user cancelled the dialog.
+#[tauri::command]
+pub async fn api_export_subtitles<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: SubtitleFormat,
+    options: Option<SubtitleOptions>,
+    output_path: Option<String>,
+) -> Result<Option<String>, String> {
+    log_info!("api_export_subtitles called for {} ({:?})", meeting_id, format);
+    let pool = state.db_manager.pool();
+
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
+
+    let options = options.unwrap_or_default();
+    let cues = subtitles::build_cues(&meeting.transcripts, &options);
+    if cues.is_empty() {
+        return Err("This meeting has no timed transcript to export as captions".to_string());
+    }
+
+    let Some(output_path) =
+        resolve_output_path(&app, output_path, &meeting.title, "Subtitles", format.extension())
+    else {
+        log_info!("Subtitle export cancelled");
+        return Ok(None);
+    };
+
+    write_export(&output_path, subtitles::render(&cues, format, &options).as_bytes())?;
+    log_info!("Exported {} cues to {}", cues.len(), output_path);
+    Ok(Some(output_path))
+}
+
+/// Export a meeting as a Markdown, HTML, DOCX or PDF document
+///
+/// `options` selects the sections (summary, action items, transcript appendix).
+/// Without `output_path` a save dialog is shown. Returns the written path, or None
+/// if the user cancelled the dialog.
+#[tauri::command]
+pub async fn api_export_meeting_document<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: DocumentFormat,
+    options: Option<DocumentOptions>,
+    output_path: Option<String>,
+) -> Result<Option<String>, String> {
+    log_info!("api_export_meeting_document called for {} ({:?})", meeting_id, format);
+    let pool = state.db_manager.pool();
+
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
+    let summary_result = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .and_then(|process| process.result)
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok());
+
+    let document = MeetingDocument::new(meeting, summary_result.as_ref());
+    let Some(output_path) =
+        resolve_output_path(&app, output_path, &document.title, "Documents", format.extension())
+    else {
+        log_info!("Document export cancelled");
+        return Ok(None);
+    };
+
+    let options = options.unwrap_or_default();
+    let content = document
+        .render(format, &options)
+        .map_err(|e| format!("Failed to render {:?} document: {}", format, e))?;
+    write_export(&output_path, &content)?;
+
+    log_info!("Exported meeting {} to {}", meeting_id, output_path);
+    Ok(Some(output_path))
+}
+
+/// transcripts.json segments for a meeting, built from the database so edits and
+/// speaker assignments made after recording are included
+fn archive_segments(transcripts: &[MeetingTranscript]) -> Vec<TranscriptSegment> {
+    let mut transcripts: Vec<&MeetingTranscript> = transcripts.iter().collect();
+    transcripts.sort_by(|a, b| {
+        a.audio_start_time
+            .unwrap_or(0.0)
+            .total_cmp(&b.audio_start_time.unwrap_or(0.0))
+    });
+    transcripts
+        .into_iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let start = t.audio_start_time.unwrap_or(0.0);
+            let end = t.audio_end_time.unwrap_or(start);
+            TranscriptSegment {
+                id: format!("seg_{}", i),
+                text: t.text.clone(),
+                audio_start_time: start,
+                audio_end_time: end,
+                duration: t.duration.unwrap_or(end - start),
+                display_time: t.timestamp.clone(),
+                confidence: 1.0, // Not stored in the database
+                sequence_id: i as u64,
+                speaker_id: t.speaker_id.map(|id| id as usize),
+                speaker_profile_id: t.speaker_profile_id,
+                speaker_name: t.speaker_name.clone(),
+                words: t.words.clone().unwrap_or_default(),
+                language: t.language.clone(),
+                language_probability: t.language_probability.map(|p| p as f32),
+                audio_source: t.audio_source.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Export a meeting as a portable archive (zip) for another Meetily installation
+///
+/// The archive holds the audio, transcripts.json, metadata.json, the summary and the
+/// registered speakers, plus a manifest with checksums. Without `output_path` a save
+/// dialog is shown. Returns the written path, or None if the user cancelled the dialog.
+#[tauri::command]
+pub async fn api_export_meeting_archive<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    output_path: Option<String>,
+) -> Result<Option<String>, String> {
+    log_info!("api_export_meeting_archive called for {}", meeting_id);
+    let pool = state.db_manager.pool();
+
+    let meeting = MeetingsRepository::get_meeting(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| format!("Meeting {} not found", meeting_id))?;
+    let folder: Option<PathBuf> =
+        sqlx::query_scalar::<_, Option<String>>("SELECT folder_path FROM meetings WHERE id = ?")
+            .bind(&meeting_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Failed to load meeting folder: {}", e))?
+            .flatten()
+            .map(PathBuf::from)
+            .filter(|folder| folder.is_dir());
+    let summary = SummaryProcessesRepository::get_summary_data(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .and_then(|process| process.result)
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok());
+
+    let mut speakers: Vec<ArchivedSpeaker> = Vec::new();
+    for profile_id in meeting.transcripts.iter().filter_map(|t| t.speaker_profile_id) {
+        if speakers.iter().any(|s| s.profile_id == profile_id) {
+            continue;
+        }
+        if let Some(speaker) = SpeakersRepository::get_speaker(pool, profile_id)
+            .await
+            .map_err(|e| format!("Failed to load speaker: {}", e))?
+        {
+            speakers.push(ArchivedSpeaker {
+                profile_id,
+                name: speaker.name,
+                sample_count: speaker.sample_count,
+                embedding: embedding_from_bytes(&speaker.embedding),
+            });
+        }
+    }
+
+    let metadata = folder
+        .as_deref()
+        .and_then(|folder| read_meeting_metadata(folder).ok())
+        .unwrap_or_else(|| MeetingMetadata {
+            version: "1.0".to_string(),
+            meeting_id: Some(meeting.id.clone()),
+            meeting_name: Some(meeting.title.clone()),
+            created_at: meeting.created_at.clone(),
+            completed_at: Some(meeting.updated_at.clone()),
+            duration_seconds: None,
+            devices: DeviceInfo {
+                microphone: None,
+                system_audio: None,
+            },
+            audio_file: "audio.mp4".to_string(),
+            transcript_file: TRANSCRIPTS_FILE.to_string(),
+            sample_rate: 48000,
+            status: "completed".to_string(),
+            stems: None,
+            encoder: None,
+        });
+    let contents = ArchiveContents {
+        metadata,
+        segments: archive_segments(&meeting.transcripts),
+        summary,
+        speakers,
+    };
+    let archived = ArchivedMeeting {
+        id: meeting.id,
+        title: meeting.title,
+        created_at: meeting.created_at,
+        updated_at: meeting.updated_at,
+    };
+
+    let Some(output_path) =
+        resolve_output_path(&app, output_path, &archived.title, "Meeting Archive", "zip")
+    else {
+        log_info!("Archive export cancelled");
+        return Ok(None);
+    };
+
+    let archive_path = PathBuf::from(&output_path);
+    let manifest = tokio::task::spawn_blocking(move || {
+        archive::write_archive(&archive_path, archived, &contents, folder.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| {
+        log_error!("Failed to write archive {}: {}", output_path, e);
+        format!("Failed to write archive: {}", e)
+    })?;
+
+    log_info!(
+        "Exported meeting {} to {} ({} files)",
+        meeting_id,
+        output_path,
+        manifest.files.len()
+    );
+    Ok(Some(output_path))
+}
+
+/// Create an empty meeting folder for an imported archive, adding a counter to the
+/// name if a folder of the same name (and minute) already holds a meeting
+fn create_import_folder(save_folder: &Path, title: &str) -> anyhow::Result<PathBuf> {
+    let save_folder = save_folder.to_path_buf();
+    for attempt in 1..=100 {
+        let name = if attempt == 1 {
+            title.to_string()
+        } else {
+            format!("{} ({})", title, attempt)
+        };
+        let folder = create_meeting_folder(&save_folder, &name)?;
+        let _ = std::fs::remove_dir(folder.join(".checkpoints"));
+        if std::fs::read_dir(&folder)?.next().is_none() {
+            return Ok(folder);
+        }
+    }
+    Err(anyhow::anyhow!("Could not find a free folder name for {}", title))
+}
+
+/// Import a meeting archive created by `api_export_meeting_archive`
+///
+/// Every file is checked against the manifest before the meeting is registered. The
+/// meeting keeps its archived id unless that id is taken; `on_conflict` decides whether
+/// it is then imported under a new id (default) or replaces the existing meeting.
+/// Without `archive_path` a file picker is shown; returns None if it was cancelled.
+#[tauri::command]
+pub async fn api_import_meeting_archive<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    archive_path: Option<String>,
+    on_conflict: Option<ArchiveConflict>,
+) -> Result<Option<ArchiveImportResult>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let archive_path = match archive_path {
+        Some(path) => path,
+        None => match app
+            .dialog()
+            .file()
+            .add_filter("Meeting Archive", &["zip"])
+            .blocking_pick_file()
+        {
+            Some(path) => path.to_string(),
+            None => {
+                log_info!("Archive import cancelled");
+                return Ok(None);
+            }
+        },
+    };
+    log_info!("api_import_meeting_archive called for {}", archive_path);
+
+    let path = PathBuf::from(&archive_path);
+    let (reader, contents) = tokio::task::spawn_blocking(move || {
+        let mut reader = ArchiveReader::open(&path)?;
+        let contents = reader.read_contents()?;
+        anyhow::Ok((reader, contents))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Invalid meeting archive: {:#}", e))?;
+
+    let preferences = load_recording_preferences(&app)
+        .await
+        .map_err(|e| format!("Failed to load recording preferences: {}", e))?;
+    let title = reader.manifest().meeting.title.clone();
+    let folder = create_import_folder(&preferences.save_folder, &title)
+        .map_err(|e| format!("Failed to create meeting folder: {}", e))?;
+
+    let result = import_archive_into_folder(
+        &state,
+        reader,
+        contents,
+        &folder,
+        on_conflict.unwrap_or_default(),
+    )
+    .await;
+    match result {
+        Ok(result) => {
+            log_info!(
+                "Imported archive {} as meeting {} ({} segments, {} audio files)",
+                archive_path,
+                result.meeting_id,
+                result.segments,
+                result.audio_files
+            );
+            Ok(Some(result))
+        }
+        Err(e) => {
+            log_error!("Import of archive {} failed: {}", archive_path, e);
+            if let Err(cleanup) = std::fs::remove_dir_all(&folder) {
+                log_warn!("Failed to remove {}: {}", folder.display(), cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+async fn import_archive_into_folder(
+    state: &AppState,
+    mut reader: ArchiveReader,
+    contents: ArchiveContents,
+    folder: &Path,
+    on_conflict: ArchiveConflict,
+) -> Result<ArchiveImportResult, String> {
+    let pool = state.db_manager.pool();
+    let archived = reader.manifest().meeting.clone();
+
+    // Audio first: a corrupted file aborts the import before the database is touched
+    let extract_folder = folder.to_path_buf();
+    let (audio_files, mut contents) = tokio::task::spawn_blocking(move || {
+        let extracted = reader.extract_audio(&contents, &extract_folder)?;
+        anyhow::Ok((extracted.len(), contents))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Invalid meeting archive: {:#}", e))?;
+
+    let id_taken: bool = sqlx::query_scalar::<_, i64>("SELECT 1 FROM meetings WHERE id = ?")
+        .bind(&archived.id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Failed to check meeting id: {}", e))?
+        .is_some();
+    // A replaced meeting is deleted together with the insert, so a failed import keeps it
+    let replace = id_taken && on_conflict == ArchiveConflict::Replace;
+    let meeting_id = if id_taken && !replace {
+        format!("meeting-{}", uuid::Uuid::new_v4())
+    } else {
+        archived.id.clone()
+    };
+    let replaced_folder: Option<String> = if replace {
+        log_info!("Replacing existing meeting {} with the archived copy", archived.id);
+        sqlx::query_scalar("SELECT folder_path FROM meetings WHERE id = ?")
+            .bind(&archived.id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Failed to check meeting id: {}", e))?
+            .flatten()
+    } else {
+        None
+    };
+
+    // Link speakers to local profiles of the same name, recreating missing ones
+    let mut profile_ids: HashMap<i64, i64> = HashMap::new();
+    for speaker in &contents.speakers {
+        let existing = SpeakersRepository::find_speaker_by_name(pool, &speaker.name)
+            .await
+            .map_err(|e| format!("Failed to look up speaker: {}", e))?;
+        let local_id = match existing {
+            Some(local) => Some(local.id),
+            None if !speaker.embedding.is_empty() => Some(
+                SpeakersRepository::enroll_speaker(
+                    pool,
+                    &speaker.name,
+                    &speaker.embedding,
+                    speaker.sample_count.max(1),
+                )
+                .await
+                .map_err(|e| format!("Failed to add speaker {}: {}", speaker.name, e))?,
+            ),
+            None => None,
+        };
+        if let Some(local_id) = local_id {
+            profile_ids.insert(speaker.profile_id, local_id);
+        }
+    }
+    for segment in &mut contents.segments {
+        segment.speaker_profile_id = segment
+            .speaker_profile_id
+            .and_then(|id| profile_ids.get(&id).copied());
+    }
+
+    write_transcripts_json_file(folder, &contents.segments)
+        .map_err(|e| format!("Failed to save transcripts: {}", e))?;
+    contents.metadata.meeting_id = Some(meeting_id.clone());
+    contents.metadata.meeting_name = Some(archived.title.clone());
+    contents.metadata.transcript_file = TRANSCRIPTS_FILE.to_string();
+    write_meeting_metadata(folder, &contents.metadata)
+        .map_err(|e| format!("Failed to save metadata: {}", e))?;
+
+    let db_segments: Vec<DbTranscriptSegment> = contents
+        .segments
+        .iter()
+        .map(|s| DbTranscriptSegment {
+            id: s.id.clone(),
+            text: s.text.clone(),
+            timestamp: s.display_time.clone(),
+            audio_start_time: Some(s.audio_start_time),
+            audio_end_time: Some(s.audio_end_time),
+            duration: Some(s.duration),
+            speaker_id: s.speaker_id.map(|id| id as i64),
+            speaker_profile_id: s.speaker_profile_id,
+            words: (!s.words.is_empty()).then(|| s.words.clone()),
+            language: s.language.clone(),
+            language_probability: s.language_probability.map(f64::from),
+            audio_source: s.audio_source.clone(),
+        })
+        .collect();
+    let created_at = chrono::DateTime::parse_from_rfc3339(&archived.created_at)
+        .map(|date| date.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|_| chrono::Utc::now());
+    let folder_path = folder.to_string_lossy().to_string();
+    TranscriptsRepository::save_transcript_with_id(
+        pool,
+        &meeting_id,
+        &archived.title,
+        created_at,
+        &db_segments,
+        Some(folder_path.clone()),
+        replace,
+    )
+    .await
+    .map_err(|e| format!("Failed to save meeting: {}", e))?;
+
+    if let Some(old_folder) = replaced_folder.map(PathBuf::from) {
+        if old_folder.is_dir() && old_folder != folder {
+            if let Err(e) = std::fs::remove_dir_all(&old_folder) {
+                log_warn!("Failed to remove folder of replaced meeting {}: {}", old_folder.display(), e);
+            }
+        }
+    }
+
+    if let Some(summary) = contents.summary {
+        let restored = async {
+            SummaryProcessesRepository::create_or_reset_process(pool, &meeting_id).await?;
+            SummaryProcessesRepository::update_process_completed(pool, &meeting_id, summary, 0, 0.0)
+                .await
+        };
+        if let Err(e) = restored.await {
+            // The meeting itself is imported; the summary can be regenerated
+            log_warn!("Failed to restore summary for {}: {}", meeting_id, e);
+        }
+    }
+
+    Ok(ArchiveImportResult {
+        original_meeting_id: (meeting_id != archived.id).then(|| archived.id.clone()),
+        meeting_id,
+        meeting_name: archived.title,
+        folder_path,
+        segments: db_segments.len(),
+        audio_files,
+    })
+}
@@ -0,0 +1,551 @@
+// export/document.rs
+//
+// A meeting as one shareable document: title and details, the summary, action items
+// and the full transcript as an appendix. The document is composed as Markdown, which
+// is also the source for the other formats: HTML through pulldown-cmark, DOCX and PDF
+// through a small block model (`Block`) rendered by `docx` and `pdf`. Everything is
+// pure Rust, so exports work offline and without any office suite installed.
+
+use super::{docx, pdf, speaker_label};
+use crate::api::{MeetingDetails, MeetingTranscript};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    Markdown,
+    Html,
+    Docx,
+    Pdf,
+}
+
+impl DocumentFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocumentFormat::Markdown => "md",
+            DocumentFormat::Html => "html",
+            DocumentFormat::Docx => "docx",
+            DocumentFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Sections to include in an exported document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocumentOptions {
+    pub summary: bool,
+    /// Action items from a structured summary, as a table
+    pub action_items: bool,
+    /// Full transcript as an appendix
+    pub transcript: bool,
+    /// Prefix transcript lines with their recording time
+    pub timestamps: bool,
+    /// Prefix transcript lines with their speaker
+    pub speaker_labels: bool,
+}
+
+impl Default for DocumentOptions {
+    fn default() -> Self {
+        Self {
+            summary: true,
+            action_items: true,
+            transcript: true,
+            timestamps: true,
+            speaker_labels: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionItem {
+    pub owner: Option<String>,
+    pub task: String,
+    pub due: Option<String>,
+}
+
+/// Everything an export draws from, loaded from the meeting and its summary process
+pub struct MeetingDocument {
+    pub title: String,
+    pub created_at: String,
+    pub summary_markdown: Option<String>,
+    pub action_items: Vec<ActionItem>,
+    pub transcript: Vec<MeetingTranscript>,
+}
+
+impl MeetingDocument {
+    /// Build from a meeting and the `result` JSON of its summary process
+    pub fn new(meeting: MeetingDetails, summary_result: Option<&Value>) -> Self {
+        let summary_markdown = summary_result
+            .and_then(|r| r["markdown"].as_str())
+            .map(|m| m.trim().to_string())
+            .filter(|m| !m.is_empty());
+        let action_items = summary_result
+            .map(|r| action_items_from_structured(&r["structured"]))
+            .unwrap_or_default();
+
+        let mut transcript = meeting.transcripts;
+        // Rows come back in insertion order; untimed (legacy) rows keep it
+        transcript.sort_by(|a, b| {
+            a.audio_start_time
+                .unwrap_or(f64::MAX)
+                .total_cmp(&b.audio_start_time.unwrap_or(f64::MAX))
+        });
+
+        Self {
+            title: meeting.title,
+            created_at: meeting.created_at,
+            summary_markdown,
+            action_items,
+            transcript,
+        }
+    }
+
+    /// Speakers in order of first appearance
+    fn participants(&self) -> Vec<String> {
+        let mut participants: Vec<String> = Vec::new();
+        for label in self.transcript.iter().filter_map(speaker_label) {
+            if !participants.contains(&label) {
+                participants.push(label);
+            }
+        }
+        participants
+    }
+
+    /// Italic line under the title: date, length and participants
+    fn details_line(&self) -> String {
+        let mut details = Vec::new();
+        if let Ok(created) = DateTime::parse_from_rfc3339(&self.created_at) {
+            details.push(created.with_timezone(&Local).format("%B %-d, %Y %H:%M").to_string());
+        }
+        let length = self
+            .transcript
+            .iter()
+            .filter_map(|t| t.audio_end_time.or(t.audio_start_time))
+            .fold(0.0, f64::max);
+        if length > 0.0 {
+            let minutes = (length / 60.0).round() as u64;
+            details.push(if minutes >= 60 {
+                format!("{}h {:02}m", minutes / 60, minutes % 60)
+            } else {
+                format!("{} min", minutes.max(1))
+            });
+        }
+        let participants = self.participants();
+        if !participants.is_empty() {
+            details.push(format!("Participants: {}", participants.join(", ")));
+        }
+        escape_markdown(&details.join(" · "))
+    }
+
+    /// Title, details, summary and action items
+    fn body_markdown(&self, options: &DocumentOptions) -> String {
+        let mut markdown = format!("# {}\n\n", escape_markdown(&self.title));
+        let details = self.details_line();
+        if !details.is_empty() {
+            markdown.push_str(&format!("*{}*\n\n", details));
+        }
+
+        if options.summary {
+            markdown.push_str("## Summary\n\n");
+            match &self.summary_markdown {
+                Some(summary) => markdown.push_str(summary),
+                None => markdown.push_str("*No summary has been generated for this meeting.*"),
+            }
+            markdown.push_str("\n\n");
+        }
+
+        if options.action_items && !self.action_items.is_empty() {
+            markdown.push_str("## Action Items\n\n| Owner | Task | Due |\n| --- | --- | --- |\n");
+            for item in &self.action_items {
+                let cell = |value: Option<&str>| {
+                    value.map(|v| escape_markdown(v).replace('\n', " ")).unwrap_or_else(|| "-".to_string())
+                };
+                markdown.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    cell(item.owner.as_deref()),
+                    cell(Some(&item.task)),
+                    cell(item.due.as_deref())
+                ));
+            }
+            markdown.push('\n');
+        }
+        markdown.trim_end().to_string()
+    }
+
+    /// Transcript appendix, None when excluded or empty
+    fn appendix_markdown(&self, options: &DocumentOptions) -> Option<String> {
+        if !options.transcript || self.transcript.is_empty() {
+            return None;
+        }
+
+        let mut markdown = String::from("## Appendix: Transcript\n\n");
+        for segment in &self.transcript {
+            let mut prefix = Vec::new();
+            if options.timestamps {
+                prefix.push(match segment.audio_start_time {
+                    Some(start) => format!("[{}]", clock(start)),
+                    None => format!("[{}]", segment.timestamp),
+                });
+            }
+            if options.speaker_labels {
+                if let Some(speaker) = speaker_label(segment) {
+                    prefix.push(format!("{}:", speaker));
+                }
+            }
+            let text = escape_markdown(segment.text.trim());
+            if prefix.is_empty() {
+                markdown.push_str(&format!("{}\n\n", text));
+            } else {
+                markdown.push_str(&format!("**{}** {}\n\n", escape_markdown(&prefix.join(" ")), text));
+            }
+        }
+        Some(markdown.trim_end().to_string())
+    }
+
+    pub fn to_markdown(&self, options: &DocumentOptions) -> String {
+        let mut markdown = self.body_markdown(options);
+        if let Some(appendix) = self.appendix_markdown(options) {
+            markdown.push_str("\n\n---\n\n");
+            markdown.push_str(&appendix);
+        }
+        markdown.push('\n');
+        markdown
+    }
+
+    /// Standalone HTML page with embedded styles
+    pub fn to_html(&self, options: &DocumentOptions) -> String {
+        let mut body = String::new();
+        pulldown_cmark::html::push_html(&mut body, markdown_events(&self.body_markdown(options)));
+        if let Some(appendix) = self.appendix_markdown(options) {
+            body.push_str("<section class=\"appendix\">\n");
+            pulldown_cmark::html::push_html(&mut body, markdown_events(&appendix));
+            body.push_str("</section>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.title),
+            HTML_STYLE,
+            body
+        )
+    }
+
+    /// Block model for the paginated formats; the appendix starts on a new page
+    pub fn to_blocks(&self, options: &DocumentOptions) -> Vec<Block> {
+        let mut blocks = parse_blocks(&self.body_markdown(options));
+        if let Some(appendix) = self.appendix_markdown(options) {
+            blocks.push(Block::PageBreak);
+            blocks.extend(parse_blocks(&appendix));
+        }
+        blocks
+    }
+
+    pub fn render(&self, format: DocumentFormat, options: &DocumentOptions) -> Result<Vec<u8>> {
+        match format {
+            DocumentFormat::Markdown => Ok(self.to_markdown(options).into_bytes()),
+            DocumentFormat::Html => Ok(self.to_html(options).into_bytes()),
+            DocumentFormat::Docx => docx::render(&self.title, &self.to_blocks(options)),
+            DocumentFormat::Pdf => Ok(pdf::render(&self.title, &self.to_blocks(options))),
+        }
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;max-width:820px;margin:2rem auto;padding:0 1.5rem;line-height:1.55;color:#1f2933}\
+h1{font-size:1.9rem;margin-bottom:.25rem}h2{margin-top:2rem;border-bottom:1px solid #e4e7eb;padding-bottom:.3rem}\
+table{border-collapse:collapse;width:100%}th,td{border:1px solid #d9dde3;padding:.4rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f5f7fa}code,pre{font-family:Menlo,Consolas,monospace;font-size:.9em}\
+.appendix{margin-top:3rem}.appendix p{margin:.35rem 0}\
+@media print{.appendix{page-break-before:always}}";
+
+/// Action items in a structured summary: arrays of objects with a "task"
+fn action_items_from_structured(structured: &Value) -> Vec<ActionItem> {
+    let Some(fields) = structured.as_object() else {
+        return Vec::new();
+    };
+    let text = |item: &Value, key: &str| {
+        item[key].as_str().map(str::trim).filter(|s| !s.is_empty()).map(String::from)
+    };
+    fields
+        .values()
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(|item| {
+            Some(ActionItem {
+                task: text(item, "task")?,
+                owner: text(item, "owner"),
+                due: text(item, "due"),
+            })
+        })
+        .collect()
+}
+
+/// `H:MM:SS` recording time
+fn clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{}:{:02}:{:02}", total / 3600, (total / 60) % 60, total % 60)
+}
+
+/// Escape text so Markdown renders it literally
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Markdown events with raw HTML turned into text, so summaries can't inject markup
+fn markdown_events(markdown: &str) -> impl Iterator<Item = Event<'_>> {
+    Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        other => other,
+    })
+}
+
+/// Inline text with its style
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub code: bool,
+}
+
+impl Span {
+    /// Forced line break inside a block
+    pub fn is_break(&self) -> bool {
+        self.text == "\n"
+    }
+}
+
+/// Block-level content of the paginated formats
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Heading(u8, Vec<Span>),
+    Paragraph(Vec<Span>),
+    /// List entry with its nesting depth and marker ("•" or "1.")
+    ListItem { depth: usize, marker: String, spans: Vec<Span> },
+    /// Rows of cells; the first row is the header
+    Table(Vec<Vec<Vec<Span>>>),
+    Code(String),
+    Rule,
+    PageBreak,
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Parse Markdown into blocks
+pub fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut spans: Vec<Span> = Vec::new();
+    let (mut bold, mut italic) = (0u32, 0u32);
+    // Per open list: next number for ordered lists
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut item_markers: Vec<String> = Vec::new();
+    let mut table: Option<Vec<Vec<Vec<Span>>>> = None;
+    let mut row: Vec<Vec<Span>> = Vec::new();
+    let mut code: Option<String> = None;
+
+    let flush_item = |blocks: &mut Vec<Block>, spans: &mut Vec<Span>, markers: &mut Vec<String>, depth: usize| {
+        if let Some(marker) = markers.last_mut() {
+            if !spans.is_empty() {
+                blocks.push(Block::ListItem {
+                    depth,
+                    marker: std::mem::take(marker),
+                    spans: std::mem::take(spans),
+                });
+            }
+        }
+    };
+
+    for event in markdown_events(markdown) {
+        match event {
+            Event::Start(Tag::Strong) => bold += 1,
+            Event::End(TagEnd::Strong) => bold = bold.saturating_sub(1),
+            Event::Start(Tag::Emphasis) => italic += 1,
+            Event::End(TagEnd::Emphasis) => italic = italic.saturating_sub(1),
+            Event::Start(Tag::CodeBlock(_)) => code = Some(String::new()),
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some(code) = code.take() {
+                    blocks.push(Block::Code(code.trim_end().to_string()));
+                }
+            }
+            Event::Text(text) => match code.as_mut() {
+                Some(code) => code.push_str(&text),
+                None => spans.push(Span { text: text.to_string(), bold: bold > 0, italic: italic > 0, code: false }),
+            },
+            Event::Code(text) => {
+                spans.push(Span { text: text.to_string(), bold: bold > 0, italic: italic > 0, code: true })
+            }
+            Event::SoftBreak => spans.push(Span { text: " ".to_string(), bold: false, italic: false, code: false }),
+            Event::HardBreak => spans.push(Span { text: "\n".to_string(), bold: false, italic: false, code: false }),
+            Event::Rule => blocks.push(Block::Rule),
+            Event::End(TagEnd::Heading(level)) => {
+                blocks.push(Block::Heading(heading_level(level), std::mem::take(&mut spans)))
+            }
+            Event::End(TagEnd::Paragraph) => {
+                if item_markers.is_empty() && !spans.is_empty() {
+                    blocks.push(Block::Paragraph(std::mem::take(&mut spans)));
+                } else if !spans.is_empty() {
+                    // Loose list items: keep paragraphs of one item apart
+                    spans.push(Span { text: "\n".to_string(), bold: false, italic: false, code: false });
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                // Text of the enclosing item comes before the nested list
+                let depth = lists.len().saturating_sub(1);
+                flush_item(&mut blocks, &mut spans, &mut item_markers, depth);
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                let marker = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "•".to_string(),
+                };
+                item_markers.push(marker);
+            }
+            Event::End(TagEnd::Item) => {
+                while spans.last().is_some_and(Span::is_break) {
+                    spans.pop();
+                }
+                let depth = lists.len().saturating_sub(1);
+                flush_item(&mut blocks, &mut spans, &mut item_markers, depth);
+                item_markers.pop();
+            }
+            Event::Start(Tag::Table(_)) => table = Some(Vec::new()),
+            Event::End(TagEnd::TableCell) => row.push(std::mem::take(&mut spans)),
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                if let Some(table) = table.as_mut() {
+                    table.push(std::mem::take(&mut row));
+                }
+            }
+            Event::End(TagEnd::Table) => {
+                if let Some(table) = table.take() {
+                    blocks.push(Block::Table(table));
+                }
+            }
+            _ => {}
+        }
+    }
+    if !spans.is_empty() {
+        blocks.push(Block::Paragraph(spans));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn meeting() -> MeetingDetails {
+        let segment = |id: &str, text: &str, start: f64, speaker: &str| MeetingTranscript {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: String::new(),
+            audio_start_time: Some(start),
+            audio_end_time: Some(start + 5.0),
+            duration: Some(5.0),
+            speaker_id: None,
+            speaker_profile_id: None,
+            speaker_name: Some(speaker.to_string()),
+            words: None,
+            language: None,
+            language_probability: None,
+            audio_source: None,
+        };
+        MeetingDetails {
+            id: "meeting-1".to_string(),
+            title: "Weekly sync".to_string(),
+            created_at: "2025-03-04T10:00:00+00:00".to_string(),
+            updated_at: "2025-03-04T11:00:00+00:00".to_string(),
+            // Stored out of order on purpose
+            transcripts: vec![
+                segment("b", "Sounds good, I'll take it.", 65.0, "Bob"),
+                segment("a", "Let's ship the *beta* on Friday.", 3.0, "Alice"),
+            ],
+        }
+    }
+
+    fn summary() -> Value {
+        json!({
+            "markdown": "**Summary**\n\n- Beta ships Friday\n- Docs follow\n",
+            "structured": {
+                "summary": "Beta ships Friday",
+                "action_items": [{"owner": "Bob", "task": "Cut the beta build", "due": "Friday"}]
+            }
+        })
+    }
+
+    #[test]
+    fn test_composes_selected_sections_in_order() {
+        let document = MeetingDocument::new(meeting(), Some(&summary()));
+        let markdown = document.to_markdown(&DocumentOptions::default());
+
+        let summary_at = markdown.find("## Summary").unwrap();
+        let actions_at = markdown.find("## Action Items").unwrap();
+        let appendix_at = markdown.find("## Appendix: Transcript").unwrap();
+        assert!(summary_at < actions_at && actions_at < appendix_at);
+        assert!(markdown.contains("Participants: Alice, Bob"));
+        assert!(markdown.contains("| Bob | Cut the beta build | Friday |"));
+        // Sorted by recording time, with transcript text kept literal
+        let alice = markdown.find("**\\[0:00:03\\] Alice:** Let's ship the \\*beta\\* on Friday.").unwrap();
+        assert!(alice < markdown.find("\\[0:01:05\\] Bob:").unwrap());
+
+        let options = DocumentOptions { action_items: false, transcript: false, ..Default::default() };
+        let markdown = document.to_markdown(&options);
+        assert!(!markdown.contains("Action Items") && !markdown.contains("Appendix"));
+    }
+
+    #[test]
+    fn test_html_is_standalone_and_escapes_raw_markup() {
+        let mut details = meeting();
+        details.title = "Q&A <review>".to_string();
+        let result = json!({"markdown": "Plan <script>alert(1)</script>"});
+        let html = MeetingDocument::new(details, Some(&result)).to_html(&DocumentOptions::default());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Q&amp;A &lt;review&gt;</title>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("<section class=\"appendix\">"));
+    }
+
+    #[test]
+    fn test_parses_markdown_into_blocks() {
+        let blocks = parse_blocks("# Title\n\nSome **bold** text\n\n1. one\n2. two\n   - nested\n\n| A | B |\n| - | - |\n| x | y |\n");
+
+        assert!(matches!(&blocks[0], Block::Heading(1, spans) if spans[0].text == "Title"));
+        assert!(matches!(&blocks[1], Block::Paragraph(spans) if spans.iter().any(|s| s.bold && s.text == "bold")));
+        assert!(matches!(&blocks[2], Block::ListItem { depth: 0, marker, .. } if marker == "1."));
+        assert!(matches!(&blocks[3], Block::ListItem { depth: 0, marker, .. } if marker == "2."));
+        assert!(matches!(&blocks[4], Block::ListItem { depth: 1, marker, .. } if marker == "•"));
+        assert!(matches!(&blocks[5], Block::Table(rows) if rows.len() == 2 && rows[1][1][0].text == "y"));
+    }
+}
@@ -0,0 +1,208 @@
+// export/docx.rs
+//
+// Minimal WordprocessingML writer for exported documents: one document part with
+// heading styles, so Word's navigation pane and outline work, packed into the
+// OPC zip container.
+
+use super::document::{Block, Span};
+use anyhow::Result;
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/><Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/><Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/></Types>"#;
+
+const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/></Relationships>"#;
+
+const DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:eastAsia="Calibri" w:cs="Calibri"/><w:sz w:val="22"/><w:szCs w:val="22"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr><w:spacing w:after="120" w:line="276" w:lineRule="auto"/></w:pPr></w:pPrDefault></w:docDefaults><w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="120"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="36"/><w:szCs w:val="36"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240" w:after="80"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="28"/><w:szCs w:val="28"/></w:rPr></w:style><w:style w:type="paragraph" w:styleId="Heading3"><w:name w:val="heading 3"/><w:basedOn w:val="Normal"/><w:next w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="200" w:after="60"/><w:outlineLvl w:val="2"/></w:pPr><w:rPr><w:b/><w:sz w:val="24"/><w:szCs w:val="24"/></w:rPr></w:style></w:styles>"#;
+
+/// Escape text for XML, dropping characters XML 1.0 cannot contain
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(' '),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn run(text: &str, bold: bool, italic: bool, mono: bool) -> String {
+    let mut props = String::new();
+    if mono {
+        props.push_str(r#"<w:rFonts w:ascii="Courier New" w:hAnsi="Courier New" w:cs="Courier New"/>"#);
+    }
+    if bold {
+        props.push_str("<w:b/>");
+    }
+    if italic {
+        props.push_str("<w:i/>");
+    }
+    let props = if props.is_empty() { props } else { format!("<w:rPr>{}</w:rPr>", props) };
+    format!(r#"<w:r>{}<w:t xml:space="preserve">{}</w:t></w:r>"#, props, escape_xml(text))
+}
+
+fn runs(spans: &[Span], force_bold: bool) -> String {
+    spans
+        .iter()
+        .map(|span| {
+            if span.is_break() {
+                "<w:r><w:br/></w:r>".to_string()
+            } else {
+                run(&span.text, span.bold || force_bold, span.italic, span.code)
+            }
+        })
+        .collect()
+}
+
+fn paragraph(properties: &str, content: &str) -> String {
+    if properties.is_empty() {
+        format!("<w:p>{}</w:p>", content)
+    } else {
+        format!("<w:p><w:pPr>{}</w:pPr>{}</w:p>", properties, content)
+    }
+}
+
+fn table(rows: &[Vec<Vec<Span>>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    // Full text width (A4 minus 1" margins) in twentieths of a point, split evenly
+    let column_width = 9026 / columns;
+
+    let mut xml = String::from(
+        r#"<w:tbl><w:tblPr><w:tblW w:w="5000" w:type="pct"/><w:tblBorders><w:top w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/><w:left w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/><w:bottom w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/><w:right w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/><w:insideH w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/><w:insideV w:val="single" w:sz="4" w:space="0" w:color="BFBFBF"/></w:tblBorders><w:tblCellMar><w:left w:w="100" w:type="dxa"/><w:right w:w="100" w:type="dxa"/></w:tblCellMar></w:tblPr><w:tblGrid>"#,
+    );
+    for _ in 0..columns {
+        xml.push_str(&format!(r#"<w:gridCol w:w="{}"/>"#, column_width));
+    }
+    xml.push_str("</w:tblGrid>");
+
+    for (index, row) in rows.iter().enumerate() {
+        let header = index == 0;
+        xml.push_str(if header { "<w:tr><w:trPr><w:tblHeader/></w:trPr>" } else { "<w:tr>" });
+        for column in 0..columns {
+            let cell = row.get(column).map(|spans| runs(spans, header)).unwrap_or_default();
+            xml.push_str(&format!(
+                r#"<w:tc><w:tcPr><w:tcW w:w="{}" w:type="dxa"/></w:tcPr>{}</w:tc>"#,
+                column_width,
+                paragraph(r#"<w:spacing w:after="0"/>"#, &cell)
+            ));
+        }
+        xml.push_str("</w:tr>");
+    }
+    xml.push_str("</w:tbl>");
+    // Word requires a paragraph between a table and whatever follows
+    xml.push_str("<w:p/>");
+    xml
+}
+
+fn body(blocks: &[Block]) -> String {
+    let mut xml = String::new();
+    for block in blocks {
+        match block {
+            Block::Heading(level, spans) => {
+                let style = format!(r#"<w:pStyle w:val="Heading{}"/>"#, (*level).clamp(1, 3));
+                xml.push_str(&paragraph(&style, &runs(spans, false)));
+            }
+            Block::Paragraph(spans) => xml.push_str(&paragraph("", &runs(spans, false))),
+            Block::ListItem { depth, marker, spans } => {
+                let indent = 360 * (*depth as u32 + 1);
+                let properties = format!(
+                    r#"<w:spacing w:after="40"/><w:ind w:left="{}" w:hanging="360"/>"#,
+                    indent
+                );
+                let content = format!("{}{}", run(&format!("{}\u{a0}", marker), false, false, false), runs(spans, false));
+                xml.push_str(&paragraph(&properties, &content));
+            }
+            Block::Table(rows) => xml.push_str(&table(rows)),
+            Block::Code(code) => {
+                let content = code
+                    .lines()
+                    .map(|line| run(line, false, false, true))
+                    .collect::<Vec<_>>()
+                    .join("<w:r><w:br/></w:r>");
+                xml.push_str(&paragraph("", &content));
+            }
+            Block::Rule => xml.push_str(&paragraph(
+                r#"<w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="BFBFBF"/></w:pBdr>"#,
+                "",
+            )),
+            Block::PageBreak => xml.push_str(r#"<w:p><w:r><w:br w:type="page"/></w:r></w:p>"#),
+        }
+    }
+    xml
+}
+
+fn document_xml(blocks: &[Block]) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440" w:header="708" w:footer="708" w:gutter="0"/></w:sectPr></w:body></w:document>"#,
+        body(blocks)
+    )
+}
+
+fn core_xml(title: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><dc:title>{}</dc:title><dc:creator>Meetily</dc:creator><dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created></cp:coreProperties>"#,
+        escape_xml(title),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+    )
+}
+
+/// Write blocks as a .docx file
+pub fn render(title: &str, blocks: &[Block]) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", PACKAGE_RELS.to_string()),
+        ("docProps/core.xml", core_xml(title)),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELS.to_string()),
+        ("word/styles.xml", STYLES.to_string()),
+        ("word/document.xml", document_xml(blocks)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::document::parse_blocks;
+    use std::io::Read;
+
+    #[test]
+    fn test_writes_a_docx_package_with_escaped_text() {
+        let blocks = parse_blocks("# R&D <sync>\n\n- first\n\n| A | B |\n| - | - |\n| x | y |\n");
+        let bytes = render("R&D", &blocks).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        for part in ["[Content_Types].xml", "_rels/.rels", "word/styles.xml"] {
+            assert!(archive.by_name(part).is_ok(), "missing {}", part);
+        }
+        let mut document = String::new();
+        archive.by_name("word/document.xml").unwrap().read_to_string(&mut document).unwrap();
+        assert!(document.contains(r#"<w:pStyle w:val="Heading1"/>"#));
+        assert!(document.contains("R&amp;D") && document.contains("&lt;sync&gt;"));
+        assert!(document.contains("<w:tbl>") && document.contains("<w:tblHeader/>"));
+    }
+}
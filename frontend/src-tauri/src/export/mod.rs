@@ -0,0 +1,46 @@
+/// Export module - meeting transcripts as files for use outside the app
+///
+/// This module contains:
+/// - Subtitle writers (SRT / WebVTT) that re-flow transcript segments into
+///   caption-sized cues
+/// - Meeting documents (Markdown, HTML, DOCX, PDF) with summary, action items and
+///   a transcript appendix, rendered without external tools
+/// - Portable meeting archives (zip) with a checksummed manifest, for moving a
+///   meeting with its audio, summary and speakers to another installation
+/// - Tauri commands for frontend integration
+
+pub mod archive;
+pub mod commands;
+pub mod document;
+mod docx;
+mod pdf;
+pub mod subtitles;
+
+pub use document::{DocumentFormat, DocumentOptions, MeetingDocument};
+pub use subtitles::{SubtitleFormat, SubtitleOptions};
+
+use crate::api::MeetingTranscript;
+
+/// Speaker shown for a segment, matching the transcript view: the registered or
+/// diarized speaker, else "Me"/"Them" for dual-channel recordings
+pub fn speaker_label(transcript: &MeetingTranscript) -> Option<String> {
+    if let Some(name) = &transcript.speaker_name {
+        return Some(name.clone());
+    }
+    if let Some(id) = transcript.speaker_id {
+        return Some(format!("Speaker {}", id));
+    }
+    transcript.audio_source.as_deref().map(|source| {
+        if source == "mic" { "Me" } else { "Them" }.to_string()
+    })
+}
+
+/// File name stem for exported files, derived from the meeting title
+pub fn export_file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    let stem = stem.trim();
+    if stem.is_empty() { "meeting".to_string() } else { stem.to_string() }
+}
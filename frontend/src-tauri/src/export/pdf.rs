@@ -0,0 +1,487 @@
+// export/pdf.rs
+//
+// Minimal PDF writer for exported documents. Text is set in the standard Helvetica and
+// Courier fonts every PDF viewer ships, so no font files are embedded; these fonts cover
+// Latin-1 (WinAnsi) text and other characters are replaced with '?'. Layout is a single
+// column on A4 with word wrapping from the fonts' metrics.
+
+use super::document::{Block, Span};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const TEXT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+const BODY_SIZE: f32 = 10.5;
+const LIST_INDENT: f32 = 16.0;
+const CELL_PADDING: f32 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl Font {
+    const ALL: [Font; 5] = [Font::Regular, Font::Bold, Font::Italic, Font::BoldItalic, Font::Mono];
+
+    fn of(span: &Span, force_bold: bool) -> Font {
+        match (span.code, span.bold || force_bold, span.italic) {
+            (true, _, _) => Font::Mono,
+            (_, true, true) => Font::BoldItalic,
+            (_, true, false) => Font::Bold,
+            (_, false, true) => Font::Italic,
+            _ => Font::Regular,
+        }
+    }
+
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+            Font::BoldItalic => "F4",
+            Font::Mono => "F5",
+        }
+    }
+
+    fn base_font(&self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Italic => "Helvetica-Oblique",
+            Font::BoldItalic => "Helvetica-BoldOblique",
+            Font::Mono => "Courier",
+        }
+    }
+
+    /// Advance width of a WinAnsi byte in points
+    fn width(&self, byte: u8, size: f32) -> f32 {
+        let units = match self {
+            Font::Mono => 600,
+            Font::Regular | Font::Italic => helvetica_width(byte),
+            // Bold glyphs are a little wider; close enough for line breaking
+            Font::Bold | Font::BoldItalic => helvetica_width(byte) * 105 / 100,
+        };
+        units as f32 * size / 1000.0
+    }
+
+    fn text_width(&self, text: &[u8], size: f32) -> f32 {
+        text.iter().map(|b| self.width(*b, size)).sum()
+    }
+}
+
+/// Helvetica glyph widths (1/1000 em) for printable ASCII, from the font's AFM metrics
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // space - /
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0 - ?
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @ - O
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P - _
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // ` - o
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p - ~
+];
+
+fn helvetica_width(byte: u8) -> u32 {
+    match byte {
+        0x20..=0x7e => HELVETICA_WIDTHS[(byte - 0x20) as usize] as u32,
+        0x95 => 350, // bullet
+        _ => 556,
+    }
+}
+
+/// Encode text as WinAnsi bytes
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '\t' | '\n' | '\r' => b' ',
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '™' => 0x99,
+            'Œ' => 0x8c,
+            'œ' => 0x9c,
+            'Š' => 0x8a,
+            'š' => 0x9a,
+            'Ž' => 0x8e,
+            'ž' => 0x9e,
+            'Ÿ' => 0x9f,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// PDF literal string
+fn pdf_string(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 2);
+    out.push(b'(');
+    for &b in bytes {
+        if matches!(b, b'(' | b')' | b'\\') {
+            out.push(b'\\');
+        }
+        out.push(b);
+    }
+    out.push(b')');
+    out
+}
+
+/// A run of text in one font within a laid-out line
+struct Run {
+    font: Font,
+    text: Vec<u8>,
+}
+
+/// Break styled spans into lines no wider than `width`
+fn wrap(spans: &[Span], force_bold: bool, size: f32, width: f32) -> Vec<Vec<Run>> {
+    let mut lines: Vec<Vec<Run>> = vec![Vec::new()];
+    let mut line_width = 0.0;
+    // Whether a space is owed before the next word on this line
+    let mut pending_space = false;
+
+    for span in spans {
+        if span.is_break() {
+            lines.push(Vec::new());
+            line_width = 0.0;
+            pending_space = false;
+            continue;
+        }
+        let font = Font::of(span, force_bold);
+        let starts_with_space = span.text.starts_with(char::is_whitespace);
+        let mut words = span.text.split_whitespace().peekable();
+        if starts_with_space {
+            pending_space = true;
+        }
+
+        while let Some(word) = words.next() {
+            let bytes = win_ansi(word);
+            let space = if pending_space && line_width > 0.0 { font.width(b' ', size) } else { 0.0 };
+            let word_width = font.text_width(&bytes, size);
+            if line_width > 0.0 && line_width + space + word_width > width {
+                lines.push(Vec::new());
+                line_width = 0.0;
+            }
+
+            let line = lines.last_mut().expect("at least one line");
+            let mut text = Vec::new();
+            if pending_space && line_width > 0.0 {
+                text.push(b' ');
+                line_width += space;
+            }
+            text.extend_from_slice(&bytes);
+            line_width += word_width;
+            match line.last_mut() {
+                Some(run) if run.font == font => run.text.extend(text),
+                _ => line.push(Run { font, text }),
+            }
+            pending_space = words.peek().is_some();
+        }
+        if span.text.ends_with(char::is_whitespace) {
+            pending_space = true;
+        }
+    }
+    lines
+}
+
+struct Layout {
+    pages: Vec<Vec<u8>>,
+    current: Vec<u8>,
+    /// Baseline of the next line, from the bottom of the page
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self { pages: Vec::new(), current: Vec::new(), y: PAGE_HEIGHT - MARGIN }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.y >= PAGE_HEIGHT - MARGIN
+    }
+
+    /// Start a new page unless `height` still fits above the bottom margin
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        if !self.at_page_top() {
+            self.y -= height;
+        }
+    }
+
+    fn text_at(&mut self, runs: &[Run], x: f32, y: f32, size: f32) {
+        let mut x = x;
+        for run in runs {
+            self.current.extend_from_slice(
+                format!("BT /{} {:.1} Tf {:.2} {:.2} Td ", run.font.resource(), size, x, y).as_bytes(),
+            );
+            self.current.extend(pdf_string(&run.text));
+            self.current.extend_from_slice(b" Tj ET\n");
+            x += run.font.text_width(&run.text, size);
+        }
+    }
+
+    /// Set wrapped lines at `x`, breaking pages between lines
+    fn lines(&mut self, lines: &[Vec<Run>], x: f32, size: f32, leading: f32) {
+        for line in lines {
+            self.reserve(leading);
+            self.y -= leading;
+            self.text_at(line, x, self.y + (leading - size) / 2.0, size);
+        }
+    }
+
+    fn horizontal_line(&mut self, x1: f32, x2: f32, y: f32) {
+        self.current
+            .extend_from_slice(format!("0.75 G 0.5 w {:.2} {:.2} m {:.2} {:.2} l S 0 G\n", x1, y, x2, y).as_bytes());
+    }
+
+    fn table(&mut self, rows: &[Vec<Vec<Span>>]) {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let size = BODY_SIZE - 1.0;
+        let leading = size * 1.35;
+        let column_width = TEXT_WIDTH / columns as f32;
+
+        for (index, row) in rows.iter().enumerate() {
+            let cells: Vec<Vec<Vec<Run>>> = (0..columns)
+                .map(|c| {
+                    row.get(c)
+                        .map(|spans| wrap(spans, index == 0, size, column_width - 2.0 * CELL_PADDING))
+                        .unwrap_or_default()
+                })
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1).max(1) as f32 * leading + 2.0 * CELL_PADDING;
+
+            self.reserve(height);
+            let top = self.y;
+            for (c, cell) in cells.iter().enumerate() {
+                let x = MARGIN + c as f32 * column_width + CELL_PADDING;
+                for (l, line) in cell.iter().enumerate() {
+                    let baseline = top - CELL_PADDING - (l + 1) as f32 * leading + (leading - size) / 2.0;
+                    self.text_at(line, x, baseline, size);
+                }
+            }
+            self.y = top - height;
+            self.horizontal_line(MARGIN, MARGIN + TEXT_WIDTH, self.y);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Heading(level, spans) => {
+                let size = match level {
+                    1 => 20.0,
+                    2 => 15.0,
+                    3 => 12.5,
+                    _ => 11.0,
+                };
+                let lines = wrap(spans, true, size, TEXT_WIDTH);
+                self.space(size * 0.8);
+                // Keep a heading together with the first lines below it
+                self.reserve(lines.len() as f32 * size * 1.25 + 3.0 * BODY_SIZE * 1.4);
+                self.lines(&lines, MARGIN, size, size * 1.25);
+                self.y -= size * 0.35;
+            }
+            Block::Paragraph(spans) => {
+                let lines = wrap(spans, false, BODY_SIZE, TEXT_WIDTH);
+                self.lines(&lines, MARGIN, BODY_SIZE, BODY_SIZE * 1.4);
+                self.y -= BODY_SIZE * 0.6;
+            }
+            Block::ListItem { depth, marker, spans } => {
+                let indent = LIST_INDENT * (*depth as f32 + 1.0);
+                let lines = wrap(spans, false, BODY_SIZE, TEXT_WIDTH - indent);
+                let leading = BODY_SIZE * 1.4;
+                self.reserve(leading);
+                let marker = [Run { font: Font::Regular, text: win_ansi(marker) }];
+                self.text_at(&marker, MARGIN + indent - LIST_INDENT + 4.0, self.y - leading + (leading - BODY_SIZE) / 2.0, BODY_SIZE);
+                self.lines(&lines, MARGIN + indent, BODY_SIZE, leading);
+                self.y -= BODY_SIZE * 0.2;
+            }
+            Block::Table(rows) => {
+                self.table(rows);
+                self.y -= BODY_SIZE * 0.8;
+            }
+            Block::Code(code) => {
+                let size = BODY_SIZE - 1.5;
+                let lines: Vec<Vec<Run>> = code
+                    .lines()
+                    .map(|line| vec![Run { font: Font::Mono, text: win_ansi(line) }])
+                    .collect();
+                self.lines(&lines, MARGIN + 8.0, size, size * 1.3);
+                self.y -= BODY_SIZE * 0.6;
+            }
+            Block::Rule => {
+                self.space(BODY_SIZE * 0.5);
+                self.horizontal_line(MARGIN, MARGIN + TEXT_WIDTH, self.y);
+                self.y -= BODY_SIZE;
+            }
+            Block::PageBreak => {
+                if !self.at_page_top() {
+                    self.new_page();
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Vec<u8>> {
+        if !self.current.is_empty() || self.pages.is_empty() {
+            self.pages.push(self.current);
+        }
+        self.pages
+    }
+}
+
+/// Serialize pages into a PDF file, numbering them in the footer
+fn assemble(title: &str, pages: Vec<Vec<u8>>) -> Vec<u8> {
+    let mut out: Vec<u8> = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets: Vec<usize> = Vec::new();
+    let mut object = |out: &mut Vec<u8>, body: &[u8]| {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", offsets.len()).as_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(b"\nendobj\n");
+    };
+
+    // Object numbers: 1 catalog, 2 page tree, 3 info, 4.. fonts, then page + content pairs
+    let font_base = 4;
+    let page_base = font_base + Font::ALL.len();
+    let page_count = pages.len();
+
+    object(&mut out, b"<< /Type /Catalog /Pages 2 0 R >>");
+    let kids: Vec<String> = (0..page_count).map(|i| format!("{} 0 R", page_base + 2 * i)).collect();
+    object(&mut out, format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_count).as_bytes());
+    let mut info = b"<< /Title ".to_vec();
+    info.extend(pdf_string(&win_ansi(title)));
+    info.extend_from_slice(b" /Producer (Meetily) >>");
+    object(&mut out, &info);
+
+    for font in Font::ALL {
+        object(
+            &mut out,
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                font.base_font()
+            )
+            .as_bytes(),
+        );
+    }
+
+    let fonts: Vec<String> = Font::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, font)| format!("/{} {} 0 R", font.resource(), font_base + i))
+        .collect();
+    for (i, mut content) in pages.into_iter().enumerate() {
+        let footer = format!("Page {} of {}", i + 1, page_count);
+        let size = 8.0;
+        let x = (PAGE_WIDTH - Font::Regular.text_width(footer.as_bytes(), size)) / 2.0;
+        content.extend_from_slice(format!("0.45 g BT /F1 {:.1} Tf {:.2} {:.2} Td ", size, x, MARGIN / 2.0).as_bytes());
+        content.extend(pdf_string(footer.as_bytes()));
+        content.extend_from_slice(b" Tj ET 0 g\n");
+
+        object(
+            &mut out,
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                fonts.join(" "),
+                page_base + 2 * i + 1
+            )
+            .as_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        object(&mut out, &stream);
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1).as_bytes());
+    for offset in &offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{}\n%%EOF\n",
+            offsets.len() + 1,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+/// Write blocks as a PDF file
+pub fn render(title: &str, blocks: &[Block]) -> Vec<u8> {
+    let mut layout = Layout::new();
+    for block in blocks {
+        layout.block(block);
+    }
+    assemble(title, layout.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::document::parse_blocks;
+
+    fn plain(text: &str) -> Vec<Span> {
+        vec![Span { text: text.to_string(), bold: false, italic: false, code: false }]
+    }
+
+    #[test]
+    fn test_wraps_words_within_the_width() {
+        let lines = wrap(&plain("the quick brown fox jumps over the lazy dog"), false, 10.0, 80.0);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            let width: f32 = line.iter().map(|r| r.font.text_width(&r.text, 10.0)).sum();
+            assert!(width <= 80.0);
+        }
+        assert_eq!(win_ansi("café – “ok”"), b"caf\xe9 \x96 \x93ok\x94".to_vec());
+    }
+
+    #[test]
+    fn test_paginates_and_writes_a_valid_xref() {
+        let markdown: String = (0..120).map(|i| format!("Paragraph number {} (with parens)\n\n", i)).collect();
+        let mut blocks = parse_blocks(&markdown);
+        blocks.push(Block::PageBreak);
+        blocks.extend(parse_blocks("## Appendix"));
+        let pdf = render("Weekly sync", &blocks);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains("(Paragraph number 7 \\(with parens\\)) Tj"));
+        let pages = text.matches("/Type /Page ").count();
+        assert!(pages >= 3);
+        assert!(text.contains(&format!("(Page {} of {}) Tj", pages, pages)));
+
+        // Every xref entry points at the start of its object
+        let xref = text.rfind("\nxref\n").unwrap() + 1;
+        for (i, entry) in text[xref..].lines().skip(3).take_while(|l| l.ends_with(" n ")).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,364 @@
+// export/subtitles.rs
+//
+// SRT and WebVTT captions from a meeting transcript. Transcript segments can run for
+// half a minute, so they are re-flowed into cues that fit a caption box: a few short
+// lines, shown for a few seconds. Cue timing comes from word timings when the engine
+// provided them, otherwise the segment's span is spread over its words by length.
+
+use super::speaker_label;
+use crate::api::MeetingTranscript;
+use serde::{Deserialize, Serialize};
+
+/// Shortest time a cue stays on screen, unless the next cue starts sooner
+const MIN_CUE_SECONDS: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleOptions {
+    /// Maximum characters per caption line
+    pub max_line_chars: usize,
+    /// Maximum lines per cue
+    pub max_lines: usize,
+    /// Longest time a single cue stays on screen
+    pub max_cue_seconds: f64,
+    /// Prefix a cue with its speaker whenever the speaker changes
+    pub speaker_labels: bool,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: 42,
+            max_lines: 2,
+            max_cue_seconds: 7.0,
+            speaker_labels: true,
+        }
+    }
+}
+
+impl SubtitleOptions {
+    /// Clamp options to values that still produce readable cues
+    fn validated(&self) -> Self {
+        Self {
+            max_line_chars: self.max_line_chars.max(10),
+            max_lines: self.max_lines.max(1),
+            max_cue_seconds: self.max_cue_seconds.max(MIN_CUE_SECONDS),
+            speaker_labels: self.speaker_labels,
+        }
+    }
+}
+
+/// One caption: text (unwrapped) shown from `start` to `end` seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start: f64,
+    pub end: f64,
+    /// Speaker to announce, set only when the speaker changes
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+struct TimedWord {
+    text: String,
+    start: f64,
+    end: f64,
+}
+
+/// Words of a segment with their timings
+fn segment_words(transcript: &MeetingTranscript, start: f64, end: f64) -> Vec<TimedWord> {
+    // Word timings are only trusted while they still match the text (it may have been edited)
+    if let Some(words) = transcript.words.as_ref().filter(|w| !w.is_empty()) {
+        let joined: Vec<&str> = words.iter().flat_map(|w| w.word.split_whitespace()).collect();
+        let text: Vec<&str> = transcript.text.split_whitespace().collect();
+        if joined == text {
+            return words
+                .iter()
+                .filter(|w| !w.word.trim().is_empty())
+                .map(|w| TimedWord {
+                    text: w.word.trim().to_string(),
+                    start: w.start,
+                    end: w.end.max(w.start),
+                })
+                .collect();
+        }
+    }
+
+    let tokens: Vec<&str> = transcript.text.split_whitespace().collect();
+    let total: usize = tokens.iter().map(|w| w.chars().count() + 1).sum();
+    let span = (end - start).max(0.0);
+    let mut offset = 0;
+    tokens
+        .into_iter()
+        .map(|token| {
+            let word_start = start + span * offset as f64 / total as f64;
+            offset += token.chars().count() + 1;
+            TimedWord {
+                text: token.to_string(),
+                start: word_start,
+                end: start + span * offset as f64 / total as f64,
+            }
+        })
+        .collect()
+}
+
+/// Greedy word wrap; a single word longer than the limit gets a line of its own
+pub fn wrap_lines(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn labelled(speaker: Option<&str>, text: &str) -> String {
+    match speaker {
+        Some(speaker) => format!("{}: {}", speaker, text),
+        None => text.to_string(),
+    }
+}
+
+/// Split the transcript into caption cues. Segments without recording timestamps
+/// (e.g. meetings imported from text) are skipped.
+pub fn build_cues(transcripts: &[MeetingTranscript], options: &SubtitleOptions) -> Vec<Cue> {
+    let options = options.validated();
+    let mut timed: Vec<(&MeetingTranscript, f64)> = transcripts
+        .iter()
+        .filter_map(|t| t.audio_start_time.map(|start| (t, start)))
+        .collect();
+    timed.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut last_speaker: Option<String> = None;
+
+    for (i, (transcript, start)) in timed.iter().enumerate() {
+        let start = *start;
+        let next_start = timed.get(i + 1).map(|(_, s)| *s);
+        let end = transcript
+            .audio_end_time
+            .or(transcript.duration.map(|d| start + d))
+            .or(next_start)
+            .unwrap_or(start + options.max_cue_seconds)
+            .max(start);
+        let speaker = if options.speaker_labels { speaker_label(transcript) } else { None };
+        // Only announce the speaker when it changes
+        let mut announce = speaker.is_some() && speaker != last_speaker;
+
+        let words = segment_words(transcript, start, end);
+        let mut current: Vec<&TimedWord> = Vec::new();
+        for word in &words {
+            if let Some(first) = current.first() {
+                let text = current.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+                let candidate = labelled(announce.then_some(speaker.as_deref()).flatten(), &format!("{} {}", text, word.text));
+                let too_long = wrap_lines(&candidate, options.max_line_chars).len() > options.max_lines;
+                let too_slow = word.end - first.start > options.max_cue_seconds;
+                // Prefer ending a cue at a sentence once it fills a line
+                let sentence_end = text.ends_with(['.', '?', '!']) && text.chars().count() >= options.max_line_chars;
+
+                if too_long || too_slow || sentence_end {
+                    cues.push(Cue {
+                        start: first.start,
+                        end: current.last().map_or(first.end, |w| w.end),
+                        speaker: announce.then(|| speaker.clone()).flatten(),
+                        text,
+                    });
+                    announce = false;
+                    current.clear();
+                }
+            }
+            current.push(word);
+        }
+        if let (Some(first), Some(last)) = (current.first(), current.last()) {
+            cues.push(Cue {
+                start: first.start,
+                end: last.end,
+                speaker: announce.then(|| speaker.clone()).flatten(),
+                text: current.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "),
+            });
+        }
+        if !words.is_empty() {
+            last_speaker = speaker;
+        }
+    }
+
+    // Give short cues time to be read, without overlapping the next one
+    for i in 0..cues.len() {
+        let next_start = cues.get(i + 1).map(|c| c.start);
+        let cue = &mut cues[i];
+        cue.end = cue.end.max(cue.start + MIN_CUE_SECONDS);
+        if let Some(next) = next_start.filter(|next| *next > cue.start) {
+            cue.end = cue.end.min(next);
+        }
+    }
+    cues
+}
+
+/// `HH:MM:SS,mmm` (SRT) or `HH:MM:SS.mmm` (WebVTT)
+fn timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render cues in the given format
+pub fn render(cues: &[Cue], format: SubtitleFormat, options: &SubtitleOptions) -> String {
+    let options = options.validated();
+    let mut out = String::new();
+    match format {
+        SubtitleFormat::Srt => {
+            for (i, cue) in cues.iter().enumerate() {
+                let text = labelled(cue.speaker.as_deref(), &cue.text);
+                out.push_str(&format!(
+                    "{}\n{} --> {}\n{}\n\n",
+                    i + 1,
+                    timestamp(cue.start, ','),
+                    timestamp(cue.end, ','),
+                    wrap_lines(&text, options.max_line_chars).join("\n")
+                ));
+            }
+        }
+        SubtitleFormat::Vtt => {
+            out.push_str("WEBVTT\n\n");
+            for cue in cues {
+                // Wrapped like SRT so the label's width is kept, then the label becomes a voice tag
+                let speaker = cue.speaker.as_ref().map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "));
+                let text = labelled(speaker.as_deref(), &cue.text);
+                let mut lines: Vec<String> = wrap_lines(&text, options.max_line_chars);
+                if let (Some(speaker), Some(first)) = (&speaker, lines.first_mut()) {
+                    let prefix = format!("{}:", speaker);
+                    let rest = first.strip_prefix(&prefix).unwrap_or(first).trim_start().to_string();
+                    *first = format!("<v {}>{}", escape_vtt(speaker), escape_vtt(&rest));
+                }
+                for line in lines.iter_mut().skip(usize::from(speaker.is_some())) {
+                    *line = escape_vtt(line);
+                }
+                out.push_str(&format!(
+                    "{} --> {}\n{}\n\n",
+                    timestamp(cue.start, '.'),
+                    timestamp(cue.end, '.'),
+                    lines.join("\n")
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// Captions for a meeting transcript
+pub fn export_subtitles(
+    transcripts: &[MeetingTranscript],
+    format: SubtitleFormat,
+    options: &SubtitleOptions,
+) -> String {
+    render(&build_cues(transcripts, options), format, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start: f64, end: f64, speaker: Option<&str>) -> MeetingTranscript {
+        MeetingTranscript {
+            id: format!("t-{}", start),
+            text: text.to_string(),
+            timestamp: String::new(),
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+            speaker_id: None,
+            speaker_profile_id: None,
+            speaker_name: speaker.map(String::from),
+            words: None,
+            language: None,
+            language_probability: None,
+            audio_source: None,
+        }
+    }
+
+    #[test]
+    fn test_formats_timestamps() {
+        assert_eq!(timestamp(3725.5, ','), "01:02:05,500");
+        assert_eq!(timestamp(0.0421, '.'), "00:00:00.042");
+    }
+
+    #[test]
+    fn test_reflows_long_segments_into_caption_sized_cues() {
+        let text = "This is a long segment that keeps going well past what fits into a \
+                    single caption box, so it has to be split across several cues in order";
+        let options = SubtitleOptions::default();
+        let cues = build_cues(&[segment(text, 10.0, 40.0, None)], &options);
+
+        assert!(cues.len() > 1);
+        for cue in &cues {
+            assert!(wrap_lines(&cue.text, options.max_line_chars).len() <= options.max_lines);
+            assert!(cue.end - cue.start <= options.max_cue_seconds + 1e-9);
+        }
+        assert_eq!(cues.first().unwrap().start, 10.0);
+        assert!(cues.last().unwrap().end >= 40.0 - 1e-9);
+        let rejoined = cues.iter().map(|c| c.text.as_str()).collect::<Vec<_>>().join(" ");
+        assert_eq!(rejoined, text.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    #[test]
+    fn test_announces_speaker_only_when_it_changes() {
+        let transcripts = [
+            segment("Hello there.", 0.0, 2.0, Some("Alice")),
+            segment("How are you?", 2.0, 4.0, Some("Alice")),
+            segment("Fine, thanks.", 4.0, 6.0, Some("Bob")),
+        ];
+        let options = SubtitleOptions::default();
+        let srt = export_subtitles(&transcripts, SubtitleFormat::Srt, &options);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:02,000\nAlice: Hello there.\n\n\
+             2\n00:00:02,000 --> 00:00:04,000\nHow are you?\n\n\
+             3\n00:00:04,000 --> 00:00:06,000\nBob: Fine, thanks.\n\n"
+        );
+
+        let unlabelled = SubtitleOptions { speaker_labels: false, ..Default::default() };
+        assert!(!export_subtitles(&transcripts, SubtitleFormat::Srt, &unlabelled).contains("Alice"));
+    }
+
+    #[test]
+    fn test_writes_webvtt_voice_tags_and_escapes_text() {
+        let transcripts = [segment("a < b & c", 1.0, 1.2, Some("Me"))];
+        let vtt = export_subtitles(&transcripts, SubtitleFormat::Vtt, &SubtitleOptions::default());
+        // Short cues are held on screen long enough to read
+        assert_eq!(vtt, "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\n<v Me>a &lt; b &amp; c\n\n");
+    }
+}
@@ -41,12 +41,15 @@ pub mod audio;
 pub mod console_utils;
 pub mod database;
 pub mod diarization;
+pub mod export;
 pub mod notifications;
 pub mod ollama;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod semantic_search;
 pub mod state;
 pub mod summary;
+pub mod transcript_edit;
 pub mod tray;
 pub mod utils;
 pub mod whisper_engine;
@@ -529,6 +532,13 @@ pub fn run() {
             diarization::commands::diarization_init,
             diarization::commands::diarize_segment,
             diarization::commands::diarization_download_model,
+            // Speaker registry commands
+            diarization::speaker_commands::api_list_speakers,
+            diarization::speaker_commands::api_enroll_speaker,
+            diarization::speaker_commands::api_rename_speaker,
+            diarization::speaker_commands::api_merge_speakers,
+            diarization::speaker_commands::api_delete_speaker,
+            diarization::batch::api_rediarize_meeting,
             // Parakeet engine commands
             parakeet_engine::commands::parakeet_init,
             parakeet_engine::commands::parakeet_get_available_models,
@@ -555,6 +565,9 @@ pub fn run() {
             whisper_engine::parallel_commands::calculate_optimal_workers,
             whisper_engine::parallel_commands::prepare_audio_chunks,
             whisper_engine::parallel_commands::test_parallel_processing_setup,
+            whisper_engine::retranscribe::api_retranscribe_meeting,
+            whisper_engine::retranscribe::api_list_transcript_versions,
+            whisper_engine::retranscribe::api_rollback_transcript,
             get_audio_devices,
             start_recording_with_devices,
             start_recording_with_devices_and_meeting,
@@ -576,6 +589,22 @@ pub fn run() {
             audio::recording_commands::attempt_device_reconnect,
             // Playback device detection (Bluetooth warning)
             audio::recording_commands::get_active_audio_output,
+            // Import existing audio/video files as meetings
+            audio::import::api_import_meeting_file,
+            // Recovery of recordings interrupted by a crash
+            audio::recovery::api_list_interrupted_recordings,
+            audio::recovery::api_recover_recording,
+            audio::recovery::api_discard_interrupted_recording,
+            audio::recovery::api_discard_dropped_checkpoints,
+            // Transcript export
+            export::commands::api_export_subtitles,
+            export::commands::api_export_meeting_document,
+            export::commands::api_export_meeting_archive,
+            export::commands::api_import_meeting_archive,
+            audio::transcription::vocabulary_commands::api_list_vocabulary,
+            audio::transcription::vocabulary_commands::api_save_vocabulary_term,
+            audio::transcription::vocabulary_commands::api_delete_vocabulary_term,
+            audio::transcription::vocabulary_commands::api_learn_vocabulary_correction,
             console_utils::show_console,
             console_utils::hide_console,
             console_utils::toggle_console,
@@ -584,7 +613,30 @@ pub fn run() {
             ollama::delete_ollama_model,
             ollama::get_ollama_model_context,
             api::api_get_meetings,
+            api::api_list_meetings,
+            api::organization::api_list_tags,
+            api::organization::api_save_tag,
+            api::organization::api_delete_tag,
+            api::organization::api_set_meeting_tags,
+            api::organization::api_list_projects,
+            api::organization::api_create_project,
+            api::organization::api_update_project,
+            api::organization::api_delete_project,
+            api::organization::api_set_meeting_project,
+            api::organization::api_get_meeting_participants,
+            api::organization::api_set_meeting_participants,
+            api::organization::api_list_participant_names,
             api::api_search_transcripts,
+            api::api_search_meetings,
+            semantic_search::commands::api_ask_meetings,
+            semantic_search::commands::api_semantic_search,
+            semantic_search::commands::api_reindex_meeting_embeddings,
+            transcript_edit::commands::api_edit_transcript_segment,
+            transcript_edit::commands::api_set_segment_speaker,
+            transcript_edit::commands::api_split_transcript_segment,
+            transcript_edit::commands::api_merge_transcript_segments,
+            transcript_edit::commands::api_list_transcript_revisions,
+            transcript_edit::commands::api_revert_transcript_revision,
             api::api_get_profile,
             api::api_save_profile,
             api::api_update_profile,
@@ -608,6 +660,7 @@ pub fn run() {
             summary::api_process_transcript,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
+            summary::api_cancel_summary,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
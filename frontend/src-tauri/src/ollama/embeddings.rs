@@ -0,0 +1,74 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::time::Duration;
+
+/// Embedding model used when none is configured (`ollama pull nomic-embed-text`)
+pub const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+const DEFAULT_OLLAMA_ENDPOINT: &str = "http://localhost:11434";
+
+/// Texts sent per /api/embed request
+const EMBED_BATCH_SIZE: usize = 32;
+
+const EMBED_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Embed texts with a local Ollama embedding model, preserving input order
+pub async fn embed_texts(
+    client: &Client,
+    endpoint: Option<&str>,
+    model: &str,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let host = endpoint
+        .filter(|e| !e.is_empty())
+        .unwrap_or(DEFAULT_OLLAMA_ENDPOINT)
+        .trim_end_matches('/');
+    let url = format!("{}/api/embed", host);
+
+    let mut embeddings = Vec::with_capacity(texts.len());
+    for batch in texts.chunks(EMBED_BATCH_SIZE) {
+        let response = client
+            .post(&url)
+            .timeout(EMBED_TIMEOUT)
+            .json(&EmbedRequest { model, input: batch })
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Ollama for embeddings: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!(
+                "Ollama embedding request failed ({}): {}. Is '{}' pulled?",
+                status, body, model
+            ));
+        }
+
+        let parsed: EmbedResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse embedding response: {}", e))?;
+
+        if parsed.embeddings.len() != batch.len() {
+            return Err(format!(
+                "Ollama returned {} embeddings for {} inputs",
+                parsed.embeddings.len(),
+                batch.len()
+            ));
+        }
+        embeddings.extend(parsed.embeddings);
+    }
+
+    Ok(embeddings)
+}
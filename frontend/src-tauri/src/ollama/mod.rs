@@ -1,6 +1,7 @@
 pub mod ollama;
 pub mod commands;
 pub mod metadata;
+pub mod embeddings;
 
 pub use ollama::*;
 // Don't re-export commands to avoid conflicts - lib.rs will import directly
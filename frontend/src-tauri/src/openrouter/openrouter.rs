@@ -1,6 +1,19 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tauri::command;
 use reqwest::blocking::Client;
+use tokio::sync::RwLock;
+
+const MODELS_URL: &str = "https://openrouter.ai/api/v1/models";
+
+/// How long fetched context lengths are reused before the model list is fetched again
+const CONTEXT_CACHE_TTL: Duration = Duration::from_secs(3600);
+
+/// Context length per model id, with the time it was fetched
+static CONTEXT_LENGTHS: Lazy<RwLock<Option<(Instant, HashMap<String, u32>)>>> =
+    Lazy::new(|| RwLock::new(None));
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OpenRouterModel {
@@ -42,7 +55,7 @@ struct OpenRouterResponse {
 pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
     let client = Client::new();
     let response = client
-        .get("https://openrouter.ai/api/v1/models")
+        .get(MODELS_URL)
         .send()
         .map_err(|e| format!("Failed to make HTTP request: {}", e))?;
 
@@ -54,7 +67,11 @@ pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
         .json()
         .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
 
-    let models = api_response
+    Ok(into_models(api_response))
+}
+
+fn into_models(api_response: OpenRouterResponse) -> Vec<OpenRouterModel> {
+    api_response
         .data
         .into_iter()
         .map(|m| OpenRouterModel {
@@ -67,7 +84,43 @@ pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
             prompt_price: m.pricing.as_ref().and_then(|p| p.prompt.clone()),
             completion_price: m.pricing.as_ref().and_then(|p| p.completion.clone()),
         })
+        .collect()
+}
+
+/// Context length of an OpenRouter model, from the (cached) public model list.
+/// Returns `Ok(None)` if the model isn't listed or doesn't report one.
+pub async fn get_openrouter_context_length(model_id: &str) -> Result<Option<usize>, String> {
+    {
+        let cache = CONTEXT_LENGTHS.read().await;
+        if let Some((fetched_at, lengths)) = cache.as_ref() {
+            if fetched_at.elapsed() < CONTEXT_CACHE_TTL {
+                return Ok(lengths.get(model_id).map(|&len| len as usize));
+            }
+        }
+    }
+
+    let response = reqwest::Client::new()
+        .get(MODELS_URL)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to make HTTP request: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP request failed with status: {}", response.status()));
+    }
+
+    let api_response: OpenRouterResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
+
+    let lengths: HashMap<String, u32> = into_models(api_response)
+        .into_iter()
+        .filter_map(|m| m.context_length.map(|len| (m.id, len)))
         .collect();
+    let context_length = lengths.get(model_id).map(|&len| len as usize);
 
-    Ok(models)
+    *CONTEXT_LENGTHS.write().await = Some((Instant::now(), lengths));
+    Ok(context_length)
 }
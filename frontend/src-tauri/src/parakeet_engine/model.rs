@@ -10,6 +10,8 @@ use regex::Regex;
 use std::fs;
 use std::path::Path;
 
+use crate::audio::transcription::provider::{words_from_tokens, WordTimestamp};
+
 pub type DecoderState = (Array3<f32>, Array3<f32>);
 
 const SUBSAMPLING_FACTOR: usize = 8;
@@ -26,6 +28,23 @@ pub struct TimestampedResult {
     pub tokens: Vec<String>,
 }
 
+impl TimestampedResult {
+    /// Group tokens into words. The decoder only reports when each token starts, so a
+    /// token ends where the next one begins (the last one at `audio_duration`).
+    pub fn word_timestamps(&self, audio_duration: f32) -> Vec<WordTimestamp> {
+        let starts: Vec<f64> = self.timestamps.iter().map(|&t| t as f64).collect();
+        let tokens = self.tokens.iter().zip(&starts).enumerate().map(|(i, (token, &start))| {
+            let end = starts
+                .get(i + 1)
+                .copied()
+                .unwrap_or(audio_duration as f64)
+                .max(start);
+            (token.clone(), start, end, None)
+        });
+        words_from_tokens(tokens)
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ParakeetError {
     #[error("ORT error")]
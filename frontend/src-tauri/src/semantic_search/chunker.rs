@@ -0,0 +1,127 @@
+use crate::database::models::Transcript;
+
+/// Target size of an embedded chunk. Small enough to stay on one topic,
+/// large enough that a paraphrased question still finds its context.
+const TARGET_CHUNK_WORDS: usize = 120;
+
+/// Chunks never span a silence longer than this (seconds)
+const MAX_GAP_SECONDS: f64 = 30.0;
+
+/// Consecutive transcript segments of one meeting, embedded as a unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChunk {
+    pub chunk_index: usize,
+    pub content: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// Group a meeting's segments (in playback order) into chunks of roughly
+/// `TARGET_CHUNK_WORDS` words. The last segment of each chunk is repeated at the
+/// start of the next so answers spanning a boundary are still retrievable.
+pub fn chunk_segments(segments: &[Transcript]) -> Vec<TextChunk> {
+    let segments: Vec<&Transcript> = segments
+        .iter()
+        .filter(|s| !s.transcript.trim().is_empty())
+        .collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < segments.len() {
+        let mut end = start;
+        let mut words = 0;
+
+        while end < segments.len() {
+            if end > start {
+                let gap = match (segments[end - 1].audio_end_time, segments[end].audio_start_time) {
+                    (Some(prev_end), Some(next_start)) => next_start - prev_end,
+                    _ => 0.0,
+                };
+                if words >= TARGET_CHUNK_WORDS || gap > MAX_GAP_SECONDS {
+                    break;
+                }
+            }
+            words += segments[end].transcript.split_whitespace().count();
+            end += 1;
+        }
+
+        let members = &segments[start..end];
+        chunks.push(TextChunk {
+            chunk_index: chunks.len(),
+            content: members
+                .iter()
+                .map(|s| s.transcript.trim())
+                .collect::<Vec<_>>()
+                .join(" "),
+            audio_start_time: members.first().and_then(|s| s.audio_start_time),
+            audio_end_time: members.last().and_then(|s| s.audio_end_time),
+        });
+
+        if end >= segments.len() {
+            break;
+        }
+        // Overlap by one segment unless that would make no progress
+        start = if end - start > 1 { end - 1 } else { end };
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start: f64, end: f64) -> Transcript {
+        Transcript {
+            id: format!("t-{}", start),
+            meeting_id: "m".to_string(),
+            transcript: text.to_string(),
+            timestamp: String::new(),
+            summary: None,
+            action_items: None,
+            key_points: None,
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+            speaker_id: None,
+            speaker_profile_id: None,
+            speaker_name: None,
+            words: None,
+            language: None,
+            language_probability: None,
+            audio_source: None,
+        }
+    }
+
+    #[test]
+    fn test_short_meeting_is_one_chunk() {
+        let chunks = chunk_segments(&[segment("hello there", 0.0, 1.0), segment("general kenobi", 1.0, 2.0)]);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].content, "hello there general kenobi");
+        assert_eq!(chunks[0].audio_start_time, Some(0.0));
+        assert_eq!(chunks[0].audio_end_time, Some(2.0));
+    }
+
+    #[test]
+    fn test_long_meeting_splits_with_overlap() {
+        let long = "word ".repeat(80);
+        let segments: Vec<Transcript> = (0..4)
+            .map(|i| segment(&long, i as f64 * 10.0, i as f64 * 10.0 + 10.0))
+            .collect();
+
+        let chunks = chunk_segments(&segments);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].audio_end_time, Some(20.0));
+        assert_eq!(chunks[1].audio_start_time, Some(10.0)); // overlapping segment
+        assert_eq!(chunks[2].audio_end_time, Some(40.0));
+        assert!(chunks.iter().enumerate().all(|(i, c)| c.chunk_index == i));
+    }
+
+    #[test]
+    fn test_long_silence_starts_new_chunk() {
+        let chunks = chunk_segments(&[segment("before", 0.0, 1.0), segment("after", 120.0, 121.0)]);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].content, "after");
+    }
+}
@@ -0,0 +1,130 @@
+use crate::database::repositories::{
+    embedding::EmbeddingsRepository, setting::SettingsRepository,
+};
+use crate::ollama::embeddings::DEFAULT_EMBEDDING_MODEL;
+use crate::semantic_search::service::{
+    self, AskConfig, AskResult, Citation, DEFAULT_CONTEXT_CHUNKS,
+};
+use crate::state::AppState;
+use crate::summary::llm_client::LLMProvider;
+use log::{error as log_error, info as log_info};
+use sqlx::SqlitePool;
+
+/// Builds the retrieval/LLM configuration from the saved model settings
+async fn load_ask_config(
+    pool: &SqlitePool,
+    embedding_model: Option<String>,
+) -> Result<AskConfig, String> {
+    let settings = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model settings: {}", e))?
+        .ok_or_else(|| "No summary model configured. Please choose one in settings.".to_string())?;
+
+    let provider = LLMProvider::from_str(&settings.provider)?;
+    let api_key = match SettingsRepository::get_api_key(pool, &settings.provider).await {
+        Ok(Some(key)) if !key.is_empty() => key,
+        Ok(_) if provider == LLMProvider::Ollama => String::new(),
+        Ok(_) => return Err(format!("Api key not found for {}", settings.provider)),
+        Err(e) => {
+            return Err(format!(
+                "Failed to retrieve api key for {} : {}",
+                settings.provider, e
+            ))
+        }
+    };
+
+    Ok(AskConfig {
+        provider,
+        model: settings.model,
+        api_key,
+        ollama_endpoint: settings.ollama_endpoint,
+        embedding_model: embedding_model
+            .filter(|m| !m.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_EMBEDDING_MODEL.to_string()),
+    })
+}
+
+/// Answers a natural-language question across all meetings (or the given ones),
+/// citing the meeting ids and audio timestamps the answer is based on
+#[tauri::command]
+pub async fn api_ask_meetings(
+    state: tauri::State<'_, AppState>,
+    question: String,
+    meeting_ids: Option<Vec<String>>,
+    limit: Option<usize>,
+    embedding_model: Option<String>,
+) -> Result<AskResult, String> {
+    log_info!("api_ask_meetings called: '{}'", question);
+    let pool = state.db_manager.pool();
+    let config = load_ask_config(pool, embedding_model).await?;
+    let client = reqwest::Client::new();
+
+    service::ask_meetings(
+        pool,
+        &client,
+        &config,
+        &question,
+        limit.unwrap_or(DEFAULT_CONTEXT_CHUNKS).clamp(1, 50),
+        meeting_ids.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to answer question '{}': {}", question, e);
+        e
+    })
+}
+
+/// Finds transcript passages similar in meaning to `query`
+#[tauri::command]
+pub async fn api_semantic_search(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    meeting_ids: Option<Vec<String>>,
+    limit: Option<usize>,
+    embedding_model: Option<String>,
+) -> Result<Vec<Citation>, String> {
+    log_info!("api_semantic_search called: '{}'", query);
+    let pool = state.db_manager.pool();
+    let config = load_ask_config(pool, embedding_model).await?;
+    let client = reqwest::Client::new();
+
+    service::semantic_search(
+        pool,
+        &client,
+        &config,
+        &query,
+        limit.unwrap_or(20).clamp(1, 100),
+        meeting_ids.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Semantic search failed for '{}': {}", query, e);
+        e
+    })
+}
+
+/// Re-embeds one meeting (e.g. after its transcript changed), or every meeting when
+/// `meeting_id` is omitted. Returns the number of meetings indexed.
+#[tauri::command]
+pub async fn api_reindex_meeting_embeddings(
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+    embedding_model: Option<String>,
+) -> Result<usize, String> {
+    log_info!("api_reindex_meeting_embeddings called for {:?}", meeting_id);
+    let pool = state.db_manager.pool();
+    let config = load_ask_config(pool, embedding_model).await?;
+    let client = reqwest::Client::new();
+
+    match meeting_id {
+        Some(id) => service::index_meeting(pool, &client, &config, &id)
+            .await
+            .map(|chunks| usize::from(chunks > 0)),
+        None => {
+            EmbeddingsRepository::delete_model_embeddings(pool, &config.embedding_model)
+                .await
+                .map_err(|e| format!("Failed to clear embeddings: {}", e))?;
+            service::index_pending_meetings(pool, &client, &config).await
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Semantic search module - "ask your meetings" over transcript history
+///
+/// This module contains:
+/// - Chunker grouping transcript segments into embeddable passages
+/// - Service embedding passages with a local Ollama model, retrieving by cosine
+///   similarity and answering questions with the configured LLM provider
+/// - Tauri commands for frontend integration
+
+pub mod chunker;
+pub mod commands;
+pub mod service;
+
+pub use service::{ask_meetings, semantic_search, AskConfig, AskResult, Citation};
@@ -0,0 +1,433 @@
+use crate::database::repositories::embedding::{EmbeddingMatch, EmbeddingsRepository};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::ollama::embeddings::embed_texts;
+use crate::semantic_search::chunker::chunk_segments;
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tracing::{info, warn};
+
+/// Number of excerpts handed to the LLM when answering a question
+pub const DEFAULT_CONTEXT_CHUNKS: usize = 8;
+
+const ASK_SYSTEM_PROMPT: &str = "You answer questions about the user's past meetings using only the numbered transcript excerpts provided. \
+Cite the excerpts you rely on inline with their numbers in square brackets, e.g. [2]. \
+If the excerpts do not contain the answer, say that you could not find it in the meetings.";
+
+/// Everything needed to embed queries and call the configured LLM
+#[derive(Debug, Clone)]
+pub struct AskConfig {
+    pub provider: LLMProvider,
+    pub model: String,
+    pub api_key: String,
+    pub ollama_endpoint: Option<String>,
+    pub embedding_model: String,
+}
+
+/// A transcript excerpt that supports an answer or matches a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub index: usize, // The [n] used in the answer text
+    pub meeting_id: String,
+    pub meeting_title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_start_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_end_time: Option<f64>,
+    pub excerpt: String,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AskResult {
+    pub answer: String,
+    pub citations: Vec<Citation>,
+}
+
+impl Citation {
+    fn from_match(index: usize, m: EmbeddingMatch) -> Self {
+        Self {
+            index,
+            meeting_id: m.meeting_id,
+            meeting_title: m.meeting_title,
+            audio_start_time: m.audio_start_time,
+            audio_end_time: m.audio_end_time,
+            excerpt: m.content,
+            score: m.score,
+        }
+    }
+}
+
+/// Chunk and embed one meeting's transcript, replacing any previous vectors.
+/// Returns the number of chunks stored.
+pub async fn index_meeting(
+    pool: &SqlitePool,
+    client: &Client,
+    config: &AskConfig,
+    meeting_id: &str,
+) -> Result<usize, String> {
+    let segments = TranscriptsRepository::get_meeting_transcripts(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcripts for {}: {}", meeting_id, e))?;
+
+    let chunks = chunk_segments(&segments);
+    if chunks.is_empty() {
+        return Ok(0);
+    }
+
+    let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+    let embeddings = embed_texts(
+        client,
+        config.ollama_endpoint.as_deref(),
+        &config.embedding_model,
+        &texts,
+    )
+    .await?;
+
+    let embedded: Vec<_> = chunks.into_iter().zip(embeddings).collect();
+    EmbeddingsRepository::replace_meeting_embeddings(pool, meeting_id, &config.embedding_model, &embedded)
+        .await
+        .map_err(|e| format!("Failed to store embeddings for {}: {}", meeting_id, e))?;
+
+    Ok(embedded.len())
+}
+
+/// Embed every meeting that has no vectors for the configured model yet.
+/// A meeting that fails is logged and skipped so one bad transcript can't block search.
+pub async fn index_pending_meetings(
+    pool: &SqlitePool,
+    client: &Client,
+    config: &AskConfig,
+) -> Result<usize, String> {
+    let pending = EmbeddingsRepository::meetings_without_embeddings(pool, &config.embedding_model)
+        .await
+        .map_err(|e| format!("Failed to find meetings to index: {}", e))?;
+
+    if !pending.is_empty() {
+        info!("Embedding {} meetings for semantic search", pending.len());
+    }
+
+    let mut indexed = 0;
+    for meeting_id in &pending {
+        match index_meeting(pool, client, config, meeting_id).await {
+            Ok(_) => indexed += 1,
+            // Connection problems will fail every meeting - surface them instead
+            Err(e) if indexed == 0 && e.starts_with("Failed to reach") => return Err(e),
+            Err(e) => warn!("Skipping meeting {} during indexing: {}", meeting_id, e),
+        }
+    }
+
+    Ok(indexed)
+}
+
+/// Find the transcript excerpts closest in meaning to `query`
+pub async fn semantic_search(
+    pool: &SqlitePool,
+    client: &Client,
+    config: &AskConfig,
+    query: &str,
+    limit: usize,
+    meeting_ids: Option<&[String]>,
+) -> Result<Vec<Citation>, String> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    index_pending_meetings(pool, client, config).await?;
+
+    let query_embedding = embed_texts(
+        client,
+        config.ollama_endpoint.as_deref(),
+        &config.embedding_model,
+        &[query.to_string()],
+    )
+    .await?
+    .pop()
+    .ok_or_else(|| "No embedding returned for query".to_string())?;
+
+    let matches = EmbeddingsRepository::search_similar(
+        pool,
+        &config.embedding_model,
+        &query_embedding,
+        limit,
+        meeting_ids,
+    )
+    .await
+    .map_err(|e| format!("Semantic search failed: {}", e))?;
+
+    Ok(matches
+        .into_iter()
+        .enumerate()
+        .map(|(i, m)| Citation::from_match(i + 1, m))
+        .collect())
+}
+
+/// Answer a natural-language question from meeting history.
+///
+/// Retrieves the most relevant excerpts, asks the configured LLM to answer from them,
+/// and returns the excerpts it cited (or all of them if it cited none).
+pub async fn ask_meetings(
+    pool: &SqlitePool,
+    client: &Client,
+    config: &AskConfig,
+    question: &str,
+    limit: usize,
+    meeting_ids: Option<&[String]>,
+) -> Result<AskResult, String> {
+    let context = semantic_search(pool, client, config, question, limit, meeting_ids).await?;
+    if context.is_empty() {
+        return Ok(AskResult {
+            answer: "I couldn't find anything related to that in your meetings.".to_string(),
+            citations: Vec::new(),
+        });
+    }
+
+    let user_prompt = build_ask_prompt(question, &context);
+    let answer = generate_summary(
+        client,
+        &config.provider,
+        &config.model,
+        &config.api_key,
+        ASK_SYSTEM_PROMPT,
+        &user_prompt,
+        config.ollama_endpoint.as_deref(),
+    )
+    .await?;
+
+    let cited = cited_indices(&answer, context.len());
+    let citations = if cited.is_empty() {
+        context
+    } else {
+        context
+            .into_iter()
+            .filter(|c| cited.contains(&c.index))
+            .collect()
+    };
+
+    Ok(AskResult { answer, citations })
+}
+
+fn build_ask_prompt(question: &str, context: &[Citation]) -> String {
+    let mut prompt = String::from("Transcript excerpts:\n\n");
+    for c in context {
+        let time = match (c.audio_start_time, c.audio_end_time) {
+            (Some(start), Some(end)) => format!(", {}-{}", format_time(start), format_time(end)),
+            (Some(start), None) => format!(", {}", format_time(start)),
+            _ => String::new(),
+        };
+        prompt.push_str(&format!(
+            "[{}] Meeting \"{}\" ({}{})\n{}\n\n",
+            c.index, c.meeting_title, c.meeting_id, time, c.excerpt
+        ));
+    }
+    prompt.push_str(&format!("Question: {}", question));
+    prompt
+}
+
+fn format_time(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total / 60) % 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+/// Excerpt numbers referenced as [n] (or [n, m]) in an answer, within 1..=max
+fn cited_indices(answer: &str, max: usize) -> Vec<usize> {
+    let mut cited = Vec::new();
+    let mut rest = answer;
+
+    while let Some(open) = rest.find('[') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(']') else { break };
+        let inner = &rest[..close];
+        if inner.chars().all(|c| c.is_ascii_digit() || c == ',' || c.is_whitespace()) {
+            for n in inner.split(',').filter_map(|n| n.trim().parse::<usize>().ok()) {
+                if (1..=max).contains(&n) && !cited.contains(&n) {
+                    cited.push(n);
+                }
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+
+    cited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::TranscriptSegment;
+    use serde_json::{json, Value};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal HTTP server standing in for Ollama: answers /api/embed and /v1/chat/completions
+    async fn spawn_mock_server(handler: fn(&str, &Value) -> Value) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { break };
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let (head_len, content_length) = loop {
+                        let n = socket.read(&mut chunk).await.unwrap_or(0);
+                        if n == 0 {
+                            return;
+                        }
+                        buffer.extend_from_slice(&chunk[..n]);
+                        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                            let head = String::from_utf8_lossy(&buffer[..pos]).to_lowercase();
+                            let length = head
+                                .lines()
+                                .find_map(|l| l.strip_prefix("content-length:"))
+                                .and_then(|v| v.trim().parse::<usize>().ok())
+                                .unwrap_or(0);
+                            break (pos + 4, length);
+                        }
+                    };
+                    while buffer.len() < head_len + content_length {
+                        let n = socket.read(&mut chunk).await.unwrap_or(0);
+                        if n == 0 {
+                            break;
+                        }
+                        buffer.extend_from_slice(&chunk[..n]);
+                    }
+
+                    let head = String::from_utf8_lossy(&buffer[..head_len]).to_string();
+                    let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let body: Value = serde_json::from_slice(&buffer[head_len..]).unwrap_or(Value::Null);
+                    let response = handler(&path, &body).to_string();
+
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        response.len(),
+                        response
+                    );
+                    let _ = socket.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+
+        format!("http://{}", address)
+    }
+
+    /// Toy embedding: budget talk points one way, everything else another
+    fn mock_handler(path: &str, body: &Value) -> Value {
+        match path {
+            "/api/embed" => {
+                let embeddings: Vec<Vec<f32>> = body["input"]
+                    .as_array()
+                    .map(|inputs| {
+                        inputs
+                            .iter()
+                            .map(|text| {
+                                let text = text.as_str().unwrap_or("").to_lowercase();
+                                if text.contains("budget") || text.contains("money") {
+                                    vec![1.0, 0.1, 0.0]
+                                } else {
+                                    vec![0.0, 0.1, 1.0]
+                                }
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                json!({ "embeddings": embeddings })
+            }
+            "/v1/chat/completions" => {
+                let prompt = body["messages"][1]["content"].as_str().unwrap_or("");
+                assert!(prompt.contains("Question: How much money"));
+                json!({ "choices": [{ "message": { "content": "The budget was set to 10k [1]." } }] })
+            }
+            _ => json!({}),
+        }
+    }
+
+    fn segment(text: &str, start: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            id: String::new(),
+            text: text.to_string(),
+            timestamp: "10:00:00".to_string(),
+            audio_start_time: Some(start),
+            audio_end_time: Some(start + 5.0),
+            duration: Some(5.0),
+            speaker_id: None,
+            speaker_profile_id: None,
+            words: None,
+            language: None,
+            language_probability: None,
+            audio_source: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ask_meetings_against_mock_server() {
+        let endpoint = spawn_mock_server(mock_handler).await;
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        let budget_meeting = TranscriptsRepository::save_transcript(
+            &pool,
+            "Planning",
+            &[segment("We agreed the budget is ten thousand.", 0.0)],
+            None,
+        )
+        .await
+        .unwrap();
+        TranscriptsRepository::save_transcript(
+            &pool,
+            "Standup",
+            &[segment("Yesterday I fixed the login page.", 60.0)],
+            None,
+        )
+        .await
+        .unwrap();
+
+        let config = AskConfig {
+            provider: LLMProvider::Ollama,
+            model: "llama3.2".to_string(),
+            api_key: String::new(),
+            ollama_endpoint: Some(endpoint),
+            embedding_model: "mock-embed".to_string(),
+        };
+        let client = Client::new();
+
+        let result = ask_meetings(&pool, &client, &config, "How much money do we have?", 1, None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.answer, "The budget was set to 10k [1].");
+        assert_eq!(result.citations.len(), 1);
+        assert_eq!(result.citations[0].meeting_id, budget_meeting);
+        assert_eq!(result.citations[0].audio_start_time, Some(0.0));
+
+        // Both meetings were indexed on first use and are not re-embedded
+        let pending = EmbeddingsRepository::meetings_without_embeddings(&pool, "mock-embed")
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_cited_indices() {
+        assert_eq!(cited_indices("See [2] and [1, 3]. Also [2].", 3), vec![2, 1, 3]);
+        assert_eq!(cited_indices("Out of range [7], not a ref [a]", 3), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(65.4), "01:05");
+        assert_eq!(format_time(3725.0), "1:02:05");
+    }
+}
@@ -3,7 +3,9 @@ use crate::database::repositories::{
     transcript_chunk::TranscriptChunksRepository,
 };
 use crate::state::AppState;
+use crate::summary::jobs::SummaryJobs;
 use crate::summary::service::SummaryService;
+use crate::summary::structured::SummaryFormat;
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
@@ -18,6 +20,12 @@ pub struct SummaryResponse {
     pub end: Option<String>,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Output saved so far while processing, or before a failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<serde_json::Value>,
+    /// The transcript was edited after this summary was generated
+    #[serde(default)]
+    pub stale: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -85,21 +93,22 @@ pub async fn api_get_summary<R: Runtime>(
             let status = process.status.to_lowercase();
             let error = process.error;
 
-            // Parse result data if completed
-            let data = if status == "completed" {
-                if let Some(result_str) = process.result {
-                    match serde_json::from_str::<serde_json::Value>(&result_str) {
-                        Ok(parsed) => Some(parsed),
-                        Err(e) => {
-                            log_error!("Failed to parse summary result JSON: {}", e);
-                            None
-                        }
+            let parsed_result = match process.result {
+                Some(result_str) => match serde_json::from_str::<serde_json::Value>(&result_str) {
+                    Ok(parsed) => Some(parsed),
+                    Err(e) => {
+                        log_error!("Failed to parse summary result JSON: {}", e);
+                        None
                     }
-                } else {
-                    None
-                }
+                },
+                None => None,
+            };
+
+            // Only a completed result is the summary; anything else is partial output
+            let (data, partial) = if status == "completed" {
+                (parsed_result, None)
             } else {
-                None
+                (None, parsed_result)
             };
 
             // Fetch meeting title from database
@@ -126,6 +135,8 @@ pub async fn api_get_summary<R: Runtime>(
                 end: process.end_time.map(|t| t.to_rfc3339()),
                 data,
                 error,
+                partial,
+                stale: process.stale,
             };
 
             log_info!(
@@ -154,6 +165,8 @@ pub async fn api_get_summary<R: Runtime>(
                 end: None,
                 data: None,
                 error: None,
+                partial: None,
+                stale: false,
             })
         }
         Err(e) => {
@@ -165,7 +178,9 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Spawns a background task and returns immediately with process_id.
+/// `output_format` "structured" stores the summary as schema-validated JSON under
+/// `result.structured` alongside the rendered Markdown.
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
     app: AppHandle<R>,
@@ -178,6 +193,8 @@ pub async fn api_process_transcript<R: Runtime>(
     _overlap: Option<i32>,
     custom_prompt: Option<String>,
     template_id: Option<String>,
+    output_format: Option<String>,
+    output_language: Option<String>,
     _auth_token: Option<String>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
@@ -192,6 +209,11 @@ pub async fn api_process_transcript<R: Runtime>(
     let pool = state.db_manager.pool().clone();
     let final_prompt = custom_prompt.unwrap_or_else(|| "".to_string());
     let final_template_id = template_id.unwrap_or_else(|| "daily_standup".to_string());
+    let summary_format = SummaryFormat::from_name(output_format.as_deref());
+    // "auto" (or nothing) keeps the summary in the transcript's language
+    let output_language = output_language
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case("auto"));
 
     // Create or reset the process entry in the database
     SummaryProcessesRepository::create_or_reset_process(&pool, &m_id)
@@ -218,9 +240,9 @@ pub async fn api_process_transcript<R: Runtime>(
 
     log_info!("✓ Transcript chunks saved for meeting_id: {}", &m_id);
 
-    // Spawn background task for actual processing
+    // Spawn background task for actual processing (replaces any job still running for this meeting)
     let meeting_id_clone = m_id.clone();
-    tauri::async_runtime::spawn(async move {
+    SummaryJobs::spawn(&m_id, async move {
         SummaryService::process_transcript_background(
             app,
             pool,
@@ -230,6 +252,8 @@ pub async fn api_process_transcript<R: Runtime>(
             model_name,
             final_prompt,
             final_template_id,
+            summary_format,
+            output_language,
         )
         .await;
     });
@@ -241,3 +265,35 @@ pub async fn api_process_transcript<R: Runtime>(
         process_id: m_id,
     })
 }
+
+/// Cancels a running summary generation
+///
+/// Chunk summaries finished so far stay checkpointed, so processing the transcript
+/// again resumes where this job stopped. Returns false if nothing was running.
+#[tauri::command]
+pub async fn api_cancel_summary<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<bool, String> {
+    log_info!("api_cancel_summary called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+
+    let aborted = SummaryJobs::cancel(&meeting_id);
+
+    // Also clears a 'processing' status left behind by a job that died with the app
+    let marked = SummaryProcessesRepository::update_process_cancelled(pool, &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Failed to mark summary cancelled for {}: {}", meeting_id, e);
+            format!("Failed to cancel summary: {}", e)
+        })?;
+
+    log_info!(
+        "Summary for {} cancelled (task aborted: {}, status updated: {})",
+        meeting_id,
+        aborted,
+        marked
+    );
+    Ok(aborted || marked)
+}
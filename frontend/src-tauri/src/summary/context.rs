@@ -0,0 +1,130 @@
+use crate::ollama::metadata::ModelMetadataCache;
+use crate::openrouter::get_openrouter_context_length;
+use crate::summary::llm_client::LLMProvider;
+use once_cell::sync::Lazy;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// Global cache for model metadata (5 minute TTL)
+static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
+    ModelMetadataCache::new(Duration::from_secs(300))
+});
+
+/// Tokens kept free for the model's answer (Claude requests are capped at 2048)
+const OUTPUT_RESERVE_TOKENS: usize = 2048;
+
+/// Tokens for the system prompt, template and instructions wrapped around the transcript
+const PROMPT_OVERHEAD_TOKENS: usize = 1000;
+
+/// Smallest transcript budget per call, so tiny context windows still make progress
+const MIN_TRANSCRIPT_TOKENS: usize = 1000;
+
+/// Used when a model's context window can't be determined
+const FALLBACK_CONTEXT_WINDOW: usize = 8192;
+
+/// Known context windows by model-name prefix, most specific prefix first
+const KNOWN_CONTEXT_WINDOWS: &[(LLMProvider, &str, usize)] = &[
+    (LLMProvider::OpenAI, "gpt-5", 400_000),
+    (LLMProvider::OpenAI, "gpt-4.1", 1_047_576),
+    (LLMProvider::OpenAI, "gpt-4o", 128_000),
+    (LLMProvider::OpenAI, "gpt-4-turbo", 128_000),
+    (LLMProvider::OpenAI, "gpt-4-32k", 32_768),
+    (LLMProvider::OpenAI, "gpt-4", 8_192),
+    (LLMProvider::OpenAI, "gpt-3.5-turbo", 16_385),
+    (LLMProvider::OpenAI, "o1-mini", 128_000),
+    (LLMProvider::OpenAI, "o1", 200_000),
+    (LLMProvider::OpenAI, "o3", 200_000),
+    (LLMProvider::OpenAI, "o4", 200_000),
+    (LLMProvider::OpenAI, "", 128_000),
+    (LLMProvider::Claude, "claude-2", 100_000),
+    (LLMProvider::Claude, "", 200_000),
+    (LLMProvider::Groq, "llama3-70b-8192", 8_192),
+    (LLMProvider::Groq, "llama3-8b-8192", 8_192),
+    (LLMProvider::Groq, "llama-3.1", 131_072),
+    (LLMProvider::Groq, "llama-3.3", 131_072),
+    (LLMProvider::Groq, "meta-llama/llama-4", 131_072),
+    (LLMProvider::Groq, "mixtral-8x7b-32768", 32_768),
+    (LLMProvider::Groq, "gemma2-9b-it", 8_192),
+    (LLMProvider::Groq, "deepseek-r1-distill", 131_072),
+    (LLMProvider::Groq, "qwen", 131_072),
+    (LLMProvider::Groq, "openai/gpt-oss", 131_072),
+    (LLMProvider::Groq, "", 8_192),
+];
+
+/// Context window for providers without a metadata API, from `KNOWN_CONTEXT_WINDOWS`
+pub fn known_context_window(provider: &LLMProvider, model_name: &str) -> Option<usize> {
+    let model = model_name.to_lowercase();
+    KNOWN_CONTEXT_WINDOWS
+        .iter()
+        .find(|(p, prefix, _)| p == provider && model.starts_with(prefix))
+        .map(|(_, _, size)| *size)
+}
+
+/// Context window of `model_name` in tokens.
+///
+/// Ollama models are asked via /api/show, OpenRouter models via the public model
+/// list; other providers use `KNOWN_CONTEXT_WINDOWS`.
+pub async fn context_window(
+    provider: &LLMProvider,
+    model_name: &str,
+    ollama_endpoint: Option<&str>,
+) -> usize {
+    let fetched = match provider {
+        LLMProvider::Ollama => METADATA_CACHE
+            .get_or_fetch(model_name, ollama_endpoint)
+            .await
+            .map(|metadata| Some(metadata.context_size)),
+        LLMProvider::OpenRouter => get_openrouter_context_length(model_name).await,
+        _ => Ok(known_context_window(provider, model_name)),
+    };
+
+    match fetched {
+        Ok(Some(size)) => {
+            info!("✓ Context window for {}: {} tokens", model_name, size);
+            size
+        }
+        Ok(None) => {
+            warn!(
+                "⚠️ Unknown context window for {}. Using default {}",
+                model_name, FALLBACK_CONTEXT_WINDOW
+            );
+            FALLBACK_CONTEXT_WINDOW
+        }
+        Err(e) => {
+            warn!(
+                "⚠️ Failed to fetch context for {}: {}. Using default {}",
+                model_name, e, FALLBACK_CONTEXT_WINDOW
+            );
+            FALLBACK_CONTEXT_WINDOW
+        }
+    }
+}
+
+/// Tokens of transcript (or chunk summaries) that fit in one call to a model
+/// with the given context window, after prompt overhead and the answer
+pub fn transcript_budget(context_window: usize) -> usize {
+    context_window
+        .saturating_sub(OUTPUT_RESERVE_TOKENS + PROMPT_OVERHEAD_TOKENS)
+        .max(MIN_TRANSCRIPT_TOKENS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_context_window_prefers_specific_prefix() {
+        assert_eq!(known_context_window(&LLMProvider::OpenAI, "gpt-4o-mini"), Some(128_000));
+        assert_eq!(known_context_window(&LLMProvider::OpenAI, "gpt-4-0613"), Some(8_192));
+        assert_eq!(known_context_window(&LLMProvider::OpenAI, "GPT-4.1-nano"), Some(1_047_576));
+        assert_eq!(known_context_window(&LLMProvider::Groq, "llama3-8b-8192"), Some(8_192));
+        assert_eq!(known_context_window(&LLMProvider::Claude, "claude-sonnet-4-5"), Some(200_000));
+        assert_eq!(known_context_window(&LLMProvider::Ollama, "llama3.2"), None);
+    }
+
+    #[test]
+    fn test_transcript_budget_leaves_room_for_prompt_and_answer() {
+        assert_eq!(transcript_budget(8_192), 8_192 - 3_048);
+        assert_eq!(transcript_budget(2_048), MIN_TRANSCRIPT_TOKENS);
+    }
+}
@@ -0,0 +1,65 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::async_runtime::JoinHandle;
+use tracing::info;
+
+struct SummaryJob {
+    id: u64,
+    handle: JoinHandle<()>,
+}
+
+/// Running summary tasks, at most one per meeting
+static SUMMARY_JOBS: Lazy<Mutex<HashMap<String, SummaryJob>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Registry of in-flight summary generation tasks keyed by meeting_id
+pub struct SummaryJobs;
+
+impl SummaryJobs {
+    /// Spawns `job` for the meeting and tracks it until it ends. An earlier job for the
+    /// same meeting is aborted, since only the newest request's result should be stored.
+    pub fn spawn<F>(meeting_id: &str, job: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+        let key = meeting_id.to_string();
+
+        // Held across the spawn so a job that ends immediately can't unregister before it is registered
+        let mut jobs = SUMMARY_JOBS.lock().unwrap();
+        let handle = tauri::async_runtime::spawn(async move {
+            job.await;
+            Self::finish(&key, id);
+        });
+
+        if let Some(previous) = jobs.insert(meeting_id.to_string(), SummaryJob { id, handle }) {
+            info!("Replacing running summary job for meeting_id: {}", meeting_id);
+            previous.handle.abort();
+        }
+    }
+
+    /// Leaves a newer job for the same meeting in place
+    fn finish(meeting_id: &str, id: u64) {
+        let mut jobs = SUMMARY_JOBS.lock().unwrap();
+        if jobs.get(meeting_id).map(|job| job.id == id).unwrap_or(false) {
+            jobs.remove(meeting_id);
+        }
+    }
+
+    /// Aborts the meeting's job. Dropping the task also drops its in-flight LLM request.
+    /// Returns false if nothing was running.
+    pub fn cancel(meeting_id: &str) -> bool {
+        match SUMMARY_JOBS.lock().unwrap().remove(meeting_id) {
+            Some(job) => {
+                job.handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
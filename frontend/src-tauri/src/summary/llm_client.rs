@@ -1,3 +1,4 @@
+use futures_util::StreamExt;
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use tracing::info;
@@ -76,28 +77,34 @@ impl LLMProvider {
     }
 }
 
-/// Generates a summary using the specified LLM provider
-///
-/// # Arguments
-/// * `client` - Reqwest HTTP client (reused for performance)
-/// * `provider` - The LLM provider to use
-/// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
-/// * `api_key` - API key for the provider (not needed for Ollama)
-/// * `system_prompt` - System instructions for the LLM
-/// * `user_prompt` - User query/content to process
-/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
+/// Name of the tool Claude is forced to call to return structured output
+const STRUCTURED_TOOL_NAME: &str = "record_meeting_summary";
+
+/// How a chat request should be answered
+#[derive(Clone, Copy)]
+enum RequestMode<'a> {
+    /// One complete response
+    Complete,
+    /// Incremental response (SSE, or NDJSON for Ollama)
+    Stream,
+    /// One complete response constrained to a JSON schema
+    Structured(&'a serde_json::Value),
+}
+
+/// Builds the endpoint URL, headers and JSON body for a chat completion request
 ///
-/// # Returns
-/// The generated summary text or an error message
-pub async fn generate_summary(
-    client: &Client,
+/// In `Stream` mode OpenAI-compatible providers and Claude are asked for SSE. Ollama
+/// is called through its native `/api/chat` endpoint for streaming and structured
+/// output, since that is where its `format` schema option lives.
+fn build_chat_request(
     provider: &LLMProvider,
     model_name: &str,
     api_key: &str,
     system_prompt: &str,
     user_prompt: &str,
     ollama_endpoint: Option<&str>,
-) -> Result<String, String> {
+    mode: RequestMode,
+) -> Result<(String, header::HeaderMap, serde_json::Value), String> {
     let (api_url, mut headers) = match provider {
         LLMProvider::OpenAI => (
             "https://api.openai.com/v1/chat/completions".to_string(),
@@ -115,10 +122,11 @@ pub async fn generate_summary(
             let host = ollama_endpoint
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "http://localhost:11434".to_string());
-            (
-                format!("{}/v1/chat/completions", host),
-                header::HeaderMap::new(),
-            )
+            let path = match mode {
+                RequestMode::Complete => "/v1/chat/completions",
+                RequestMode::Stream | RequestMode::Structured(_) => "/api/chat",
+            };
+            (format!("{}{}", host, path), header::HeaderMap::new())
         }
         LLMProvider::Claude => {
             let mut header_map = header::HeaderMap::new();
@@ -155,7 +163,7 @@ pub async fn generate_summary(
     );
 
     // Build request body based on provider
-    let request_body = if provider != &LLMProvider::Claude {
+    let mut request_body = if provider != &LLMProvider::Claude {
         serde_json::json!(ChatRequest {
             model: model_name.to_string(),
             messages: vec![
@@ -181,6 +189,78 @@ pub async fn generate_summary(
         })
     };
 
+    match mode {
+        RequestMode::Complete => {}
+        RequestMode::Stream => {
+            request_body["stream"] = serde_json::Value::Bool(true);
+        }
+        RequestMode::Structured(schema) => match provider {
+            LLMProvider::OpenAI | LLMProvider::OpenRouter => {
+                request_body["response_format"] = serde_json::json!({
+                    "type": "json_schema",
+                    "json_schema": {
+                        "name": "meeting_summary",
+                        "strict": true,
+                        "schema": schema,
+                    }
+                });
+            }
+            // Groq's schema mode is limited to a few models; plain JSON mode works everywhere
+            LLMProvider::Groq => {
+                request_body["response_format"] = serde_json::json!({ "type": "json_object" });
+            }
+            LLMProvider::Ollama => {
+                request_body["stream"] = serde_json::Value::Bool(false);
+                request_body["format"] = schema.clone();
+            }
+            // Claude has no JSON mode; forcing a tool call yields input matching the schema
+            LLMProvider::Claude => {
+                request_body["tools"] = serde_json::json!([{
+                    "name": STRUCTURED_TOOL_NAME,
+                    "description": "Record the filled-in meeting summary.",
+                    "input_schema": schema,
+                }]);
+                request_body["tool_choice"] =
+                    serde_json::json!({ "type": "tool", "name": STRUCTURED_TOOL_NAME });
+            }
+        },
+    }
+
+    Ok((api_url, headers, request_body))
+}
+
+/// Generates a summary using the specified LLM provider
+///
+/// # Arguments
+/// * `client` - Reqwest HTTP client (reused for performance)
+/// * `provider` - The LLM provider to use
+/// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
+/// * `api_key` - API key for the provider (not needed for Ollama)
+/// * `system_prompt` - System instructions for the LLM
+/// * `user_prompt` - User query/content to process
+/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
+///
+/// # Returns
+/// The generated summary text or an error message
+pub async fn generate_summary(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+) -> Result<String, String> {
+    let (api_url, headers, request_body) = build_chat_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        RequestMode::Complete,
+    )?;
+
     info!("🐞 LLM Request to {}: model={}", provider_name(provider), model_name);
 
     // Send request
@@ -235,6 +315,238 @@ pub async fn generate_summary(
     }
 }
 
+/// Requests output matching `schema` using the provider's structured-output mode:
+/// JSON schema response format (OpenAI, OpenRouter), JSON mode (Groq), the `format`
+/// option (Ollama) or a forced tool call (Claude).
+///
+/// Returns the raw JSON text. Providers don't all enforce the schema, so callers
+/// still need to validate it.
+pub async fn generate_structured(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    schema: &serde_json::Value,
+    ollama_endpoint: Option<&str>,
+) -> Result<String, String> {
+    let (api_url, headers, request_body) = build_chat_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        RequestMode::Structured(schema),
+    )?;
+
+    info!(
+        "🐞 Structured LLM Request to {}: model={}",
+        provider_name(provider),
+        model_name
+    );
+
+    let response = client
+        .post(api_url)
+        .headers(headers)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API request failed: {}", error_body));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    info!("🐞 Structured LLM Response received from {}", provider_name(provider));
+
+    structured_content(provider, &body).ok_or_else(|| "No content in LLM response".to_string())
+}
+
+/// Pulls the JSON text out of a structured-output response body
+fn structured_content(provider: &LLMProvider, body: &serde_json::Value) -> Option<String> {
+    match provider {
+        LLMProvider::Claude => body["content"]
+            .as_array()?
+            .iter()
+            .find(|block| block["type"] == "tool_use")
+            .map(|block| block["input"].to_string()),
+        LLMProvider::Ollama => body["message"]["content"].as_str().map(str::to_string),
+        _ => body["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string),
+    }
+}
+
+/// A parsed line of a streaming completion
+#[derive(Debug, PartialEq)]
+pub(crate) enum StreamEvent {
+    Delta(String),
+    Done,
+}
+
+/// Parses one line of a streaming response.
+///
+/// OpenAI-compatible providers and Claude send SSE (`data: {...}`), Ollama's native
+/// API sends one JSON object per line. Lines carrying no text (SSE `event:` lines,
+/// keep-alives, role-only deltas) yield `Ok(None)`.
+pub(crate) fn parse_stream_line(
+    provider: &LLMProvider,
+    line: &str,
+) -> Result<Option<StreamEvent>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    if provider == &LLMProvider::Ollama {
+        let value: serde_json::Value = serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse LLM stream: {}", e))?;
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(format!("LLM API request failed: {}", error));
+        }
+        let content = value["message"]["content"].as_str().unwrap_or_default();
+        if !content.is_empty() {
+            return Ok(Some(StreamEvent::Delta(content.to_string())));
+        }
+        return Ok(value["done"].as_bool().unwrap_or(false).then_some(StreamEvent::Done));
+    }
+
+    let data = match line.strip_prefix("data:") {
+        Some(data) => data.trim(),
+        None => return Ok(None),
+    };
+    if data == "[DONE]" {
+        return Ok(Some(StreamEvent::Done));
+    }
+
+    let value: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| format!("Failed to parse LLM stream: {}", e))?;
+    if let Some(error) = value.get("error") {
+        let message = error["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!("LLM API request failed: {}", message));
+    }
+
+    if provider == &LLMProvider::Claude {
+        return Ok(match value["type"].as_str() {
+            Some("content_block_delta") => value["delta"]["text"]
+                .as_str()
+                .map(|t| StreamEvent::Delta(t.to_string())),
+            Some("message_stop") => Some(StreamEvent::Done),
+            _ => None,
+        });
+    }
+
+    Ok(value["choices"][0]["delta"]["content"]
+        .as_str()
+        .filter(|t| !t.is_empty())
+        .map(|t| StreamEvent::Delta(t.to_string())))
+}
+
+/// Streaming variant of [`generate_summary`]
+///
+/// `on_delta` is called with each text fragment as it arrives; the complete
+/// (trimmed) text is returned once the stream ends.
+pub async fn generate_summary_stream(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    on_delta: &(dyn Fn(&str) + Send + Sync),
+) -> Result<String, String> {
+    let (api_url, headers, request_body) = build_chat_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+        RequestMode::Stream,
+    )?;
+
+    info!(
+        "🐞 Streaming LLM Request to {}: model={}",
+        provider_name(provider),
+        model_name
+    );
+
+    let response = client
+        .post(api_url)
+        .headers(headers)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send request to LLM: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("LLM API request failed: {}", error_body));
+    }
+
+    let mut stream = response.bytes_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut content = String::new();
+    let mut done = false;
+
+    while let Some(bytes) = stream.next().await {
+        let bytes = bytes.map_err(|e| format!("LLM stream interrupted: {}", e))?;
+        buffer.extend_from_slice(&bytes);
+
+        // Only complete lines are parsed so multi-byte characters are never split
+        while let Some(newline) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=newline).collect();
+            match parse_stream_line(provider, &String::from_utf8_lossy(&line))? {
+                Some(StreamEvent::Delta(delta)) => {
+                    on_delta(&delta);
+                    content.push_str(&delta);
+                }
+                Some(StreamEvent::Done) => done = true,
+                None => {}
+            }
+        }
+        if done {
+            break;
+        }
+    }
+
+    // A final line without a trailing newline
+    if !done && !buffer.is_empty() {
+        if let Some(StreamEvent::Delta(delta)) =
+            parse_stream_line(provider, &String::from_utf8_lossy(&buffer))?
+        {
+            on_delta(&delta);
+            content.push_str(&delta);
+        }
+    }
+
+    info!(
+        "🐞 LLM stream finished from {} ({} chars)",
+        provider_name(provider),
+        content.len()
+    );
+
+    if content.trim().is_empty() {
+        return Err("No content in LLM response".to_string());
+    }
+    Ok(content.trim().to_string())
+}
+
 /// Helper function to get provider name for logging
 fn provider_name(provider: &LLMProvider) -> &str {
     match provider {
@@ -245,3 +557,82 @@ fn provider_name(provider: &LLMProvider) -> &str {
         LLMProvider::OpenRouter => "OpenRouter",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openai_sse() {
+        let provider = LLMProvider::OpenAI;
+        assert_eq!(
+            parse_stream_line(&provider, r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#),
+            Ok(Some(StreamEvent::Delta("Hel".to_string())))
+        );
+        assert_eq!(
+            parse_stream_line(&provider, r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            Ok(None)
+        );
+        assert_eq!(parse_stream_line(&provider, "data: [DONE]"), Ok(Some(StreamEvent::Done)));
+        assert_eq!(parse_stream_line(&provider, ": keep-alive"), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_claude_sse() {
+        let provider = LLMProvider::Claude;
+        assert_eq!(parse_stream_line(&provider, "event: content_block_delta"), Ok(None));
+        assert_eq!(
+            parse_stream_line(
+                &provider,
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#
+            ),
+            Ok(Some(StreamEvent::Delta("Hi".to_string())))
+        );
+        assert_eq!(
+            parse_stream_line(&provider, r#"data: {"type":"message_stop"}"#),
+            Ok(Some(StreamEvent::Done))
+        );
+        assert!(parse_stream_line(
+            &provider,
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_structured_content_per_provider() {
+        let claude = serde_json::json!({
+            "content": [
+                {"type": "text", "text": "Here you go"},
+                {"type": "tool_use", "name": STRUCTURED_TOOL_NAME, "input": {"title": "Sync"}}
+            ]
+        });
+        assert_eq!(
+            structured_content(&LLMProvider::Claude, &claude).as_deref(),
+            Some(r#"{"title":"Sync"}"#)
+        );
+
+        let ollama = serde_json::json!({"message": {"role": "assistant", "content": "{}"}, "done": true});
+        assert_eq!(structured_content(&LLMProvider::Ollama, &ollama).as_deref(), Some("{}"));
+
+        let openai = serde_json::json!({"choices": [{"message": {"content": "{\"a\":1}"}}]});
+        assert_eq!(
+            structured_content(&LLMProvider::OpenAI, &openai).as_deref(),
+            Some(r#"{"a":1}"#)
+        );
+    }
+
+    #[test]
+    fn test_parse_ollama_ndjson() {
+        let provider = LLMProvider::Ollama;
+        assert_eq!(
+            parse_stream_line(&provider, r#"{"message":{"role":"assistant","content":"Yo"},"done":false}"#),
+            Ok(Some(StreamEvent::Delta("Yo".to_string())))
+        );
+        assert_eq!(
+            parse_stream_line(&provider, r#"{"message":{"role":"assistant","content":""},"done":true}"#),
+            Ok(Some(StreamEvent::Done))
+        );
+        assert!(parse_stream_line(&provider, r#"{"error":"model not found"}"#).is_err());
+    }
+}
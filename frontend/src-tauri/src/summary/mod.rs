@@ -3,21 +3,28 @@
 /// This module contains:
 /// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter)
 /// - Processor for chunking transcripts and generating summaries
+/// - Context window lookup for sizing chunks per model
 /// - Service layer for orchestrating summary generation
+/// - Job registry for cancelling running summaries
 /// - Templates for structured meeting summary generation
+/// - Structured (JSON) output mode with schema validation
 /// - Tauri commands for frontend integration
 
 pub mod commands;
+pub mod context;
+pub mod jobs;
 pub mod llm_client;
 pub mod processor;
 pub mod service;
+pub mod structured;
 pub mod template_commands;
 pub mod templates;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
-    __cmd__api_get_summary, __cmd__api_process_transcript, __cmd__api_save_meeting_summary,
-    api_get_summary, api_process_transcript, api_save_meeting_summary,
+    __cmd__api_cancel_summary, __cmd__api_get_summary, __cmd__api_process_transcript,
+    __cmd__api_save_meeting_summary, api_cancel_summary, api_get_summary,
+    api_process_transcript, api_save_meeting_summary,
 };
 
 // Re-export template commands
@@ -29,7 +36,9 @@ pub use template_commands::{
 // Re-export commonly used items
 pub use llm_client::LLMProvider;
 pub use processor::{
-    chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    chunk_text, chunk_transcript, clean_llm_markdown_output, extract_meeting_name_from_markdown,
+    generate_meeting_summary, rough_token_count, SummaryProgress, SummaryStage,
 };
+pub use jobs::SummaryJobs;
 pub use service::SummaryService;
+pub use structured::SummaryFormat;
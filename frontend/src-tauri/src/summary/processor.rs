@@ -1,12 +1,228 @@
-use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::llm_client::{generate_summary, generate_summary_stream, LLMProvider};
+use crate::summary::structured::{generate_structured_report, render_markdown, SummaryFormat};
 use crate::summary::templates;
 use regex::Regex;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{error, info};
 
-/// Rough token count estimation (4 characters ≈ 1 token)
+/// The LLM call a progress update belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStage {
+    /// Summarizing one transcript chunk (multi-level path only)
+    Chunk,
+    /// Merging the chunk summaries
+    Combine,
+    /// Filling in the template
+    Final,
+}
+
+/// Progress reported while a summary is being generated
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SummaryProgress {
+    /// An LLM call is starting. `chunk_index` is set for the chunk stage (0-based)
+    Stage {
+        stage: SummaryStage,
+        chunk_index: Option<usize>,
+        total_chunks: usize,
+    },
+    /// Text streamed from the current LLM call
+    Delta { stage: SummaryStage, text: String },
+    /// A transcript chunk has been summarized, or its summary reused from an earlier run
+    ChunkCompleted {
+        chunk_index: usize,
+        total_chunks: usize,
+        summary: String,
+        resumed: bool,
+        #[serde(skip)]
+        fingerprint: String,
+    },
+}
+
+/// A chunk summary saved so a retried job doesn't have to regenerate it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointedChunk {
+    pub index: usize,
+    /// `chunk_fingerprint` of the chunk text; a changed transcript or chunk size invalidates it
+    pub fingerprint: String,
+    pub summary: String,
+}
+
+/// Stable fingerprint of a chunk's text (64-bit FNV-1a, hex encoded)
+pub fn chunk_fingerprint(chunk: &str) -> String {
+    let hash = chunk.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn report(progress: Option<&UnboundedSender<SummaryProgress>>, update: SummaryProgress) {
+    if let Some(tx) = progress {
+        // The receiver only goes away once the job has finished with it
+        let _ = tx.send(update);
+    }
+}
+
+/// Runs one LLM call, streaming its output as `Delta` updates when progress is wanted
+async fn run_stage(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    stage: SummaryStage,
+    progress: Option<&UnboundedSender<SummaryProgress>>,
+) -> Result<String, String> {
+    match progress {
+        Some(tx) => {
+            let tx = tx.clone();
+            let on_delta = move |text: &str| {
+                let _ = tx.send(SummaryProgress::Delta {
+                    stage,
+                    text: text.to_string(),
+                });
+            };
+            generate_summary_stream(
+                client,
+                provider,
+                model_name,
+                api_key,
+                system_prompt,
+                user_prompt,
+                ollama_endpoint,
+                &on_delta,
+            )
+            .await
+        }
+        None => {
+            generate_summary(
+                client,
+                provider,
+                model_name,
+                api_key,
+                system_prompt,
+                user_prompt,
+                ollama_endpoint,
+            )
+            .await
+        }
+    }
+}
+
+/// Upper bound on the transcript repeated between consecutive chunks
+const MAX_CHUNK_OVERLAP_TOKENS: usize = 200;
+
+const SUMMARY_SEPARATOR: &str = "\n---\n";
+
+const COMBINE_SYSTEM_PROMPT: &str = "You are an expert at synthesizing meeting summaries.";
+const COMBINE_USER_PROMPT: &str = "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>";
+
+/// Rough token count estimation.
+///
+/// ASCII text averages about 4 characters per token. Other scripts tokenize far
+/// less efficiently: accented and Cyrillic/Greek letters are counted at 2 per token,
+/// CJK and other wide scripts at one token each, so budgets err on the safe side.
 pub fn rough_token_count(s: &str) -> usize {
-    (s.chars().count() as f64 / 4.0).ceil() as usize
+    let tokens: f64 = s
+        .chars()
+        .map(|c| {
+            if c.is_ascii() {
+                0.25
+            } else if c >= '\u{2E80}' {
+                1.0
+            } else {
+                0.5
+            }
+        })
+        .sum();
+    tokens.ceil() as usize
+}
+
+/// Chunks a transcript on segment (line) boundaries
+///
+/// Segments are packed greedily up to `chunk_size_tokens`; each chunk after the first
+/// starts with the trailing segments of the previous one, up to `overlap_tokens`, so
+/// context isn't lost at the cut. A single segment larger than a chunk is split with
+/// `chunk_text`.
+pub fn chunk_transcript(text: &str, chunk_size_tokens: usize, overlap_tokens: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut current_tokens = 0;
+
+    for segment in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let tokens = rough_token_count(segment) + 1; // the joining newline
+
+        if tokens > chunk_size_tokens {
+            if !current.is_empty() {
+                chunks.push(current.join("\n"));
+                current.clear();
+                current_tokens = 0;
+            }
+            chunks.extend(chunk_text(segment, chunk_size_tokens, overlap_tokens));
+            continue;
+        }
+
+        if current_tokens + tokens > chunk_size_tokens && !current.is_empty() {
+            chunks.push(current.join("\n"));
+
+            // Carry trailing segments over, but never the whole chunk
+            let mut carried_tokens = 0;
+            let mut keep_from = current.len();
+            while keep_from > 1 {
+                let t = rough_token_count(current[keep_from - 1]) + 1;
+                if carried_tokens + t > overlap_tokens {
+                    break;
+                }
+                carried_tokens += t;
+                keep_from -= 1;
+            }
+            current.drain(..keep_from);
+            current_tokens = carried_tokens;
+
+            if current_tokens + tokens > chunk_size_tokens {
+                current.clear();
+                current_tokens = 0;
+            }
+        }
+
+        current.push(segment);
+        current_tokens += tokens;
+    }
+
+    if !current.is_empty() {
+        chunks.push(current.join("\n"));
+    }
+
+    info!("Created {} segment-aligned chunks", chunks.len());
+    chunks
+}
+
+/// Groups consecutive summaries so each group fits `budget_tokens` once joined.
+/// A summary larger than the budget gets a group of its own.
+fn group_to_budget(summaries: &[String], budget_tokens: usize) -> Vec<&[String]> {
+    let separator_tokens = rough_token_count(SUMMARY_SEPARATOR);
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+
+    for (i, summary) in summaries.iter().enumerate() {
+        let t = rough_token_count(summary) + separator_tokens;
+        if i > start && tokens + t > budget_tokens {
+            groups.push(&summaries[start..i]);
+            start = i;
+            tokens = 0;
+        }
+        tokens += t;
+    }
+    if start < summaries.len() {
+        groups.push(&summaries[start..]);
+    }
+    groups
 }
 
 /// Chunks text into overlapping segments based on token count
@@ -117,7 +333,7 @@ pub fn extract_meeting_name_from_markdown(markdown: &str) -> Option<String> {
         .map(|line| line.trim_start_matches("# ").trim().to_string())
 }
 
-/// Generates a complete meeting summary with conditional chunking strategy
+/// Generates a complete meeting summary, map-reducing transcripts that exceed one call
 ///
 /// # Arguments
 /// * `client` - Reqwest HTTP client
@@ -127,11 +343,17 @@ pub fn extract_meeting_name_from_markdown(markdown: &str) -> Option<String> {
 /// * `text` - Full transcript text to summarize
 /// * `custom_prompt` - Optional user-provided context
 /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
-/// * `token_threshold` - Token limit for single-pass processing (default 4000)
+/// * `format` - Markdown, or structured JSON validated against the template schema
+/// * `token_threshold` - Tokens of transcript that fit in one call (see `context::transcript_budget`)
 /// * `ollama_endpoint` - Optional custom Ollama endpoint
+/// * `progress` - Optional channel for stage, chunk and streamed-text updates.
+///   When set, every LLM call is made in streaming mode.
+/// * `resume_from` - Chunk summaries from an earlier attempt; chunks whose text is
+///   unchanged are not sent to the LLM again
 ///
 /// # Returns
-/// Tuple of (final_summary_markdown, number_of_chunks_processed)
+/// Tuple of (final_summary_markdown, structured_summary, number_of_chunks_processed).
+/// In structured mode the Markdown is rendered from the validated JSON.
 pub async fn generate_meeting_summary(
     client: &Client,
     provider: &LLMProvider,
@@ -140,9 +362,13 @@ pub async fn generate_meeting_summary(
     text: &str,
     custom_prompt: &str,
     template_id: &str,
+    format: SummaryFormat,
+    output_language: Option<&str>,
     token_threshold: usize,
     ollama_endpoint: Option<&str>,
-) -> Result<(String, i64), String> {
+    progress: Option<&UnboundedSender<SummaryProgress>>,
+    resume_from: &[CheckpointedChunk],
+) -> Result<(String, Option<serde_json::Value>, i64), String> {
     info!(
         "Starting summary generation with provider: {:?}, model: {}",
         provider, model_name
@@ -154,9 +380,9 @@ pub async fn generate_meeting_summary(
     let content_to_summarize: String;
     let successful_chunk_count: i64;
 
-    // Strategy: Use single-pass for cloud providers or short transcripts
-    // Use multi-level chunking for Ollama with long transcripts
-    if provider != &LLMProvider::Ollama || total_tokens < token_threshold {
+    // Strategy: single pass when the transcript fits the model's context window,
+    // otherwise map-reduce: summarize segment-aligned chunks, then merge the summaries
+    if total_tokens <= token_threshold {
         info!(
             "Using single-pass summarization (tokens: {}, threshold: {})",
             total_tokens, token_threshold
@@ -169,8 +395,8 @@ pub async fn generate_meeting_summary(
             total_tokens, token_threshold
         );
 
-        // Reserve 300 tokens for prompt overhead
-        let chunks = chunk_text(text, token_threshold - 300, 100);
+        let overlap_tokens = (token_threshold / 20).min(MAX_CHUNK_OVERLAP_TOKENS);
+        let chunks = chunk_transcript(text, token_threshold, overlap_tokens);
         let num_chunks = chunks.len();
         info!("Split transcript into {} chunks", num_chunks);
 
@@ -179,10 +405,38 @@ pub async fn generate_meeting_summary(
         let user_prompt_template_chunk = "Provide a concise but comprehensive summary of the following transcript chunk. Capture all key points, decisions, action items, and mentioned individuals.\n\n<transcript_chunk>\n{}\n</transcript_chunk>";
 
         for (i, chunk) in chunks.iter().enumerate() {
+            let fingerprint = chunk_fingerprint(chunk);
+            if let Some(saved) = resume_from
+                .iter()
+                .find(|c| c.index == i && c.fingerprint == fingerprint)
+            {
+                info!("↩️ Reusing saved summary for chunk {}/{}", i + 1, num_chunks);
+                report(
+                    progress,
+                    SummaryProgress::ChunkCompleted {
+                        chunk_index: i,
+                        total_chunks: num_chunks,
+                        summary: saved.summary.clone(),
+                        resumed: true,
+                        fingerprint,
+                    },
+                );
+                chunk_summaries.push(saved.summary.clone());
+                continue;
+            }
+
             info!("⏲️ Processing chunk {}/{}", i + 1, num_chunks);
             let user_prompt_chunk = user_prompt_template_chunk.replace("{}", chunk.as_str());
+            report(
+                progress,
+                SummaryProgress::Stage {
+                    stage: SummaryStage::Chunk,
+                    chunk_index: Some(i),
+                    total_chunks: num_chunks,
+                },
+            );
 
-            match generate_summary(
+            match run_stage(
                 client,
                 provider,
                 model_name,
@@ -190,15 +444,35 @@ pub async fn generate_meeting_summary(
                 system_prompt_chunk,
                 &user_prompt_chunk,
                 ollama_endpoint,
+                SummaryStage::Chunk,
+                progress,
             )
             .await
             {
                 Ok(summary) => {
+                    report(
+                        progress,
+                        SummaryProgress::ChunkCompleted {
+                            chunk_index: i,
+                            total_chunks: num_chunks,
+                            summary: summary.clone(),
+                            resumed: false,
+                            fingerprint,
+                        },
+                    );
                     chunk_summaries.push(summary);
                     info!("✓ Chunk {}/{} processed successfully", i + 1, num_chunks);
                 }
                 Err(e) => {
+                    // Stop here rather than summarize with a hole in the transcript;
+                    // completed chunks are checkpointed and a retry resumes from this one
                     error!("⚠️ Failed processing chunk {}/{}: {}", i + 1, num_chunks, e);
+                    return Err(format!(
+                        "Failed processing chunk {}/{}: {}",
+                        i + 1,
+                        num_chunks,
+                        e
+                    ));
                 }
             }
         }
@@ -216,43 +490,97 @@ pub async fn generate_meeting_summary(
             successful_chunk_count, num_chunks
         );
 
-        // Combine chunk summaries if multiple chunks
-        content_to_summarize = if chunk_summaries.len() > 1 {
+        // Merge the chunk summaries, in several rounds if they don't fit one call
+        let mut summaries = chunk_summaries;
+        while summaries.len() > 1 {
+            let groups = group_to_budget(&summaries, token_threshold);
+            if groups.len() == summaries.len() {
+                // Every summary alone fills the budget; merging can't shrink them further
+                break;
+            }
             info!(
-                "Combining {} chunk summaries into cohesive summary",
-                chunk_summaries.len()
+                "Combining {} summaries into {} (token budget: {})",
+                summaries.len(),
+                groups.len(),
+                token_threshold
             );
-            let combined_text = chunk_summaries.join("\n---\n");
-            let system_prompt_combine = "You are an expert at synthesizing meeting summaries.";
-            let user_prompt_combine_template = "The following are consecutive summaries of a meeting. Combine them into a single, coherent, and detailed narrative summary that retains all important details, organized logically.\n\n<summaries>\n{}\n</summaries>";
 
-            let user_prompt_combine = user_prompt_combine_template.replace("{}", &combined_text);
-            generate_summary(
-                client,
-                provider,
-                model_name,
-                api_key,
-                system_prompt_combine,
-                &user_prompt_combine,
-                ollama_endpoint,
-            )
-            .await?
-        } else {
-            chunk_summaries.remove(0)
-        };
+            let mut merged = Vec::with_capacity(groups.len());
+            for group in groups {
+                if group.len() == 1 {
+                    merged.push(group[0].clone());
+                    continue;
+                }
+                report(
+                    progress,
+                    SummaryProgress::Stage {
+                        stage: SummaryStage::Combine,
+                        chunk_index: None,
+                        total_chunks: num_chunks,
+                    },
+                );
+                merged.push(
+                    run_stage(
+                        client,
+                        provider,
+                        model_name,
+                        api_key,
+                        COMBINE_SYSTEM_PROMPT,
+                        &COMBINE_USER_PROMPT.replace("{}", &group.join(SUMMARY_SEPARATOR)),
+                        ollama_endpoint,
+                        SummaryStage::Combine,
+                        progress,
+                    )
+                    .await?,
+                );
+            }
+            summaries = merged;
+        }
+        content_to_summarize = summaries.join(SUMMARY_SEPARATOR);
     }
 
-    info!("Generating final markdown report with template: {}", template_id);
-
     // Load the template using the provided template_id
     let template = templates::get_template(template_id)
         .map_err(|e| format!("Failed to load template '{}': {}", template_id, e))?;
 
+    if format == SummaryFormat::Structured {
+        info!("Generating structured report with template: {}", template_id);
+        report(
+            progress,
+            SummaryProgress::Stage {
+                stage: SummaryStage::Final,
+                chunk_index: None,
+                total_chunks: successful_chunk_count as usize,
+            },
+        );
+        let structured = generate_structured_report(
+            client,
+            provider,
+            model_name,
+            api_key,
+            &template,
+            &content_to_summarize,
+            custom_prompt,
+            output_language,
+            ollama_endpoint,
+        )
+        .await?;
+
+        info!("Structured summary generation completed successfully");
+        return Ok((
+            render_markdown(&template, &structured),
+            Some(structured),
+            successful_chunk_count,
+        ));
+    }
+
+    info!("Generating final markdown report with template: {}", template_id);
+
     // Generate markdown structure and section instructions using template methods
     let clean_template_markdown = template.to_markdown_structure();
     let section_instructions = template.to_section_instructions();
 
-    let final_system_prompt = format!(
+    let mut final_system_prompt = format!(
         r#"You are an expert meeting summarizer. Generate a final meeting report by filling in the provided Markdown template based on the source text.
 
 **CRITICAL INSTRUCTIONS:**
@@ -272,6 +600,12 @@ pub async fn generate_meeting_summary(
 "#,
         section_instructions, clean_template_markdown
     );
+    if let Some(language) = output_language {
+        final_system_prompt.push_str(&format!(
+            "\nWrite the entire report in {}, including the section headings, whatever language the source text is in.\n",
+            language
+        ));
+    }
 
     let mut final_user_prompt = format!(
         r#"
@@ -288,7 +622,15 @@ pub async fn generate_meeting_summary(
         final_user_prompt.push_str("\n</user_context>");
     }
 
-    let raw_markdown = generate_summary(
+    report(
+        progress,
+        SummaryProgress::Stage {
+            stage: SummaryStage::Final,
+            chunk_index: None,
+            total_chunks: successful_chunk_count as usize,
+        },
+    );
+    let raw_markdown = run_stage(
         client,
         provider,
         model_name,
@@ -296,6 +638,8 @@ pub async fn generate_meeting_summary(
         &final_system_prompt,
         &final_user_prompt,
         ollama_endpoint,
+        SummaryStage::Final,
+        progress,
     )
     .await?;
 
@@ -303,5 +647,57 @@ pub async fn generate_meeting_summary(
     let final_markdown = clean_llm_markdown_output(&raw_markdown);
 
     info!("Summary generation completed successfully");
-    Ok((final_markdown, successful_chunk_count))
+    Ok((final_markdown, None, successful_chunk_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_fingerprint_is_stable() {
+        // Reference FNV-1a values; checkpoints saved by older builds must keep matching
+        assert_eq!(chunk_fingerprint(""), "cbf29ce484222325");
+        assert_eq!(chunk_fingerprint("a"), "af63dc4c8601ec8c");
+        assert_ne!(chunk_fingerprint("chunk one"), chunk_fingerprint("chunk two"));
+    }
+
+    #[test]
+    fn test_rough_token_count_weights_scripts() {
+        assert_eq!(rough_token_count("abcdefgh"), 2);
+        assert_eq!(rough_token_count("éé"), 1);
+        assert_eq!(rough_token_count("会議"), 2);
+    }
+
+    #[test]
+    fn test_chunk_transcript_splits_on_segments_with_overlap() {
+        // Each segment is 10 tokens including its newline
+        let segment = |n: usize| format!("{:02}{}", n, "x".repeat(34));
+        let text: Vec<String> = (0..6).map(segment).collect();
+
+        let chunks = chunk_transcript(&text.join("\n"), 30, 10);
+        assert_eq!(chunks.len(), 3);
+        for chunk in &chunks {
+            assert!(chunk.lines().all(|l| text.contains(&l.to_string())));
+        }
+        // The last segment of one chunk opens the next
+        assert!(chunks[1].starts_with(&text[2]));
+        assert!(chunks[2].ends_with(&text[5]));
+    }
+
+    #[test]
+    fn test_chunk_transcript_splits_oversized_segment() {
+        let long = "word ".repeat(100);
+        let chunks = chunk_transcript(&format!("short\n{}", long.trim()), 40, 0);
+        assert_eq!(chunks[0], "short");
+        assert!(chunks.len() > 2);
+    }
+
+    #[test]
+    fn test_group_to_budget() {
+        let summaries: Vec<String> = vec!["a".repeat(40), "b".repeat(40), "c".repeat(40), "d".repeat(400)];
+        let groups = group_to_budget(&summaries, 25);
+        let sizes: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+        assert_eq!(sizes, vec![2, 1, 1]);
+    }
 }
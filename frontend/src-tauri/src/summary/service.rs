@@ -1,19 +1,47 @@
 use crate::database::repositories::{
     meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
 };
+use crate::summary::context::{context_window, transcript_budget};
 use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
-use crate::ollama::metadata::ModelMetadataCache;
+use crate::summary::structured::SummaryFormat;
+use crate::summary::processor::{
+    extract_meeting_name_from_markdown, generate_meeting_summary, CheckpointedChunk,
+    SummaryProgress, SummaryStage,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::SqlitePool;
 use std::time::{Duration, Instant};
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
 use tracing::{error, info, warn};
-use once_cell::sync::Lazy;
 
-// Global cache for model metadata (5 minute TTL)
-static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
-    ModelMetadataCache::new(Duration::from_secs(300))
-});
+/// How often streamed text is written to `summary_processes` while a stage is running
+const PARTIAL_SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Key of the chunk checkpoint inside `summary_processes.metadata`
+const CHECKPOINT_KEY: &str = "chunk_checkpoint";
+
+/// Chunk summaries of the last attempt. They are only reused with the model that produced them.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ChunkCheckpoint {
+    model: String,
+    chunks: Vec<CheckpointedChunk>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SummaryProgressEvent<'a> {
+    meeting_id: &'a str,
+    #[serde(flatten)]
+    progress: &'a SummaryProgress,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SummaryDeltaEvent<'a> {
+    meeting_id: &'a str,
+    stage: SummaryStage,
+    text: &'a str,
+}
 
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
@@ -25,7 +53,7 @@ impl SummaryService {
     /// the main thread. It updates the database with progress and results.
     ///
     /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
+    /// * `app` - Tauri app handle, used to emit `summary-progress` and `summary-delta` events
     /// * `pool` - SQLx connection pool
     /// * `meeting_id` - Unique identifier for the meeting
     /// * `text` - Full transcript text
@@ -33,8 +61,10 @@ impl SummaryService {
     /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
     /// * `custom_prompt` - Optional user-provided context
     /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+    /// * `format` - Markdown or structured (schema-validated JSON) output
+    /// * `output_language` - Language to write the summary in (None keeps the transcript's)
     pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
+        app: AppHandle<R>,
         pool: SqlitePool,
         meeting_id: String,
         text: String,
@@ -42,6 +72,8 @@ impl SummaryService {
         model_name: String,
         custom_prompt: String,
         template_id: String,
+        format: SummaryFormat,
+        output_language: Option<String>,
     ) {
         let start_time = Instant::now();
         info!(
@@ -90,30 +122,44 @@ impl SummaryService {
             None
         };
 
-        // Dynamically fetch context size for Ollama models
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
-                Ok(metadata) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = metadata.context_size.saturating_sub(300);
-                    info!(
-                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                        model_name, metadata.context_size, optimal
-                    );
-                    optimal
-                }
-                Err(e) => {
-                    warn!(
-                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
-                        model_name, e
-                    );
-                    4000  // Fallback to safe default
-                }
-            }
-        } else {
-            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
-            100000  // Effectively unlimited for single-pass processing
-        };
+        // Size each call from the model's real context window
+        let context_window = context_window(&provider, &model_name, ollama_endpoint.as_deref()).await;
+        let token_threshold = transcript_budget(context_window);
+        info!(
+            "✓ Using context window of {} tokens for {} (transcript budget per call: {})",
+            context_window, model_name, token_threshold
+        );
+
+        // Resume from the chunk summaries of an earlier attempt, if it used the same model
+        let metadata = Self::load_metadata(&pool, &meeting_id).await;
+        let checkpoint_model = format!("{}:{}", model_provider.to_lowercase(), model_name);
+        let checkpoint = metadata
+            .get(CHECKPOINT_KEY)
+            .and_then(|c| serde_json::from_value::<ChunkCheckpoint>(c.clone()).ok())
+            .filter(|c| c.model == checkpoint_model)
+            .unwrap_or_else(|| ChunkCheckpoint {
+                model: checkpoint_model,
+                chunks: Vec::new(),
+            });
+        if !checkpoint.chunks.is_empty() {
+            info!(
+                "↩️ Found {} checkpointed chunk summaries for meeting_id: {}",
+                checkpoint.chunks.len(),
+                meeting_id
+            );
+        }
+        let resume_from = checkpoint.chunks.clone();
+
+        // Forward progress to the frontend and persist partial output while generating
+        let (progress_tx, progress_rx) = mpsc::unbounded_channel();
+        let progress_task = tokio::spawn(Self::forward_progress(
+            app,
+            pool.clone(),
+            meeting_id.clone(),
+            progress_rx,
+            metadata,
+            checkpoint,
+        ));
 
         // Generate summary
         let client = reqwest::Client::new();
@@ -125,15 +171,25 @@ impl SummaryService {
             &text,
             &custom_prompt,
             &template_id,
+            format,
+            output_language.as_deref(),
             token_threshold,
             ollama_endpoint.as_deref(),
+            Some(&progress_tx),
+            &resume_from,
         )
         .await;
 
+        // Let the last partial write land before the final status is stored
+        drop(progress_tx);
+        if let Err(e) = progress_task.await {
+            warn!("Summary progress task for {} ended abnormally: {}", meeting_id, e);
+        }
+
         let duration = start_time.elapsed().as_secs_f64();
 
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok((mut final_markdown, structured, num_chunks)) => {
                 if num_chunks == 0 && final_markdown.is_empty() {
                     Self::update_process_failed(
                         &pool,
@@ -182,10 +238,14 @@ impl SummaryService {
                     }
                 }
 
-                // Create result JSON with markdown only (summary_json will be added on first edit)
-                let result_json = serde_json::json!({
+                // Create result JSON with markdown (summary_json will be added on first edit).
+                // Structured summaries also keep the validated JSON for programmatic use.
+                let mut result_json = serde_json::json!({
                     "markdown": final_markdown,
                 });
+                if let Some(structured) = structured {
+                    result_json["structured"] = structured;
+                }
 
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
@@ -214,6 +274,140 @@ impl SummaryService {
         }
     }
 
+    /// Emits progress events and periodically saves the partial result.
+    ///
+    /// The saved JSON has the same `markdown` key as a finished summary (text of the
+    /// final stage streamed so far) plus the completed `chunk_summaries`, so whatever
+    /// was generated before a crash can be shown. Each newly summarized chunk is also
+    /// checkpointed into the process metadata for a later retry to resume from.
+    async fn forward_progress<R: tauri::Runtime>(
+        app: AppHandle<R>,
+        pool: SqlitePool,
+        meeting_id: String,
+        mut progress_rx: mpsc::UnboundedReceiver<SummaryProgress>,
+        mut metadata: Value,
+        mut checkpoint: ChunkCheckpoint,
+    ) {
+        let mut chunk_summaries: Vec<String> = Vec::new();
+        let mut final_markdown = String::new();
+        let mut last_saved = Instant::now();
+        let mut dirty = false;
+
+        while let Some(progress) = progress_rx.recv().await {
+            let mut save_now = false;
+            match &progress {
+                SummaryProgress::Delta { stage, text } => {
+                    let _ = app.emit(
+                        "summary-delta",
+                        SummaryDeltaEvent {
+                            meeting_id: &meeting_id,
+                            stage: *stage,
+                            text,
+                        },
+                    );
+                    if *stage == SummaryStage::Final {
+                        final_markdown.push_str(text);
+                        dirty = true;
+                    }
+                }
+                SummaryProgress::Stage { .. } => {
+                    let _ = app.emit(
+                        "summary-progress",
+                        SummaryProgressEvent {
+                            meeting_id: &meeting_id,
+                            progress: &progress,
+                        },
+                    );
+                }
+                SummaryProgress::ChunkCompleted {
+                    chunk_index,
+                    summary,
+                    resumed,
+                    fingerprint,
+                    ..
+                } => {
+                    chunk_summaries.push(summary.clone());
+                    save_now = true;
+                    if !resumed {
+                        checkpoint.chunks.retain(|c| c.index != *chunk_index);
+                        checkpoint.chunks.push(CheckpointedChunk {
+                            index: *chunk_index,
+                            fingerprint: fingerprint.clone(),
+                            summary: summary.clone(),
+                        });
+                        Self::save_checkpoint(&pool, &meeting_id, &mut metadata, &checkpoint)
+                            .await;
+                    }
+                    let _ = app.emit(
+                        "summary-progress",
+                        SummaryProgressEvent {
+                            meeting_id: &meeting_id,
+                            progress: &progress,
+                        },
+                    );
+                }
+            }
+
+            if save_now || (dirty && last_saved.elapsed() >= PARTIAL_SAVE_INTERVAL) {
+                Self::save_partial(&pool, &meeting_id, &final_markdown, &chunk_summaries).await;
+                last_saved = Instant::now();
+                dirty = false;
+            }
+        }
+
+        if dirty {
+            Self::save_partial(&pool, &meeting_id, &final_markdown, &chunk_summaries).await;
+        }
+    }
+
+    /// Current process metadata as a JSON object (empty if unset or unreadable)
+    async fn load_metadata(pool: &SqlitePool, meeting_id: &str) -> Value {
+        match SummaryProcessesRepository::get_summary_data(pool, meeting_id).await {
+            Ok(Some(process)) => process
+                .metadata
+                .and_then(|m| serde_json::from_str::<Value>(&m).ok())
+                .filter(|m| m.is_object())
+                .unwrap_or_else(|| serde_json::json!({})),
+            Ok(None) => serde_json::json!({}),
+            Err(e) => {
+                warn!("⚠️ Failed to load summary metadata for {}: {}", meeting_id, e);
+                serde_json::json!({})
+            }
+        }
+    }
+
+    async fn save_checkpoint(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        metadata: &mut Value,
+        checkpoint: &ChunkCheckpoint,
+    ) {
+        metadata[CHECKPOINT_KEY] = serde_json::json!(checkpoint);
+        if let Err(e) =
+            SummaryProcessesRepository::update_process_metadata(pool, meeting_id, metadata).await
+        {
+            warn!("⚠️ Failed to checkpoint chunk summaries for {}: {}", meeting_id, e);
+        }
+    }
+
+    async fn save_partial(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        markdown: &str,
+        chunk_summaries: &[String],
+    ) {
+        let partial = serde_json::json!({
+            "markdown": markdown,
+            "chunk_summaries": chunk_summaries,
+            "partial": true,
+        });
+        if let Err(e) =
+            SummaryProcessesRepository::update_process_partial(pool, meeting_id, &partial).await
+        {
+            warn!("⚠️ Failed to save partial summary for {}: {}", meeting_id, e);
+        }
+    }
+
     /// Updates the summary process status to failed with error message
     ///
     /// # Arguments
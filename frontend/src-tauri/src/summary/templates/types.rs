@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +22,65 @@ pub struct TemplateSection {
     pub example_item_format: Option<String>,
 }
 
+impl TemplateSection {
+    /// Whether items of this section are tasks, structured as {owner, task, due}
+    pub fn is_action_items(&self) -> bool {
+        self.title.to_lowercase().contains("action item")
+            || self
+                .item_format
+                .as_ref()
+                .or(self.example_item_format.as_ref())
+                .map(|f| f.to_lowercase().contains("owner"))
+                .unwrap_or(false)
+    }
+
+    /// JSON schema of this section's field in structured output
+    fn to_json_schema(&self) -> Value {
+        if self.is_action_items() {
+            json!({
+                "type": "array",
+                "description": self.instruction,
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "owner": { "type": ["string", "null"] },
+                        "task": { "type": "string" },
+                        "due": { "type": ["string", "null"] }
+                    },
+                    "required": ["owner", "task", "due"],
+                    "additionalProperties": false
+                }
+            })
+        } else if self.format == "list" {
+            json!({
+                "type": "array",
+                "description": self.instruction,
+                "items": { "type": "string" }
+            })
+        } else {
+            json!({ "type": "string", "description": self.instruction })
+        }
+    }
+}
+
+/// Field name of a section title in structured output ("Key Decisions" -> "key_decisions")
+pub fn section_key(title: &str) -> String {
+    let mut key = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            key.extend(c.to_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    let key = key.trim_end_matches('_').to_string();
+    if key.is_empty() {
+        "section".to_string()
+    } else {
+        key
+    }
+}
+
 /// Represents a complete meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Template {
@@ -81,6 +141,44 @@ impl Template {
         markdown
     }
 
+    /// Structured-output field name of each section, in order. Duplicates get a numeric
+    /// suffix and "title" is reserved for the meeting title.
+    pub fn section_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::with_capacity(self.sections.len());
+        for section in &self.sections {
+            let base = section_key(&section.title);
+            let mut key = base.clone();
+            let mut n = 2;
+            while key == "title" || keys.contains(&key) {
+                key = format!("{}_{}", base, n);
+                n += 1;
+            }
+            keys.push(key);
+        }
+        keys
+    }
+
+    /// JSON schema for structured output: the meeting title plus one typed field per
+    /// section. Every field is required and no others are allowed (strict-mode compatible).
+    pub fn to_json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        properties.insert(
+            "title".to_string(),
+            json!({ "type": "string", "description": "Concise, descriptive meeting title" }),
+        );
+        for (key, section) in self.section_keys().into_iter().zip(&self.sections) {
+            properties.insert(key, section.to_json_schema());
+        }
+        let required: Vec<Value> = properties.keys().cloned().map(Value::String).collect();
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false
+        })
+    }
+
     /// Generates section-specific instructions for the LLM
     pub fn to_section_instructions(&self) -> String {
         let mut instructions = String::from(
@@ -161,4 +259,39 @@ mod tests {
 
         assert!(template.validate().is_err());
     }
+
+    #[test]
+    fn test_json_schema_types_sections() {
+        let section = |title: &str, format: &str| TemplateSection {
+            title: title.to_string(),
+            instruction: "Do it".to_string(),
+            format: format.to_string(),
+            item_format: None,
+            example_item_format: None,
+        };
+        let template = Template {
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            sections: vec![
+                section("Summary", "paragraph"),
+                section("Key Decisions", "list"),
+                section("Action Items", "list"),
+                section("Title", "string"),
+            ],
+        };
+
+        assert_eq!(
+            template.section_keys(),
+            vec!["summary", "key_decisions", "action_items", "title_2"]
+        );
+
+        let schema = template.to_json_schema();
+        assert_eq!(schema["properties"]["summary"]["type"], "string");
+        assert_eq!(schema["properties"]["key_decisions"]["items"]["type"], "string");
+        assert_eq!(
+            schema["properties"]["action_items"]["items"]["required"],
+            json!(["owner", "task", "due"])
+        );
+        assert_eq!(schema["required"].as_array().map(|r| r.len()), Some(5));
+    }
 }
@@ -0,0 +1,263 @@
+// transcript_edit/commands.rs
+//
+// Tauri commands for correcting transcript segments. Every edit is stored as a revision
+// (who, when, segments before and after) that can be reviewed and reverted, and marks the
+// meeting's summary as stale.
+
+use super::operations;
+use crate::audio::transcription::vocabulary_commands::learn_correction;
+use crate::database::models::{Transcript, TranscriptRevisionModel};
+use crate::database::repositories::embedding::EmbeddingsRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::database::repositories::transcript_revision::TranscriptRevisionsRepository;
+use crate::state::AppState;
+use crate::whisper_engine::retranscribe::{load_folder, write_transcripts_from_rows};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Transcript revision for UI display
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptRevisionInfo {
+    pub id: i64,
+    pub meeting_id: String,
+    /// "edit_text", "set_speaker", "split", "merge" or "revert"
+    pub operation: String,
+    pub author: Option<String>,
+    pub before: Vec<Transcript>,
+    pub after: Vec<Transcript>,
+    /// Revision undone by this one
+    pub reverts_id: Option<i64>,
+    /// Revision that undid this one
+    pub reverted_by: Option<i64>,
+    pub created_at: String,
+}
+
+impl From<TranscriptRevisionModel> for TranscriptRevisionInfo {
+    fn from(r: TranscriptRevisionModel) -> Self {
+        Self {
+            id: r.id,
+            before: serde_json::from_str(&r.before_segments).unwrap_or_default(),
+            after: serde_json::from_str(&r.after_segments).unwrap_or_default(),
+            meeting_id: r.meeting_id,
+            operation: r.operation,
+            author: r.author,
+            reverts_id: r.reverts_id,
+            reverted_by: r.reverted_by,
+            created_at: r.created_at.0.to_rfc3339(),
+        }
+    }
+}
+
+async fn load_segments(pool: &SqlitePool, meeting_id: &str) -> Result<Vec<Transcript>, String> {
+    TranscriptsRepository::get_meeting_transcripts(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))
+}
+
+fn find_segment<'a>(segments: &'a [Transcript], transcript_id: &str) -> Result<&'a Transcript, String> {
+    segments
+        .iter()
+        .find(|s| s.id == transcript_id)
+        .ok_or_else(|| format!("Transcript segment {} not found", transcript_id))
+}
+
+/// Record an edit, then bring transcripts.json and the search index in line with it
+async fn apply(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    operation: &str,
+    author: Option<String>,
+    before: &[Transcript],
+    after: &[Transcript],
+    reverts_id: Option<i64>,
+) -> Result<TranscriptRevisionInfo, String> {
+    let author = author.as_deref().map(str::trim).filter(|a| !a.is_empty());
+    let revision = TranscriptRevisionsRepository::apply_revision(
+        pool, meeting_id, operation, author, before, after, reverts_id,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Failed to apply {} to {}: {}", operation, meeting_id, e);
+        format!("Failed to save transcript edit: {}", e)
+    })?;
+
+    match load_folder(pool, meeting_id).await {
+        Ok(folder) => match load_segments(pool, meeting_id).await {
+            Ok(rows) => {
+                if let Err(e) = write_transcripts_from_rows(&folder, &rows) {
+                    log_warn!("Failed to rewrite transcripts.json for {}: {}", meeting_id, e);
+                }
+            }
+            Err(e) => log_warn!("Failed to rewrite transcripts.json for {}: {}", meeting_id, e),
+        },
+        Err(e) => log_warn!("Skipping transcripts.json update for {}: {}", meeting_id, e),
+    }
+    if let Err(e) = EmbeddingsRepository::delete_meeting_embeddings(pool, meeting_id).await {
+        log_warn!("Failed to invalidate embeddings for {}: {}", meeting_id, e);
+    }
+
+    Ok(revision.into())
+}
+
+/// Correct the text of a segment. With `learn_vocabulary`, a corrected word or short
+/// phrase is also saved as a glossary alias so future transcripts spell it correctly.
+#[tauri::command]
+pub async fn api_edit_transcript_segment(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_id: String,
+    text: String,
+    author: Option<String>,
+    learn_vocabulary: Option<bool>,
+) -> Result<TranscriptRevisionInfo, String> {
+    log_info!("api_edit_transcript_segment called for {} in {}", transcript_id, meeting_id);
+    let pool = state.db_manager.pool();
+    let segments = load_segments(pool, &meeting_id).await?;
+    let original = find_segment(&segments, &transcript_id)?;
+    let edited = operations::edit_text(original, &text)?;
+    let correction = operations::changed_words(&original.transcript, &edited.transcript);
+    let revision = apply(pool, &meeting_id, "edit_text", author, &[original.clone()], &[edited], None).await?;
+
+    if learn_vocabulary.unwrap_or(false) {
+        if let Some((misheard, corrected)) = correction {
+            if let Err(e) = learn_correction(pool, &misheard, &corrected, None).await {
+                log_warn!("Failed to learn vocabulary from edit in {}: {}", meeting_id, e);
+            }
+        }
+    }
+    Ok(revision)
+}
+
+/// Attribute a segment to another speaker label and/or registered speaker
+#[tauri::command]
+pub async fn api_set_segment_speaker(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_id: String,
+    speaker_id: Option<i64>,
+    speaker_profile_id: Option<i64>,
+    author: Option<String>,
+) -> Result<TranscriptRevisionInfo, String> {
+    log_info!(
+        "api_set_segment_speaker called for {} in {} -> {:?}/{:?}",
+        transcript_id,
+        meeting_id,
+        speaker_id,
+        speaker_profile_id
+    );
+    let pool = state.db_manager.pool();
+    let segments = load_segments(pool, &meeting_id).await?;
+    let original = find_segment(&segments, &transcript_id)?;
+    let edited = operations::set_speaker(original, speaker_id, speaker_profile_id)?;
+    apply(pool, &meeting_id, "set_speaker", author, &[original.clone()], &[edited], None).await
+}
+
+/// Split a segment in two at character `position` of its text
+#[tauri::command]
+pub async fn api_split_transcript_segment(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_id: String,
+    position: usize,
+    author: Option<String>,
+) -> Result<TranscriptRevisionInfo, String> {
+    log_info!(
+        "api_split_transcript_segment called for {} in {} at {}",
+        transcript_id,
+        meeting_id,
+        position
+    );
+    let pool = state.db_manager.pool();
+    let segments = load_segments(pool, &meeting_id).await?;
+    let original = find_segment(&segments, &transcript_id)?;
+    let new_id = format!("transcript-{}", Uuid::new_v4());
+    let (first, second) = operations::split(original, position, new_id)?;
+    apply(pool, &meeting_id, "split", author, &[original.clone()], &[first, second], None).await
+}
+
+/// Merge neighbouring segments into the earliest one. The ids must form a contiguous run
+/// of the transcript in playback order.
+#[tauri::command]
+pub async fn api_merge_transcript_segments(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_ids: Vec<String>,
+    author: Option<String>,
+) -> Result<TranscriptRevisionInfo, String> {
+    log_info!(
+        "api_merge_transcript_segments called for {} segments in {}",
+        transcript_ids.len(),
+        meeting_id
+    );
+    let pool = state.db_manager.pool();
+    let segments = load_segments(pool, &meeting_id).await?;
+
+    let mut positions = transcript_ids
+        .iter()
+        .map(|id| {
+            segments
+                .iter()
+                .position(|s| &s.id == id)
+                .ok_or_else(|| format!("Transcript segment {} not found", id))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    positions.sort_unstable();
+    positions.dedup();
+    if positions.windows(2).any(|w| w[1] != w[0] + 1) {
+        return Err("Only neighbouring segments can be merged".to_string());
+    }
+
+    let selected: Vec<Transcript> = positions.iter().map(|&i| segments[i].clone()).collect();
+    let merged = operations::merge(&selected)?;
+    apply(pool, &meeting_id, "merge", author, &selected, &[merged], None).await
+}
+
+/// Edit history of a meeting's transcript, newest first
+#[tauri::command]
+pub async fn api_list_transcript_revisions(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<TranscriptRevisionInfo>, String> {
+    TranscriptRevisionsRepository::list_revisions(state.db_manager.pool(), &meeting_id)
+        .await
+        .map(|revisions| revisions.into_iter().map(Into::into).collect())
+        .map_err(|e| format!("Failed to load transcript history: {}", e))
+}
+
+/// Undo a revision by applying its inverse as a new revision. Fails if the segments it
+/// produced have been edited since.
+#[tauri::command]
+pub async fn api_revert_transcript_revision(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    revision_id: i64,
+    author: Option<String>,
+) -> Result<TranscriptRevisionInfo, String> {
+    log_info!("api_revert_transcript_revision called for {} (revision {})", meeting_id, revision_id);
+    let pool = state.db_manager.pool();
+    let revision: TranscriptRevisionInfo =
+        TranscriptRevisionsRepository::get_revision(pool, &meeting_id, revision_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript revision: {}", e))?
+            .ok_or_else(|| format!("Transcript revision {} not found", revision_id))?
+            .into();
+    if let Some(reverted_by) = revision.reverted_by {
+        return Err(format!(
+            "Revision {} was already reverted by revision {}",
+            revision_id, reverted_by
+        ));
+    }
+
+    apply(
+        pool,
+        &meeting_id,
+        "revert",
+        author,
+        &revision.after,
+        &revision.before,
+        Some(revision_id),
+    )
+    .await
+}
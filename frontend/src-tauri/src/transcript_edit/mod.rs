@@ -0,0 +1,9 @@
+/// Transcript editing module - segment-level corrections with revision history
+///
+/// This module contains:
+/// - Operations editing, re-attributing, splitting and merging transcript segments
+/// - Tauri commands applying edits to the database and the meeting folder's
+///   transcripts.json, recording each one as a revertible revision
+
+pub mod commands;
+pub mod operations;
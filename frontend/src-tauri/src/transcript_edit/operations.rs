@@ -0,0 +1,306 @@
+// transcript_edit/operations.rs
+//
+// Segment edits as pure functions over `transcripts` rows: each returns the rows that
+// replace the edited ones, keeping timings and word timestamps consistent.
+
+use crate::audio::import::format_offset;
+use crate::audio::transcription::WordTimestamp;
+use crate::database::models::Transcript;
+
+/// Longest word run `changed_words` reports as a misrecognition
+const MAX_LEARNED_WORDS: usize = 4;
+
+fn parse_words(row: &Transcript) -> Option<Vec<WordTimestamp>> {
+    row.words
+        .as_deref()
+        .and_then(|w| serde_json::from_str::<Vec<WordTimestamp>>(w).ok())
+        .filter(|w| !w.is_empty())
+}
+
+fn store_words(words: Option<Vec<WordTimestamp>>) -> Option<String> {
+    words
+        .filter(|w| !w.is_empty())
+        .and_then(|w| serde_json::to_string(&w).ok())
+}
+
+/// Word timings that still line up with `text`: kept (re-spelled) when the word count is
+/// unchanged, dropped otherwise
+fn realign_words(words: Option<Vec<WordTimestamp>>, text: &str) -> Option<Vec<WordTimestamp>> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut words = words?;
+    if words.len() != tokens.len() {
+        return None;
+    }
+    for (word, token) in words.iter_mut().zip(tokens) {
+        if word.word != token {
+            word.word = token.to_string();
+        }
+    }
+    Some(words)
+}
+
+/// Replace a segment's text
+pub fn edit_text(row: &Transcript, text: &str) -> Result<Transcript, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Segment text cannot be empty; merge or delete the segment instead".to_string());
+    }
+    if text == row.transcript {
+        return Err("The text is unchanged".to_string());
+    }
+
+    let mut edited = row.clone();
+    edited.transcript = text.to_string();
+    edited.words = store_words(realign_words(parse_words(row), text));
+    Ok(edited)
+}
+
+/// The one run of words an edit changed, as (original, corrected) with outer punctuation
+/// trimmed. None when nothing but case or punctuation changed, when words were only added
+/// or removed, or when the run is longer than `MAX_LEARNED_WORDS` (a rewrite, not a fix).
+pub fn changed_words(original: &str, edited: &str) -> Option<(String, String)> {
+    let before: Vec<&str> = original.split_whitespace().collect();
+    let after: Vec<&str> = edited.split_whitespace().collect();
+    let prefix = before.iter().zip(&after).take_while(|(a, b)| a == b).count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let from = &before[prefix..before.len() - suffix];
+    let to = &after[prefix..after.len() - suffix];
+    if from.is_empty() || to.is_empty() || from.len().max(to.len()) > MAX_LEARNED_WORDS {
+        return None;
+    }
+
+    let trim = |words: &[&str]| words.join(" ").trim_matches(|c: char| !c.is_alphanumeric()).to_string();
+    let (from, to) = (trim(from), trim(to));
+    (!from.is_empty() && !to.is_empty() && from.to_lowercase() != to.to_lowercase()).then_some((from, to))
+}
+
+/// Assign a segment to a diarized speaker label and/or a registered speaker
+pub fn set_speaker(
+    row: &Transcript,
+    speaker_id: Option<i64>,
+    speaker_profile_id: Option<i64>,
+) -> Result<Transcript, String> {
+    if row.speaker_id == speaker_id && row.speaker_profile_id == speaker_profile_id {
+        return Err("The speaker is unchanged".to_string());
+    }
+    let mut edited = row.clone();
+    edited.speaker_id = speaker_id;
+    edited.speaker_profile_id = speaker_profile_id;
+    edited.speaker_name = None;
+    Ok(edited)
+}
+
+/// Split a segment in two at character `position` of its text.
+///
+/// The split time comes from the word timings when they match the text, otherwise it is
+/// interpolated by character position. The first part keeps the row's id; the second
+/// gets `new_id`.
+pub fn split(row: &Transcript, position: usize, new_id: String) -> Result<(Transcript, Transcript), String> {
+    let chars: Vec<char> = row.transcript.chars().collect();
+    if position == 0 || position >= chars.len() {
+        return Err("The split position must be inside the segment text".to_string());
+    }
+    let left: String = chars[..position].iter().collect::<String>().trim().to_string();
+    let right: String = chars[position..].iter().collect::<String>().trim().to_string();
+    if left.is_empty() || right.is_empty() {
+        return Err("Both parts of a split segment need text".to_string());
+    }
+
+    let words = realign_words(parse_words(row), &row.transcript);
+    let left_count = left.split_whitespace().count();
+    // A split inside a word leaves the word counts out of step with the timings
+    let words = words.filter(|w| left_count + right.split_whitespace().count() == w.len());
+
+    let mut first = row.clone();
+    let mut second = row.clone();
+    second.id = new_id;
+    first.transcript = left;
+    second.transcript = right;
+
+    let split_time = match (&words, row.audio_start_time, row.audio_end_time) {
+        (Some(words), _, _) => Some(words[left_count].start),
+        (None, Some(start), Some(end)) => {
+            Some(start + (end - start) * position as f64 / chars.len() as f64)
+        }
+        _ => None,
+    };
+    if let Some(split_time) = split_time {
+        first.audio_end_time = Some(split_time);
+        first.duration = first.audio_start_time.map(|start| split_time - start);
+        second.audio_start_time = Some(split_time);
+        second.duration = row.audio_end_time.map(|end| end - split_time);
+        second.timestamp = format_offset(split_time);
+    }
+    match words {
+        Some(mut words) => {
+            let rest = words.split_off(left_count);
+            first.words = store_words(Some(words));
+            second.words = store_words(Some(rest));
+        }
+        None => {
+            first.words = None;
+            second.words = None;
+        }
+    }
+
+    Ok((first, second))
+}
+
+/// Merge consecutive segments into the first one. Text is joined with spaces and the
+/// timing spans all of them; the speaker and language of the first segment are kept.
+pub fn merge(rows: &[Transcript]) -> Result<Transcript, String> {
+    let (first, rest) = rows
+        .split_first()
+        .filter(|(_, rest)| !rest.is_empty())
+        .ok_or_else(|| "Select at least two segments to merge".to_string())?;
+
+    let mut merged = first.clone();
+    merged.transcript = rows
+        .iter()
+        .map(|r| r.transcript.trim())
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    merged.audio_end_time = rows
+        .iter()
+        .filter_map(|r| r.audio_end_time)
+        .reduce(f64::max)
+        .or(first.audio_end_time);
+    merged.duration = match (merged.audio_start_time, merged.audio_end_time) {
+        (Some(start), Some(end)) => Some(end - start),
+        _ => None,
+    };
+
+    let words: Option<Vec<WordTimestamp>> = rows
+        .iter()
+        .map(parse_words)
+        .collect::<Option<Vec<_>>>()
+        .map(|parts| parts.concat());
+    merged.words = store_words(words);
+    if rest.iter().any(|r| r.audio_source != first.audio_source) {
+        merged.audio_source = None;
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, end: f64) -> WordTimestamp {
+        WordTimestamp {
+            word: text.to_string(),
+            start,
+            end,
+            confidence: None,
+        }
+    }
+
+    fn row(id: &str, text: &str, start: f64, end: f64, words: Option<Vec<WordTimestamp>>) -> Transcript {
+        Transcript {
+            id: id.to_string(),
+            meeting_id: "meeting-1".to_string(),
+            transcript: text.to_string(),
+            timestamp: format_offset(start),
+            summary: None,
+            action_items: None,
+            key_points: None,
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+            speaker_id: Some(1),
+            speaker_profile_id: None,
+            speaker_name: None,
+            words: store_words(words),
+            language: Some("en".to_string()),
+            language_probability: None,
+            audio_source: None,
+        }
+    }
+
+    #[test]
+    fn test_edit_text_keeps_timings_for_same_word_count() {
+        let original = row(
+            "t1",
+            "ship the bild",
+            0.0,
+            3.0,
+            Some(vec![word("ship", 0.0, 1.0), word("the", 1.0, 2.0), word("bild", 2.0, 3.0)]),
+        );
+        let edited = edit_text(&original, "ship the build").unwrap();
+        let words = parse_words(&edited).unwrap();
+        assert_eq!(words[2].word, "build");
+        assert_eq!(words[2].start, 2.0);
+
+        let reworded = edit_text(&original, "release it").unwrap();
+        assert!(reworded.words.is_none());
+        assert!(edit_text(&original, "  ").is_err());
+    }
+
+    #[test]
+    fn test_split_uses_word_timings() {
+        let original = row(
+            "t1",
+            "Hello team. Next item",
+            10.0,
+            14.0,
+            Some(vec![
+                word("Hello", 10.0, 10.5),
+                word("team.", 10.5, 11.0),
+                word("Next", 12.5, 13.0),
+                word("item", 13.0, 14.0),
+            ]),
+        );
+        let (first, second) = split(&original, 11, "t2".to_string()).unwrap();
+        assert_eq!(first.transcript, "Hello team.");
+        assert_eq!(second.transcript, "Next item");
+        assert_eq!(first.audio_end_time, Some(12.5));
+        assert_eq!(second.audio_start_time, Some(12.5));
+        assert_eq!(second.timestamp, "[00:12]");
+        assert_eq!(parse_words(&second).unwrap().len(), 2);
+        assert_eq!(second.id, "t2");
+    }
+
+    #[test]
+    fn test_split_without_words_interpolates() {
+        let original = row("t1", "abcdefghij", 0.0, 10.0, None);
+        let (first, second) = split(&original, 4, "t2".to_string()).unwrap();
+        assert_eq!(first.audio_end_time, Some(4.0));
+        assert_eq!(second.duration, Some(6.0));
+        assert!(split(&original, 0, "t3".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_merge_spans_segments() {
+        let a = row("t1", "First part", 0.0, 2.0, Some(vec![word("First", 0.0, 1.0), word("part", 1.0, 2.0)]));
+        let b = row("t2", "second part", 2.5, 4.0, None);
+        let merged = merge(&[a.clone(), b]).unwrap();
+        assert_eq!(merged.id, "t1");
+        assert_eq!(merged.transcript, "First part second part");
+        assert_eq!(merged.audio_end_time, Some(4.0));
+        assert_eq!(merged.duration, Some(4.0));
+        assert!(merged.words.is_none()); // One side had no timings
+        assert!(merge(&[a]).is_err());
+    }
+
+    #[test]
+    fn test_changed_words_finds_the_corrected_run() {
+        assert_eq!(
+            changed_words("we deploy on cooper netties today.", "we deploy on Kubernetes today."),
+            Some(("cooper netties".to_string(), "Kubernetes".to_string()))
+        );
+        assert_eq!(
+            changed_words("ship it to meetilly.", "ship it to Meetily."),
+            Some(("meetilly".to_string(), "Meetily".to_string()))
+        );
+        // Case, punctuation, pure insertions and rewrites are not misrecognitions
+        assert_eq!(changed_words("ship the build", "Ship the build."), None);
+        assert_eq!(changed_words("ship the build", "ship the new build"), None);
+        assert_eq!(changed_words("ship the build now", "we will release it later today"), None);
+    }
+}
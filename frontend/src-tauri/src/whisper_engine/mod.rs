@@ -3,6 +3,7 @@ pub mod commands;
 pub mod system_monitor;
 pub mod parallel_processor;
 pub mod parallel_commands;
+pub mod retranscribe;
 // pub mod stderr_suppressor;
 
 pub use whisper_engine::*;
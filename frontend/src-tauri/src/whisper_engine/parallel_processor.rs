@@ -7,6 +7,7 @@ use serde::{Serialize, Deserialize};
 
 use super::whisper_engine::WhisperEngine;
 use super::system_monitor::SystemMonitor;
+use crate::audio::transcription::WordTimestamp;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioChunk {
@@ -25,6 +26,12 @@ pub struct TranscriptionResult {
     pub model_used: String,
     pub start_time_ms: f64,
     pub confidence_score: Option<f32>,
+    #[serde(default)]
+    pub words: Vec<WordTimestamp>, // Chunk-relative word timings
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub language_probability: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +63,7 @@ pub struct ParallelConfig {
     pub retry_delay_ms: u64,         // Delay between retries
     pub resource_check_interval_ms: u64, // How often to check system resources
     pub enable_fallback_mode: bool,  // Fall back to sequential processing on failures
+    pub initial_prompt: Option<String>, // Vocabulary prompt passed to every chunk
 }
 
 impl Default for ParallelConfig {
@@ -67,6 +75,7 @@ impl Default for ParallelConfig {
             retry_delay_ms: 1000,        // 1 second retry delay
             resource_check_interval_ms: 10000, // Check resources every 10 seconds
             enable_fallback_mode: true,  // Always enable fallback for safety
+            initial_prompt: None,
         }
     }
 }
@@ -87,7 +96,7 @@ struct Worker {
     id: u32,
     handle: Option<JoinHandle<Result<()>>>,
     #[allow(dead_code)] // Used in async tasks
-    whisper_engine: Arc<RwLock<Option<WhisperEngine>>>,
+    whisper_engine: Arc<RwLock<Option<WhisperEngine>>>, // Shared by all workers
 }
 
 struct ChunkQueue {
@@ -188,20 +197,32 @@ impl ParallelProcessor {
     async fn spawn_workers(&mut self, worker_count: usize, model_name: String) -> Result<()> {
         self.workers.clear();
 
+        // Load the model once (from the app's models directory) and share it: each
+        // transcription creates its own decoder state, so workers only add that state's
+        // memory rather than another copy of the model in RAM/VRAM
+        let engine = WhisperEngine::new_with_models_dir(super::commands::get_models_directory())
+            .map_err(|e| anyhow!("Failed to create WhisperEngine: {}", e))?;
+        engine.discover_models().await.map_err(|e| anyhow!("Failed to discover models: {}", e))?;
+        engine.load_model(&model_name).await.map_err(|e| anyhow!("Failed to load model {}: {}", model_name, e))?;
+        info!("Loaded model {} for {} workers", model_name, worker_count);
+        let whisper_engine = Arc::new(RwLock::new(Some(engine)));
+
         for worker_id in 0..worker_count {
-            let worker = self.create_worker(worker_id as u32, model_name.clone()).await?;
+            let worker = self.create_worker(worker_id as u32, model_name.clone(), whisper_engine.clone()).await?;
             self.workers.push(worker);
         }
 
         Ok(())
     }
 
-    async fn create_worker(&self, worker_id: u32, model_name: String) -> Result<Worker> {
+    async fn create_worker(
+        &self,
+        worker_id: u32,
+        model_name: String,
+        whisper_engine: Arc<RwLock<Option<WhisperEngine>>>,
+    ) -> Result<Worker> {
         info!("Creating worker {}", worker_id);
 
-        // Create isolated WhisperEngine for this worker
-        let whisper_engine = Arc::new(RwLock::new(None));
-
         // Clone necessary data for worker task
         let chunk_queue = self.chunk_queue.clone();
         let event_sender = self.event_sender.clone();
@@ -219,15 +240,6 @@ impl ParallelProcessor {
             info!("Worker {} started", worker_id);
             let _ = event_sender.send(ProcessingEvent::WorkerStarted(worker_id));
 
-            // Load model for this worker
-            {
-                let mut engine_guard = engine_ref.write().await;
-                let engine = WhisperEngine::new().map_err(|e| anyhow!("Failed to create WhisperEngine: {}", e))?;
-                engine.load_model(&model_name).await.map_err(|e| anyhow!("Failed to load model {}: {}", model_name, e))?;
-                *engine_guard = Some(engine);
-                info!("Worker {} loaded model {}", worker_id, model_name);
-            }
-
             // Main worker loop
             loop {
                 // Check if we should stop
@@ -263,6 +275,7 @@ impl ParallelProcessor {
                             &engine_ref,
                             chunk.clone(),
                             &model_name,
+                            config.initial_prompt.as_deref(),
                             worker_id
                         ).await;
 
@@ -329,6 +342,7 @@ impl ParallelProcessor {
         engine_ref: &Arc<RwLock<Option<WhisperEngine>>>,
         chunk: AudioChunk,
         model_name: &str,
+        initial_prompt: Option<&str>,
         worker_id: u32,
     ) -> Result<TranscriptionResult> {
         let start_time = std::time::Instant::now();
@@ -344,15 +358,16 @@ impl ParallelProcessor {
         let language = crate::get_language_preference_internal();
 
         // Transcribe with timeout to prevent hanging
-        let transcription_future = engine.transcribe_audio(chunk.data.clone(), language);
+        let transcription_future = engine.transcribe_audio_with_confidence(chunk.data.clone(), language, initial_prompt);
         let timeout_duration = tokio::time::Duration::from_secs(120); // 2 minute timeout per chunk
 
-        let text = tokio::time::timeout(timeout_duration, transcription_future)
+        let (text, confidence, _, words, language) = tokio::time::timeout(timeout_duration, transcription_future)
             .await
             .map_err(|_| anyhow!("Transcription timeout for chunk {}", chunk.id))?
             .map_err(|e| anyhow!("Transcription failed for chunk {}: {}", chunk.id, e))?;
 
         let processing_time = start_time.elapsed().as_millis() as u64;
+        let (language, language_probability) = language.unzip();
 
         let result = TranscriptionResult {
             chunk_id: chunk.id,
@@ -360,7 +375,10 @@ impl ParallelProcessor {
             processing_time_ms: processing_time,
             model_used: model_name.to_string(),
             start_time_ms: chunk.start_time_ms,
-            confidence_score: None, // TODO: Add confidence scoring if available
+            confidence_score: Some(confidence),
+            words,
+            language,
+            language_probability: language_probability.flatten(),
         };
 
         debug!("Worker {} completed chunk {} in {}ms",
@@ -441,6 +459,48 @@ impl ParallelProcessor {
         info!("All workers stopped");
     }
 
+    /// Wait for every worker to finish, then stop resource monitoring.
+    ///
+    /// Returns completed results in chunk order and the chunks that failed after all
+    /// retries. Fails if workers exited (e.g. the model could not be loaded) without
+    /// completing anything.
+    pub async fn wait_for_completion(&mut self) -> Result<(Vec<TranscriptionResult>, Vec<ProcessingError>)> {
+        let mut worker_errors = Vec::new();
+        for worker in &mut self.workers {
+            if let Some(handle) = worker.handle.take() {
+                match handle.await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => {
+                        error!("Worker {} exited with error: {}", worker.id, e);
+                        worker_errors.push(e.to_string());
+                    }
+                    Err(e) => {
+                        error!("Worker {} panicked: {}", worker.id, e);
+                        worker_errors.push(e.to_string());
+                    }
+                }
+            }
+        }
+        self.workers.clear();
+        *self.is_stopped.write().await = true;
+
+        let queue = self.chunk_queue.read().await;
+        let unfinished = queue.pending.len() + queue.retry_queue.len() + queue.processing.len();
+        if unfinished > 0 {
+            return Err(anyhow!(
+                "{} chunks were not processed: {}",
+                unfinished,
+                worker_errors.first().cloned().unwrap_or_else(|| "workers stopped".to_string())
+            ));
+        }
+
+        let mut completed: Vec<TranscriptionResult> = queue.completed.values().cloned().collect();
+        completed.sort_by_key(|r| r.chunk_id);
+        let mut failed: Vec<ProcessingError> = queue.failed.values().cloned().collect();
+        failed.sort_by_key(|e| e.chunk_id);
+        Ok((completed, failed))
+    }
+
     pub async fn get_processing_status(&self) -> ProcessingStatus {
         let queue = self.chunk_queue.read().await;
         ProcessingStatus {
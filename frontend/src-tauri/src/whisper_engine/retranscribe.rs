@@ -0,0 +1,472 @@
+// whisper_engine/retranscribe.rs
+//
+// High-accuracy re-transcription of a finished recording. Live transcription favours
+// latency and often runs a small model; this pass decodes the saved audio, fans 30s
+// chunks out to the parallel processor with a larger Whisper model, and atomically
+// swaps the result in. The replaced transcript is kept as a version for rollback.
+
+use crate::api::TranscriptSegment as DbTranscriptSegment;
+use crate::audio::decoder::decode_media_file_16k;
+use crate::audio::import::format_offset;
+use crate::audio::recording_saver::{meeting_track_path, write_transcripts_json_file, AudioTrack, TranscriptSegment};
+use crate::audio::transcription::provider::offset_words;
+use crate::audio::transcription::vocabulary_commands::load_meeting_vocabulary;
+use crate::audio::transcription::WordTimestamp;
+use crate::database::models::{Transcript, TranscriptVersion};
+use crate::database::repositories::embedding::EmbeddingsRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use crate::whisper_engine::commands::WHISPER_ENGINE;
+use crate::whisper_engine::parallel_commands::{prepare_audio_chunks, ParallelProcessorState};
+use crate::whisper_engine::{ModelStatus, ParallelConfig, ParallelProcessor, ProcessingEvent, TranscriptionResult};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// A pause between words longer than this starts a new segment
+const SEGMENT_GAP_SECONDS: f64 = 1.0;
+
+/// Segments are closed once they span this long, even mid-sentence
+const MAX_SEGMENT_SECONDS: f64 = 15.0;
+
+/// Meetings with a re-transcription in flight
+static RUNNING: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetranscriptionResult {
+    pub meeting_id: String,
+    pub model: String,
+    pub segments: usize,
+    /// Version holding the replaced transcript, for `api_rollback_transcript`
+    pub previous_version_id: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RetranscriptionProgress {
+    meeting_id: String,
+    stage: String,
+    progress: u32,
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, stage: &str, progress: u32) {
+    let _ = app.emit(
+        "retranscription-progress",
+        RetranscriptionProgress {
+            meeting_id: meeting_id.to_string(),
+            stage: stage.to_string(),
+            progress,
+        },
+    );
+}
+
+/// Removes the meeting from `RUNNING` when the job ends, however it ends
+struct RunningGuard(String);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running) = RUNNING.lock() {
+            running.remove(&self.0);
+        }
+    }
+}
+
+fn push_segment(segments: &mut Vec<TranscriptSegment>, result: &TranscriptionResult, text: String, start: f64, end: f64, words: Vec<WordTimestamp>) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let sequence_id = segments.len() as u64;
+    let duration = (end - start).max(0.0);
+    segments.push(TranscriptSegment {
+        id: format!("seg_{}", sequence_id),
+        text: text.trim().to_string(),
+        audio_start_time: start,
+        audio_end_time: start + duration,
+        duration,
+        display_time: format_offset(start),
+        confidence: result.confidence_score.unwrap_or(0.85),
+        sequence_id,
+        speaker_id: None,
+        speaker_profile_id: None,
+        speaker_name: None,
+        words,
+        language: result.language.clone(),
+        language_probability: result.language_probability,
+        audio_source: None,
+    });
+}
+
+/// Turn chunk results into recording-relative segments.
+///
+/// Chunks are cut at fixed 30s boundaries, so their text is re-split on word timings:
+/// at sentence-ending punctuation, at pauses, and at `MAX_SEGMENT_SECONDS`. Chunks
+/// without word timings become a single segment. `chunk_durations` maps chunk id to
+/// its length in seconds.
+fn build_segments(results: &[TranscriptionResult], chunk_durations: &HashMap<u32, f64>) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+
+    for result in results {
+        let offset = result.start_time_ms / 1000.0;
+
+        if result.words.is_empty() {
+            let duration = chunk_durations.get(&result.chunk_id).copied().unwrap_or(0.0);
+            push_segment(&mut segments, result, result.text.clone(), offset, offset + duration, Vec::new());
+            continue;
+        }
+
+        let mut words = result.words.clone();
+        offset_words(&mut words, offset);
+
+        let mut current: Vec<WordTimestamp> = Vec::new();
+        for (i, word) in words.iter().enumerate() {
+            current.push(word.clone());
+            let segment_start = current[0].start;
+            let sentence_end = word.word.ends_with(['.', '?', '!']);
+            let long_pause = words
+                .get(i + 1)
+                .is_some_and(|next| next.start - word.end > SEGMENT_GAP_SECONDS);
+            let too_long = word.end - segment_start >= MAX_SEGMENT_SECONDS;
+
+            if sentence_end || long_pause || too_long || i + 1 == words.len() {
+                let text = current.iter().map(|w| w.word.as_str()).collect::<Vec<_>>().join(" ");
+                let end = word.end;
+                push_segment(&mut segments, result, text, segment_start, end, std::mem::take(&mut current));
+            }
+        }
+    }
+
+    segments
+}
+
+pub(crate) async fn load_folder(pool: &SqlitePool, meeting_id: &str) -> Result<PathBuf, String> {
+    let folder_path: Option<String> = sqlx::query_scalar("SELECT folder_path FROM meetings WHERE id = ?")
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    folder_path
+        .map(PathBuf::from)
+        .ok_or_else(|| "Recording folder not available for this meeting".to_string())
+}
+
+/// Rewrite transcripts.json from database rows so the folder matches the database
+pub(crate) fn write_transcripts_from_rows(folder: &Path, rows: &[Transcript]) -> Result<(), String> {
+    let segments: Vec<TranscriptSegment> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let start = row.audio_start_time.unwrap_or(0.0);
+            let duration = row.duration.unwrap_or(0.0);
+            TranscriptSegment {
+                id: format!("seg_{}", i),
+                text: row.transcript.clone(),
+                audio_start_time: start,
+                audio_end_time: row.audio_end_time.unwrap_or(start + duration),
+                duration,
+                display_time: row.timestamp.clone(),
+                confidence: 0.85,
+                sequence_id: i as u64,
+                speaker_id: row.speaker_id.map(|id| id as usize),
+                speaker_profile_id: row.speaker_profile_id,
+                speaker_name: row.speaker_name.clone(),
+                words: row
+                    .words
+                    .as_deref()
+                    .and_then(|w| serde_json::from_str(w).ok())
+                    .unwrap_or_default(),
+                language: row.language.clone(),
+                language_probability: row.language_probability.map(|p| p as f32),
+                audio_source: row.audio_source.clone(),
+            }
+        })
+        .collect();
+    write_transcripts_json_file(folder, &segments).map_err(|e| e.to_string())
+}
+
+/// Re-transcribe a saved meeting's audio with a larger Whisper model
+///
+/// The meeting's segments are replaced only if every chunk transcribes; otherwise the
+/// existing transcript is left untouched. The previous segments are kept as a version
+/// that `api_rollback_transcript` can restore. Speaker labels are not carried over, so
+/// re-run diarization afterwards if needed.
+///
+/// `track` picks the recording to transcribe; the microphone or system stems of a
+/// multitrack recording label every segment with that source.
+#[tauri::command]
+pub async fn api_retranscribe_meeting<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    parallel: tauri::State<'_, ParallelProcessorState>,
+    meeting_id: String,
+    model_name: String,
+    max_workers: Option<usize>,
+    track: Option<AudioTrack>,
+) -> Result<RetranscriptionResult, String> {
+    let track = track.unwrap_or_default();
+    log_info!(
+        "api_retranscribe_meeting called for {} (model: {}, max_workers: {:?}, track: {:?})",
+        meeting_id,
+        model_name,
+        max_workers,
+        track
+    );
+
+    if !RUNNING.lock().map_err(|e| e.to_string())?.insert(meeting_id.clone()) {
+        return Err("This meeting is already being re-transcribed".to_string());
+    }
+    let _guard = RunningGuard(meeting_id.clone());
+
+    let pool = state.db_manager.pool();
+    let folder = load_folder(pool, &meeting_id).await?;
+
+    // Fail fast before decoding if the requested model isn't downloaded
+    let engine = {
+        let guard = WHISPER_ENGINE.lock().unwrap();
+        guard.as_ref().cloned()
+    };
+    let engine = engine.ok_or_else(|| "Whisper engine not initialized".to_string())?;
+    let models = engine
+        .discover_models()
+        .await
+        .map_err(|e| format!("Failed to discover models: {}", e))?;
+    match models.iter().find(|m| m.name == model_name) {
+        Some(model) if matches!(model.status, ModelStatus::Available) => {}
+        Some(_) => return Err(format!("Model '{}' is not downloaded yet", model_name)),
+        None => return Err(format!("Unknown Whisper model: {}", model_name)),
+    }
+
+    let audio_path = meeting_track_path(&folder, track).map_err(|e| e.to_string())?;
+    emit_progress(&app, &meeting_id, "decoding", 0);
+    let audio = tokio::task::spawn_blocking(move || decode_media_file_16k(&audio_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to decode meeting audio: {}", e))?;
+
+    let chunks = prepare_audio_chunks(audio.samples, 16000, None).await?;
+    if chunks.is_empty() {
+        return Err("The meeting recording contains no audio".to_string());
+    }
+    let total = chunks.len();
+    let chunk_durations: HashMap<u32, f64> = chunks.iter().map(|c| (c.id, c.duration_ms / 1000.0)).collect();
+
+    let vocabulary = load_meeting_vocabulary(pool, &meeting_id).await;
+    let mut config = ParallelConfig::default();
+    if let Some(workers) = max_workers {
+        config.max_workers = workers.max(1);
+    }
+    config.initial_prompt = vocabulary.initial_prompt();
+    let (mut processor, mut events) = ParallelProcessor::new(config, parallel.system_monitor.clone())
+        .map_err(|e| format!("Failed to create parallel processor: {}", e))?;
+
+    emit_progress(&app, &meeting_id, "transcribing", 5);
+    let forward_app = app.clone();
+    let forward_id = meeting_id.clone();
+    let forwarder = tokio::spawn(async move {
+        let mut done = 0usize;
+        while let Some(event) = events.recv().await {
+            match event {
+                ProcessingEvent::ChunkCompleted(_) => {
+                    done += 1;
+                    let progress = 5 + (done * 85 / total) as u32;
+                    emit_progress(&forward_app, &forward_id, "transcribing", progress);
+                }
+                ProcessingEvent::ChunkFailed(e) if !e.is_recoverable => {
+                    log_warn!("Chunk {} failed: {}", e.chunk_id, e.error_message);
+                }
+                ProcessingEvent::ResourceConstraint(reason) => {
+                    log_warn!("Re-transcription throttled: {}", reason);
+                }
+                _ => {}
+            }
+        }
+    });
+
+    let outcome = match processor.start_processing(chunks, model_name.clone()).await {
+        Ok(()) => processor.wait_for_completion().await,
+        Err(e) => Err(e),
+    };
+    forwarder.abort();
+    let (mut results, failed) = outcome.map_err(|e| {
+        log_error!("Re-transcription of {} failed: {}", meeting_id, e);
+        format!("Re-transcription failed: {}", e)
+    })?;
+    if !failed.is_empty() {
+        return Err(format!(
+            "{} of {} chunks failed to transcribe; the existing transcript was kept",
+            failed.len(),
+            total
+        ));
+    }
+
+    emit_progress(&app, &meeting_id, "saving", 92);
+    for result in &mut results {
+        result.text = vocabulary.replace_aliases(&result.text);
+        vocabulary.correct_words(&mut result.words, false);
+    }
+    let mut segments = build_segments(&results, &chunk_durations);
+    if segments.is_empty() {
+        return Err("No speech was recognised; the existing transcript was kept".to_string());
+    }
+    if let Some(source) = track.audio_source() {
+        for segment in &mut segments {
+            segment.audio_source = Some(source.to_string());
+        }
+    }
+    let db_segments: Vec<DbTranscriptSegment> = segments
+        .iter()
+        .map(|s| DbTranscriptSegment {
+            id: s.id.clone(),
+            text: s.text.clone(),
+            timestamp: s.display_time.clone(),
+            audio_start_time: Some(s.audio_start_time),
+            audio_end_time: Some(s.audio_end_time),
+            duration: Some(s.duration),
+            speaker_id: None,
+            speaker_profile_id: None,
+            words: (!s.words.is_empty()).then(|| s.words.clone()),
+            language: s.language.clone(),
+            language_probability: s.language_probability.map(f64::from),
+            audio_source: s.audio_source.clone(),
+        })
+        .collect();
+    let previous_version_id = TranscriptsRepository::replace_meeting_transcripts(
+        pool,
+        &meeting_id,
+        &db_segments,
+        "retranscribe",
+        Some(&model_name),
+    )
+    .await
+    .map_err(|e| format!("Failed to save transcript: {}", e))?;
+
+    if let Err(e) = write_transcripts_json_file(&folder, &segments) {
+        log_warn!("Failed to rewrite transcripts.json for {}: {}", meeting_id, e);
+    }
+    // Embeddings describe the old text
+    if let Err(e) = EmbeddingsRepository::delete_meeting_embeddings(pool, &meeting_id).await {
+        log_warn!("Failed to invalidate embeddings for {}: {}", meeting_id, e);
+    }
+
+    emit_progress(&app, &meeting_id, "complete", 100);
+    log_info!(
+        "Re-transcribed meeting {} with {}: {} segments (previous kept as version {})",
+        meeting_id,
+        model_name,
+        segments.len(),
+        previous_version_id
+    );
+
+    Ok(RetranscriptionResult {
+        meeting_id,
+        model: model_name,
+        segments: segments.len(),
+        previous_version_id,
+    })
+}
+
+/// List the saved transcript versions of a meeting, newest first
+#[tauri::command]
+pub async fn api_list_transcript_versions(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<TranscriptVersion>, String> {
+    TranscriptsRepository::list_transcript_versions(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to list transcript versions: {}", e))
+}
+
+/// Restore a saved transcript version, keeping the current transcript as a new version
+#[tauri::command]
+pub async fn api_rollback_transcript(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    version_id: i64,
+) -> Result<usize, String> {
+    log_info!("api_rollback_transcript called for {} (version {})", meeting_id, version_id);
+    let pool = state.db_manager.pool();
+
+    let restored = TranscriptsRepository::restore_transcript_version(pool, &meeting_id, version_id)
+        .await
+        .map_err(|e| format!("Failed to restore transcript: {}", e))?
+        .ok_or_else(|| format!("Transcript version {} not found", version_id))?;
+
+    match load_folder(pool, &meeting_id).await {
+        Ok(folder) => {
+            if let Err(e) = write_transcripts_from_rows(&folder, &restored) {
+                log_warn!("Failed to rewrite transcripts.json for {}: {}", meeting_id, e);
+            }
+        }
+        Err(e) => log_warn!("Skipping transcripts.json update for {}: {}", meeting_id, e),
+    }
+    if let Err(e) = EmbeddingsRepository::delete_meeting_embeddings(pool, &meeting_id).await {
+        log_warn!("Failed to invalidate embeddings for {}: {}", meeting_id, e);
+    }
+
+    Ok(restored.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, end: f64) -> WordTimestamp {
+        WordTimestamp {
+            word: text.to_string(),
+            start,
+            end,
+            confidence: None,
+        }
+    }
+
+    fn result(chunk_id: u32, start_time_ms: f64, text: &str, words: Vec<WordTimestamp>) -> TranscriptionResult {
+        TranscriptionResult {
+            chunk_id,
+            text: text.to_string(),
+            processing_time_ms: 0,
+            model_used: "large-v3".to_string(),
+            start_time_ms,
+            confidence_score: Some(0.9),
+            words,
+            language: Some("en".to_string()),
+            language_probability: Some(0.97),
+        }
+    }
+
+    #[test]
+    fn test_splits_on_sentences_and_pauses_and_offsets_chunks() {
+        let results = vec![
+            result(0, 0.0, "Hello there. How are you", vec![
+                word("Hello", 0.0, 0.4),
+                word("there.", 0.5, 0.9),
+                word("How", 1.0, 1.2),
+                word("are", 1.3, 1.5),
+                word("you", 3.0, 3.3),
+            ]),
+            result(1, 30_000.0, "Fine", vec![word("Fine", 0.5, 0.9)]),
+        ];
+        let segments = build_segments(&results, &HashMap::new());
+
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Hello there.", "How are", "you", "Fine"]);
+        assert_eq!(segments[3].audio_start_time, 30.5);
+        assert_eq!(segments[3].words[0].start, 30.5);
+        assert_eq!(segments[3].sequence_id, 3);
+        assert_eq!(segments[3].language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_chunk_without_words_becomes_one_segment() {
+        let durations = HashMap::from([(2, 12.0)]);
+        let segments = build_segments(&[result(2, 60_000.0, " Just text ", Vec::new())], &durations);
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].text, "Just text");
+        assert_eq!(segments[0].audio_start_time, 60.0);
+        assert_eq!(segments[0].audio_end_time, 72.0);
+    }
+}
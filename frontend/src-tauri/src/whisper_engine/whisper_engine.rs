@@ -4,13 +4,14 @@ use std::path::{PathBuf};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
+use whisper_rs::{WhisperContext, WhisperContextParameters, WhisperState, FullParams, SamplingStrategy};
 use serde::{Serialize, Deserialize};
 use anyhow::{Result, anyhow};
 use reqwest::Client;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use crate::{perf_debug, perf_trace};
+use crate::audio::transcription::provider::{words_from_tokens, WordTimestamp};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelStatus {
@@ -529,8 +530,36 @@ impl WhisperEngine {
         repeated_words as f32 / total_words
     }
     
+    /// Identify the spoken language of `audio_data`, returning its code and probability
+    fn detect_language(state: &mut WhisperState, audio_data: &[f32]) -> Option<(&'static str, f32)> {
+        let threads = std::thread::available_parallelism().map(|n| n.get().min(4)).unwrap_or(2);
+        if let Err(e) = state.pcm_to_mel(audio_data, threads) {
+            log::warn!("Language detection failed to compute mel spectrogram: {}", e);
+            return None;
+        }
+        match state.lang_detect(0, threads) {
+            Ok((id, probabilities)) => {
+                let code = whisper_rs::get_lang_str(id)?;
+                Some((code, probabilities.get(id as usize).copied().unwrap_or(0.0)))
+            }
+            Err(e) => {
+                log::warn!("Language detection failed: {}", e);
+                None
+            }
+        }
+    }
+
     /// Transcribe audio with streaming support for partial results and adaptive quality
-    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool)> {
+    ///
+    /// Returns (text, confidence, is_partial, word timings, language).
+    /// Word timings are relative to the start of `audio_data`. `initial_prompt` primes the
+    /// decoder with vocabulary it should prefer (see `audio::transcription::vocabulary`).
+    ///
+    /// With language "auto" each call detects the spoken language itself and decodes in
+    /// it, so consecutive segments of a mixed-language meeting keep their own language.
+    /// The returned language is (code, detection probability); the probability is None
+    /// when the language was fixed rather than detected.
+    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>, initial_prompt: Option<&str>) -> Result<(String, f32, bool, Vec<WordTimestamp>, Option<(String, Option<f32>)>)> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -554,8 +583,17 @@ impl WhisperEngine {
             Some("auto-translate") => (None, true),
             Some(lang) => (Some(lang), false),
         };
-        params.set_language(language_code);
+        let mut state = ctx.create_state()?;
+        let detected = if language_code.is_none() && !should_translate {
+            Self::detect_language(&mut state, &audio_data)
+        } else {
+            None
+        };
+        params.set_language(detected.map(|(code, _)| code).or(language_code));
         params.set_translate(should_translate);
+        if let Some(prompt) = initial_prompt {
+            params.set_initial_prompt(prompt);
+        }
 
         // CRITICAL: Disable timestamp tokens to prevent whisper.cpp chunking heuristics
         // The "single timestamp ending - skip entire chunk" optimization incorrectly discards
@@ -595,19 +633,14 @@ impl WhisperEngine {
 
         // PERFORMANCE: Suppress verbose C library logs during transcription
         // This hides whisper_full_with_state debug logs and beam search details
-        let (num_segments, state) = {
-            // let _suppressor = crate::whisper_engine::StderrSuppressor::new();
-
-            let mut state = ctx.create_state()?;
-            state.full(params, &audio_data)?;
-            let num_segments = state.full_n_segments();
-
-            (num_segments, state)
-            // Suppressor dropped here, stderr restored
-        };
+        // let _suppressor = crate::whisper_engine::StderrSuppressor::new();
+        state.full(params, &audio_data)?;
+        let num_segments = state.full_n_segments();
         let mut result = String::new();
         let mut total_confidence = 0.0;
         let mut segment_count = 0;
+        let mut tokens = Vec::new();
+        let eot_token = ctx.token_eot();
 
         let num_segments = num_segments?;
         for i in 0..num_segments {
@@ -616,6 +649,21 @@ impl WhisperEngine {
                 Err(_) => continue,
             };
 
+            // Token timings are in 10ms units; special tokens (timestamps, EOT, ...) are skipped
+            let num_tokens = state.full_n_tokens(i).unwrap_or(0);
+            for j in 0..num_tokens {
+                let (Ok(data), Ok(text)) = (
+                    state.full_get_token_data(i, j),
+                    state.full_get_token_text_lossy(i, j),
+                ) else {
+                    continue;
+                };
+                if data.id >= eot_token {
+                    continue;
+                }
+                tokens.push((text, data.t0 as f64 / 100.0, data.t1 as f64 / 100.0, Some(data.p)));
+            }
+
             // Calculate confidence based on segment length and duration (simplified approach)
             let segment_length = segment_text.len() as f32;
             let segment_confidence = if segment_length > 0.0 {
@@ -644,7 +692,22 @@ impl WhisperEngine {
             0.0
         };
 
-        Ok((cleaned_result, avg_confidence, is_partial))
+        // Word timings no longer line up with the text if repetition cleanup removed anything
+        let words = if cleaned_result == final_result {
+            words_from_tokens(tokens)
+        } else {
+            Vec::new()
+        };
+
+        // Translation always produces English
+        let language = match (detected, language_code, should_translate) {
+            (_, _, true) => Some(("en".to_string(), None)),
+            (Some((code, probability)), _, _) => Some((code.to_string(), Some(probability))),
+            (None, Some(code), _) => Some((code.to_string(), None)),
+            (None, None, false) => None,
+        };
+
+        Ok((cleaned_result, avg_confidence, is_partial, words, language))
     }
 
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<String> {
@@ -12,6 +12,7 @@ import { useState, useEffect } from 'react'
 import { listen } from '@tauri-apps/api/event'
 import { invoke } from '@tauri-apps/api/core'
 import { LegacyDatabaseImport } from '@/components/DatabaseImport/LegacyDatabaseImport'
+import { RecordingRecovery } from '@/components/RecordingRecovery'
 import { TooltipProvider } from '@/components/ui/tooltip'
 import { RecordingStateProvider } from '@/contexts/RecordingStateContext'
 import { OllamaDownloadProvider } from '@/contexts/OllamaDownloadContext'
@@ -71,6 +72,7 @@ export default function RootLayout({
             <OllamaDownloadProvider>
               <SidebarProvider>
                 <TooltipProvider>
+                  <RecordingRecovery />
                   {/* <div className="titlebar h-8 w-full fixed top-0 left-0 bg-transparent" /> */}
                   <div className="flex">
                     <Sidebar />
@@ -53,6 +53,7 @@ export default function PageContent({
     modelConfig: modelConfig.modelConfig,
     isModelConfigLoading: modelConfig.isLoading,
     selectedTemplate: templates.selectedTemplate,
+    summaryLanguage: templates.summaryLanguage,
     onMeetingUpdated,
     updateMeetingTitle: meetingData.updateMeetingTitle,
     setAiSummary: meetingData.setAiSummary,
@@ -108,6 +109,9 @@ export default function PageContent({
           onPromptChange={setCustomPrompt}
           onCopyTranscript={copyOperations.handleCopyTranscript}
           onOpenMeetingFolder={meetingOperations.handleOpenMeetingFolder}
+          onExportSubtitles={meetingOperations.handleExportSubtitles}
+          onExportDocument={meetingOperations.handleExportDocument}
+          onExportArchive={meetingOperations.handleExportArchive}
           isRecording={isRecording}
         />
 
@@ -142,6 +146,8 @@ export default function PageContent({
           availableTemplates={templates.availableTemplates}
           selectedTemplate={templates.selectedTemplate}
           onTemplateSelect={templates.handleTemplateSelection}
+          summaryLanguage={templates.summaryLanguage}
+          onSummaryLanguageSelect={templates.handleSummaryLanguageSelection}
           isModelConfigLoading={modelConfig.isLoading}
         />
 
@@ -455,6 +455,13 @@ export default function Home() {
             audio_start_time: event.payload.audio_start_time,
             audio_end_time: event.payload.audio_end_time,
             duration: event.payload.duration,
+            speaker_id: event.payload.speaker_id,
+            speaker_profile_id: event.payload.speaker_profile_id,
+            speaker_name: event.payload.speaker_name,
+            words: event.payload.words,
+            language: event.payload.language ?? undefined,
+            language_probability: event.payload.language_probability ?? undefined,
+            audio_source: event.payload.audio_source ?? undefined,
           };
 
           // Add to buffer
@@ -517,6 +524,13 @@ export default function Home() {
             audio_start_time: segment.audio_start_time,
             audio_end_time: segment.audio_end_time,
             duration: segment.duration,
+            speaker_id: segment.speaker_id,
+            speaker_profile_id: segment.speaker_profile_id,
+            speaker_name: segment.speaker_name,
+            words: segment.words,
+            language: segment.language,
+            language_probability: segment.language_probability,
+            audio_source: segment.audio_source,
           }));
 
           setTranscripts(formattedTranscripts);
@@ -37,7 +37,8 @@ export default function SettingsPage() {
           setTranscriptModelConfig({
             provider: config.provider || 'localWhisper',
             model: config.model || 'large-v3',
-            apiKey: config.apiKey || null
+            apiKey: config.apiKey || null,
+            endpoint: config.endpoint || null
           });
         }
       } catch (error) {
@@ -54,7 +55,8 @@ export default function SettingsPage() {
       await invoke('api_save_transcript_config', {
         provider: config.provider,
         model: config.model,
-        apiKey: config.apiKey
+        apiKey: config.apiKey,
+        endpoint: config.endpoint ?? null
       });
       console.log('[SettingsPage] ✅ Successfully saved transcript config');
     } catch (error) {
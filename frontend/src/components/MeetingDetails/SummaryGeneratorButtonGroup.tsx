@@ -14,6 +14,8 @@ import {
   DropdownMenu,
   DropdownMenuContent,
   DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuSeparator,
   DropdownMenuTrigger,
 } from '@/components/ui/dropdown-menu';
 import { Sparkles, Settings, Loader2, FileText, Check } from 'lucide-react';
@@ -22,6 +24,21 @@ import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
 import { useState } from 'react';
 
+// Languages a summary can be written in; 'auto' keeps the transcript's language
+const SUMMARY_LANGUAGES: Array<{ value: string; label: string }> = [
+  { value: 'auto', label: 'Same as transcript' },
+  { value: 'English', label: 'English' },
+  { value: 'Spanish', label: 'Spanish' },
+  { value: 'French', label: 'French' },
+  { value: 'German', label: 'German' },
+  { value: 'Italian', label: 'Italian' },
+  { value: 'Portuguese', label: 'Portuguese' },
+  { value: 'Dutch', label: 'Dutch' },
+  { value: 'Hindi', label: 'Hindi' },
+  { value: 'Japanese', label: 'Japanese' },
+  { value: 'Chinese', label: 'Chinese' },
+];
+
 interface SummaryGeneratorButtonGroupProps {
   modelConfig: ModelConfig;
   setModelConfig: (config: ModelConfig | ((prev: ModelConfig) => ModelConfig)) => void;
@@ -32,6 +49,8 @@ interface SummaryGeneratorButtonGroupProps {
   availableTemplates: Array<{id: string, name: string, description: string}>;
   selectedTemplate: string;
   onTemplateSelect: (templateId: string, templateName: string) => void;
+  summaryLanguage?: string;
+  onSummaryLanguageSelect?: (language: string) => void;
   hasTranscripts?: boolean;
   isModelConfigLoading?: boolean;
 }
@@ -46,6 +65,8 @@ export function SummaryGeneratorButtonGroup({
   availableTemplates,
   selectedTemplate,
   onTemplateSelect,
+  summaryLanguage = 'auto',
+  onSummaryLanguageSelect,
   hasTranscripts = true,
   isModelConfigLoading = false
 }: SummaryGeneratorButtonGroupProps) {
@@ -187,6 +208,24 @@ export function SummaryGeneratorButtonGroup({
               </DropdownMenuItem>
             ))}
 
+            {onSummaryLanguageSelect && (
+              <>
+                <DropdownMenuSeparator />
+                <DropdownMenuLabel className="text-xs text-gray-500">Summary language</DropdownMenuLabel>
+                {SUMMARY_LANGUAGES.map((language) => (
+                  <DropdownMenuItem
+                    key={language.value}
+                    onClick={() => onSummaryLanguageSelect(language.value)}
+                    className="flex items-center justify-between gap-2"
+                  >
+                    <span>{language.label}</span>
+                    {summaryLanguage === language.value && (
+                      <Check className="h-4 w-4 text-green-600" />
+                    )}
+                  </DropdownMenuItem>
+                ))}
+              </>
+            )}
           </DropdownMenuContent>
         </DropdownMenu>
       )}
@@ -45,6 +45,8 @@ interface SummaryPanelProps {
   availableTemplates: Array<{id: string, name: string, description: string}>;
   selectedTemplate: string;
   onTemplateSelect: (templateId: string, templateName: string) => void;
+  summaryLanguage?: string;
+  onSummaryLanguageSelect?: (language: string) => void;
   isModelConfigLoading?: boolean;
 }
 
@@ -79,6 +81,8 @@ export function SummaryPanel({
   availableTemplates,
   selectedTemplate,
   onTemplateSelect,
+  summaryLanguage,
+  onSummaryLanguageSelect,
   isModelConfigLoading = false
 }: SummaryPanelProps) {
   const isSummaryLoading = summaryStatus === 'processing' || summaryStatus === 'summarizing' || summaryStatus === 'regenerating';
@@ -110,6 +114,8 @@ export function SummaryPanel({
                 availableTemplates={availableTemplates}
                 selectedTemplate={selectedTemplate}
                 onTemplateSelect={onTemplateSelect}
+                summaryLanguage={summaryLanguage}
+                onSummaryLanguageSelect={onSummaryLanguageSelect}
                 hasTranscripts={transcripts.length > 0}
                 isModelConfigLoading={isModelConfigLoading}
               />
@@ -148,6 +154,8 @@ export function SummaryPanel({
               availableTemplates={availableTemplates}
               selectedTemplate={selectedTemplate}
               onTemplateSelect={onTemplateSelect}
+              summaryLanguage={summaryLanguage}
+              onSummaryLanguageSelect={onSummaryLanguageSelect}
               hasTranscripts={transcripts.length > 0}
               isModelConfigLoading={isModelConfigLoading}
             />
@@ -174,6 +182,8 @@ export function SummaryPanel({
               availableTemplates={availableTemplates}
               selectedTemplate={selectedTemplate}
               onTemplateSelect={onTemplateSelect}
+              summaryLanguage={summaryLanguage}
+              onSummaryLanguageSelect={onSummaryLanguageSelect}
               hasTranscripts={transcripts.length > 0}
               isModelConfigLoading={isModelConfigLoading}
             />
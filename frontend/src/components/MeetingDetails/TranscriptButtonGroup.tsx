@@ -2,7 +2,24 @@
 
 import { Button } from '@/components/ui/button';
 import { ButtonGroup } from '@/components/ui/button-group';
-import { Copy, FolderOpen } from 'lucide-react';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuSeparator,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { Copy, Download, FolderOpen } from 'lucide-react';
+
+export type DocumentExportFormat = 'markdown' | 'html' | 'docx' | 'pdf';
+
+const DOCUMENT_FORMATS: { value: DocumentExportFormat; label: string }[] = [
+  { value: 'pdf', label: 'PDF (.pdf)' },
+  { value: 'docx', label: 'Word (.docx)' },
+  { value: 'html', label: 'Web page (.html)' },
+  { value: 'markdown', label: 'Markdown (.md)' },
+];
 import Analytics from '@/lib/analytics';
 
 
@@ -10,13 +27,19 @@ interface TranscriptButtonGroupProps {
   transcriptCount: number;
   onCopyTranscript: () => void;
   onOpenMeetingFolder: () => Promise<void>;
+  onExportSubtitles: (format: 'srt' | 'vtt') => Promise<void>;
+  onExportDocument: (format: DocumentExportFormat) => Promise<void>;
+  onExportArchive: () => Promise<void>;
 }
 
 
 export function TranscriptButtonGroup({
   transcriptCount,
   onCopyTranscript,
-  onOpenMeetingFolder
+  onOpenMeetingFolder,
+  onExportSubtitles,
+  onExportDocument,
+  onExportArchive
 }: TranscriptButtonGroupProps) {
   return (
     <div className="flex items-center justify-center w-full gap-2">
@@ -48,6 +71,62 @@ export function TranscriptButtonGroup({
           <FolderOpen className="xl:mr-2" size={18} />
           <span className="hidden lg:inline">Recording</span>
         </Button>
+
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button
+              size="sm"
+              variant="outline"
+              disabled={transcriptCount === 0}
+              title={transcriptCount === 0 ? 'No transcript available' : 'Export Meeting'}
+            >
+              <Download />
+              <span className="hidden lg:inline">Export</span>
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="end">
+            <DropdownMenuLabel>Document</DropdownMenuLabel>
+            {DOCUMENT_FORMATS.map(format => (
+              <DropdownMenuItem
+                key={format.value}
+                onClick={() => {
+                  Analytics.trackButtonClick(`export_document_${format.value}`, 'meeting_details');
+                  onExportDocument(format.value);
+                }}
+              >
+                {format.label}
+              </DropdownMenuItem>
+            ))}
+            <DropdownMenuSeparator />
+            <DropdownMenuLabel>Captions</DropdownMenuLabel>
+            <DropdownMenuItem
+              onClick={() => {
+                Analytics.trackButtonClick('export_subtitles_srt', 'meeting_details');
+                onExportSubtitles('srt');
+              }}
+            >
+              SubRip (.srt)
+            </DropdownMenuItem>
+            <DropdownMenuItem
+              onClick={() => {
+                Analytics.trackButtonClick('export_subtitles_vtt', 'meeting_details');
+                onExportSubtitles('vtt');
+              }}
+            >
+              WebVTT (.vtt)
+            </DropdownMenuItem>
+            <DropdownMenuSeparator />
+            <DropdownMenuLabel>Share</DropdownMenuLabel>
+            <DropdownMenuItem
+              onClick={() => {
+                Analytics.trackButtonClick('export_meeting_archive', 'meeting_details');
+                onExportArchive();
+              }}
+            >
+              Meeting archive (.zip)
+            </DropdownMenuItem>
+          </DropdownMenuContent>
+        </DropdownMenu>
       </ButtonGroup>
     </div>
   );
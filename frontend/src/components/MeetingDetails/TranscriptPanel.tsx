@@ -2,7 +2,7 @@
 
 import { Transcript } from '@/types';
 import { TranscriptView } from '@/components/TranscriptView';
-import { TranscriptButtonGroup } from './TranscriptButtonGroup';
+import { DocumentExportFormat, TranscriptButtonGroup } from './TranscriptButtonGroup';
 
 interface TranscriptPanelProps {
   transcripts: Transcript[];
@@ -10,6 +10,9 @@ interface TranscriptPanelProps {
   onPromptChange: (value: string) => void;
   onCopyTranscript: () => void;
   onOpenMeetingFolder: () => Promise<void>;
+  onExportSubtitles: (format: 'srt' | 'vtt') => Promise<void>;
+  onExportDocument: (format: DocumentExportFormat) => Promise<void>;
+  onExportArchive: () => Promise<void>;
   isRecording: boolean;
 }
 
@@ -19,6 +22,9 @@ export function TranscriptPanel({
   onPromptChange,
   onCopyTranscript,
   onOpenMeetingFolder,
+  onExportSubtitles,
+  onExportDocument,
+  onExportArchive,
   isRecording
 }: TranscriptPanelProps) {
   return (
@@ -29,6 +35,9 @@ export function TranscriptPanel({
           transcriptCount={transcripts?.length || 0}
           onCopyTranscript={onCopyTranscript}
           onOpenMeetingFolder={onOpenMeetingFolder}
+          onExportSubtitles={onExportSubtitles}
+          onExportDocument={onExportDocument}
+          onExportArchive={onExportArchive}
         />
       </div>
 
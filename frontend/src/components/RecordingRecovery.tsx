@@ -0,0 +1,120 @@
+'use client';
+
+import { useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { useRouter } from 'next/navigation';
+import { toast } from 'sonner';
+import { useSidebar } from '@/components/Sidebar/SidebarProvider';
+
+interface InterruptedRecording {
+  folder_path: string;
+  meeting_name: string | null;
+  created_at: string;
+  audio_seconds: number;
+  segments: number;
+}
+
+interface RecoveryResult {
+  meeting_id: string;
+  meeting_name: string;
+  folder_path: string;
+  segments: number;
+  audio_recovered: boolean;
+  checkpoints_left: boolean;
+  dropped_checkpoints: string[];
+}
+
+function describe(recording: InterruptedRecording): string {
+  const started = new Date(recording.created_at).toLocaleString();
+  const minutes = Math.max(1, Math.round(recording.audio_seconds / 60));
+  return `Started ${started} · ~${minutes} min of audio · ${recording.segments} transcript segments`;
+}
+
+/**
+ * Offers to recover recordings that were cut short by a crash or power loss.
+ * Checks once on startup and shows one toast per interrupted recording.
+ */
+export function RecordingRecovery() {
+  const router = useRouter();
+  const { refetchMeetings } = useSidebar();
+
+  useEffect(() => {
+    const recover = async (recording: InterruptedRecording) => {
+      const toastId = toast.loading('Recovering recording...');
+      try {
+        const result = await invoke<RecoveryResult>('api_recover_recording', {
+          folderPath: recording.folder_path,
+        });
+        await refetchMeetings();
+        toast.success(`Recovered "${result.meeting_name}"`, {
+          id: toastId,
+          description: result.checkpoints_left
+            ? `${result.segments} transcript segments restored, but some audio could not be merged yet. It will be offered for recovery again.`
+            : result.audio_recovered
+              ? `${result.segments} transcript segments restored.`
+              : `${result.segments} transcript segments restored, but the audio could not be merged.`,
+          action: {
+            label: 'View Meeting',
+            onClick: () => router.push(`/meeting-details?id=${result.meeting_id}`),
+          },
+          duration: 10000,
+        });
+        if (result.dropped_checkpoints.length > 0) {
+          confirmDroppedAudio(result);
+        }
+      } catch (error) {
+        console.error('Failed to recover recording:', error);
+        toast.error('Failed to recover recording', { id: toastId, description: String(error) });
+      }
+    };
+
+    // The damaged tail of the audio stays on disk until the user lets it go
+    const confirmDroppedAudio = (result: RecoveryResult) => {
+      toast.warning('The last seconds of audio were damaged', {
+        description: `Up to ${result.dropped_checkpoints.length * 30} seconds at the end of "${result.meeting_name}" could not be recovered. The damaged audio is kept in the meeting folder until you delete it.`,
+        duration: Infinity,
+        action: {
+          label: 'Delete',
+          onClick: async () => {
+            try {
+              await invoke('api_discard_dropped_checkpoints', { folderPath: result.folder_path });
+            } catch (error) {
+              console.error('Failed to delete damaged audio:', error);
+              toast.error('Failed to delete damaged audio', { description: String(error) });
+            }
+          },
+        },
+        cancel: { label: 'Keep', onClick: () => {} },
+      });
+    };
+
+    const discard = async (recording: InterruptedRecording) => {
+      try {
+        await invoke('api_discard_interrupted_recording', { folderPath: recording.folder_path });
+        toast.info('Interrupted recording discarded');
+      } catch (error) {
+        console.error('Failed to discard recording:', error);
+        toast.error('Failed to discard recording', { description: String(error) });
+      }
+    };
+
+    invoke<InterruptedRecording[]>('api_list_interrupted_recordings')
+      .then((recordings) => {
+        recordings.forEach((recording) => {
+          toast.warning(`"${recording.meeting_name || 'Untitled recording'}" was interrupted`, {
+            description: describe(recording),
+            duration: Infinity,
+            action: { label: 'Recover', onClick: () => recover(recording) },
+            cancel: { label: 'Discard', onClick: () => discard(recording) },
+          });
+        });
+      })
+      .catch((error) => {
+        console.error('Failed to check for interrupted recordings:', error);
+      });
+    // Only check once per app launch
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  return null;
+}
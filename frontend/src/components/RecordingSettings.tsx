@@ -1,5 +1,12 @@
 import React, { useState, useEffect } from 'react';
 import { Switch } from '@/components/ui/switch';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 import { FolderOpen } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { DeviceSelection, SelectedDevices } from '@/components/DeviceSelection';
@@ -10,10 +17,35 @@ export interface RecordingPreferences {
   save_folder: string;
   auto_save: boolean;
   file_format: string;
+  audio_bitrate_kbps?: number | null;
+  audio_sample_rate?: number | null;
+  dual_channel_transcription?: boolean;
+  multitrack_recording?: boolean;
+  rediarize_after_recording?: boolean;
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
 }
 
+// Output formats for saved recordings; "mp4" is AAC, the historical default
+const AUDIO_FORMATS = [
+  { value: 'mp4', label: 'AAC (.mp4)', description: 'Compressed, plays everywhere (requires FFmpeg, falls back to FLAC without it)' },
+  { value: 'opus', label: 'Opus (.ogg)', description: 'Smallest files, ideal for long-term storage' },
+  { value: 'flac', label: 'FLAC (.flac)', description: 'Lossless, about half the size of WAV' },
+  { value: 'wav', label: 'WAV (.wav)', description: 'Lossless and uncompressed, largest files' },
+];
+
+const BITRATES: Record<string, number[]> = {
+  mp4: [64, 96, 128, 192, 256],
+  opus: [16, 24, 32, 48, 64, 96],
+};
+
+const SAMPLE_RATES: Record<string, number[]> = {
+  mp4: [16000, 24000, 44100, 48000],
+  opus: [16000, 24000, 48000],
+  flac: [16000, 24000, 44100, 48000],
+  wav: [16000, 24000, 44100, 48000],
+};
+
 interface RecordingSettingsProps {
   onSave?: (preferences: RecordingPreferences) => void;
 }
@@ -79,6 +111,68 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
     });
   };
 
+  const handleDualChannelToggle = async (enabled: boolean) => {
+    const newPreferences = { ...preferences, dual_channel_transcription: enabled };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('dual_channel_transcription_toggled', {
+      enabled: enabled.toString()
+    });
+  };
+
+  const handleMultitrackToggle = async (enabled: boolean) => {
+    const newPreferences = { ...preferences, multitrack_recording: enabled };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('multitrack_recording_toggled', {
+      enabled: enabled.toString()
+    });
+  };
+
+  const handleRediarizeToggle = async (enabled: boolean) => {
+    const newPreferences = { ...preferences, rediarize_after_recording: enabled };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('rediarize_after_recording_toggled', {
+      enabled: enabled.toString()
+    });
+  };
+
+  const handleFormatChange = async (format: string) => {
+    // Bitrate and sample rate limits differ per format, so start from its defaults
+    const newPreferences = {
+      ...preferences,
+      file_format: format,
+      audio_bitrate_kbps: null,
+      audio_sample_rate: null,
+    };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('recording_format_changed', { format });
+  };
+
+  const handleBitrateChange = async (value: string) => {
+    const newPreferences = {
+      ...preferences,
+      audio_bitrate_kbps: value === 'default' ? null : Number(value),
+    };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+  };
+
+  const handleSampleRateChange = async (value: string) => {
+    const newPreferences = {
+      ...preferences,
+      audio_sample_rate: value === 'original' ? null : Number(value),
+    };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+  };
+
   const handleDeviceChange = async (devices: SelectedDevices) => {
     const newPreferences = {
       ...preferences,
@@ -193,12 +287,57 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
             </button>
           </div>
 
-          <div className="p-4 border rounded-lg bg-blue-50">
-            <div className="text-sm text-blue-800">
-              <strong>File Format:</strong> {preferences.file_format.toUpperCase()} files
+          <div className="p-4 border rounded-lg space-y-3">
+            <div>
+              <div className="font-medium">File Format</div>
+              <div className="text-sm text-gray-600">
+                {AUDIO_FORMATS.find(f => f.value === preferences.file_format)?.description
+                  ?? 'Applies from the next recording'}
+              </div>
             </div>
-            <div className="text-xs text-blue-600 mt-1">
-              Recordings are saved with timestamp: recording_YYYYMMDD_HHMMSS.{preferences.file_format}
+            <div className="grid grid-cols-3 gap-3">
+              <Select value={preferences.file_format} onValueChange={handleFormatChange} disabled={saving}>
+                <SelectTrigger>
+                  <SelectValue placeholder="Format" />
+                </SelectTrigger>
+                <SelectContent>
+                  {AUDIO_FORMATS.map(format => (
+                    <SelectItem key={format.value} value={format.value}>{format.label}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <Select
+                value={preferences.audio_bitrate_kbps ? String(preferences.audio_bitrate_kbps) : 'default'}
+                onValueChange={handleBitrateChange}
+                disabled={saving || !BITRATES[preferences.file_format]}
+              >
+                <SelectTrigger>
+                  <SelectValue placeholder="Bitrate" />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="default">
+                    {BITRATES[preferences.file_format] ? 'Default bitrate' : 'Lossless'}
+                  </SelectItem>
+                  {(BITRATES[preferences.file_format] ?? []).map(kbps => (
+                    <SelectItem key={kbps} value={String(kbps)}>{kbps} kbps</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              <Select
+                value={preferences.audio_sample_rate ? String(preferences.audio_sample_rate) : 'original'}
+                onValueChange={handleSampleRateChange}
+                disabled={saving}
+              >
+                <SelectTrigger>
+                  <SelectValue placeholder="Sample rate" />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="original">48 kHz (original)</SelectItem>
+                  {(SAMPLE_RATES[preferences.file_format] ?? []).filter(rate => rate !== 48000).map(rate => (
+                    <SelectItem key={rate} value={String(rate)}>{rate / 1000} kHz</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
             </div>
           </div>
         </div>
@@ -213,6 +352,51 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
         </div>
       )}
 
+      {/* Dual-Channel Transcription Toggle */}
+      <div className="flex items-center justify-between p-4 border rounded-lg">
+        <div className="flex-1">
+          <div className="font-medium">Separate "Me" and "Them"</div>
+          <div className="text-sm text-gray-600">
+            Transcribe your microphone and system audio separately, labelling what you said as "Me" and what others said as "Them". Works best with headphones. Applies from the next recording.
+          </div>
+        </div>
+        <Switch
+          checked={preferences.dual_channel_transcription ?? false}
+          onCheckedChange={handleDualChannelToggle}
+          disabled={saving}
+        />
+      </div>
+
+      {/* Multitrack Recording Toggle */}
+      <div className="flex items-center justify-between p-4 border rounded-lg">
+        <div className="flex-1">
+          <div className="font-medium">Save Separate Audio Tracks</div>
+          <div className="text-sm text-gray-600">
+            Also save your microphone and system audio as separate files, plus a stereo file with you on the left and others on the right. Lets you re-transcribe or re-diarize one side only. Applies from the next recording.
+          </div>
+        </div>
+        <Switch
+          checked={preferences.multitrack_recording ?? false}
+          onCheckedChange={handleMultitrackToggle}
+          disabled={saving || !preferences.auto_save}
+        />
+      </div>
+
+      {/* Re-diarize After Recording Toggle */}
+      <div className="flex items-center justify-between p-4 border rounded-lg">
+        <div className="flex-1">
+          <div className="font-medium">Refine Speakers After Recording</div>
+          <div className="text-sm text-gray-600">
+            Once a recording is saved, re-run speaker detection over the whole meeting to fix labels the live pass got wrong. Needs the speaker model and takes a few minutes for long meetings.
+          </div>
+        </div>
+        <Switch
+          checked={preferences.rediarize_after_recording ?? false}
+          onCheckedChange={handleRediarizeToggle}
+          disabled={saving || !preferences.auto_save}
+        />
+      </div>
+
       {/* Recording Notification Toggle */}
       <div className="flex items-center justify-between p-4 border rounded-lg">
         <div className="flex-1">
@@ -26,6 +26,20 @@ interface TranscriptSearchResult {
   timestamp: string;
 };
 
+// Ranked full-text search page returned by api_search_meetings
+interface MeetingSearchPage {
+  results: {
+    meeting_id: string;
+    title: string;
+    created_at: string;
+    matches: { snippet: string; timestamp?: string }[];
+  }[];
+  total: number;
+}
+
+// Largest page api_search_meetings returns
+const SEARCH_PAGE_SIZE = 100;
+
 interface SidebarContextType {
   currentMeeting: CurrentMeeting | null;
   setCurrentMeeting: (meeting: CurrentMeeting | null) => void;
@@ -169,9 +183,21 @@ export function SidebarProvider({ children }: { children: React.ReactNode }) {
     try {
       setIsSearching(true);
 
-
-      const results = await invoke('api_search_transcripts', { query }) as TranscriptSearchResult[];
-      setSearchResults(results);
+      // Results come back best match first; the sidebar keeps that order
+      const page = await invoke('api_search_meetings', {
+        query,
+        limit: SEARCH_PAGE_SIZE,
+        offset: 0,
+      }) as MeetingSearchPage;
+      setSearchResults(page.results.map(result => {
+        const best = result.matches[0];
+        return {
+          id: result.meeting_id,
+          title: result.title,
+          matchContext: best ? best.snippet.replace(/<\/?mark>/g, '') : '',
+          timestamp: best?.timestamp ?? result.created_at,
+        };
+      }));
     } catch (error) {
       console.error('Error searching transcripts:', error);
       setSearchResults([]);
@@ -224,8 +250,8 @@ export function SidebarProvider({ children }: { children: React.ReactNode }) {
         // Call the update callback with result
         onUpdate(result);
 
-        // Stop polling if completed, error, failed, or idle (after initial processing)
-        if (result.status === 'completed' || result.status === 'error' || result.status === 'failed') {
+        // Stop polling if completed, error, failed, cancelled, or idle (after initial processing)
+        if (result.status === 'completed' || result.status === 'error' || result.status === 'failed' || result.status === 'cancelled') {
           console.log(`✅ Polling completed for ${meetingId}, status: ${result.status}`);
           clearInterval(pollInterval);
           setActiveSummaryPolls(prev => {
@@ -214,6 +214,7 @@ const Sidebar: React.FC = () => {
         provider: payload.provider,
         model: payload.model,
         apiKey: payload.apiKey,
+        endpoint: configToSave.endpoint ?? null,
       });
 
       
@@ -254,6 +255,7 @@ const Sidebar: React.FC = () => {
     if (searchResults.length > 0) {
       // Get the IDs of meetings that matched in transcripts
       const matchedMeetingIds = new Set(searchResults.map(result => result.id));
+      const searchRank = new Map(searchResults.map((result, index) => [result.id, index]));
       
       return sidebarItems
         .map(folder => {
@@ -269,6 +271,13 @@ const Sidebar: React.FC = () => {
               // Or if the title matches the search query
               return item.title.toLowerCase().includes(searchQuery.toLowerCase());
             });
+
+            // Show full-text matches in relevance order, ahead of title-only matches
+            filteredChildren.sort((a, b) => {
+              const rankA = searchRank.get(a.id) ?? Number.MAX_SAFE_INTEGER;
+              const rankB = searchRank.get(b.id) ?? Number.MAX_SAFE_INTEGER;
+              return rankA - rankB;
+            });
             
             return {
               ...folder,
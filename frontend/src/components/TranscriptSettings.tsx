@@ -8,12 +8,14 @@ import { Eye, EyeOff, Lock, Unlock } from 'lucide-react';
 import { ModelManager } from './WhisperModelManager';
 import { ParakeetModelManager } from './ParakeetModelManager';
 import { DiarizationSettings } from './DiarizationSettings';
+import { VocabularySettings } from './VocabularySettings';
 
 
 export interface TranscriptModelProps {
-    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai';
+    provider: 'localWhisper' | 'parakeet' | 'deepgram' | 'elevenLabs' | 'groq' | 'openai' | 'openaiCompatible';
     model: string;
     apiKey?: string | null;
+    endpoint?: string | null;
 }
 
 export interface TranscriptSettingsProps {
@@ -29,6 +31,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const [isLockButtonVibrating, setIsLockButtonVibrating] = useState<boolean>(false);
     const [selectedWhisperModel, setSelectedWhisperModel] = useState<string>(transcriptModelConfig.provider === 'localWhisper' ? transcriptModelConfig.model : 'small');
     const [selectedParakeetModel, setSelectedParakeetModel] = useState<string>(transcriptModelConfig.provider === 'parakeet' ? transcriptModelConfig.model : 'parakeet-tdt-0.6b-v3-int8');
+    const [isSavingRemote, setIsSavingRemote] = useState<boolean>(false);
 
     useEffect(() => {
         if (transcriptModelConfig.provider === 'localWhisper' || transcriptModelConfig.provider === 'parakeet') {
@@ -52,10 +55,33 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
         parakeet: [selectedParakeetModel],
         deepgram: ['nova-2-phonecall'],
         elevenLabs: ['eleven_multilingual_v2'],
-        groq: ['llama-3.3-70b-versatile'],
-        openai: ['gpt-4o'],
+        groq: ['whisper-large-v3-turbo', 'whisper-large-v3'],
+        openai: ['whisper-1'],
+        openaiCompatible: ['whisper-large-v3'],
     };
     const requiresApiKey = transcriptModelConfig.provider === 'deepgram' || transcriptModelConfig.provider === 'elevenLabs' || transcriptModelConfig.provider === 'openai' || transcriptModelConfig.provider === 'groq';
+    // OpenAI-compatible providers transcribe on a server and fall back to a downloaded local model
+    const isRemote = transcriptModelConfig.provider === 'openai' || transcriptModelConfig.provider === 'groq' || transcriptModelConfig.provider === 'openaiCompatible';
+
+    const handleSaveRemoteConfig = async () => {
+        setIsSavingRemote(true);
+        try {
+            await invoke('api_save_transcript_config', {
+                provider: transcriptModelConfig.provider,
+                model: transcriptModelConfig.model,
+                apiKey: apiKey,
+                endpoint: transcriptModelConfig.provider === 'openaiCompatible' ? (transcriptModelConfig.endpoint ?? '') : null,
+            });
+            setTranscriptModelConfig({ ...transcriptModelConfig, apiKey });
+            if (onModelSelect) {
+                onModelSelect();
+            }
+        } catch (err) {
+            console.error('Error saving remote transcription settings:', err);
+        } finally {
+            setIsSavingRemote(false);
+        }
+    };
 
     const handleInputClick = () => {
         if (isApiKeyLocked) {
@@ -121,14 +147,24 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                 <SelectContent>
                                     <SelectItem value="parakeet">⚡ Parakeet (Recommended - Real-time / Accurate)</SelectItem>
                                     <SelectItem value="localWhisper">🏠 Local Whisper (High Accuracy)</SelectItem>
-                                    {/* <SelectItem value="deepgram">☁️ Deepgram (Backup)</SelectItem>
-                                    <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem>
+                                    <SelectItem value="openaiCompatible">🖥️ Remote Server (OpenAI-compatible)</SelectItem>
                                     <SelectItem value="groq">☁️ Groq</SelectItem>
-                                    <SelectItem value="openai">☁️ OpenAI</SelectItem> */}
+                                    <SelectItem value="openai">☁️ OpenAI</SelectItem>
+                                    {/* <SelectItem value="deepgram">☁️ Deepgram (Backup)</SelectItem>
+                                    <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem> */}
                                 </SelectContent>
                             </Select>
 
-                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && (
+                            {transcriptModelConfig.provider === 'openaiCompatible' && (
+                                <Input
+                                    className='focus:ring-1 focus:ring-blue-500 focus:border-blue-500'
+                                    value={transcriptModelConfig.model}
+                                    onChange={(e) => setTranscriptModelConfig({ ...transcriptModelConfig, model: e.target.value })}
+                                    placeholder="Model name"
+                                />
+                            )}
+
+                            {transcriptModelConfig.provider !== 'localWhisper' && transcriptModelConfig.provider !== 'parakeet' && transcriptModelConfig.provider !== 'openaiCompatible' && (
                                 <Select
                                     value={transcriptModelConfig.model}
                                     onValueChange={(value) => {
@@ -175,11 +211,29 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                         <DiarizationSettings />
                     )}
 
+                    <VocabularySettings />
+
 
-                    {requiresApiKey && (
+                    {transcriptModelConfig.provider === 'openaiCompatible' && (
                         <div>
                             <Label className="block text-sm font-medium text-gray-700 mb-1">
-                                API Key
+                                Server URL
+                            </Label>
+                            <div className="mx-1">
+                                <Input
+                                    className='focus:ring-1 focus:ring-blue-500 focus:border-blue-500'
+                                    value={transcriptModelConfig.endpoint || ''}
+                                    onChange={(e) => setTranscriptModelConfig({ ...transcriptModelConfig, endpoint: e.target.value })}
+                                    placeholder="http://gpu-box:8080/v1"
+                                />
+                            </div>
+                        </div>
+                    )}
+
+                    {(requiresApiKey || transcriptModelConfig.provider === 'openaiCompatible') && (
+                        <div>
+                            <Label className="block text-sm font-medium text-gray-700 mb-1">
+                                {requiresApiKey ? 'API Key' : 'API Key (optional)'}
                             </Label>
                             <div className="relative mx-1">
                                 <Input
@@ -222,6 +276,21 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                             </div>
                         </div>
                     )}
+
+                    {isRemote && (
+                        <div className="mx-1 space-y-2">
+                            <p className="text-xs text-gray-500">
+                                Audio segments are sent to the server for transcription. If it can't be reached, a downloaded local model is used instead.
+                            </p>
+                            <Button
+                                type="button"
+                                onClick={handleSaveRemoteConfig}
+                                disabled={isSavingRemote || (transcriptModelConfig.provider === 'openaiCompatible' && !transcriptModelConfig.endpoint?.trim())}
+                            >
+                                {isSavingRemote ? 'Saving...' : 'Save'}
+                            </Button>
+                        </div>
+                    )}
                 </div>
             </div>
         </div>
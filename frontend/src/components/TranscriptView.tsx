@@ -283,9 +283,13 @@ export const TranscriptView: React.FC<TranscriptViewProps> = ({ transcripts, isR
           >
             <div className="flex items-start gap-2">
               <div className="flex flex-col items-end min-w-[70px]">
-                {transcript.speaker_id && (
+                {(transcript.speaker_name || transcript.speaker_id != null) ? (
                    <span className="text-xs font-bold text-blue-500 mb-0.5">
-                     {transcript.speaker_id}
+                     {transcript.speaker_name ?? `Speaker ${transcript.speaker_id}`}
+                   </span>
+                ) : transcript.audio_source && (
+                   <span className={`text-xs font-bold mb-0.5 ${transcript.audio_source === 'mic' ? 'text-green-600' : 'text-purple-500'}`}>
+                     {transcript.audio_source === 'mic' ? 'Me' : 'Them'}
                    </span>
                 )}
                 <Tooltip>
@@ -310,6 +314,16 @@ export const TranscriptView: React.FC<TranscriptViewProps> = ({ transcripts, isR
                     )}
                   </TooltipContent>
                 </Tooltip>
+                {transcript.language && (
+                  <span
+                    className="text-[10px] uppercase text-gray-400"
+                    title={transcript.language_probability !== undefined
+                      ? `Detected language (${Math.round(transcript.language_probability * 100)}%)`
+                      : 'Language'}
+                  >
+                    {transcript.language}
+                  </span>
+                )}
               </div>
               <div className="flex-1">
                 {isStreaming ? (
@@ -0,0 +1,117 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Button } from './ui/button';
+import { Input } from './ui/input';
+import { Label } from './ui/label';
+import { Plus, X } from 'lucide-react';
+import { toast } from 'sonner';
+
+export interface VocabularyTerm {
+    id: number;
+    term: string;
+    aliases: string[];
+    meeting_id: string | null;
+    created_at: string;
+    updated_at: string;
+}
+
+interface VocabularySettingsProps {
+    meetingId?: string;
+}
+
+export function VocabularySettings({ meetingId }: VocabularySettingsProps) {
+    const [terms, setTerms] = useState<VocabularyTerm[]>([]);
+    const [term, setTerm] = useState<string>('');
+    const [aliases, setAliases] = useState<string>('');
+    const [isSaving, setIsSaving] = useState<boolean>(false);
+
+    const loadTerms = async () => {
+        try {
+            const data = await invoke<VocabularyTerm[]>('api_list_vocabulary', { meetingId: meetingId ?? null });
+            setTerms(data);
+        } catch (err) {
+            console.error('Error loading vocabulary:', err);
+        }
+    };
+
+    useEffect(() => {
+        loadTerms();
+        // eslint-disable-next-line react-hooks/exhaustive-deps
+    }, [meetingId]);
+
+    const handleAdd = async () => {
+        if (!term.trim()) return;
+        setIsSaving(true);
+        try {
+            await invoke('api_save_vocabulary_term', {
+                term: term.trim(),
+                aliases: aliases.split(',').map((a) => a.trim()).filter(Boolean),
+                meetingId: meetingId ?? null,
+            });
+            setTerm('');
+            setAliases('');
+            await loadTerms();
+        } catch (err) {
+            console.error('Error saving vocabulary term:', err);
+            toast.error('Failed to save vocabulary term');
+        } finally {
+            setIsSaving(false);
+        }
+    };
+
+    const handleDelete = async (id: number) => {
+        try {
+            await invoke('api_delete_vocabulary_term', { id });
+            setTerms((current) => current.filter((t) => t.id !== id));
+        } catch (err) {
+            console.error('Error deleting vocabulary term:', err);
+            toast.error('Failed to delete vocabulary term');
+        }
+    };
+
+    return (
+        <div className="border rounded-md p-4 bg-gray-50 mt-4">
+            <Label className="text-sm font-semibold text-gray-900">Custom Vocabulary</Label>
+            <p className="text-xs text-gray-500 mt-1 mb-3">
+                Product names and acronyms the transcription should spell correctly. Add common misspellings, separated by commas, to have them replaced.
+            </p>
+
+            <div className="flex space-x-2 mb-3">
+                <Input
+                    className='focus:ring-1 focus:ring-blue-500 focus:border-blue-500'
+                    value={term}
+                    onChange={(e) => setTerm(e.target.value)}
+                    placeholder="Term"
+                />
+                <Input
+                    className='focus:ring-1 focus:ring-blue-500 focus:border-blue-500'
+                    value={aliases}
+                    onChange={(e) => setAliases(e.target.value)}
+                    onKeyDown={(e) => e.key === 'Enter' && handleAdd()}
+                    placeholder="Misspellings (optional)"
+                />
+                <Button type="button" size="icon" variant="outline" onClick={handleAdd} disabled={isSaving || !term.trim()}>
+                    <Plus className="h-4 w-4" />
+                </Button>
+            </div>
+
+            {terms.length > 0 && (
+                <ul className="space-y-1 max-h-48 overflow-y-auto">
+                    {terms.map((t) => (
+                        <li key={t.id} className="flex items-center justify-between text-sm bg-white border rounded px-2 py-1">
+                            <span>
+                                <span className="font-medium">{t.term}</span>
+                                {t.aliases.length > 0 && (
+                                    <span className="text-xs text-gray-500 ml-2">{t.aliases.join(', ')}</span>
+                                )}
+                            </span>
+                            <Button type="button" size="icon" variant="ghost" className="h-6 w-6" onClick={() => handleDelete(t.id)}>
+                                <X className="h-3 w-3" />
+                            </Button>
+                        </li>
+                    ))}
+                </ul>
+            )}
+        </div>
+    );
+}
@@ -20,7 +20,57 @@ export function useMeetingOperations({
     }
   }, [meeting.id]);
 
+  // Export the transcript as captions (the backend shows a save dialog)
+  const handleExportSubtitles = useCallback(async (format: 'srt' | 'vtt') => {
+    try {
+      const path = await invokeTauri<string | null>('api_export_subtitles', {
+        meetingId: meeting.id,
+        format,
+      });
+      if (path) {
+        toast.success('Captions exported', { description: path });
+      }
+    } catch (error) {
+      console.error('Failed to export captions:', error);
+      toast.error('Failed to export captions', { description: String(error) });
+    }
+  }, [meeting.id]);
+
+  // Export the meeting as a document with summary, action items and transcript
+  const handleExportDocument = useCallback(async (format: 'markdown' | 'html' | 'docx' | 'pdf') => {
+    try {
+      const path = await invokeTauri<string | null>('api_export_meeting_document', {
+        meetingId: meeting.id,
+        format,
+      });
+      if (path) {
+        toast.success('Meeting exported', { description: path });
+      }
+    } catch (error) {
+      console.error('Failed to export meeting:', error);
+      toast.error('Failed to export meeting', { description: String(error) });
+    }
+  }, [meeting.id]);
+
+  // Export the meeting with its audio as a portable archive for teammates
+  const handleExportArchive = useCallback(async () => {
+    try {
+      const path = await invokeTauri<string | null>('api_export_meeting_archive', {
+        meetingId: meeting.id,
+      });
+      if (path) {
+        toast.success('Meeting archive exported', { description: path });
+      }
+    } catch (error) {
+      console.error('Failed to export meeting archive:', error);
+      toast.error('Failed to export meeting archive', { description: String(error) });
+    }
+  }, [meeting.id]);
+
   return {
     handleOpenMeetingFolder,
+    handleExportSubtitles,
+    handleExportDocument,
+    handleExportArchive,
   };
 }
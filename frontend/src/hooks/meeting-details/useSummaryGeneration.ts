@@ -14,6 +14,7 @@ interface UseSummaryGenerationProps {
   modelConfig: ModelConfig;
   isModelConfigLoading: boolean;
   selectedTemplate: string;
+  summaryLanguage?: string;
   onMeetingUpdated?: () => Promise<void>;
   updateMeetingTitle: (title: string) => void;
   setAiSummary: (summary: Summary | null) => void;
@@ -25,6 +26,7 @@ export function useSummaryGeneration({
   modelConfig,
   isModelConfigLoading,
   selectedTemplate,
+  summaryLanguage = 'auto',
   onMeetingUpdated,
   updateMeetingTitle,
   setAiSummary,
@@ -103,6 +105,7 @@ export function useSummaryGeneration({
         overlap: 1000,
         customPrompt: customPrompt,
         templateId: selectedTemplate,
+        outputLanguage: summaryLanguage,
       }) as any;
 
       const process_id = result.process_id;
@@ -112,6 +115,13 @@ export function useSummaryGeneration({
       startSummaryPolling(meeting.id, process_id, async (pollingResult) => {
         console.log('Summary status:', pollingResult);
 
+        // Cancelled by the user; finished chunks are kept and reused on the next attempt
+        if (pollingResult.status === 'cancelled') {
+          setSummaryStatus('idle');
+          toast.info(`Summary ${isRegeneration ? 'regeneration' : 'generation'} cancelled`);
+          return;
+        }
+
         // Handle errors
         if (pollingResult.status === 'error' || pollingResult.status === 'failed') {
           console.error('Backend returned error:', pollingResult.error);
@@ -256,6 +266,7 @@ export function useSummaryGeneration({
     meeting.created_at,
     modelConfig,
     selectedTemplate,
+    summaryLanguage,
     startSummaryPolling,
     setAiSummary,
     updateMeetingTitle,
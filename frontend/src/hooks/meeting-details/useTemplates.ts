@@ -10,6 +10,8 @@ export function useTemplates() {
     description: string;
   }>>([]);
   const [selectedTemplate, setSelectedTemplate] = useState<string>('standard_meeting');
+  // 'auto' writes the summary in the transcript's language
+  const [summaryLanguage, setSummaryLanguage] = useState<string>('auto');
 
   // Fetch available templates on mount
   useEffect(() => {
@@ -38,9 +40,16 @@ export function useTemplates() {
     Analytics.trackFeatureUsed('template_selected');
   }, []);
 
+  const handleSummaryLanguageSelection = useCallback((language: string) => {
+    setSummaryLanguage(language);
+    Analytics.trackFeatureUsed('summary_language_selected');
+  }, []);
+
   return {
     availableTemplates,
     selectedTemplate,
     handleTemplateSelection,
+    summaryLanguage,
+    handleSummaryLanguageSelection,
   };
 }
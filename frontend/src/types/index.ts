@@ -4,6 +4,13 @@ export interface Message {
   timestamp: string;
 }
 
+export interface WordTimestamp {
+  word: string;
+  start: number; // Seconds from recording start
+  end: number;
+  confidence?: number;
+}
+
 export interface Transcript {
   id: string;
   text: string;
@@ -16,7 +23,13 @@ export interface Transcript {
   audio_start_time?: number; // Seconds from recording start (e.g., 125.3)
   audio_end_time?: number;   // Seconds from recording start (e.g., 128.6)
   duration?: number;          // Segment duration in seconds (e.g., 3.3)
-  speaker_id?: string;        // Speaker identification (e.g., "Speaker 1")
+  speaker_id?: number | null; // Diarized speaker (1, 2, ...), null if diarization unavailable
+  speaker_profile_id?: number | null; // Registered speaker from the voice registry
+  speaker_name?: string | null;
+  words?: WordTimestamp[]; // Word-level timings, when the engine provides them
+  language?: string; // Language code of the text (e.g., "de"), when known
+  language_probability?: number; // Detection confidence, when auto-detected
+  audio_source?: 'mic' | 'system'; // Capture channel in dual-channel mode ("Me" / "Them")
 }
 
 export interface TranscriptUpdate {
@@ -31,6 +44,13 @@ export interface TranscriptUpdate {
   audio_start_time: number; // Seconds from recording start
   audio_end_time: number;   // Seconds from recording start
   duration: number;          // Segment duration in seconds
+  speaker_id: number | null; // Diarized speaker, null if diarization unavailable
+  speaker_profile_id: number | null; // Registered speaker matched from the voice registry
+  speaker_name: string | null;
+  words?: WordTimestamp[]; // Recording-relative word timings (omitted if unsupported)
+  language?: string | null; // Detected or configured language code
+  language_probability?: number | null; // Detection confidence, when auto-detected
+  audio_source?: 'mic' | 'system' | null; // Capture channel in dual-channel mode
 }
 
 export interface Block {